@@ -0,0 +1,10 @@
+#pragma c23
+0b11uwb
+
+/*===
+Constant
+    Integer "11"
+        IntegerBase Binary
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
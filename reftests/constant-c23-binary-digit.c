@@ -0,0 +1,6 @@
+#pragma c23
+0b102
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+0b101Uwb
+
+/*===
+Constant
+    Integer "101"
+        IntegerBase Binary
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
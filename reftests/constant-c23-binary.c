@@ -0,0 +1,10 @@
+#pragma c23
+0b1010u
+
+/*===
+Constant
+    Integer "1010"
+        IntegerBase Binary
+        IntegerSuffix true false
+            IntegerSize Int
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+1.05d32
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+0x1p'1
+
+/*===
+~ERROR
+===*/
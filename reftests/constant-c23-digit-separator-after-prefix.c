@@ -0,0 +1,6 @@
+#pragma c23
+0x'1
+
+/*===
+~ERROR
+===*/
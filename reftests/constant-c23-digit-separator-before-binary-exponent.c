@@ -0,0 +1,6 @@
+#pragma c23
+0x1'p1
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+0x7FFF'FFFFull
+
+/*===
+Constant
+    Integer "7FFF\'FFFF"
+        IntegerBase Hexadecimal
+        IntegerSuffix true false
+            IntegerSize LongLong
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+0o78
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+0o1'7LLU
+
+/*===
+Constant
+    Integer "1\'7"
+        IntegerBase OctalExplicit
+        IntegerSuffix true false
+            IntegerSize LongLong
+===*/
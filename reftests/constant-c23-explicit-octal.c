@@ -0,0 +1,10 @@
+#pragma c23
+0o7'77u
+
+/*===
+Constant
+    Integer "7\'77"
+        IntegerBase OctalExplicit
+        IntegerSuffix true false
+            IntegerSize Int
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+017UWB
+
+/*===
+Constant
+    Integer "17"
+        IntegerBase Octal
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+u8'\x41'
+
+/*===
+Constant Character u8'\x41'
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+u8'ab'
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+0xFFuwb
+
+/*===
+Constant
+    Integer "FF"
+        IntegerBase Hexadecimal
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
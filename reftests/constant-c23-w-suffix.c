@@ -0,0 +1,6 @@
+#pragma c23
+1w
+
+/*===
+~ERROR
+===*/
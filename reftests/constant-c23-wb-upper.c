@@ -0,0 +1,10 @@
+#pragma c23
+0x7fWB
+
+/*===
+Constant
+    Integer "7f"
+        IntegerBase Hexadecimal
+        IntegerSuffix false false
+            IntegerSize BitPrecise
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+017wbu
+
+/*===
+Constant
+    Integer "17"
+        IntegerBase Octal
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+0x7fwbU
+
+/*===
+Constant
+    Integer "7f"
+        IntegerBase Hexadecimal
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+123WBU
+
+/*===
+Constant
+    Integer "123"
+        IntegerBase Decimal
+        IntegerSuffix true false
+            IntegerSize BitPrecise
+===*/
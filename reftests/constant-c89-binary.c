@@ -0,0 +1,6 @@
+#pragma c89
+0b1010
+
+/*===
+~ERROR
+===*/
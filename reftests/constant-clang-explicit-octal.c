@@ -0,0 +1,10 @@
+#pragma clang
+0O17ul
+
+/*===
+Constant
+    Integer "17"
+        IntegerBase OctalExplicit
+        IntegerSuffix true false
+            IntegerSize Long
+===*/
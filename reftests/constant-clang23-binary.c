@@ -0,0 +1,10 @@
+#pragma clang23
+0B1010
+
+/*===
+Constant
+    Integer "1010"
+        IntegerBase Binary
+        IntegerSuffix false false
+            IntegerSize Int
+===*/
@@ -0,0 +1,5 @@
+1.05df
+
+/*===
+~ERROR
+===*/
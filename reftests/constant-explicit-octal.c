@@ -0,0 +1,5 @@
+0o17
+
+/*===
+~ERROR
+===*/
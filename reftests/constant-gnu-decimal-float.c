@@ -0,0 +1,12 @@
+#pragma gnu
+1.05DL
+
+/*===
+Constant
+    Float "1.05"
+        FloatBase Decimal
+        FloatSuffix false
+            FloatFormat
+                TS18661FloatType 128
+                    TS18661FloatFormat DecimalInterchange
+===*/
@@ -0,0 +1,6 @@
+#pragma gnu
+1'000
+
+/*===
+~ERROR
+===*/
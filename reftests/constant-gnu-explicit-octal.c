@@ -0,0 +1,6 @@
+#pragma gnu
+0o17
+
+/*===
+~ERROR
+===*/
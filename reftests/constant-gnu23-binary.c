@@ -0,0 +1,10 @@
+#pragma gnu23
+0b1ull
+
+/*===
+Constant
+    Integer "1"
+        IntegerBase Binary
+        IntegerSuffix true false
+            IntegerSize LongLong
+===*/
@@ -0,0 +1,10 @@
+#pragma gnu23
+0o777
+
+/*===
+Constant
+    Integer "777"
+        IntegerBase OctalExplicit
+        IntegerSuffix false false
+            IntegerSize Int
+===*/
@@ -0,0 +1,10 @@
+#pragma gnu23
+123wb
+
+/*===
+Constant
+    Integer "123"
+        IntegerBase Decimal
+        IntegerSuffix false false
+            IntegerSize BitPrecise
+===*/
@@ -0,0 +1,5 @@
+u'x'
+
+/*===
+Constant Character u'x'
+===*/
@@ -30,9 +30,8 @@ Declaration
                                                 Declarator
                                                     DeclaratorKind
                                                         Identifier "i"
-                        SpecifierQualifier
-                            Extension
-                                Attribute "packed"
+                                    Extension
+                                        Attribute "packed"
                         StructDeclarator
                             Declarator
                                 DeclaratorKind
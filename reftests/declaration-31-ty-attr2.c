@@ -25,13 +25,12 @@ Declaration
                                                 Declarator
                                                     DeclaratorKind
                                                         Identifier "i"
-                        SpecifierQualifier
-                            Extension
-                                Attribute "aligned"
-                                    Expression
-                                        Constant
-                                            Integer "8"
-                                                IntegerBase Decimal
-                                                IntegerSuffix false false
-                                                    IntegerSize Int
+                                    Extension
+                                        Attribute "aligned"
+                                            Expression
+                                                Constant
+                                                    Integer "8"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
 ===*/
@@ -0,0 +1,5 @@
+auto x = 1;
+
+/*===
+~ERROR
+===*/
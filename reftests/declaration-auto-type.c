@@ -0,0 +1,5 @@
+__auto_type x = 1;
+
+/*===
+~ERROR
+===*/
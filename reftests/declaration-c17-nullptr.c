@@ -0,0 +1,10 @@
+int nullptr;
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "nullptr"
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+auto a[] = { 1 };
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+auto *p = 0;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+_ExtInt(24) x;
+
+/*===
+~ERROR
+===*/
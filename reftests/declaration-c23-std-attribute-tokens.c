@@ -0,0 +1,18 @@
+#pragma c23
+[[vendor::weird(a + , [{x y}] ; "s)" (1))]] int x;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        Extension
+            StdAttribute
+                Identifier "vendor"
+                Identifier "weird"
+                StdAttributeArguments "a + , [{x y}] ; \"s)\" (1)"
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+===*/
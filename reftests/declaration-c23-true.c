@@ -0,0 +1,5 @@
+#pragma c23
+int true;
+/*===
+~ERROR
+===*/
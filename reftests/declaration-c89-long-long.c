@@ -0,0 +1,5 @@
+#pragma c89
+unsigned long long x;
+/*===
+~ERROR
+===*/
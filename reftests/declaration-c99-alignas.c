@@ -0,0 +1,5 @@
+#pragma c99
+_Alignas(8) int x;
+/*===
+~ERROR
+===*/
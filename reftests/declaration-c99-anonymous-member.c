@@ -0,0 +1,10 @@
+#pragma c99
+struct s {
+    union {
+        int a;
+        float b;
+    };
+} x;
+/*===
+~ERROR
+===*/
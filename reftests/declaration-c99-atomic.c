@@ -0,0 +1,5 @@
+#pragma c99
+_Atomic int x;
+/*===
+~ERROR
+===*/
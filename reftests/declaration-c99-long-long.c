@@ -0,0 +1,38 @@
+#pragma c99
+unsigned long long int x = { .a = (struct s){ 1 } };
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Unsigned
+    DeclarationSpecifier
+        TypeSpecifier Long
+    DeclarationSpecifier
+        TypeSpecifier Long
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+        Initializer
+            InitializerListItem
+                Designator
+                    Identifier "a"
+                Initializer
+                    Expression
+                        CompoundLiteral
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            Identifier "s"
+                            InitializerListItem
+                                Initializer
+                                    Expression
+                                        Constant
+                                            Integer "1"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+===*/
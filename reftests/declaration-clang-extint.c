@@ -0,0 +1,20 @@
+#pragma clang
+_ExtInt(24) unsigned x;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier BitInt
+            Expression
+                Constant
+                    Integer "24"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+    DeclarationSpecifier
+        TypeSpecifier Unsigned
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+===*/
@@ -0,0 +1,5 @@
+_Decimal32 price;
+
+/*===
+~ERROR
+===*/
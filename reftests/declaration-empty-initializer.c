@@ -0,0 +1,5 @@
+int a[10] = {};
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+#pragma gnu
+__auto_type x;
+
+/*===
+~ERROR
+===*/
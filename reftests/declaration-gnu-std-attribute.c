@@ -0,0 +1,17 @@
+#pragma gnu
+[[gnu::unused]] int x;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        Extension
+            StdAttribute
+                Identifier "gnu"
+                Identifier "unused"
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+===*/
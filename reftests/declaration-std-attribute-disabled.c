@@ -0,0 +1,5 @@
+[[nodiscard]] int f(void);
+
+/*===
+~ERROR
+===*/
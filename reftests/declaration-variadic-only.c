@@ -0,0 +1,5 @@
+int printf_like(...);
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+__builtin_available(macOS 12.0, *)
+
+/*===
+~ERROR
+===*/
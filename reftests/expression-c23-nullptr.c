@@ -0,0 +1,12 @@
+#pragma c23
+p == nullptr
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            Identifier "p"
+        Expression
+            Constant Nullptr
+        BinaryOperator Equals
+===*/
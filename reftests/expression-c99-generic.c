@@ -0,0 +1,5 @@
+#pragma c99
+_Generic(x, int: 1)
+/*===
+~ERROR
+===*/
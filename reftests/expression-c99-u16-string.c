@@ -0,0 +1,6 @@
+#pragma c99
+u"a"
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,7 @@
+#pragma c99
+L"a" "b"
+
+/*===
+Expression
+    StringLiteral ["L\"a\"", "\"b\""]
+===*/
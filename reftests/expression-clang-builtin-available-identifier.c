@@ -0,0 +1,7 @@
+#pragma clang
+__builtin_available
+
+/*===
+Expression
+    Identifier "__builtin_available"
+===*/
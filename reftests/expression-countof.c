@@ -0,0 +1,10 @@
+_Countof(a)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "_Countof"
+        Expression
+            Identifier "a"
+===*/
@@ -0,0 +1,20 @@
+a<:1:> %b
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            BinaryOperatorExpression
+                Expression
+                    Identifier "a"
+                Expression
+                    Constant
+                        Integer "1"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+                BinaryOperator Index
+        Expression
+            Identifier "b"
+        BinaryOperator Modulo
+===*/
@@ -0,0 +1,5 @@
+(int[2]){}
+
+/*===
+~ERROR
+===*/
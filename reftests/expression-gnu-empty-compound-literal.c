@@ -0,0 +1,21 @@
+#pragma gnu
+(int[2]){}
+
+/*===
+Expression
+    CompoundLiteral
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind Abstract
+                DerivedDeclarator
+                    ArrayDeclarator
+                        ArraySize VariableExpression
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+===*/
@@ -0,0 +1,15 @@
+#pragma gnu
+sizeof(int __seg_gs *)
+
+/*===
+Expression
+    SizeOfTy
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier Int
+            SpecifierQualifier
+                TypeQualifier AddressSpace SegGs
+            Declarator
+                DeclaratorKind Abstract
+                DerivedDeclarator Pointer
+===*/
@@ -0,0 +1,5 @@
+&&label
+
+/*===
+~ERROR
+===*/
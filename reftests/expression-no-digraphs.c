@@ -0,0 +1,6 @@
+#pragma no_digraphs
+a<:1:>
+
+/*===
+~ERROR
+===*/
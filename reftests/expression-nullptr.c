@@ -0,0 +1,11 @@
+p == nullptr
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            Identifier "p"
+        Expression
+            Identifier "nullptr"
+        BinaryOperator Equals
+===*/
@@ -0,0 +1,5 @@
+__real__ z
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,16 @@
+// without the extension the keyword is an ordinary identifier
+__seg_gs + 1
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            Identifier "__seg_gs"
+        Expression
+            Constant
+                Integer "1"
+                    IntegerBase Decimal
+                    IntegerSuffix false false
+                        IntegerSize Int
+        BinaryOperator Plus
+===*/
@@ -0,0 +1,5 @@
+sizeof(int __seg_gs *)
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+u"a" "b" L"c" U"d" u8"e"
+
+/*===
+Expression
+    StringLiteral ["u\"a\"", "\"b\"", "L\"c\"", "U\"d\"", "u8\"e\""]
+===*/
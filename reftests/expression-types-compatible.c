@@ -0,0 +1,5 @@
+__builtin_types_compatible_p(int, long)
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+__asm { nop }
+
+/*===
+~ERROR
+===*/
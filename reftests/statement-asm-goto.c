@@ -0,0 +1,5 @@
+asm goto("jmp %l0" :::: out);
+
+/*===
+~ERROR
+===*/
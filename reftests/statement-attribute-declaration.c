@@ -0,0 +1,15 @@
+{
+    __attribute__((fallthrough));
+}
+
+/*===
+Statement Compound
+    BlockItem
+        Statement
+            Expression
+                CallExpression
+                    Expression
+                        Identifier "__attribute__"
+                    Expression
+                        Identifier "fallthrough"
+===*/
@@ -0,0 +1,5 @@
+#pragma c89
+for (int i = 0; i < 10; i++) ;
+/*===
+~ERROR
+===*/
@@ -0,0 +1,56 @@
+#pragma c99
+for (int i = 0; i < 10; i++) {
+    i++;
+    _Bool b;
+}
+/*===
+Statement
+    ForStatement
+        ForInitializer
+            Declaration
+                DeclarationSpecifier
+                    TypeSpecifier Int
+                InitDeclarator
+                    Declarator
+                        DeclaratorKind
+                            Identifier "i"
+                    Initializer
+                        Expression
+                            Constant
+                                Integer "0"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+        Expression
+            BinaryOperatorExpression
+                Expression
+                    Identifier "i"
+                Expression
+                    Constant
+                        Integer "10"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+                BinaryOperator Less
+        Expression
+            UnaryOperatorExpression
+                Expression
+                    Identifier "i"
+                UnaryOperator PostIncrement
+        Statement Compound
+            BlockItem
+                Statement
+                    Expression
+                        UnaryOperatorExpression
+                            Expression
+                                Identifier "i"
+                            UnaryOperator PostIncrement
+            BlockItem
+                Declaration
+                    DeclarationSpecifier
+                        TypeSpecifier
+                    InitDeclarator
+                        Declarator
+                            DeclaratorKind
+                                Identifier "b"
+===*/
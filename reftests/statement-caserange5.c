@@ -2,5 +2,21 @@
 case 1...3:;
 
 /*===
-~ERROR
+Statement
+    LabeledStatement
+        Label
+            CaseRange
+                Expression
+                    Constant
+                        Integer "1"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+                Expression
+                    Constant
+                        Integer "3"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+        Statement
 ===*/
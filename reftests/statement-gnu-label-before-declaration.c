@@ -0,0 +1,45 @@
+#pragma gnu
+{
+    a: int x;
+    case 1: const int y = 1;
+}
+
+/*===
+Statement Compound
+    BlockItem
+        Label
+            Identifier "a"
+    BlockItem
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+    BlockItem
+        Label
+            Expression
+                Constant
+                    Integer "1"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+    BlockItem
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "y"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+===*/
@@ -0,0 +1,9 @@
+#pragma gnu
+{
+    int x;
+    __label__ retry;
+}
+
+/*===
+~ERROR
+===*/
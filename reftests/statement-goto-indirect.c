@@ -0,0 +1,5 @@
+goto *target;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,7 @@
+{
+    __label__ retry;
+}
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,9 @@
+#pragma msvc
+{ __asm cpuid }
+
+/*===
+Statement Compound
+    BlockItem
+        Statement
+            AsmStatement "cpuid"
+===*/
@@ -0,0 +1,6 @@
+#pragma msvc
+__try f(); __finally { }
+
+/*===
+~ERROR
+===*/
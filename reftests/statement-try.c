@@ -0,0 +1,5 @@
+__try { } __finally { }
+
+/*===
+~ERROR
+===*/
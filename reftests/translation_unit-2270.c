@@ -5,6 +5,8 @@ __extension__ union { long l; };
 TranslationUnit
     ExternalDeclaration
         Declaration
+            DeclarationSpecifier
+                Extension __extension__
             DeclarationSpecifier
                 TypeSpecifier
                     StructType
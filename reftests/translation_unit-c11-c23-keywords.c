@@ -0,0 +1,37 @@
+int bool, alignas, alignof, static_assert, thread_local, true, false;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "bool"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "alignas"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "alignof"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "static_assert"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "thread_local"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "true"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "false"
+===*/
@@ -0,0 +1,134 @@
+#pragma c23
+static auto x = 1, y = 2.0;
+const auto z = x;
+
+void f(void) {
+    auto int a = 1;
+    auto p = &a;
+    for (auto i = 0; i < 1; i++);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                TypeSpecifier Auto
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "y"
+                Initializer
+                    Expression
+                        Constant
+                            Float "2.0"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat Double
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Auto
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "z"
+                Initializer
+                    Expression
+                        Identifier "x"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Auto
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "1"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Auto
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "p"
+                            Initializer
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Address
+                                        Expression
+                                            Identifier "a"
+                BlockItem
+                    Statement
+                        ForStatement
+                            ForInitializer
+                                Declaration
+                                    DeclarationSpecifier
+                                        TypeSpecifier Auto
+                                    InitDeclarator
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "i"
+                                        Initializer
+                                            Expression
+                                                Constant
+                                                    Integer "0"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    Expression
+                                        Constant
+                                            Integer "1"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                                    BinaryOperator Less
+                            Expression
+                                UnaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    UnaryOperator PostIncrement
+                            Statement
+===*/
@@ -0,0 +1,114 @@
+#pragma c23
+typedef unsigned _BitInt(7) u7;
+u7 x = (u7)3;
+const signed _BitInt(2 * 64) y;
+_BitInt(8) *p = (unsigned _BitInt(8) *)0;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier BitInt
+                    Expression
+                        Constant
+                            Integer "7"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "u7"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "u7"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+                Initializer
+                    Expression
+                        CastExpression
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier TypedefName
+                                        Identifier "u7"
+                            Expression
+                                Constant
+                                    Integer "3"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Signed
+            DeclarationSpecifier
+                TypeSpecifier BitInt
+                    Expression
+                        BinaryOperatorExpression
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            Expression
+                                Constant
+                                    Integer "64"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            BinaryOperator Multiply
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "y"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier BitInt
+                    Expression
+                        Constant
+                            Integer "8"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        CastExpression
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier BitInt
+                                        Expression
+                                            Constant
+                                                Integer "8"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                Declarator
+                                    DeclaratorKind Abstract
+                                    DerivedDeclarator Pointer
+                            Expression
+                                Constant
+                                    Integer "0"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+===*/
@@ -0,0 +1,50 @@
+#pragma c23
+int c[] = { 'a', 'ab', L'\x41', u'\n', U'\0', u8'a', u8'\x7f', u8'\'' };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "c"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize Unknown
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character 'a'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character 'ab'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character L'\x41'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character u'\n'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character U'\0'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character u8'a'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character u8'\x7f'
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant Character u8'\''
+===*/
@@ -0,0 +1,46 @@
+#pragma c23
+constexpr int (*f)(void) = 0, *g[2];
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Constexpr
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "f"
+                            DerivedDeclarator Pointer
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+                Initializer
+                    Expression
+                        Constant
+                            Integer "0"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "g"
+                    DerivedDeclarator Pointer
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "2"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+===*/
@@ -0,0 +1,6 @@
+#pragma c23
+constexpr int *f(void);
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,7 @@
+#pragma c23
+// the function declarator is rejected even when other declarators follow
+constexpr int *f(void), x;
+
+/*===
+~ERROR
+===*/
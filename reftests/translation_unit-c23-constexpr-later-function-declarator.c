@@ -0,0 +1,6 @@
+#pragma c23
+constexpr int x = 1, f(void);
+
+/*===
+~ERROR
+===*/
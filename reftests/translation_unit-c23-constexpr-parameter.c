@@ -0,0 +1,6 @@
+#pragma c23
+void f(constexpr int x);
+
+/*===
+~ERROR
+===*/
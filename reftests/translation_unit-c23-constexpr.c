@@ -0,0 +1,101 @@
+#pragma c23
+static constexpr unsigned long page = 4096;
+constexpr const int limits[2] = { 1, 2 }, *first = limits;
+constexpr int (*handler)(int) = 0;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                StorageClassSpecifier Constexpr
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "page"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "4096"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Constexpr
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "limits"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "2"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "first"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        Identifier "limits"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Constexpr
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "handler"
+                            DerivedDeclarator Pointer
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                            Ellipsis None
+                Initializer
+                    Expression
+                        Constant
+                            Integer "0"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+===*/
@@ -0,0 +1,112 @@
+#pragma c23
+typedef int row[8];
+int table[4][8];
+unsigned long n = _Countof(table) + _Countof table[0] + _Countof(row) + _Countof(int[3]);
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "row"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "8"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "table"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "4"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "8"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "n"
+                Initializer
+                    Expression
+                        BinaryOperatorExpression
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                CountOfVal
+                                                    Expression
+                                                        Identifier "table"
+                                            Expression
+                                                CountOfVal
+                                                    Expression
+                                                        BinaryOperatorExpression
+                                                            Expression
+                                                                Identifier "table"
+                                                            Expression
+                                                                Constant
+                                                                    Integer "0"
+                                                                        IntegerBase Decimal
+                                                                        IntegerSuffix false false
+                                                                            IntegerSize Int
+                                                            BinaryOperator Index
+                                            BinaryOperator Plus
+                                    Expression
+                                        CountOfTy
+                                            TypeName
+                                                SpecifierQualifier
+                                                    TypeSpecifier TypedefName
+                                                        Identifier "row"
+                                    BinaryOperator Plus
+                            Expression
+                                CountOfTy
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier Int
+                                        Declarator
+                                            DeclaratorKind Abstract
+                                            DerivedDeclarator
+                                                ArrayDeclarator
+                                                    ArraySize VariableExpression
+                                                        Expression
+                                                            Constant
+                                                                Integer "3"
+                                                                    IntegerBase Decimal
+                                                                    IntegerSuffix false false
+                                                                        IntegerSize Int
+                            BinaryOperator Plus
+===*/
@@ -0,0 +1,158 @@
+#pragma c23
+_Decimal32 price = 1.05DF;
+_Decimal64 rate = 0.10dd;
+_Decimal128 tiny = 2e-3DL;
+double d = 1.5;
+_Decimal64 scale(_Decimal64 x, double y) {
+    return x * (_Decimal64)y + _Generic(x, _Decimal64: 1.0dd, default: 0.0df);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    TS18661FloatType 32
+                        TS18661FloatFormat DecimalInterchange
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "price"
+                Initializer
+                    Expression
+                        Constant
+                            Float "1.05"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        TS18661FloatType 32
+                                            TS18661FloatFormat DecimalInterchange
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    TS18661FloatType 64
+                        TS18661FloatFormat DecimalInterchange
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "rate"
+                Initializer
+                    Expression
+                        Constant
+                            Float "0.10"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        TS18661FloatType 64
+                                            TS18661FloatFormat DecimalInterchange
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    TS18661FloatType 128
+                        TS18661FloatFormat DecimalInterchange
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "tiny"
+                Initializer
+                    Expression
+                        Constant
+                            Float "2e-3"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        TS18661FloatType 128
+                                            TS18661FloatFormat DecimalInterchange
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Double
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "d"
+                Initializer
+                    Expression
+                        Constant
+                            Float "1.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat Double
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier
+                    TS18661FloatType 64
+                        TS18661FloatFormat DecimalInterchange
+            Declarator
+                DeclaratorKind
+                    Identifier "scale"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier
+                                    TS18661FloatType 64
+                                        TS18661FloatFormat DecimalInterchange
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Double
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "x"
+                                        Expression
+                                            CastExpression
+                                                TypeName
+                                                    SpecifierQualifier
+                                                        TypeSpecifier
+                                                            TS18661FloatType 64
+                                                                TS18661FloatFormat DecimalInterchange
+                                                Expression
+                                                    Identifier "y"
+                                        BinaryOperator Multiply
+                                Expression
+                                    GenericSelection
+                                        Expression
+                                            Identifier "x"
+                                        GenericAssociation
+                                            GenericAssociationType
+                                                TypeName
+                                                    SpecifierQualifier
+                                                        TypeSpecifier
+                                                            TS18661FloatType 64
+                                                                TS18661FloatFormat DecimalInterchange
+                                                Expression
+                                                    Constant
+                                                        Float "1.0"
+                                                            FloatBase Decimal
+                                                            FloatSuffix false
+                                                                FloatFormat
+                                                                    TS18661FloatType 64
+                                                                        TS18661FloatFormat DecimalInterchange
+                                        GenericAssociation
+                                            Expression
+                                                Constant
+                                                    Float "0.0"
+                                                        FloatBase Decimal
+                                                        FloatSuffix false
+                                                            FloatFormat
+                                                                TS18661FloatType 32
+                                                                    TS18661FloatFormat DecimalInterchange
+                                BinaryOperator Plus
+===*/
@@ -0,0 +1,95 @@
+#pragma c23
+int i[] = { 1'000'000, 0xFFFF'FFFFu, 0'7'7, 0b1010'0101 };
+double d[] = { 3.141'592f, 1'0.5e1'0, .5'5, 0x1'0.8p1'0 };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "i"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize Unknown
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "1\'000\'000"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "FFFF\'FFFF"
+                                        IntegerBase Hexadecimal
+                                        IntegerSuffix true false
+                                            IntegerSize Int
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "7\'7"
+                                        IntegerBase Octal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "1010\'0101"
+                                        IntegerBase Binary
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Double
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "d"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize Unknown
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float "3.141\'592"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Float
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float "1\'0.5e1\'0"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Double
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float ".5\'5"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Double
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float "1\'0.8p1\'0"
+                                        FloatBase Hexadecimal
+                                        FloatSuffix false
+                                            FloatFormat Double
+===*/
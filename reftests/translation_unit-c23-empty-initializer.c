@@ -0,0 +1,113 @@
+#pragma c23
+struct S { int a[2]; int b; } s = {};
+int a[10] = {};
+struct S t[2] = { {}, {} };
+struct S *p = &(struct S){};
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "S"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "a"
+                                        DerivedDeclarator
+                                            ArrayDeclarator
+                                                ArraySize VariableExpression
+                                                    Expression
+                                                        Constant
+                                                            Integer "2"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "b"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "s"
+                Initializer
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "10"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                Initializer
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "S"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "t"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "2"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                Initializer
+                    InitializerListItem
+                        Initializer
+                    InitializerListItem
+                        Initializer
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "S"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        UnaryOperatorExpression
+                            UnaryOperator Address
+                            Expression
+                                CompoundLiteral
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier
+                                                StructType
+                                                    StructKind Struct
+                                                    Identifier "S"
+===*/
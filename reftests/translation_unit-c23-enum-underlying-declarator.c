@@ -0,0 +1,6 @@
+#pragma c23
+enum E : int x;
+
+/*===
+~ERROR
+===*/
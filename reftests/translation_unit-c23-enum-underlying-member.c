@@ -0,0 +1,8 @@
+#pragma c23
+struct S {
+    enum E : int;
+};
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,106 @@
+#pragma c23
+enum Color : unsigned char { RED, GREEN };
+enum Flags : const long;
+enum : short { ONE = 1 } one;
+struct S {
+    enum Color : 3;
+    enum Color c : 2;
+    enum Small : char { A } small;
+};
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "Color"
+                        SpecifierQualifier
+                            TypeSpecifier Unsigned
+                        SpecifierQualifier
+                            TypeSpecifier Char
+                        Enumerator
+                            Identifier "RED"
+                        Enumerator
+                            Identifier "GREEN"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "Flags"
+                        SpecifierQualifier
+                            TypeQualifier Const
+                        SpecifierQualifier
+                            TypeSpecifier Long
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        SpecifierQualifier
+                            TypeSpecifier Short
+                        Enumerator
+                            Identifier "ONE"
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "one"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "S"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        EnumType
+                                            Identifier "Color"
+                                StructDeclarator
+                                    Expression
+                                        Constant
+                                            Integer "3"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        EnumType
+                                            Identifier "Color"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "c"
+                                    Expression
+                                        Constant
+                                            Integer "2"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        EnumType
+                                            Identifier "Small"
+                                            SpecifierQualifier
+                                                TypeSpecifier Char
+                                            Enumerator
+                                                Identifier "A"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "small"
+===*/
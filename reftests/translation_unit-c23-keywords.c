@@ -0,0 +1,105 @@
+#pragma c23
+thread_local bool flag = true;
+alignas(8) bool other = false;
+alignas(long) char buffer[alignof(long)];
+static_assert(sizeof(int) == 4);
+_Static_assert(sizeof(int) == 4);
+static_assert(1, "message");
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier ThreadLocal
+            DeclarationSpecifier
+                TypeSpecifier
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "flag"
+                Initializer
+                    Expression
+                        Constant Bool true
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                AlignmentSpecifier
+                    Expression
+                        Constant
+                            Integer "8"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            DeclarationSpecifier
+                TypeSpecifier
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "other"
+                Initializer
+                    Expression
+                        Constant Bool false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                AlignmentSpecifier
+                    TypeName
+                        SpecifierQualifier
+                            TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Char
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "buffer"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    AlignOf
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier Long
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                BinaryOperatorExpression
+                    Expression
+                        SizeOfTy
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                    Expression
+                        Constant
+                            Integer "4"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+                    BinaryOperator Equals
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                BinaryOperatorExpression
+                    Expression
+                        SizeOfTy
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                    Expression
+                        Constant
+                            Integer "4"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+                    BinaryOperator Equals
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                Constant
+                    Integer "1"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+            StringLiteral ["\"message\""]
+===*/
@@ -0,0 +1,92 @@
+#pragma c23
+void f(int n) {
+retry:
+    int x = 0;
+first: second:
+    int y = x;
+    switch (n) {
+    case 1:
+        goto retry;
+    default:
+    }
+done:
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Label
+                        Identifier "retry"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Label
+                        Identifier "first"
+                BlockItem
+                    Label
+                        Identifier "second"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                            Initializer
+                                Expression
+                                    Identifier "x"
+                BlockItem
+                    Statement
+                        SwitchStatement
+                            Expression
+                                Identifier "n"
+                            Statement Compound
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Expression
+                                                    Constant
+                                                        Integer "1"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                            Statement Goto
+                                                Identifier "retry"
+                                BlockItem
+                                    Label Default
+                BlockItem
+                    Label
+                        Identifier "done"
+===*/
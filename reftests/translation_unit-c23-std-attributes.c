@@ -0,0 +1,270 @@
+#pragma c23
+[[nodiscard, gnu::always_inline]] static inline int f(int x [[maybe_unused]], [[maybe_unused]] int y);
+int [[gnu::aligned(8)]] a [[deprecated("use b")]], * [[clang::nonnull]] p;
+struct [[deprecated]] s {
+    [[maybe_unused]] int m [[gnu::packed]] : 3;
+};
+enum [[nodiscard]] e { A [[deprecated]] = 1, B };
+void g(void) [[noreturn]];
+[[omp::directive(parallel for, schedule(static))]];
+int h(int n) {
+    [[maybe_unused]] int x;
+    [[likely]] if (n) n++;
+    switch (n) {
+    case 1:
+        n++;
+        [[fallthrough]];
+    [[maybe_unused]] default:
+        break;
+    }
+    [[]] return n;
+}
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension
+                    StdAttribute
+                        Identifier "nodiscard"
+                Extension
+                    StdAttribute
+                        Identifier "gnu"
+                        Identifier "always_inline"
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                FunctionSpecifier Inline
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "f"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "x"
+                                    Extension
+                                        StdAttribute
+                                            Identifier "maybe_unused"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    Extension
+                                        StdAttribute
+                                            Identifier "maybe_unused"
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "y"
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            DeclarationSpecifier
+                Extension
+                    StdAttribute
+                        Identifier "gnu"
+                        Identifier "aligned"
+                        StdAttributeArguments Expressions
+                            Expression
+                                Constant
+                                    Integer "8"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+                    Extension
+                        StdAttribute
+                            Identifier "deprecated"
+                            StdAttributeArguments Expressions
+                                Expression
+                                    StringLiteral ["\"use b\""]
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                    DerivedDeclarator Pointer
+                        PointerQualifier
+                            Extension
+                                StdAttribute
+                                    Identifier "clang"
+                                    Identifier "nonnull"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Extension
+                            StdAttribute
+                                Identifier "deprecated"
+                        Identifier "s"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    Extension
+                                        StdAttribute
+                                            Identifier "maybe_unused"
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "m"
+                                        Extension
+                                            StdAttribute
+                                                Identifier "gnu"
+                                                Identifier "packed"
+                                    Expression
+                                        Constant
+                                            Integer "3"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Extension
+                            StdAttribute
+                                Identifier "nodiscard"
+                        Identifier "e"
+                        Enumerator
+                            Identifier "A"
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            Extension
+                                StdAttribute
+                                    Identifier "deprecated"
+                        Enumerator
+                            Identifier "B"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "g"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+                    Extension
+                        StdAttribute
+                            Identifier "noreturn"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension
+                    StdAttribute
+                        Identifier "omp"
+                        Identifier "directive"
+                        StdAttributeArguments "parallel for, schedule(static)"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "h"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            Extension
+                                StdAttribute
+                                    Identifier "maybe_unused"
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                BlockItem
+                    Statement
+                        AttributedStatement
+                            Extension
+                                StdAttribute
+                                    Identifier "likely"
+                            Statement
+                                IfStatement
+                                    Expression
+                                        Identifier "n"
+                                    Statement
+                                        Expression
+                                            UnaryOperatorExpression
+                                                Expression
+                                                    Identifier "n"
+                                                UnaryOperator PostIncrement
+                BlockItem
+                    Statement
+                        SwitchStatement
+                            Expression
+                                Identifier "n"
+                            Statement Compound
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Expression
+                                                    Constant
+                                                        Integer "1"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                            Statement
+                                                Expression
+                                                    UnaryOperatorExpression
+                                                        Expression
+                                                            Identifier "n"
+                                                        UnaryOperator PostIncrement
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            Extension
+                                                StdAttribute
+                                                    Identifier "fallthrough"
+                                BlockItem
+                                    Statement
+                                        AttributedStatement
+                                            Extension
+                                                StdAttribute
+                                                    Identifier "maybe_unused"
+                                            Statement
+                                                LabeledStatement
+                                                    Label Default
+                                                    Statement Break
+                BlockItem
+                    Statement
+                        AttributedStatement
+                            Statement Return
+                                Expression
+                                    Identifier "n"
+===*/
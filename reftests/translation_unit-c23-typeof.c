@@ -0,0 +1,116 @@
+#pragma c23
+const int a[2];
+typeof(a) b[3];
+typeof(int *) p;
+typeof(typeof(p)) q;
+typeof_unqual(a[0]) c;
+static typeof_unqual(const int) d;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "2"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    TypeOf
+                        Expression
+                            Identifier "a"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "b"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "3"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    TypeOf
+                        TypeName
+                            SpecifierQualifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind Abstract
+                                DerivedDeclarator Pointer
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    TypeOf
+                        TypeName
+                            SpecifierQualifier
+                                TypeSpecifier
+                                    TypeOf
+                                        Expression
+                                            Identifier "p"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "q"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypeOfUnqual
+                    TypeOf
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "a"
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                BinaryOperator Index
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "c"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                TypeSpecifier TypeOfUnqual
+                    TypeOf
+                        TypeName
+                            SpecifierQualifier
+                                TypeQualifier Const
+                            SpecifierQualifier
+                                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "d"
+===*/
@@ -0,0 +1,80 @@
+#pragma c23
+#pragma typedef va_list
+int printf_like(...);
+int sum(...) {
+    va_list ap;
+    va_start(ap);
+    return 0;
+}
+void g(int, char *) { }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "printf_like"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            Ellipsis Some
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "sum"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        Ellipsis Some
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier TypedefName
+                                Identifier "va_list"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "ap"
+                BlockItem
+                    Statement
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "va_start"
+                                Expression
+                                    Identifier "ap"
+                BlockItem
+                    Statement Return
+                        Expression
+                            Constant
+                                Integer "0"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "g"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Char
+                            Declarator
+                                DeclaratorKind Abstract
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+===*/
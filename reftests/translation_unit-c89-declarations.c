@@ -0,0 +1,66 @@
+#pragma c89
+int restrict(int inline) {
+    int x;
+    long y;
+    x = inline;
+    y = x;
+    return y;
+}
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "restrict"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "inline"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Long
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "x"
+                                Expression
+                                    Identifier "inline"
+                                BinaryOperator Assign
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "y"
+                                Expression
+                                    Identifier "x"
+                                BinaryOperator Assign
+                BlockItem
+                    Statement Return
+                        Expression
+                            Identifier "y"
+===*/
@@ -0,0 +1,6 @@
+#pragma c89
+int sys$open;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,8 @@
+#pragma c89
+void f(void) {
+    f();
+    int x;
+}
+/*===
+~ERROR
+===*/
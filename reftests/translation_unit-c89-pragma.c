@@ -0,0 +1,51 @@
+#pragma c89
+void f() {
+    int a;
+#pragma weak b
+    int b;
+    a = b;
+#pragma inline
+}
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator KRFunction
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                BlockItem
+                    Statement
+                        Pragma "weak b"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "a"
+                                Expression
+                                    Identifier "b"
+                                BinaryOperator Assign
+                BlockItem
+                    Statement
+                        Pragma "inline"
+===*/
@@ -0,0 +1,5 @@
+#pragma c99
+_Static_assert(1, "true");
+/*===
+~ERROR
+===*/
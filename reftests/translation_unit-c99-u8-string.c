@@ -0,0 +1,6 @@
+#pragma c99
+const char *s = u8"utf8";
+
+/*===
+~ERROR
+===*/
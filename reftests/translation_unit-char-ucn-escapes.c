@@ -0,0 +1,28 @@
+int e = L'\u00e9';
+int g = U'\U0001F600';
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "e"
+                Initializer
+                    Expression
+                        Constant Character L'\u00e9'
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "g"
+                Initializer
+                    Expression
+                        Constant Character U'\U0001F600'
+===*/
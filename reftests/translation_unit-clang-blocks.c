@@ -0,0 +1,370 @@
+#pragma clang
+typedef int (^cb)(void);
+typedef struct dispatch_queue_s *dispatch_queue_t;
+void dispatch_async(dispatch_queue_t queue, void (^block)(void));
+int run(dispatch_queue_t q, cb c) {
+    __block int count = 0;
+    void (^handler)(int) = ^(int x) { count += x; };
+    cb twice = ^int (void) { return c() * 2; };
+    char *(^name)(void) = ^char *{ return 0; };
+    dispatch_async(q, ^{
+        handler(count);
+    });
+    dispatch_async(q, ^() { count++; });
+    int (^scale)(int) = ^(int cb) { return cb * 2; };
+    return twice() + ^(int n) { cb inner = c; return n + inner(); }(1);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "cb"
+                            DerivedDeclarator Block
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "dispatch_queue_s"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "dispatch_queue_t"
+                    DerivedDeclarator Pointer
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "dispatch_async"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier TypedefName
+                                        Identifier "dispatch_queue_t"
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "queue"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "block"
+                                            DerivedDeclarator Block
+                                    DerivedDeclarator
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                            Ellipsis None
+                            Ellipsis None
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "run"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier TypedefName
+                                    Identifier "dispatch_queue_t"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "q"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier TypedefName
+                                    Identifier "cb"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "c"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Block
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "count"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Void
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "handler"
+                                        DerivedDeclarator Block
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Int
+                                        Ellipsis None
+                            Initializer
+                                Expression
+                                    BlockExpression
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Int
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Identifier "x"
+                                            Ellipsis None
+                                        Statement Compound
+                                            BlockItem
+                                                Statement
+                                                    Expression
+                                                        BinaryOperatorExpression
+                                                            Expression
+                                                                Identifier "count"
+                                                            Expression
+                                                                Identifier "x"
+                                                            BinaryOperator AssignPlus
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier TypedefName
+                                Identifier "cb"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "twice"
+                            Initializer
+                                Expression
+                                    BlockExpression
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier Int
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                            Ellipsis None
+                                        Statement Compound
+                                            BlockItem
+                                                Statement Return
+                                                    Expression
+                                                        BinaryOperatorExpression
+                                                            Expression
+                                                                CallExpression
+                                                                    Expression
+                                                                        Identifier "c"
+                                                            Expression
+                                                                Constant
+                                                                    Integer "2"
+                                                                        IntegerBase Decimal
+                                                                        IntegerSuffix false false
+                                                                            IntegerSize Int
+                                                            BinaryOperator Multiply
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Char
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "name"
+                                        DerivedDeclarator Block
+                                DerivedDeclarator Pointer
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Void
+                                        Ellipsis None
+                            Initializer
+                                Expression
+                                    BlockExpression
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier Char
+                                            Declarator
+                                                DeclaratorKind Abstract
+                                                DerivedDeclarator Pointer
+                                        Statement Compound
+                                            BlockItem
+                                                Statement Return
+                                                    Expression
+                                                        Constant
+                                                            Integer "0"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+                BlockItem
+                    Statement
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "dispatch_async"
+                                Expression
+                                    Identifier "q"
+                                Expression
+                                    BlockExpression
+                                        Statement Compound
+                                            BlockItem
+                                                Statement
+                                                    Expression
+                                                        CallExpression
+                                                            Expression
+                                                                Identifier "handler"
+                                                            Expression
+                                                                Identifier "count"
+                BlockItem
+                    Statement
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "dispatch_async"
+                                Expression
+                                    Identifier "q"
+                                Expression
+                                    BlockExpression
+                                        FunctionDeclarator
+                                            Ellipsis None
+                                        Statement Compound
+                                            BlockItem
+                                                Statement
+                                                    Expression
+                                                        UnaryOperatorExpression
+                                                            Expression
+                                                                Identifier "count"
+                                                            UnaryOperator PostIncrement
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "scale"
+                                        DerivedDeclarator Block
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Int
+                                        Ellipsis None
+                            Initializer
+                                Expression
+                                    BlockExpression
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Int
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Identifier "cb"
+                                            Ellipsis None
+                                        Statement Compound
+                                            BlockItem
+                                                Statement Return
+                                                    Expression
+                                                        BinaryOperatorExpression
+                                                            Expression
+                                                                Identifier "cb"
+                                                            Expression
+                                                                Constant
+                                                                    Integer "2"
+                                                                        IntegerBase Decimal
+                                                                        IntegerSuffix false false
+                                                                            IntegerSize Int
+                                                            BinaryOperator Multiply
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "twice"
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            BlockExpression
+                                                FunctionDeclarator
+                                                    ParameterDeclaration
+                                                        DeclarationSpecifier
+                                                            TypeSpecifier Int
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "n"
+                                                    Ellipsis None
+                                                Statement Compound
+                                                    BlockItem
+                                                        Declaration
+                                                            DeclarationSpecifier
+                                                                TypeSpecifier TypedefName
+                                                                    Identifier "cb"
+                                                            InitDeclarator
+                                                                Declarator
+                                                                    DeclaratorKind
+                                                                        Identifier "inner"
+                                                                Initializer
+                                                                    Expression
+                                                                        Identifier "c"
+                                                    BlockItem
+                                                        Statement Return
+                                                            Expression
+                                                                BinaryOperatorExpression
+                                                                    Expression
+                                                                        Identifier "n"
+                                                                    Expression
+                                                                        CallExpression
+                                                                            Expression
+                                                                                Identifier "inner"
+                                                                    BinaryOperator Plus
+                                        Expression
+                                            Constant
+                                                Integer "1"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                BinaryOperator Plus
+===*/
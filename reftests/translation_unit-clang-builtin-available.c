@@ -0,0 +1,59 @@
+#pragma clang
+int f(void) {
+    if (__builtin_available(macOS 12.0, iOS 15.0.1, watchos 8, *)) {
+        return 1;
+    }
+    return __builtin_available(*) && !__builtin_available(tvOS 10.15, *);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        IfStatement
+                            Expression
+                                AvailabilityCondition 12.0
+                                    Identifier "macOS"
+                                AvailabilityCondition 15.0.1
+                                    Identifier "iOS"
+                                AvailabilityCondition 8
+                                    Identifier "watchos"
+                                AvailabilityCondition *
+                            Statement Compound
+                                BlockItem
+                                    Statement Return
+                                        Expression
+                                            Constant
+                                                Integer "1"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    AvailabilityCondition *
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Negate
+                                        Expression
+                                            AvailabilityCondition 10.15
+                                                Identifier "tvOS"
+                                            AvailabilityCondition *
+                                BinaryOperator LogicalAnd
+===*/
@@ -0,0 +1,17 @@
+#pragma clang
+int $start = sys$open;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "$start"
+                Initializer
+                    Expression
+                        Identifier "sys$open"
+===*/
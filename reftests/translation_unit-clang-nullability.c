@@ -0,0 +1,262 @@
+#pragma clang
+// From the macOS SDK <stdlib.h> and <dispatch/queue.h>, after preprocessing
+int main(int argc, char * _Nullable * _Nonnull argv);
+extern char * _Nullable getenv(const char * _Nonnull);
+typedef void (* _Nonnull dispatch_function_t)(void * _Nullable);
+int heapsort(void * _Nonnull __base, unsigned long __nel, unsigned long __width,
+    int (* _Nonnull __compar)(const void * _Nonnull, const void * _Nonnull));
+int posix_memalign(void * _Nullable * _Nonnull __memptr, unsigned long __alignment, unsigned long __size);
+void fill(int values[_Nonnull static 4], char * _Null_unspecified names[_Nullable]);
+void * _Nullable const volatile * _Null_unspecified p;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "main"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "argc"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Char
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "argv"
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nullable
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nonnull
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Extern
+            DeclarationSpecifier
+                TypeSpecifier Char
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "getenv"
+                    DerivedDeclarator Pointer
+                        PointerQualifier
+                            TypeQualifier Nullable
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeQualifier Const
+                                DeclarationSpecifier
+                                    TypeSpecifier Char
+                                Declarator
+                                    DeclaratorKind Abstract
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nonnull
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "dispatch_function_t"
+                            DerivedDeclarator Pointer
+                                PointerQualifier
+                                    TypeQualifier Nonnull
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind Abstract
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nullable
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "heapsort"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "__base"
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nonnull
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "__nel"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "__width"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "__compar"
+                                            DerivedDeclarator Pointer
+                                                PointerQualifier
+                                                    TypeQualifier Nonnull
+                                    DerivedDeclarator
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeQualifier Const
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                                Declarator
+                                                    DeclaratorKind Abstract
+                                                    DerivedDeclarator Pointer
+                                                        PointerQualifier
+                                                            TypeQualifier Nonnull
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeQualifier Const
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                                Declarator
+                                                    DeclaratorKind Abstract
+                                                    DerivedDeclarator Pointer
+                                                        PointerQualifier
+                                                            TypeQualifier Nonnull
+                                            Ellipsis None
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "posix_memalign"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "__memptr"
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nullable
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Nonnull
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "__alignment"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "__size"
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "fill"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "values"
+                                    DerivedDeclarator
+                                        ArrayDeclarator
+                                            TypeQualifier Nonnull
+                                            ArraySize StaticExpression
+                                                Expression
+                                                    Constant
+                                                        Integer "4"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Char
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "names"
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier NullUnspecified
+                                    DerivedDeclarator
+                                        ArrayDeclarator
+                                            TypeQualifier Nullable
+                                            ArraySize Unknown
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                    DerivedDeclarator Pointer
+                        PointerQualifier
+                            TypeQualifier Nullable
+                        PointerQualifier
+                            TypeQualifier Const
+                        PointerQualifier
+                            TypeQualifier Volatile
+                    DerivedDeclarator Pointer
+                        PointerQualifier
+                            TypeQualifier NullUnspecified
+===*/
@@ -0,0 +1,26 @@
+#pragma clang
+__fp16 h = (__fp16)1.0;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier __fp16
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "h"
+                Initializer
+                    Expression
+                        CastExpression
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier __fp16
+                            Expression
+                                Constant
+                                    Float "1.0"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Double
+===*/
@@ -0,0 +1,6 @@
+struct kref { int refcount; };
+struct kref k = { refcount: 1 };
+
+/*===
+~ERROR
+===*/
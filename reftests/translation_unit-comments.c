@@ -0,0 +1,30 @@
+/* leading
+ * comment */ int /* inline */ x; /* trailing */
+int *y /**/ = &x; /* multi
+line */
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "y"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        UnaryOperatorExpression
+                            UnaryOperator Address
+                            Expression
+                                Identifier "x"
+===*/
@@ -0,0 +1,355 @@
+#pragma cuda
+#pragma typedef cudaStream_t
+__constant__ float scale;
+
+__device__ __host__ static inline float twice(float x) {
+    return 2 * x;
+}
+
+__global__ void __launch_bounds__(256, 2) saxpy(int n, float a, const float *x, float *y) {
+    __shared__ float tile[256];
+    int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i < n)
+        y[i] = a * x[i] + y[i] * scale;
+}
+
+void run(int n, float *x, float *y, cudaStream_t stream) {
+    saxpy<<<(n + 255) / 256, 256>>>(n, 2.0f, x, y);
+    saxpy<<<n >> 8, 256, 0, stream>>>(n, twice(1.0f), x, y);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension __constant__
+            DeclarationSpecifier
+                TypeSpecifier Float
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "scale"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                Extension __device__
+            DeclarationSpecifier
+                Extension __host__
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                FunctionSpecifier Inline
+            DeclarationSpecifier
+                TypeSpecifier Float
+            Declarator
+                DeclaratorKind
+                    Identifier "twice"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Constant
+                                        Integer "2"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                Expression
+                                    Identifier "x"
+                                BinaryOperator Multiply
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                Extension __global__
+            DeclarationSpecifier
+                TypeSpecifier Void
+            DeclarationSpecifier
+                Extension __launch_bounds__
+                    Expression
+                        Constant
+                            Integer "256"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+                    Expression
+                        Constant
+                            Integer "2"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            Declarator
+                DeclaratorKind
+                    Identifier "saxpy"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeQualifier Const
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                                DerivedDeclarator Pointer
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            Extension __shared__
+                        DeclarationSpecifier
+                            TypeSpecifier Float
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "tile"
+                                DerivedDeclarator
+                                    ArrayDeclarator
+                                        ArraySize VariableExpression
+                                            Expression
+                                                Constant
+                                                    Integer "256"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "i"
+                            Initializer
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    MemberExpression
+                                                        MemberOperator Direct
+                                                        Expression
+                                                            Identifier "blockIdx"
+                                                        Identifier "x"
+                                                Expression
+                                                    MemberExpression
+                                                        MemberOperator Direct
+                                                        Expression
+                                                            Identifier "blockDim"
+                                                        Identifier "x"
+                                                BinaryOperator Multiply
+                                        Expression
+                                            MemberExpression
+                                                MemberOperator Direct
+                                                Expression
+                                                    Identifier "threadIdx"
+                                                Identifier "x"
+                                        BinaryOperator Plus
+                BlockItem
+                    Statement
+                        IfStatement
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    Expression
+                                        Identifier "n"
+                                    BinaryOperator Less
+                            Statement
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "y"
+                                                Expression
+                                                    Identifier "i"
+                                                BinaryOperator Index
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            Identifier "a"
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "x"
+                                                                Expression
+                                                                    Identifier "i"
+                                                                BinaryOperator Index
+                                                        BinaryOperator Multiply
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "y"
+                                                                Expression
+                                                                    Identifier "i"
+                                                                BinaryOperator Index
+                                                        Expression
+                                                            Identifier "scale"
+                                                        BinaryOperator Multiply
+                                                BinaryOperator Plus
+                                        BinaryOperator Assign
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "run"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                                DerivedDeclarator Pointer
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                                DerivedDeclarator Pointer
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier TypedefName
+                                    Identifier "cudaStream_t"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "stream"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        Expression
+                            KernelCallExpression Configuration 2
+                                Expression
+                                    Identifier "saxpy"
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "n"
+                                                Expression
+                                                    Constant
+                                                        Integer "255"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                                BinaryOperator Plus
+                                        Expression
+                                            Constant
+                                                Integer "256"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                        BinaryOperator Divide
+                                Expression
+                                    Constant
+                                        Integer "256"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                Expression
+                                    Identifier "n"
+                                Expression
+                                    Constant
+                                        Float "2.0"
+                                            FloatBase Decimal
+                                            FloatSuffix false
+                                                FloatFormat Float
+                                Expression
+                                    Identifier "x"
+                                Expression
+                                    Identifier "y"
+                BlockItem
+                    Statement
+                        Expression
+                            KernelCallExpression Configuration 4
+                                Expression
+                                    Identifier "saxpy"
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "n"
+                                        Expression
+                                            Constant
+                                                Integer "8"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                        BinaryOperator ShiftRight
+                                Expression
+                                    Constant
+                                        Integer "256"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                Expression
+                                    Identifier "stream"
+                                Expression
+                                    Identifier "n"
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "twice"
+                                        Expression
+                                            Constant
+                                                Float "1.0"
+                                                    FloatBase Decimal
+                                                    FloatSuffix false
+                                                        FloatFormat Float
+                                Expression
+                                    Identifier "x"
+                                Expression
+                                    Identifier "y"
+===*/
@@ -0,0 +1,8 @@
+#pragma cuda
+void run(int n) {
+    kernel<<<1, 2, 3, 4, 5>>>(n);
+}
+
+/*===
+~ERROR
+===*/
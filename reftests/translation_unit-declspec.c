@@ -0,0 +1,5 @@
+__declspec(dllimport) int __stdcall f(void);
+
+/*===
+~ERROR
+===*/
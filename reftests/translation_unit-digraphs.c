@@ -0,0 +1,197 @@
+%:pragma pack(1)
+int a<:3:> = <% 1, 2, <:2:> = 3 %>;
+struct s <% int m<:2:>; %> v = <% .m<:1:> = 4 %>;
+int f(int b<:static 1:>) <%
+    if (b<:0:> < a<:1:>) <% return b<:0:> %a<:2:>; %>
+    return 0;
+%>
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Pragma "pack(1)"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "3"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                    InitializerListItem
+                        Designator
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "3"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "s"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "m"
+                                        DerivedDeclarator
+                                            ArrayDeclarator
+                                                ArraySize VariableExpression
+                                                    Expression
+                                                        Constant
+                                                            Integer "2"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "v"
+                Initializer
+                    InitializerListItem
+                        Designator
+                            Identifier "m"
+                        Designator
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                        Initializer
+                            Expression
+                                Constant
+                                    Integer "4"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                                DerivedDeclarator
+                                    ArrayDeclarator
+                                        ArraySize StaticExpression
+                                            Expression
+                                                Constant
+                                                    Integer "1"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        IfStatement
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                Identifier "b"
+                                            Expression
+                                                Constant
+                                                    Integer "0"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                                            BinaryOperator Index
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                Identifier "a"
+                                            Expression
+                                                Constant
+                                                    Integer "1"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                                            BinaryOperator Index
+                                    BinaryOperator Less
+                            Statement Compound
+                                BlockItem
+                                    Statement Return
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            Identifier "b"
+                                                        Expression
+                                                            Constant
+                                                                Integer "0"
+                                                                    IntegerBase Decimal
+                                                                    IntegerSuffix false false
+                                                                        IntegerSize Int
+                                                        BinaryOperator Index
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            Identifier "a"
+                                                        Expression
+                                                            Constant
+                                                                Integer "2"
+                                                                    IntegerBase Decimal
+                                                                    IntegerSuffix false false
+                                                                        IntegerSize Int
+                                                        BinaryOperator Index
+                                                BinaryOperator Modulo
+                BlockItem
+                    Statement Return
+                        Expression
+                            Constant
+                                Integer "0"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+===*/
@@ -0,0 +1,5 @@
+int sys$open;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+struct empty {};
+
+/*===
+~ERROR
+===*/
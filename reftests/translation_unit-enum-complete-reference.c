@@ -0,0 +1,50 @@
+enum fruit { APPLE, PEAR };
+enum fruit;
+void f(void) {
+    enum fruit *basket;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "fruit"
+                        Enumerator
+                            Identifier "APPLE"
+                        Enumerator
+                            Identifier "PEAR"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "fruit"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier
+                                EnumType
+                                    Identifier "fruit"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "basket"
+                                DerivedDeclarator Pointer
+===*/
@@ -0,0 +1,5 @@
+enum fruit;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+enum e { A } __attribute__((packed));
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,8 @@
+void (*g(int x))(enum h { B } y)
+{
+    enum h z = B;
+}
+
+/*===
+~ERROR
+===*/
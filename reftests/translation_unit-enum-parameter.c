@@ -0,0 +1,108 @@
+void f(enum e { A } x)
+{
+    enum e y = A;
+}
+
+void (*g(enum h { B } x))(int (*)(enum i { C } z))
+{
+    enum h y = B;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier
+                                    EnumType
+                                        Identifier "e"
+                                        Enumerator
+                                            Identifier "A"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier
+                                EnumType
+                                    Identifier "e"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                            Initializer
+                                Expression
+                                    Identifier "A"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Declarator
+                        DeclaratorKind
+                            Identifier "g"
+                        DerivedDeclarator Pointer
+                        DerivedDeclarator
+                            FunctionDeclarator
+                                ParameterDeclaration
+                                    DeclarationSpecifier
+                                        TypeSpecifier
+                                            EnumType
+                                                Identifier "h"
+                                                Enumerator
+                                                    Identifier "B"
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "x"
+                                Ellipsis None
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Declarator
+                                        DeclaratorKind Abstract
+                                        DerivedDeclarator Pointer
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier
+                                                    EnumType
+                                                        Identifier "i"
+                                                        Enumerator
+                                                            Identifier "C"
+                                            Declarator
+                                                DeclaratorKind
+                                                    Identifier "z"
+                                        Ellipsis None
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier
+                                EnumType
+                                    Identifier "h"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                            Initializer
+                                Expression
+                                    Identifier "B"
+===*/
@@ -0,0 +1,5 @@
+enum Color : unsigned char { RED, GREEN };
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+asm(".symver foo_v1, foo@VERS_1");
+
+/*===
+~ERROR
+===*/
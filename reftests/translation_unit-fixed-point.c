@@ -0,0 +1,5 @@
+_Fract f = 0.5r;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,141 @@
+#pragma gnu
+// arch_static_branch from the Linux kernel, after preprocessing
+static inline _Bool arch_static_branch(struct static_key *key, _Bool branch) {
+    asm goto("1: .byte 0x0f,0x1f,0x44,0x00,0\n\t"
+             ".pushsection __jump_table, \"aw\"\n\t"
+             ".popsection\n\t"
+             : : "i" (key), "i" (branch) : : l_yes);
+    return 0;
+l_yes:
+    return 1;
+}
+
+int f(int x) {
+    __asm__ volatile goto("jmp %l0" :::: out);
+    asm goto("" ::: "memory" : out, again);
+again:
+    return x;
+out:
+    return 0;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                FunctionSpecifier Inline
+            DeclarationSpecifier
+                TypeSpecifier
+            Declarator
+                DeclaratorKind
+                    Identifier "arch_static_branch"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier
+                                    StructType
+                                        StructKind Struct
+                                        Identifier "static_key"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "key"
+                                DerivedDeclarator Pointer
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "branch"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        AsmStatement
+                            GnuExtendedAsmStatement goto
+                                StringLiteral ["\"1: .byte 0x0f,0x1f,0x44,0x00,0\\n\\t\"", "\".pushsection __jump_table, \\\"aw\\\"\\n\\t\"", "\".popsection\\n\\t\""]
+                                GnuAsmOperand
+                                    StringLiteral ["\"i\""]
+                                    Expression
+                                        Identifier "key"
+                                GnuAsmOperand
+                                    StringLiteral ["\"i\""]
+                                    Expression
+                                        Identifier "branch"
+                                Identifier "l_yes"
+                BlockItem
+                    Statement Return
+                        Expression
+                            Constant
+                                Integer "0"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "l_yes"
+                            Statement Return
+                                Expression
+                                    Constant
+                                        Integer "1"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        AsmStatement
+                            GnuExtendedAsmStatement goto
+                                TypeQualifier Volatile
+                                StringLiteral ["\"jmp %l0\""]
+                                Identifier "out"
+                BlockItem
+                    Statement
+                        AsmStatement
+                            GnuExtendedAsmStatement goto
+                                StringLiteral ["\"\""]
+                                StringLiteral ["\"memory\""]
+                                Identifier "out"
+                                Identifier "again"
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "again"
+                            Statement Return
+                                Expression
+                                    Identifier "x"
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "out"
+                            Statement Return
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+===*/
@@ -0,0 +1,118 @@
+#pragma gnu
+__attribute__((visibility("hidden")));
+int f(int x) {
+    switch (x) {
+    case 1:
+        x++;
+        __attribute__((fallthrough));
+    case 2:
+        x--;
+        [[fallthrough]];
+    case 3:
+        __attribute__((fallthrough)) __attribute__((unused));
+    default:
+        break;
+    }
+    return x;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension
+                    Attribute "visibility"
+                        Expression
+                            StringLiteral ["\"hidden\""]
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        SwitchStatement
+                            Expression
+                                Identifier "x"
+                            Statement Compound
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Expression
+                                                    Constant
+                                                        Integer "1"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                            Statement
+                                                Expression
+                                                    UnaryOperatorExpression
+                                                        Expression
+                                                            Identifier "x"
+                                                        UnaryOperator PostIncrement
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            Extension
+                                                Attribute "fallthrough"
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Expression
+                                                    Constant
+                                                        Integer "2"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                            Statement
+                                                Expression
+                                                    UnaryOperatorExpression
+                                                        Expression
+                                                            Identifier "x"
+                                                        UnaryOperator PostDecrement
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            Extension
+                                                StdAttribute
+                                                    Identifier "fallthrough"
+                                BlockItem
+                                    Label
+                                        Expression
+                                            Constant
+                                                Integer "3"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            Extension
+                                                Attribute "fallthrough"
+                                            Extension
+                                                Attribute "unused"
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label Default
+                                            Statement Break
+                BlockItem
+                    Statement Return
+                        Expression
+                            Identifier "x"
+===*/
@@ -0,0 +1,154 @@
+#pragma gnu
+// min() and max() from the Linux kernel, after preprocessing
+int f(int a, long b) {
+    int m = ({ __auto_type __x = (a); __auto_type __y = (b); __x < __y ? __x : __y; });
+    const __auto_type p = &a, q = (b);
+    static __auto_type __attribute__((unused)) n = ({ __auto_type __x = (a); __x; });
+    return m + *p + q;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Long
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "m"
+                            Initializer
+                                Expression
+                                    Statement Compound
+                                        BlockItem
+                                            Declaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier AutoType
+                                                InitDeclarator
+                                                    Declarator
+                                                        DeclaratorKind
+                                                            Identifier "__x"
+                                                    Initializer
+                                                        Expression
+                                                            Identifier "a"
+                                        BlockItem
+                                            Declaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier AutoType
+                                                InitDeclarator
+                                                    Declarator
+                                                        DeclaratorKind
+                                                            Identifier "__y"
+                                                    Initializer
+                                                        Expression
+                                                            Identifier "b"
+                                        BlockItem
+                                            Statement
+                                                Expression
+                                                    ConditionalExpression
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "__x"
+                                                                Expression
+                                                                    Identifier "__y"
+                                                                BinaryOperator Less
+                                                        Expression
+                                                            Identifier "__x"
+                                                        Expression
+                                                            Identifier "__y"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeQualifier Const
+                        DeclarationSpecifier
+                            TypeSpecifier AutoType
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "p"
+                            Initializer
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Address
+                                        Expression
+                                            Identifier "a"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "q"
+                            Initializer
+                                Expression
+                                    Identifier "b"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Static
+                        DeclarationSpecifier
+                            TypeSpecifier AutoType
+                        DeclarationSpecifier
+                            Extension
+                                Attribute "unused"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                            Initializer
+                                Expression
+                                    Statement Compound
+                                        BlockItem
+                                            Declaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier AutoType
+                                                InitDeclarator
+                                                    Declarator
+                                                        DeclaratorKind
+                                                            Identifier "__x"
+                                                    Initializer
+                                                        Expression
+                                                            Identifier "a"
+                                        BlockItem
+                                            Statement
+                                                Expression
+                                                    Identifier "__x"
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "m"
+                                        Expression
+                                            UnaryOperatorExpression
+                                                UnaryOperator Indirection
+                                                Expression
+                                                    Identifier "p"
+                                        BinaryOperator Plus
+                                Expression
+                                    Identifier "q"
+                                BinaryOperator Plus
+===*/
@@ -0,0 +1,135 @@
+#pragma gnu
+int classify(int c, int d) {
+    switch (c) {
+    case '0' ... '9':
+        switch (d) {
+        case 0...3: return 1;
+        case 4 ... 7: return 2;
+        }
+        break;
+    case 'a'...'z':
+        return 3;
+    }
+    return 0;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "classify"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "c"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "d"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        SwitchStatement
+                            Expression
+                                Identifier "c"
+                            Statement Compound
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                CaseRange
+                                                    Expression
+                                                        Constant Character '0'
+                                                    Expression
+                                                        Constant Character '9'
+                                            Statement
+                                                SwitchStatement
+                                                    Expression
+                                                        Identifier "d"
+                                                    Statement Compound
+                                                        BlockItem
+                                                            Statement
+                                                                LabeledStatement
+                                                                    Label
+                                                                        CaseRange
+                                                                            Expression
+                                                                                Constant
+                                                                                    Integer "0"
+                                                                                        IntegerBase Decimal
+                                                                                        IntegerSuffix false false
+                                                                                            IntegerSize Int
+                                                                            Expression
+                                                                                Constant
+                                                                                    Integer "3"
+                                                                                        IntegerBase Decimal
+                                                                                        IntegerSuffix false false
+                                                                                            IntegerSize Int
+                                                                    Statement Return
+                                                                        Expression
+                                                                            Constant
+                                                                                Integer "1"
+                                                                                    IntegerBase Decimal
+                                                                                    IntegerSuffix false false
+                                                                                        IntegerSize Int
+                                                        BlockItem
+                                                            Statement
+                                                                LabeledStatement
+                                                                    Label
+                                                                        CaseRange
+                                                                            Expression
+                                                                                Constant
+                                                                                    Integer "4"
+                                                                                        IntegerBase Decimal
+                                                                                        IntegerSuffix false false
+                                                                                            IntegerSize Int
+                                                                            Expression
+                                                                                Constant
+                                                                                    Integer "7"
+                                                                                        IntegerBase Decimal
+                                                                                        IntegerSuffix false false
+                                                                                            IntegerSize Int
+                                                                    Statement Return
+                                                                        Expression
+                                                                            Constant
+                                                                                Integer "2"
+                                                                                    IntegerBase Decimal
+                                                                                    IntegerSuffix false false
+                                                                                        IntegerSize Int
+                                BlockItem
+                                    Statement Break
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                CaseRange
+                                                    Expression
+                                                        Constant Character 'a'
+                                                    Expression
+                                                        Constant Character 'z'
+                                            Statement Return
+                                                Expression
+                                                    Constant
+                                                        Integer "3"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                BlockItem
+                    Statement Return
+                        Expression
+                            Constant
+                                Integer "0"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+===*/
@@ -0,0 +1,17 @@
+#pragma gnu
+char esc = '\e';
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Char
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "esc"
+                Initializer
+                    Expression
+                        Constant Character '\e'
+===*/
@@ -0,0 +1,97 @@
+#pragma gnu
+double d;
+// tgmath.h style dispatch on the argument type
+double r = __builtin_choose_expr(__builtin_types_compatible_p(typeof(d), float), 1.0f,
+    __builtin_choose_expr(__builtin_types_compatible_p(typeof(d), double), 2.0, 3.0L));
+// wrong number of arguments is kept as a call
+int c = __builtin_choose_expr(1, 2);
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Double
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "d"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Double
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "r"
+                Initializer
+                    Expression
+                        ChooseExpression
+                            Expression
+                                TypesCompatibleExpression
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier
+                                                TypeOf
+                                                    Expression
+                                                        Identifier "d"
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier Float
+                            Expression
+                                Constant
+                                    Float "1.0"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Float
+                            Expression
+                                ChooseExpression
+                                    Expression
+                                        TypesCompatibleExpression
+                                            TypeName
+                                                SpecifierQualifier
+                                                    TypeSpecifier
+                                                        TypeOf
+                                                            Expression
+                                                                Identifier "d"
+                                            TypeName
+                                                SpecifierQualifier
+                                                    TypeSpecifier Double
+                                    Expression
+                                        Constant
+                                            Float "2.0"
+                                                FloatBase Decimal
+                                                FloatSuffix false
+                                                    FloatFormat Double
+                                    Expression
+                                        Constant
+                                            Float "3.0"
+                                                FloatBase Decimal
+                                                FloatSuffix false
+                                                    FloatFormat LongDouble
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "c"
+                Initializer
+                    Expression
+                        CallExpression
+                            Expression
+                                Identifier "__builtin_choose_expr"
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+===*/
@@ -0,0 +1,121 @@
+#pragma gnu
+// pre-C99 GCC syntax mixed with standard designators
+struct kref { int refcount; };
+struct object { struct kref ref; const char *name; int flags[4]; };
+struct object o = { ref: { refcount: 1 }, .name = "x", flags: { [2] = 1 } };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "kref"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "refcount"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "object"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            Identifier "kref"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "ref"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeQualifier Const
+                                SpecifierQualifier
+                                    TypeSpecifier Char
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "name"
+                                        DerivedDeclarator Pointer
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "flags"
+                                        DerivedDeclarator
+                                            ArrayDeclarator
+                                                ArraySize VariableExpression
+                                                    Expression
+                                                        Constant
+                                                            Integer "4"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "object"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "o"
+                Initializer
+                    InitializerListItem
+                        Designator
+                            Identifier "ref"
+                        Initializer
+                            InitializerListItem
+                                Designator
+                                    Identifier "refcount"
+                                Initializer
+                                    Expression
+                                        Constant
+                                            Integer "1"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                    InitializerListItem
+                        Designator
+                            Identifier "name"
+                        Initializer
+                            Expression
+                                StringLiteral ["\"x\""]
+                    InitializerListItem
+                        Designator
+                            Identifier "flags"
+                        Initializer
+                            InitializerListItem
+                                Designator
+                                    Expression
+                                        Constant
+                                            Integer "2"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                                Initializer
+                                    Expression
+                                        Constant
+                                            Integer "1"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+===*/
@@ -0,0 +1,63 @@
+#pragma gnu
+enum fruit;
+enum fruit *basket;
+void eat(enum fruit *f);
+enum fruit { APPLE, PEAR };
+enum fruit;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "fruit"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "fruit"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "basket"
+                    DerivedDeclarator Pointer
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "eat"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier
+                                        EnumType
+                                            Identifier "fruit"
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "f"
+                                    DerivedDeclarator Pointer
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "fruit"
+                        Enumerator
+                            Identifier "APPLE"
+                        Enumerator
+                            Identifier "PEAR"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "fruit"
+===*/
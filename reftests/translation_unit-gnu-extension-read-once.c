@@ -0,0 +1,110 @@
+#pragma gnu
+long long read_once(int *p) {
+    long long w = __extension__ __extension__ 0LL;
+    return __extension__ ({ __extension__ int __x = *(const volatile int *)&(*p); (int)__x; })
+        + (__extension__ (long long) w);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Long
+            Declarator
+                DeclaratorKind
+                    Identifier "read_once"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "p"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Long
+                        DeclarationSpecifier
+                            TypeSpecifier Long
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "w"
+                            Initializer
+                                Expression Extension
+                                    Expression Extension
+                                        Expression
+                                            Constant
+                                                Integer "0"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize LongLong
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression Extension
+                                    Expression
+                                        Statement Compound
+                                            BlockItem
+                                                Declaration
+                                                    DeclarationSpecifier
+                                                        Extension __extension__
+                                                    DeclarationSpecifier
+                                                        TypeSpecifier Int
+                                                    InitDeclarator
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "__x"
+                                                        Initializer
+                                                            Expression
+                                                                UnaryOperatorExpression
+                                                                    UnaryOperator Indirection
+                                                                    Expression
+                                                                        CastExpression
+                                                                            TypeName
+                                                                                SpecifierQualifier
+                                                                                    TypeQualifier Const
+                                                                                SpecifierQualifier
+                                                                                    TypeQualifier Volatile
+                                                                                SpecifierQualifier
+                                                                                    TypeSpecifier Int
+                                                                                Declarator
+                                                                                    DeclaratorKind Abstract
+                                                                                    DerivedDeclarator Pointer
+                                                                            Expression
+                                                                                UnaryOperatorExpression
+                                                                                    UnaryOperator Address
+                                                                                    Expression
+                                                                                        UnaryOperatorExpression
+                                                                                            UnaryOperator Indirection
+                                                                                            Expression
+                                                                                                Identifier "p"
+                                            BlockItem
+                                                Statement
+                                                    Expression
+                                                        CastExpression
+                                                            TypeName
+                                                                SpecifierQualifier
+                                                                    TypeSpecifier Int
+                                                            Expression
+                                                                Identifier "__x"
+                                Expression Extension
+                                    Expression
+                                        CastExpression
+                                            TypeName
+                                                SpecifierQualifier
+                                                    TypeSpecifier Long
+                                                SpecifierQualifier
+                                                    TypeSpecifier Long
+                                            Expression
+                                                Identifier "w"
+                                BinaryOperator Plus
+===*/
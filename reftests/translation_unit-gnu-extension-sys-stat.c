@@ -0,0 +1,133 @@
+#pragma gnu
+__extension__ typedef unsigned long long int __dev_t;
+__extension__ typedef long int __blksize_t;
+struct stat {
+    __dev_t st_dev;
+    __extension__ __extension__ unsigned long long int __glibc_reserved;
+};
+__extension__ extern __inline __attribute__ ((__gnu_inline__)) int
+fstat (int __fd, struct stat *__statbuf)
+{
+  return __fxstat (1, __fd, __statbuf);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension __extension__
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "__dev_t"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension __extension__
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "__blksize_t"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "stat"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier TypedefName
+                                        Identifier "__dev_t"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "st_dev"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    Extension __extension__
+                                    Extension __extension__
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "__glibc_reserved"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                Extension __extension__
+            DeclarationSpecifier
+                StorageClassSpecifier Extern
+            DeclarationSpecifier
+                FunctionSpecifier Inline
+            DeclarationSpecifier
+                Extension
+                    Attribute "__gnu_inline__"
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "fstat"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "__fd"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier
+                                    StructType
+                                        StructKind Struct
+                                        Identifier "stat"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "__statbuf"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "__fxstat"
+                                Expression
+                                    Constant
+                                        Integer "1"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                Expression
+                                    Identifier "__fd"
+                                Expression
+                                    Identifier "__statbuf"
+===*/
@@ -0,0 +1,7 @@
+#pragma gnu
+int x;
+asm("mov %0, %1" : "=r"(x) : "r"(x));
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,31 @@
+#pragma gnu
+asm(".symver foo_v1, foo@VERS_1");
+__asm__(".section .note.custom\n\t"
+        ".long 4\n\t"
+        ".previous");
+__asm (".globl marker");
+int foo_v1(void);
+
+/*===
+TranslationUnit
+    ExternalDeclaration Asm
+        StringLiteral ["\".symver foo_v1, foo@VERS_1\""]
+    ExternalDeclaration Asm
+        StringLiteral ["\".section .note.custom\\n\\t\"", "\".long 4\\n\\t\"", "\".previous\""]
+    ExternalDeclaration Asm
+        StringLiteral ["\".globl marker\""]
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "foo_v1"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+===*/
@@ -0,0 +1,268 @@
+#pragma gnu
+// Adapted from gcc.dg/fixed-point/constant.c and convert.c
+short _Fract sf = 0.5hr;
+_Fract f = 0.5r;
+long _Fract lf = 0.5lr;
+long long _Fract llf = 0.5llr;
+unsigned short _Fract usf = 0.25uhr;
+unsigned _Accum ua = 1.5uk;
+short _Accum sa = 1.5hk;
+long _Accum la = 2e1lk;
+_Sat unsigned short _Fract ssf = 1k;
+_Sat signed long _Accum sla;
+
+_Accum scale(_Accum x, _Sat _Fract y) {
+    return x * (_Accum)y + 0.5K - (long _Accum)3ULK;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Short
+            DeclarationSpecifier
+                TypeSpecifier Fract
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "sf"
+                Initializer
+                    Expression
+                        Constant
+                            Float "0.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Fract Short false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Fract
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "f"
+                Initializer
+                    Expression
+                        Constant
+                            Float "0.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Fract Plain false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Fract
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "lf"
+                Initializer
+                    Expression
+                        Constant
+                            Float "0.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Fract Long false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Fract
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "llf"
+                Initializer
+                    Expression
+                        Constant
+                            Float "0.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Fract LongLong false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Short
+            DeclarationSpecifier
+                TypeSpecifier Fract
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "usf"
+                Initializer
+                    Expression
+                        Constant
+                            Float "0.25"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Fract Short true
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Accum
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "ua"
+                Initializer
+                    Expression
+                        Constant
+                            Float "1.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Accum Plain true
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Short
+            DeclarationSpecifier
+                TypeSpecifier Accum
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "sa"
+                Initializer
+                    Expression
+                        Constant
+                            Float "1.5"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Accum Short false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Accum
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "la"
+                Initializer
+                    Expression
+                        Constant
+                            Float "2e1"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Accum Long false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Sat
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Short
+            DeclarationSpecifier
+                TypeSpecifier Fract
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "ssf"
+                Initializer
+                    Expression
+                        Constant
+                            Float "1"
+                                FloatBase Decimal
+                                FloatSuffix false
+                                    FloatFormat
+                                        FixedPointFormat Accum Plain false
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Sat
+            DeclarationSpecifier
+                TypeSpecifier Signed
+            DeclarationSpecifier
+                TypeSpecifier Long
+            DeclarationSpecifier
+                TypeSpecifier Accum
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "sla"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Accum
+            Declarator
+                DeclaratorKind
+                    Identifier "scale"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Accum
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Sat
+                            DeclarationSpecifier
+                                TypeSpecifier Fract
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "x"
+                                                Expression
+                                                    CastExpression
+                                                        TypeName
+                                                            SpecifierQualifier
+                                                                TypeSpecifier Accum
+                                                        Expression
+                                                            Identifier "y"
+                                                BinaryOperator Multiply
+                                        Expression
+                                            Constant
+                                                Float "0.5"
+                                                    FloatBase Decimal
+                                                    FloatSuffix false
+                                                        FloatFormat
+                                                            FixedPointFormat Accum Plain false
+                                        BinaryOperator Plus
+                                Expression
+                                    CastExpression
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier Long
+                                            SpecifierQualifier
+                                                TypeSpecifier Accum
+                                        Expression
+                                            Constant
+                                                Float "3"
+                                                    FloatBase Decimal
+                                                    FloatSuffix false
+                                                        FloatFormat
+                                                            FixedPointFormat Accum Long true
+                                BinaryOperator Minus
+===*/
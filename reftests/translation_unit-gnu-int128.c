@@ -0,0 +1,88 @@
+#pragma gnu
+typedef unsigned __int128 u128;
+const signed __int128 limit = 1;
+__int128 mul(long x, long y) {
+    return (__int128)x * y + sizeof(unsigned __int128);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier __int128
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "u128"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Signed
+            DeclarationSpecifier
+                TypeSpecifier __int128
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "limit"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier __int128
+            Declarator
+                DeclaratorKind
+                    Identifier "mul"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Long
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Long
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            CastExpression
+                                                TypeName
+                                                    SpecifierQualifier
+                                                        TypeSpecifier __int128
+                                                Expression
+                                                    Identifier "x"
+                                        Expression
+                                            Identifier "y"
+                                        BinaryOperator Multiply
+                                Expression
+                                    SizeOfTy
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier Unsigned
+                                            SpecifierQualifier
+                                                TypeSpecifier __int128
+                                BinaryOperator Plus
+===*/
@@ -0,0 +1,131 @@
+#pragma gnu
+int run(const unsigned char *code) {
+    static const void *dispatch[] = { &&op_inc, &&op_halt };
+    int acc = 0;
+    goto *dispatch[*code++];
+op_inc:
+    acc++;
+    goto *dispatch[*code++];
+op_halt:
+    return acc && &&op_inc;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "run"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeQualifier Const
+                            DeclarationSpecifier
+                                TypeSpecifier Unsigned
+                            DeclarationSpecifier
+                                TypeSpecifier Char
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "code"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Static
+                        DeclarationSpecifier
+                            TypeQualifier Const
+                        DeclarationSpecifier
+                            TypeSpecifier Void
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "dispatch"
+                                DerivedDeclarator Pointer
+                                DerivedDeclarator
+                                    ArrayDeclarator
+                                        ArraySize Unknown
+                            Initializer
+                                InitializerListItem
+                                    Initializer
+                                        Expression LabelAddress
+                                            Identifier "op_inc"
+                                InitializerListItem
+                                    Initializer
+                                        Expression LabelAddress
+                                            Identifier "op_halt"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "acc"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Statement GotoIndirect
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "dispatch"
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Indirection
+                                        Expression
+                                            UnaryOperatorExpression
+                                                Expression
+                                                    Identifier "code"
+                                                UnaryOperator PostIncrement
+                                BinaryOperator Index
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "op_inc"
+                            Statement
+                                Expression
+                                    UnaryOperatorExpression
+                                        Expression
+                                            Identifier "acc"
+                                        UnaryOperator PostIncrement
+                BlockItem
+                    Statement GotoIndirect
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "dispatch"
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Indirection
+                                        Expression
+                                            UnaryOperatorExpression
+                                                Expression
+                                                    Identifier "code"
+                                                UnaryOperator PostIncrement
+                                BinaryOperator Index
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "op_halt"
+                            Statement Return
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "acc"
+                                        Expression LabelAddress
+                                            Identifier "op_inc"
+                                        BinaryOperator LogicalAnd
+===*/
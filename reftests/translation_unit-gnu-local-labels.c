@@ -0,0 +1,127 @@
+#pragma gnu
+int f(int n) {
+    __label__ retry, done;
+retry:
+    if (n-- > 0) {
+        __label__ done;
+        goto done;
+    done:
+        ;
+    }
+    return ({
+        __label__ out;
+        int r = n;
+        if (r < 0) goto out;
+        r++;
+    out:
+        r;
+    });
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement LocalLabels
+                        Identifier "retry"
+                        Identifier "done"
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "retry"
+                            Statement
+                                IfStatement
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                UnaryOperatorExpression
+                                                    Expression
+                                                        Identifier "n"
+                                                    UnaryOperator PostDecrement
+                                            Expression
+                                                Constant
+                                                    Integer "0"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                                            BinaryOperator Greater
+                                    Statement Compound
+                                        BlockItem
+                                            Statement LocalLabels
+                                                Identifier "done"
+                                        BlockItem
+                                            Statement Goto
+                                                Identifier "done"
+                                        BlockItem
+                                            Statement
+                                                LabeledStatement
+                                                    Label
+                                                        Identifier "done"
+                                                    Statement
+                BlockItem
+                    Statement Return
+                        Expression
+                            Statement Compound
+                                BlockItem
+                                    Statement LocalLabels
+                                        Identifier "out"
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            TypeSpecifier Int
+                                        InitDeclarator
+                                            Declarator
+                                                DeclaratorKind
+                                                    Identifier "r"
+                                            Initializer
+                                                Expression
+                                                    Identifier "n"
+                                BlockItem
+                                    Statement
+                                        IfStatement
+                                            Expression
+                                                BinaryOperatorExpression
+                                                    Expression
+                                                        Identifier "r"
+                                                    Expression
+                                                        Constant
+                                                            Integer "0"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+                                                    BinaryOperator Less
+                                            Statement Goto
+                                                Identifier "out"
+                                BlockItem
+                                    Statement
+                                        Expression
+                                            UnaryOperatorExpression
+                                                Expression
+                                                    Identifier "r"
+                                                UnaryOperator PostIncrement
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Identifier "out"
+                                            Statement
+                                                Expression
+                                                    Identifier "r"
+===*/
@@ -0,0 +1,45 @@
+#pragma gnu
+#pragma ms_extensions
+struct inner { int a; };
+struct outer { struct inner; int b; };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "inner"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "a"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "outer"
+                        StructDeclaration
+                            StructField MsUnnamed
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            Identifier "inner"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "b"
+===*/
@@ -0,0 +1,165 @@
+#pragma gnu
+typedef int T;
+int outer(int n) {
+    typedef T U;
+    int total = 0;
+    auto int add(int);
+    int add(int x) {
+        U y = x;
+        total += y;
+        return total;
+    }
+    int add2(int x); { add(x); }
+    return add(n);
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "T"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "outer"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Typedef
+                        DeclarationSpecifier
+                            TypeSpecifier TypedefName
+                                Identifier "T"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "U"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "total"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Auto
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "add"
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Int
+                                        Ellipsis None
+                BlockItem
+                    Statement NestedFunction
+                        FunctionDefinition
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "add"
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Int
+                                            Declarator
+                                                DeclaratorKind
+                                                    Identifier "x"
+                                        Ellipsis None
+                            Statement Compound
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            TypeSpecifier TypedefName
+                                                Identifier "U"
+                                        InitDeclarator
+                                            Declarator
+                                                DeclaratorKind
+                                                    Identifier "y"
+                                            Initializer
+                                                Expression
+                                                    Identifier "x"
+                                BlockItem
+                                    Statement
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "total"
+                                                Expression
+                                                    Identifier "y"
+                                                BinaryOperator AssignPlus
+                                BlockItem
+                                    Statement Return
+                                        Expression
+                                            Identifier "total"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "add2"
+                                DerivedDeclarator
+                                    FunctionDeclarator
+                                        ParameterDeclaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Int
+                                            Declarator
+                                                DeclaratorKind
+                                                    Identifier "x"
+                                        Ellipsis None
+                BlockItem
+                    Statement Compound
+                        BlockItem
+                            Statement
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "add"
+                                        Expression
+                                            Identifier "x"
+                BlockItem
+                    Statement Return
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "add"
+                                Expression
+                                    Identifier "n"
+===*/
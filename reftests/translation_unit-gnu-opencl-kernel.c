@@ -0,0 +1,24 @@
+#pragma gnu
+#pragma typedef image2d_t
+#pragma typedef sampler_t
+#pragma typedef float4
+#pragma typedef int2
+constant float weights[3] = { 0.25f, 0.5f, 0.25f };
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void blur(__read_only image2d_t src, write_only image2d_t dst, sampler_t sampler,
+          __global float *out, local float *tile) {
+    int2 pos = (int2)(get_global_id(0), get_global_id(1));
+    __private float4 sum = 0;
+    int local_id = get_local_id(0);
+    for (int i = 0; i < 3; i++)
+        sum += weights[i] * read_imagef(src, sampler, pos + (int2)(i - 1, 0));
+    tile[local_id] = sum.x;
+    write_imagef(dst, pos, sum);
+}
+
+int global;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,180 @@
+#pragma gnu
+// GCC manual, "Arrays of Variable Length"
+void tester(int len; char data[len][len], int len) {}
+void grid(int rows, int cols; int m[rows][cols]; double (*p)(int n; int a[n], int n), int rows, int cols);
+// the forward declaration hides the typedef in the size expression
+typedef int n;
+void shadow(int n; int a[n], int n);
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "tester"
+                DerivedDeclarator
+                    FunctionDeclarator ForwardParameters 1
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "len"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Char
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "data"
+                                DerivedDeclarator
+                                    ArrayDeclarator
+                                        ArraySize VariableExpression
+                                            Expression
+                                                Identifier "len"
+                                DerivedDeclarator
+                                    ArrayDeclarator
+                                        ArraySize VariableExpression
+                                            Expression
+                                                Identifier "len"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "len"
+                        Ellipsis None
+            Statement Compound
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "grid"
+                    DerivedDeclarator
+                        FunctionDeclarator ForwardParameters 3
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "rows"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "cols"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "m"
+                                    DerivedDeclarator
+                                        ArrayDeclarator
+                                            ArraySize VariableExpression
+                                                Expression
+                                                    Identifier "rows"
+                                    DerivedDeclarator
+                                        ArrayDeclarator
+                                            ArraySize VariableExpression
+                                                Expression
+                                                    Identifier "cols"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Double
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "p"
+                                            DerivedDeclarator Pointer
+                                    DerivedDeclarator
+                                        FunctionDeclarator ForwardParameters 1
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Int
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Identifier "n"
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Int
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Identifier "a"
+                                                    DerivedDeclarator
+                                                        ArrayDeclarator
+                                                            ArraySize VariableExpression
+                                                                Expression
+                                                                    Identifier "n"
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Int
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Identifier "n"
+                                            Ellipsis None
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "rows"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "cols"
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "n"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "shadow"
+                    DerivedDeclarator
+                        FunctionDeclarator ForwardParameters 1
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "n"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "a"
+                                    DerivedDeclarator
+                                        ArrayDeclarator
+                                            ArraySize VariableExpression
+                                                Expression
+                                                    Identifier "n"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "n"
+                            Ellipsis None
+===*/
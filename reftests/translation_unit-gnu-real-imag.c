@@ -0,0 +1,116 @@
+#pragma gnu
+double f(_Complex double z, _Complex double a, _Complex double b) {
+    __real__ z = 4;
+    __imag z = 5;
+    __imag__ z += __real (a + b);
+    return __real__ z * __imag__ __real__ z;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Double
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Complex
+                            DeclarationSpecifier
+                                TypeSpecifier Double
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "z"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Complex
+                            DeclarationSpecifier
+                                TypeSpecifier Double
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Complex
+                            DeclarationSpecifier
+                                TypeSpecifier Double
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Real
+                                        Expression
+                                            Identifier "z"
+                                Expression
+                                    Constant
+                                        Integer "4"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                BinaryOperator Assign
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Imag
+                                        Expression
+                                            Identifier "z"
+                                Expression
+                                    Constant
+                                        Integer "5"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                BinaryOperator Assign
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Imag
+                                        Expression
+                                            Identifier "z"
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Real
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "a"
+                                                Expression
+                                                    Identifier "b"
+                                                BinaryOperator Plus
+                                BinaryOperator AssignPlus
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Real
+                                        Expression
+                                            Identifier "z"
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Imag
+                                        Expression
+                                            UnaryOperatorExpression
+                                                UnaryOperator Real
+                                                Expression
+                                                    Identifier "z"
+                                BinaryOperator Multiply
+===*/
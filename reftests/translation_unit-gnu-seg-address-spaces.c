@@ -0,0 +1,129 @@
+#pragma gnu
+extern __seg_gs unsigned int cpu_number;
+__seg_fs void *tls_slot;
+
+static inline unsigned int raw_cpu_read(void) {
+    return ({
+        typeof(cpu_number) pfo_val__;
+        pfo_val__ = *(volatile typeof(cpu_number) __seg_gs *)(unsigned long)&(cpu_number);
+        pfo_val__;
+    });
+}
+
+int __seg_gs *p;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Extern
+            DeclarationSpecifier
+                TypeQualifier AddressSpace SegGs
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "cpu_number"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier AddressSpace SegFs
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "tls_slot"
+                    DerivedDeclarator Pointer
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                FunctionSpecifier Inline
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "raw_cpu_read"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            Statement Compound
+                                BlockItem
+                                    Declaration
+                                        DeclarationSpecifier
+                                            TypeSpecifier
+                                                TypeOf
+                                                    Expression
+                                                        Identifier "cpu_number"
+                                        InitDeclarator
+                                            Declarator
+                                                DeclaratorKind
+                                                    Identifier "pfo_val__"
+                                BlockItem
+                                    Statement
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "pfo_val__"
+                                                Expression
+                                                    UnaryOperatorExpression
+                                                        UnaryOperator Indirection
+                                                        Expression
+                                                            CastExpression
+                                                                TypeName
+                                                                    SpecifierQualifier
+                                                                        TypeQualifier Volatile
+                                                                    SpecifierQualifier
+                                                                        TypeSpecifier
+                                                                            TypeOf
+                                                                                Expression
+                                                                                    Identifier "cpu_number"
+                                                                    SpecifierQualifier
+                                                                        TypeQualifier AddressSpace SegGs
+                                                                    Declarator
+                                                                        DeclaratorKind Abstract
+                                                                        DerivedDeclarator Pointer
+                                                                Expression
+                                                                    CastExpression
+                                                                        TypeName
+                                                                            SpecifierQualifier
+                                                                                TypeSpecifier Unsigned
+                                                                            SpecifierQualifier
+                                                                                TypeSpecifier Long
+                                                                        Expression
+                                                                            UnaryOperatorExpression
+                                                                                UnaryOperator Address
+                                                                                Expression
+                                                                                    Identifier "cpu_number"
+                                                BinaryOperator Assign
+                                BlockItem
+                                    Statement
+                                        Expression
+                                            Identifier "pfo_val__"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            DeclarationSpecifier
+                TypeQualifier AddressSpace SegGs
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                    DerivedDeclarator Pointer
+===*/
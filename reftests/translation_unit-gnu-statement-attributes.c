@@ -0,0 +1,131 @@
+#pragma gnu
+enum mode {
+    MODE_NEW = 0,
+    MODE_OLD __attribute__((deprecated("use MODE_NEW"))) = 1,
+};
+
+int g(int);
+
+int f(int x) {
+    if (x < 0)
+        goto out;
+    __attribute__((musttail)) return g(x - 1);
+out: __attribute__((cold, unused));
+    return 0;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Identifier "mode"
+                        Enumerator
+                            Identifier "MODE_NEW"
+                            Expression
+                                Constant
+                                    Integer "0"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                        Enumerator
+                            Identifier "MODE_OLD"
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            Extension
+                                Attribute "deprecated"
+                                    Expression
+                                        StringLiteral ["\"use MODE_NEW\""]
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "g"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                            Ellipsis None
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        IfStatement
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        Identifier "x"
+                                    Expression
+                                        Constant
+                                            Integer "0"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                                    BinaryOperator Less
+                            Statement Goto
+                                Identifier "out"
+                BlockItem
+                    Statement
+                        AttributedStatement
+                            Extension
+                                Attribute "musttail"
+                            Statement Return
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "g"
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "x"
+                                                Expression
+                                                    Constant
+                                                        Integer "1"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                                BinaryOperator Minus
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "out"
+                            Extension
+                                Attribute "cold"
+                            Extension
+                                Attribute "unused"
+                            Statement
+                BlockItem
+                    Statement Return
+                        Expression
+                            Constant
+                                Integer "0"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+===*/
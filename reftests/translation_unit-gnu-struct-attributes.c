@@ -0,0 +1,162 @@
+#pragma gnu
+struct __attribute__((packed)) s { char c; int i; };
+struct t { int i; } __attribute__((aligned(8))) v;
+union [[deprecated]] u { int i; float f; } [[maybe_unused]];
+typedef struct __attribute__((packed)) { char c; int i; } __attribute__((aligned(4))) T;
+enum __attribute__((packed)) e { A, B } __attribute__((deprecated));
+typedef enum [[nodiscard]] { C } __attribute__((packed)) E;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Extension
+                            Attribute "packed"
+                        Identifier "s"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Char
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "c"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "i"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "t"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "i"
+                        Extension
+                            Attribute "aligned"
+                                Expression
+                                    Constant
+                                        Integer "8"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "v"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Union
+                        Extension
+                            StdAttribute
+                                Identifier "deprecated"
+                        Identifier "u"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "i"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Float
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "f"
+                        Extension
+                            StdAttribute
+                                Identifier "maybe_unused"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Extension
+                            Attribute "packed"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Char
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "c"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "i"
+                        Extension
+                            Attribute "aligned"
+                                Expression
+                                    Constant
+                                        Integer "4"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "T"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Extension
+                            Attribute "packed"
+                        Identifier "e"
+                        Enumerator
+                            Identifier "A"
+                        Enumerator
+                            Identifier "B"
+                        Extension
+                            Attribute "deprecated"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    EnumType
+                        Extension
+                            StdAttribute
+                                Identifier "nodiscard"
+                        Enumerator
+                            Identifier "C"
+                        Extension
+                            Attribute "packed"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "E"
+===*/
@@ -0,0 +1,111 @@
+#pragma gnu
+__fp16 h;
+__float80 e;
+_Complex __float128 q;
+__ibm128 f(__fp16 a, __float80 b, __float128 c, __ibm128 d) {
+    return (__ibm128)a + (__float128)b + (__float80)c + (__fp16)d;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier __fp16
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "h"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier __float80
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "e"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Complex
+            DeclarationSpecifier
+                TypeSpecifier __float128
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "q"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier __ibm128
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier __fp16
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier __float80
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier __float128
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "c"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier __ibm128
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "d"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    CastExpression
+                                                        TypeName
+                                                            SpecifierQualifier
+                                                                TypeSpecifier __ibm128
+                                                        Expression
+                                                            Identifier "a"
+                                                Expression
+                                                    CastExpression
+                                                        TypeName
+                                                            SpecifierQualifier
+                                                                TypeSpecifier __float128
+                                                        Expression
+                                                            Identifier "b"
+                                                BinaryOperator Plus
+                                        Expression
+                                            CastExpression
+                                                TypeName
+                                                    SpecifierQualifier
+                                                        TypeSpecifier __float80
+                                                Expression
+                                                    Identifier "c"
+                                        BinaryOperator Plus
+                                Expression
+                                    CastExpression
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier __fp16
+                                        Expression
+                                            Identifier "d"
+                                BinaryOperator Plus
+===*/
@@ -0,0 +1,180 @@
+#pragma gnu
+int a[4];
+int (*fp)(void);
+// __must_be_array(a) from include/linux/compiler.h, expanded
+unsigned long n = sizeof(a) / sizeof((a)[0]) + ((int)(sizeof(struct { int:(-!!(__builtin_types_compatible_p(typeof((a)), typeof(&(a)[0])))); })));
+_Static_assert(__builtin_types_compatible_p(int[], typeof(a)), "array");
+_Static_assert(!__builtin_types_compatible_p(int (*)(void), typeof(fp) const), "function pointer");
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "4"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "fp"
+                            DerivedDeclarator Pointer
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "n"
+                Initializer
+                    Expression
+                        BinaryOperatorExpression
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        SizeOfVal
+                                            Expression
+                                                Identifier "a"
+                                    Expression
+                                        SizeOfVal
+                                            Expression
+                                                BinaryOperatorExpression
+                                                    Expression
+                                                        Identifier "a"
+                                                    Expression
+                                                        Constant
+                                                            Integer "0"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+                                                    BinaryOperator Index
+                                    BinaryOperator Divide
+                            Expression
+                                CastExpression
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier Int
+                                    Expression
+                                        SizeOfTy
+                                            TypeName
+                                                SpecifierQualifier
+                                                    TypeSpecifier
+                                                        StructType
+                                                            StructKind Struct
+                                                            StructDeclaration
+                                                                StructField
+                                                                    SpecifierQualifier
+                                                                        TypeSpecifier Int
+                                                                    StructDeclarator
+                                                                        Expression
+                                                                            UnaryOperatorExpression
+                                                                                UnaryOperator Minus
+                                                                                Expression
+                                                                                    UnaryOperatorExpression
+                                                                                        UnaryOperator Negate
+                                                                                        Expression
+                                                                                            UnaryOperatorExpression
+                                                                                                UnaryOperator Negate
+                                                                                                Expression
+                                                                                                    TypesCompatibleExpression
+                                                                                                        TypeName
+                                                                                                            SpecifierQualifier
+                                                                                                                TypeSpecifier
+                                                                                                                    TypeOf
+                                                                                                                        Expression
+                                                                                                                            Identifier "a"
+                                                                                                        TypeName
+                                                                                                            SpecifierQualifier
+                                                                                                                TypeSpecifier
+                                                                                                                    TypeOf
+                                                                                                                        Expression
+                                                                                                                            UnaryOperatorExpression
+                                                                                                                                UnaryOperator Address
+                                                                                                                                Expression
+                                                                                                                                    BinaryOperatorExpression
+                                                                                                                                        Expression
+                                                                                                                                            Identifier "a"
+                                                                                                                                        Expression
+                                                                                                                                            Constant
+                                                                                                                                                Integer "0"
+                                                                                                                                                    IntegerBase Decimal
+                                                                                                                                                    IntegerSuffix false false
+                                                                                                                                                        IntegerSize Int
+                                                                                                                                        BinaryOperator Index
+                            BinaryOperator Plus
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                TypesCompatibleExpression
+                    TypeName
+                        SpecifierQualifier
+                            TypeSpecifier Int
+                        Declarator
+                            DeclaratorKind Abstract
+                            DerivedDeclarator
+                                ArrayDeclarator
+                                    ArraySize Unknown
+                    TypeName
+                        SpecifierQualifier
+                            TypeSpecifier
+                                TypeOf
+                                    Expression
+                                        Identifier "a"
+            StringLiteral ["\"array\""]
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                UnaryOperatorExpression
+                    UnaryOperator Negate
+                    Expression
+                        TypesCompatibleExpression
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind Abstract
+                                            DerivedDeclarator Pointer
+                                    DerivedDeclarator
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                            Ellipsis None
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        TypeOf
+                                            Expression
+                                                Identifier "fp"
+                                SpecifierQualifier
+                                    TypeQualifier Const
+            StringLiteral ["\"function pointer\""]
+===*/
@@ -0,0 +1,7 @@
+#pragma gnu
+struct inner { int a; };
+struct outer { struct inner; int b; };
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,26 @@
+#pragma gnu
+void g(int, char *) { }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "g"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Char
+                            Declarator
+                                DeclaratorKind Abstract
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+===*/
@@ -0,0 +1,110 @@
+#pragma gnu
+struct line {
+    int length;
+    char contents[0];
+};
+struct empty {};
+union nothing {};
+unsigned long sizes[] = { sizeof(struct line), sizeof(struct empty), sizeof(char[0]) };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "line"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "length"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Char
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "contents"
+                                        DerivedDeclarator
+                                            ArrayDeclarator
+                                                ArraySize VariableExpression
+                                                    Expression
+                                                        Constant
+                                                            Integer "0"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "empty"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Union
+                        Identifier "nothing"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "sizes"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize Unknown
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                SizeOfTy
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier
+                                                StructType
+                                                    StructKind Struct
+                                                    Identifier "line"
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                SizeOfTy
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier
+                                                StructType
+                                                    StructKind Struct
+                                                    Identifier "empty"
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                SizeOfTy
+                                    TypeName
+                                        SpecifierQualifier
+                                            TypeSpecifier Char
+                                        Declarator
+                                            DeclaratorKind Abstract
+                                            DerivedDeclarator
+                                                ArrayDeclarator
+                                                    ArraySize VariableExpression
+                                                        Expression
+                                                            Constant
+                                                                Integer "0"
+                                                                    IntegerBase Decimal
+                                                                    IntegerSuffix false false
+                                                                        IntegerSize Int
+===*/
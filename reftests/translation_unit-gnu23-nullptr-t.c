@@ -0,0 +1,32 @@
+#pragma gnu23
+typedef typeof(nullptr) nullptr_t;
+nullptr_t p = nullptr;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    TypeOf
+                        Expression
+                            Constant Nullptr
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "nullptr_t"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "nullptr_t"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                Initializer
+                    Expression
+                        Constant Nullptr
+===*/
@@ -0,0 +1,5 @@
+static count = 3;
+
+/*===
+~ERROR
+===*/
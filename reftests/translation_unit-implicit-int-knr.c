@@ -0,0 +1,165 @@
+#pragma c89
+#pragma implicit_int
+foo();
+static count = 3;
+extern const limit;
+
+main(argc, argv)
+    char **argv;
+{
+    register i;
+    static calls;
+    for (i = 0; i < argc; i++)
+        foo(argv[i]);
+    return calls;
+}
+
+static helper(x) {
+    return x + count;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier ImplicitInt
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "foo"
+                    DerivedDeclarator KRFunction
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                TypeSpecifier ImplicitInt
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "count"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "3"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Extern
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier ImplicitInt
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "limit"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier ImplicitInt
+            Declarator
+                DeclaratorKind
+                    Identifier "main"
+                DerivedDeclarator KRFunction
+                    Identifier "argc"
+                    Identifier "argv"
+            Declaration
+                DeclarationSpecifier
+                    TypeSpecifier Char
+                InitDeclarator
+                    Declarator
+                        DeclaratorKind
+                            Identifier "argv"
+                        DerivedDeclarator Pointer
+                        DerivedDeclarator Pointer
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Register
+                        DeclarationSpecifier
+                            TypeSpecifier ImplicitInt
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "i"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Static
+                        DeclarationSpecifier
+                            TypeSpecifier ImplicitInt
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "calls"
+                BlockItem
+                    Statement
+                        ForStatement
+                            ForInitializer
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "i"
+                                        Expression
+                                            Constant
+                                                Integer "0"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                        BinaryOperator Assign
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    Expression
+                                        Identifier "argc"
+                                    BinaryOperator Less
+                            Expression
+                                UnaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    UnaryOperator PostIncrement
+                            Statement
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "foo"
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "argv"
+                                                Expression
+                                                    Identifier "i"
+                                                BinaryOperator Index
+                BlockItem
+                    Statement Return
+                        Expression
+                            Identifier "calls"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                TypeSpecifier ImplicitInt
+            Declarator
+                DeclaratorKind
+                    Identifier "helper"
+                DerivedDeclarator KRFunction
+                    Identifier "x"
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "x"
+                                Expression
+                                    Identifier "count"
+                                BinaryOperator Plus
+===*/
@@ -0,0 +1,5 @@
+typedef unsigned __int128 u128;
+
+/*===
+~ERROR
+===*/
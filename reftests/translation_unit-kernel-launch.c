@@ -0,0 +1,8 @@
+#pragma clang
+void run(int n) {
+    kernel<<<1, n>>>(n);
+}
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,7 @@
+int f(void) {
+out: __attribute__((cold)) return 0;
+}
+
+/*===
+~ERROR
+===*/
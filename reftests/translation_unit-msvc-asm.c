@@ -0,0 +1,60 @@
+#pragma msvc
+// nested braces, comments and consecutive single-line statements
+void f(int x) {
+    __asm {
+        mov eax, x ; load the argument }
+        /* { unbalanced in a comment */
+        push eax
+        { pop ebx }
+    }
+    __asm mov eax, 1 __asm mov ebx, 2
+    __asm int 3 ; breakpoint
+    __asm
+    {
+        xor eax, eax
+    }
+    if (x) __asm nop
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        AsmStatement "{\u{000a}        mov eax, x ; load the argument }\u{000a}        /* { unbalanced in a comment */\u{000a}        push eax\u{000a}        { pop ebx }\u{000a}    }"
+                BlockItem
+                    Statement
+                        AsmStatement "mov eax, 1"
+                BlockItem
+                    Statement
+                        AsmStatement "mov ebx, 2"
+                BlockItem
+                    Statement
+                        AsmStatement "int 3"
+                BlockItem
+                    Statement
+                        AsmStatement "{\u{000a}        xor eax, eax\u{000a}    }"
+                BlockItem
+                    Statement
+                        IfStatement
+                            Expression
+                                Identifier "x"
+                            Statement
+                                AsmStatement "nop"
+===*/
@@ -0,0 +1,414 @@
+#pragma msvc
+// Prototypes in the style of <windows.h>, after cl /E
+__declspec(dllimport) unsigned long __stdcall GetLastError(void);
+__declspec(dllimport) __declspec(noreturn) void __stdcall ExitProcess(unsigned int uExitCode);
+void * __cdecl malloc(unsigned long long _Size);
+typedef int (__stdcall *FARPROC)(void);
+typedef long (__stdcall *WNDPROC)(void *, unsigned int, unsigned long long, long long);
+void __cdecl qsort(void *_Base, unsigned long long _NumOfElements, unsigned long long _SizeOfElements,
+    int (__cdecl *_PtFuncCompare)(void const *, void const *));
+typedef struct __declspec(align(16)) _M128A {
+    unsigned long long Low;
+    long long High;
+} M128A;
+__declspec(thread) __declspec(align(8) selectany) int counter;
+static __forceinline int __fastcall add(int a, int b) { return a + b; }
+__inline int __vectorcall sub(int a, int b) { return a - b; }
+void copy(char * __restrict dst, const char __unaligned * __restrict src);
+int x = sizeof(void (__cdecl *)(int));
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension Declspec
+                    Attribute "dllimport"
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "GetLastError"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+                    Extension __stdcall
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension Declspec
+                    Attribute "dllimport"
+            DeclarationSpecifier
+                Extension Declspec
+                    Attribute "noreturn"
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "ExitProcess"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "uExitCode"
+                            Ellipsis None
+                    Extension __stdcall
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "malloc"
+                    DerivedDeclarator Pointer
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "_Size"
+                            Ellipsis None
+                    Extension __cdecl
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "FARPROC"
+                            DerivedDeclarator Pointer
+                            Extension __stdcall
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Identifier "WNDPROC"
+                            DerivedDeclarator Pointer
+                            Extension __stdcall
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind Abstract
+                                    DerivedDeclarator Pointer
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "qsort"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "_Base"
+                                    DerivedDeclarator Pointer
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "_NumOfElements"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "_SizeOfElements"
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "_PtFuncCompare"
+                                            DerivedDeclarator Pointer
+                                            Extension __cdecl
+                                    DerivedDeclarator
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                                DeclarationSpecifier
+                                                    TypeQualifier Const
+                                                Declarator
+                                                    DeclaratorKind Abstract
+                                                    DerivedDeclarator Pointer
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Void
+                                                DeclarationSpecifier
+                                                    TypeQualifier Const
+                                                Declarator
+                                                    DeclaratorKind Abstract
+                                                    DerivedDeclarator Pointer
+                                            Ellipsis None
+                            Ellipsis None
+                    Extension __cdecl
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Extension Declspec
+                            Attribute "align"
+                                Expression
+                                    Constant
+                                        Integer "16"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                        Identifier "_M128A"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "Low"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "High"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "M128A"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension Declspec
+                    Attribute "thread"
+            DeclarationSpecifier
+                Extension Declspec
+                    Attribute "align"
+                        Expression
+                            Constant
+                                Integer "8"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                    Attribute "selectany"
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "counter"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                FunctionSpecifier ForceInline
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "add"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                        Ellipsis None
+                Extension __fastcall
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "a"
+                                Expression
+                                    Identifier "b"
+                                BinaryOperator Plus
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                FunctionSpecifier Inline
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "sub"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                        Ellipsis None
+                Extension __vectorcall
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "a"
+                                Expression
+                                    Identifier "b"
+                                BinaryOperator Minus
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "copy"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Char
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "dst"
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Restrict
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeQualifier Const
+                                DeclarationSpecifier
+                                    TypeSpecifier Char
+                                DeclarationSpecifier
+                                    TypeQualifier Unaligned
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "src"
+                                    DerivedDeclarator Pointer
+                                        PointerQualifier
+                                            TypeQualifier Restrict
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+                Initializer
+                    Expression
+                        SizeOfTy
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind Abstract
+                                            DerivedDeclarator Pointer
+                                            Extension __cdecl
+                                    DerivedDeclarator
+                                        FunctionDeclarator
+                                            ParameterDeclaration
+                                                DeclarationSpecifier
+                                                    TypeSpecifier Int
+                                            Ellipsis None
+===*/
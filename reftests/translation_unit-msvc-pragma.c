@@ -0,0 +1,79 @@
+#pragma msvc
+// expanded from pshpack1.h and poppack.h style macros
+__pragma(pack(push, 1)) struct S {
+    char c;
+    int i;
+};
+__pragma(pack(pop))
+void f(void) {
+    __pragma(warning(push)) __pragma(warning(disable: 4127))
+    while (0) {}
+    __pragma(warning(pop))
+}
+_Pragma("pack(push, \"name\", 8)")
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Pragma __pragma "pack(push, 1)"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "S"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Char
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "c"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "i"
+    ExternalDeclaration
+        Pragma __pragma "pack(pop)"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        Pragma __pragma "warning(push)"
+                BlockItem
+                    Statement
+                        Pragma __pragma "warning(disable: 4127)"
+                BlockItem
+                    Statement
+                        WhileStatement
+                            Expression
+                                Constant
+                                    Integer "0"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            Statement Compound
+                BlockItem
+                    Statement
+                        Pragma __pragma "warning(pop)"
+    ExternalDeclaration
+        Pragma _Pragma "pack(push, \"name\", 8)"
+===*/
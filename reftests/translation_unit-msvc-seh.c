@@ -0,0 +1,142 @@
+#pragma msvc
+int filter(unsigned long code);
+unsigned long GetExceptionCode(void);
+void f(int *p) {
+    __try {
+        __try {
+            *p = 1;
+            if (!p) __leave;
+        } __finally {
+            p = 0;
+        }
+    } __except (filter(GetExceptionCode())) {
+        int code = GetExceptionCode();
+    }
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "filter"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Unsigned
+                                DeclarationSpecifier
+                                    TypeSpecifier Long
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "code"
+                            Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Long
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "GetExceptionCode"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "p"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        TryStatement
+                            Statement Compound
+                                BlockItem
+                                    Statement
+                                        TryStatement Finally
+                                            Statement Compound
+                                                BlockItem
+                                                    Statement
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    UnaryOperatorExpression
+                                                                        UnaryOperator Indirection
+                                                                        Expression
+                                                                            Identifier "p"
+                                                                Expression
+                                                                    Constant
+                                                                        Integer "1"
+                                                                            IntegerBase Decimal
+                                                                            IntegerSuffix false false
+                                                                                IntegerSize Int
+                                                                BinaryOperator Assign
+                                                BlockItem
+                                                    Statement
+                                                        IfStatement
+                                                            Expression
+                                                                UnaryOperatorExpression
+                                                                    UnaryOperator Negate
+                                                                    Expression
+                                                                        Identifier "p"
+                                                            Statement Leave
+                                            Statement Compound
+                                                BlockItem
+                                                    Statement
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "p"
+                                                                Expression
+                                                                    Constant
+                                                                        Integer "0"
+                                                                            IntegerBase Decimal
+                                                                            IntegerSuffix false false
+                                                                                IntegerSize Int
+                                                                BinaryOperator Assign
+                            ExceptHandler
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "filter"
+                                        Expression
+                                            CallExpression
+                                                Expression
+                                                    Identifier "GetExceptionCode"
+                                Statement Compound
+                                    BlockItem
+                                        Declaration
+                                            DeclarationSpecifier
+                                                TypeSpecifier Int
+                                            InitDeclarator
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Identifier "code"
+                                                Initializer
+                                                    Expression
+                                                        CallExpression
+                                                            Expression
+                                                                Identifier "GetExceptionCode"
+===*/
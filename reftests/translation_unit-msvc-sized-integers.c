@@ -0,0 +1,138 @@
+#pragma msvc
+typedef unsigned __int64 ULONGLONG;
+typedef signed __int8 INT8;
+typedef __int16 SHORT;
+struct bits {
+    unsigned __int64 address : 48;
+    unsigned __int64 flags : 16;
+};
+__int32 __stdcall InterlockedIncrement(__int32 volatile *Addend);
+unsigned __int64 f(__int64 x) { return (unsigned __int64)x; }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier __int64
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "ULONGLONG"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Signed
+            DeclarationSpecifier
+                TypeSpecifier __int8
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "INT8"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier __int16
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "SHORT"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "bits"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier __int64
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "address"
+                                    Expression
+                                        Constant
+                                            Integer "48"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier __int64
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "flags"
+                                    Expression
+                                        Constant
+                                            Integer "16"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier __int32
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "InterlockedIncrement"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier __int32
+                                DeclarationSpecifier
+                                    TypeQualifier Volatile
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "Addend"
+                                    DerivedDeclarator Pointer
+                            Ellipsis None
+                    Extension __stdcall
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier __int64
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier __int64
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement Return
+                        Expression
+                            CastExpression
+                                TypeName
+                                    SpecifierQualifier
+                                        TypeSpecifier Unsigned
+                                    SpecifierQualifier
+                                        TypeSpecifier __int64
+                                Expression
+                                    Identifier "x"
+===*/
@@ -0,0 +1,182 @@
+#pragma msvc
+// winnt.h style members injected into the enclosing struct
+typedef struct _LARGE_INTEGER_PARTS { unsigned long LowPart; long HighPart; } LARGE_INTEGER_PARTS;
+struct _LIST_ENTRY { struct _LIST_ENTRY *Flink; struct _LIST_ENTRY *Blink; };
+typedef union _ULARGE_INTEGER {
+    LARGE_INTEGER_PARTS;
+    struct { unsigned long LowPart; unsigned long HighPart; } u;
+    unsigned __int64 QuadPart;
+} ULARGE_INTEGER;
+struct _ITEM {
+    struct _LIST_ENTRY;
+    union { int a; float b; };
+    int value;
+};
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "_LARGE_INTEGER_PARTS"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "LowPart"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "HighPart"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "LARGE_INTEGER_PARTS"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "_LIST_ENTRY"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            Identifier "_LIST_ENTRY"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "Flink"
+                                        DerivedDeclarator Pointer
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            Identifier "_LIST_ENTRY"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "Blink"
+                                        DerivedDeclarator Pointer
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Union
+                        Identifier "_ULARGE_INTEGER"
+                        StructDeclaration
+                            StructField MsUnnamed
+                                SpecifierQualifier
+                                    TypeSpecifier TypedefName
+                                        Identifier "LARGE_INTEGER_PARTS"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            StructDeclaration
+                                                StructField
+                                                    SpecifierQualifier
+                                                        TypeSpecifier Unsigned
+                                                    SpecifierQualifier
+                                                        TypeSpecifier Long
+                                                    StructDeclarator
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "LowPart"
+                                            StructDeclaration
+                                                StructField
+                                                    SpecifierQualifier
+                                                        TypeSpecifier Unsigned
+                                                    SpecifierQualifier
+                                                        TypeSpecifier Long
+                                                    StructDeclarator
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "HighPart"
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "u"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Unsigned
+                                SpecifierQualifier
+                                    TypeSpecifier __int64
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "QuadPart"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "ULARGE_INTEGER"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "_ITEM"
+                        StructDeclaration
+                            StructField MsUnnamed
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Struct
+                                            Identifier "_LIST_ENTRY"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        StructType
+                                            StructKind Union
+                                            StructDeclaration
+                                                StructField
+                                                    SpecifierQualifier
+                                                        TypeSpecifier Int
+                                                    StructDeclarator
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "a"
+                                            StructDeclaration
+                                                StructField
+                                                    SpecifierQualifier
+                                                        TypeSpecifier Float
+                                                    StructDeclarator
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "b"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "value"
+===*/
@@ -0,0 +1,83 @@
+int kind(int tag) {
+    switch (tag) {
+    case 'RIFF':
+    case 'ab':
+        return 1;
+    case '\0\n':
+    case 'a\tb':
+        return 2;
+    }
+    return 'abcd' == tag;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "kind"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "tag"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        SwitchStatement
+                            Expression
+                                Identifier "tag"
+                            Statement Compound
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Expression
+                                                    Constant Character 'RIFF'
+                                            Statement
+                                                LabeledStatement
+                                                    Label
+                                                        Expression
+                                                            Constant Character 'ab'
+                                                    Statement Return
+                                                        Expression
+                                                            Constant
+                                                                Integer "1"
+                                                                    IntegerBase Decimal
+                                                                    IntegerSuffix false false
+                                                                        IntegerSize Int
+                                BlockItem
+                                    Statement
+                                        LabeledStatement
+                                            Label
+                                                Expression
+                                                    Constant Character '\0\n'
+                                            Statement
+                                                LabeledStatement
+                                                    Label
+                                                        Expression
+                                                            Constant Character 'a\tb'
+                                                    Statement Return
+                                                        Expression
+                                                            Constant
+                                                                Integer "2"
+                                                                    IntegerBase Decimal
+                                                                    IntegerSuffix false false
+                                                                        IntegerSize Int
+                BlockItem
+                    Statement Return
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Constant Character 'abcd'
+                                Expression
+                                    Identifier "tag"
+                                BinaryOperator Equals
+===*/
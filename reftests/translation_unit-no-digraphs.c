@@ -0,0 +1,6 @@
+#pragma no_digraphs
+int a<:3:> = <% 1 %>;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,21 @@
+int _Nonnull, _Nullable, _Null_unspecified;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "_Nonnull"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "_Nullable"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "_Null_unspecified"
+===*/
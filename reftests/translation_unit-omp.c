@@ -0,0 +1,368 @@
+double dot(int n, double *a, double *b) {
+    double sum = 0;
+    int i;
+#pragma omp parallel for private(i) shared(a, b) reduction(+: sum) schedule(static, n / 4)
+    for (i = 0; i < n; i++)
+        sum += a[i] * b[i];
+#pragma omp parallel num_threads(4) firstprivate(n)
+#pragma omp single
+    {
+#pragma omp task
+        n++;
+#pragma omp barrier
+#pragma omp critical (update)
+        sum = -sum;
+    }
+#pragma omp for collapse(2), reduction(max: sum)
+    for (i = 0; i < n; i++)
+        for (int j = 0; j < n; j++)
+            sum = a[i] > sum ? a[i] : sum;
+#pragma omp atomic
+    sum += 1;
+#pragma omp for ordered
+    for (i = 0; i < n; i++);
+#pragma omp flush
+    return sum;
+}
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Double
+            Declarator
+                DeclaratorKind
+                    Identifier "dot"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Double
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                                DerivedDeclarator Pointer
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Double
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Double
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "sum"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "i"
+                BlockItem
+                    Statement
+                        OmpStatement
+                            OmpDirective
+                                OmpDirectiveKind ParallelFor
+                                OmpClause Private
+                                    Identifier "i"
+                                OmpClause Shared
+                                    Identifier "a"
+                                    Identifier "b"
+                                OmpClause Reduction
+                                    OmpReduction
+                                        OmpReductionOperator Plus
+                                        Identifier "sum"
+                                OmpClause Schedule
+                                    OmpSchedule
+                                        OmpScheduleKind Static
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "n"
+                                                Expression
+                                                    Constant
+                                                        Integer "4"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                                BinaryOperator Divide
+                            Statement
+                                ForStatement
+                                    ForInitializer
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "i"
+                                                Expression
+                                                    Constant
+                                                        Integer "0"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                                BinaryOperator Assign
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                Identifier "i"
+                                            Expression
+                                                Identifier "n"
+                                            BinaryOperator Less
+                                    Expression
+                                        UnaryOperatorExpression
+                                            Expression
+                                                Identifier "i"
+                                            UnaryOperator PostIncrement
+                                    Statement
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "sum"
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "a"
+                                                                Expression
+                                                                    Identifier "i"
+                                                                BinaryOperator Index
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "b"
+                                                                Expression
+                                                                    Identifier "i"
+                                                                BinaryOperator Index
+                                                        BinaryOperator Multiply
+                                                BinaryOperator AssignPlus
+                BlockItem
+                    Statement
+                        OmpStatement
+                            OmpDirective
+                                OmpDirectiveKind Parallel
+                                OmpClause NumThreads
+                                    Expression
+                                        Constant
+                                            Integer "4"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                                OmpClause FirstPrivate
+                                    Identifier "n"
+                            Statement
+                                OmpStatement
+                                    OmpDirective
+                                        OmpDirectiveKind Single
+                                    Statement Compound
+                                        BlockItem
+                                            Statement
+                                                OmpStatement
+                                                    OmpDirective
+                                                        OmpDirectiveKind Task
+                                                    Statement
+                                                        Expression
+                                                            UnaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "n"
+                                                                UnaryOperator PostIncrement
+                                        BlockItem
+                                            Statement
+                                                OmpStatement
+                                                    OmpDirective
+                                                        OmpDirectiveKind Barrier
+                                        BlockItem
+                                            Statement
+                                                OmpStatement
+                                                    OmpDirective
+                                                        OmpDirectiveKind Critical
+                                                            Identifier "update"
+                                                    Statement
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "sum"
+                                                                Expression
+                                                                    UnaryOperatorExpression
+                                                                        UnaryOperator Minus
+                                                                        Expression
+                                                                            Identifier "sum"
+                                                                BinaryOperator Assign
+                BlockItem
+                    Statement
+                        OmpStatement
+                            OmpDirective
+                                OmpDirectiveKind For
+                                OmpClause Collapse
+                                    Expression
+                                        Constant
+                                            Integer "2"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                                OmpClause Reduction
+                                    OmpReduction
+                                        OmpReductionOperator Identifier
+                                            Identifier "max"
+                                        Identifier "sum"
+                            Statement
+                                ForStatement
+                                    ForInitializer
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    Identifier "i"
+                                                Expression
+                                                    Constant
+                                                        Integer "0"
+                                                            IntegerBase Decimal
+                                                            IntegerSuffix false false
+                                                                IntegerSize Int
+                                                BinaryOperator Assign
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                Identifier "i"
+                                            Expression
+                                                Identifier "n"
+                                            BinaryOperator Less
+                                    Expression
+                                        UnaryOperatorExpression
+                                            Expression
+                                                Identifier "i"
+                                            UnaryOperator PostIncrement
+                                    Statement
+                                        ForStatement
+                                            ForInitializer
+                                                Declaration
+                                                    DeclarationSpecifier
+                                                        TypeSpecifier Int
+                                                    InitDeclarator
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Identifier "j"
+                                                        Initializer
+                                                            Expression
+                                                                Constant
+                                                                    Integer "0"
+                                                                        IntegerBase Decimal
+                                                                        IntegerSuffix false false
+                                                                            IntegerSize Int
+                                            Expression
+                                                BinaryOperatorExpression
+                                                    Expression
+                                                        Identifier "j"
+                                                    Expression
+                                                        Identifier "n"
+                                                    BinaryOperator Less
+                                            Expression
+                                                UnaryOperatorExpression
+                                                    Expression
+                                                        Identifier "j"
+                                                    UnaryOperator PostIncrement
+                                            Statement
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            Identifier "sum"
+                                                        Expression
+                                                            ConditionalExpression
+                                                                Expression
+                                                                    BinaryOperatorExpression
+                                                                        Expression
+                                                                            BinaryOperatorExpression
+                                                                                Expression
+                                                                                    Identifier "a"
+                                                                                Expression
+                                                                                    Identifier "i"
+                                                                                BinaryOperator Index
+                                                                        Expression
+                                                                            Identifier "sum"
+                                                                        BinaryOperator Greater
+                                                                Expression
+                                                                    BinaryOperatorExpression
+                                                                        Expression
+                                                                            Identifier "a"
+                                                                        Expression
+                                                                            Identifier "i"
+                                                                        BinaryOperator Index
+                                                                Expression
+                                                                    Identifier "sum"
+                                                        BinaryOperator Assign
+                BlockItem
+                    Statement
+                        OmpStatement
+                            OmpDirective
+                                OmpDirectiveKind Atomic
+                            Statement
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "sum"
+                                        Expression
+                                            Constant
+                                                Integer "1"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                        BinaryOperator AssignPlus
+                BlockItem
+                    Statement
+                        Pragma "omp for ordered"
+                BlockItem
+                    Statement
+                        ForStatement
+                            ForInitializer
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "i"
+                                        Expression
+                                            Constant
+                                                Integer "0"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                                        BinaryOperator Assign
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    Expression
+                                        Identifier "n"
+                                    BinaryOperator Less
+                            Expression
+                                UnaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    UnaryOperator PostIncrement
+                            Statement
+                BlockItem
+                    Statement
+                        Pragma "omp flush"
+                BlockItem
+                    Statement Return
+                        Expression
+                            Identifier "sum"
+===*/
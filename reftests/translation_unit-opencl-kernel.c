@@ -0,0 +1,344 @@
+#pragma opencl
+#pragma typedef image2d_t
+#pragma typedef sampler_t
+#pragma typedef float4
+#pragma typedef int2
+constant float weights[3] = { 0.25f, 0.5f, 0.25f };
+
+__kernel __attribute__((reqd_work_group_size(16, 16, 1)))
+void blur(__read_only image2d_t src, write_only image2d_t dst, sampler_t sampler,
+          __global float *out, local float *tile) {
+    int2 pos = (int2)(get_global_id(0), get_global_id(1));
+    __private float4 sum = 0;
+    int local_id = get_local_id(0);
+    for (int i = 0; i < 3; i++)
+        sum += weights[i] * read_imagef(src, sampler, pos + (int2)(i - 1, 0));
+    tile[local_id] = sum.x;
+    write_imagef(dst, pos, sum);
+}
+
+int global;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier AddressSpace Constant
+            DeclarationSpecifier
+                TypeSpecifier Float
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "weights"
+                    DerivedDeclarator
+                        ArrayDeclarator
+                            ArraySize VariableExpression
+                                Expression
+                                    Constant
+                                        Integer "3"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                Initializer
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float "0.25"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Float
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float "0.5"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Float
+                    InitializerListItem
+                        Initializer
+                            Expression
+                                Constant
+                                    Float "0.25"
+                                        FloatBase Decimal
+                                        FloatSuffix false
+                                            FloatFormat Float
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                FunctionSpecifier Kernel
+            DeclarationSpecifier
+                Extension
+                    Attribute "reqd_work_group_size"
+                        Expression
+                            Constant
+                                Integer "16"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                        Expression
+                            Constant
+                                Integer "16"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                        Expression
+                            Constant
+                                Integer "1"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "blur"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeQualifier Access ReadOnly
+                            DeclarationSpecifier
+                                TypeSpecifier TypedefName
+                                    Identifier "image2d_t"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "src"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeQualifier Access WriteOnly
+                            DeclarationSpecifier
+                                TypeSpecifier TypedefName
+                                    Identifier "image2d_t"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "dst"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier TypedefName
+                                    Identifier "sampler_t"
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "sampler"
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeQualifier AddressSpace Global
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "out"
+                                DerivedDeclarator Pointer
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeQualifier AddressSpace Local
+                            DeclarationSpecifier
+                                TypeSpecifier Float
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "tile"
+                                DerivedDeclarator Pointer
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier TypedefName
+                                Identifier "int2"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "pos"
+                            Initializer
+                                Expression
+                                    CastExpression
+                                        TypeName
+                                            SpecifierQualifier
+                                                TypeSpecifier TypedefName
+                                                    Identifier "int2"
+                                        Expression
+                                            Expression
+                                                CallExpression
+                                                    Expression
+                                                        Identifier "get_global_id"
+                                                    Expression
+                                                        Constant
+                                                            Integer "0"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+                                            Expression
+                                                CallExpression
+                                                    Expression
+                                                        Identifier "get_global_id"
+                                                    Expression
+                                                        Constant
+                                                            Integer "1"
+                                                                IntegerBase Decimal
+                                                                IntegerSuffix false false
+                                                                    IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeQualifier AddressSpace Private
+                        DeclarationSpecifier
+                            TypeSpecifier TypedefName
+                                Identifier "float4"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "sum"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "local_id"
+                            Initializer
+                                Expression
+                                    CallExpression
+                                        Expression
+                                            Identifier "get_local_id"
+                                        Expression
+                                            Constant
+                                                Integer "0"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+                BlockItem
+                    Statement
+                        ForStatement
+                            ForInitializer
+                                Declaration
+                                    DeclarationSpecifier
+                                        TypeSpecifier Int
+                                    InitDeclarator
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "i"
+                                        Initializer
+                                            Expression
+                                                Constant
+                                                    Integer "0"
+                                                        IntegerBase Decimal
+                                                        IntegerSuffix false false
+                                                            IntegerSize Int
+                            Expression
+                                BinaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    Expression
+                                        Constant
+                                            Integer "3"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                                    BinaryOperator Less
+                            Expression
+                                UnaryOperatorExpression
+                                    Expression
+                                        Identifier "i"
+                                    UnaryOperator PostIncrement
+                            Statement
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "sum"
+                                        Expression
+                                            BinaryOperatorExpression
+                                                Expression
+                                                    BinaryOperatorExpression
+                                                        Expression
+                                                            Identifier "weights"
+                                                        Expression
+                                                            Identifier "i"
+                                                        BinaryOperator Index
+                                                Expression
+                                                    CallExpression
+                                                        Expression
+                                                            Identifier "read_imagef"
+                                                        Expression
+                                                            Identifier "src"
+                                                        Expression
+                                                            Identifier "sampler"
+                                                        Expression
+                                                            BinaryOperatorExpression
+                                                                Expression
+                                                                    Identifier "pos"
+                                                                Expression
+                                                                    CastExpression
+                                                                        TypeName
+                                                                            SpecifierQualifier
+                                                                                TypeSpecifier TypedefName
+                                                                                    Identifier "int2"
+                                                                        Expression
+                                                                            Expression
+                                                                                BinaryOperatorExpression
+                                                                                    Expression
+                                                                                        Identifier "i"
+                                                                                    Expression
+                                                                                        Constant
+                                                                                            Integer "1"
+                                                                                                IntegerBase Decimal
+                                                                                                IntegerSuffix false false
+                                                                                                    IntegerSize Int
+                                                                                    BinaryOperator Minus
+                                                                            Expression
+                                                                                Constant
+                                                                                    Integer "0"
+                                                                                        IntegerBase Decimal
+                                                                                        IntegerSuffix false false
+                                                                                            IntegerSize Int
+                                                                BinaryOperator Plus
+                                                BinaryOperator Multiply
+                                        BinaryOperator AssignPlus
+                BlockItem
+                    Statement
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    BinaryOperatorExpression
+                                        Expression
+                                            Identifier "tile"
+                                        Expression
+                                            Identifier "local_id"
+                                        BinaryOperator Index
+                                Expression
+                                    MemberExpression
+                                        MemberOperator Direct
+                                        Expression
+                                            Identifier "sum"
+                                        Identifier "x"
+                                BinaryOperator Assign
+                BlockItem
+                    Statement
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "write_imagef"
+                                Expression
+                                    Identifier "dst"
+                                Expression
+                                    Identifier "pos"
+                                Expression
+                                    Identifier "sum"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "global"
+===*/
@@ -0,0 +1,5 @@
+void tester(int len; char data[len][len], int len);
+
+/*===
+~ERROR
+===*/
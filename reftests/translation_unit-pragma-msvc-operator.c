@@ -0,0 +1,6 @@
+__pragma(pack(push, 1))
+int x;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,45 @@
+_Pragma("once")
+int x;
+void f(void) {
+    _Pragma("GCC diagnostic ignored \"-Wunused\"")
+    int y;
+}
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Pragma _Pragma "once"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        Pragma _Pragma "GCC diagnostic ignored \"-Wunused\""
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "y"
+===*/
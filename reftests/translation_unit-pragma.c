@@ -0,0 +1,91 @@
+#pragma gnu
+#pragma pack(push, 1)
+struct s { char c; int i; };
+#pragma pack(pop)
+# 5 "file.h"
+#pragma GCC visibility push(default)
+int f(void) {
+#pragma GCC diagnostic push
+    int x = 1;
+#pragma GCC diagnostic ignored "-Wunused"
+    return x;
+#pragma GCC diagnostic pop
+}
+# 20 "file.h" 2
+#pragma GCC visibility pop
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Pragma "pack(push, 1)"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "s"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Char
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "c"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier Int
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "i"
+    ExternalDeclaration
+        Pragma "pack(pop)"
+    ExternalDeclaration
+        Pragma "GCC visibility push(default)"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        Pragma "GCC diagnostic push"
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "1"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Statement
+                        Pragma "GCC diagnostic ignored \"-Wunused\""
+                BlockItem
+                    Statement Return
+                        Expression
+                            Identifier "x"
+                BlockItem
+                    Statement
+                        Pragma "GCC diagnostic pop"
+    ExternalDeclaration
+        Pragma "GCC visibility pop"
+===*/
@@ -0,0 +1,5 @@
+__seg_gs unsigned int counter;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+unsigned __int64 x;
+
+/*===
+~ERROR
+===*/
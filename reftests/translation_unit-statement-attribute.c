@@ -0,0 +1,8 @@
+int g(int);
+int f(int x) {
+    __attribute__((musttail)) return g(x);
+}
+
+/*===
+~ERROR
+===*/
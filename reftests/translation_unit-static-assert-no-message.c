@@ -0,0 +1,5 @@
+_Static_assert(sizeof(int) == 4);
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,66 @@
+const char *s = u8"utf" "8";
+const unsigned short *w = u"a" "b" u"c";
+const int *l = L"wide" "text";
+const unsigned *u = "x" U"y";
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Char
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "s"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        StringLiteral ["u8\"utf\"", "\"8\""]
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            DeclarationSpecifier
+                TypeSpecifier Short
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "w"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        StringLiteral ["u\"a\"", "\"b\"", "u\"c\""]
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "l"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        StringLiteral ["L\"wide\"", "\"text\""]
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeQualifier Const
+            DeclarationSpecifier
+                TypeSpecifier Unsigned
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "u"
+                    DerivedDeclarator Pointer
+                Initializer
+                    Expression
+                        StringLiteral ["\"x\"", "U\"y\""]
+===*/
@@ -0,0 +1,5 @@
+struct __attribute__((packed)) s { char c; };
+
+/*===
+~ERROR
+===*/
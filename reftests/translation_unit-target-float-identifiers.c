@@ -0,0 +1,25 @@
+int __fp16, __float80, __float128, __ibm128;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "__fp16"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "__float80"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "__float128"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "__ibm128"
+===*/
@@ -0,0 +1,14 @@
+#pragma gnu
+int typeof_unqual;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "typeof_unqual"
+===*/
@@ -0,0 +1,6 @@
+// basic Latin letters cannot be written as universal character names
+int \u0041bc;
+
+/*===
+~ERROR
+===*/
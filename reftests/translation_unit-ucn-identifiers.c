@@ -0,0 +1,72 @@
+// typedef declared and referenced with the same UCN spelling
+typedef int caf\u00e9;
+caf\u00e9 \u00e9\U000000e8 = 1;
+// raw UTF-8 is a different spelling from the one of the typedef
+int café, π_2;
+void f(void) { caf\u00e9 * x; }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Typedef
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "caf\\u00e9"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "caf\\u00e9"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "\\u00e9\\U000000e8"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "caf\u{00e9}"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "\u{03c0}_2"
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeSpecifier TypedefName
+                                Identifier "caf\\u00e9"
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "x"
+                                DerivedDeclarator Pointer
+===*/
@@ -0,0 +1,17 @@
+int caf\U000000E9, \u03c0;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "caf\\U000000E9"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "\\u03c0"
+===*/
@@ -0,0 +1,6 @@
+// short universal character names take exactly four hexadecimal digits
+int a\u00e;
+
+/*===
+~ERROR
+===*/
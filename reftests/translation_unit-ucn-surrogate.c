@@ -0,0 +1,6 @@
+// lone surrogates are not valid universal character names
+int a\ud800;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+void g(int, char *) { }
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,5 @@
+struct line { int length; char contents[0]; };
+
+/*===
+~ERROR
+===*/
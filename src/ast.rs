@@ -19,7 +19,16 @@
 //! - extensions to the initializer list syntax
 //! - statement expressions
 //! - `typeof` type specifiers
+//!
+//! With the `serde` feature, all types implement `Serialize` and `Deserialize`, with the field
+//! and variant names used here. Enums like `Extension`, `TypeSpecifier` and `Expression` get
+//! new variants as more extensions are supported, so a tree serialized by a newer version of
+//! this crate may fail to deserialize with an older one.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use omp::OmpStatement;
 use span::Node;
 
 // From 6.4 Lexical elements
@@ -28,10 +37,42 @@ use span::Node;
 ///
 /// (C11 6.4.2)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Identifier {
+    /// Name as written, including universal character names like `\u00e9`
     pub name: String,
 }
 
+impl Identifier {
+    /// `name` with universal character names replaced by the characters they designate, so that
+    /// `caf\u00e9` and `café` compare equal
+    pub fn decoded(&self) -> String {
+        let mut decoded = String::with_capacity(self.name.len());
+        let mut rest = &self.name[..];
+        while let Some(i) = rest.find('\\') {
+            decoded.push_str(&rest[..i]);
+            rest = &rest[i..];
+            let len = if rest.starts_with("\\u") { 6 } else { 10 };
+            match rest
+                .get(2..len)
+                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                .and_then(::std::char::from_u32)
+            {
+                Some(c) => {
+                    decoded.push(c);
+                    rest = &rest[len..];
+                }
+                None => {
+                    decoded.push('\\');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        decoded.push_str(rest);
+        decoded
+    }
+}
+
 /// Constant literals
 ///
 /// C11 places string literals under primary expressions, thus they
@@ -39,38 +80,60 @@ pub struct Identifier {
 ///
 /// (C11 6.4.4)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Constant {
     Integer(Integer),
     Float(Float),
-    Character(String),
+    Character(Character),
+    /// `nullptr`, the null pointer constant
+    ///
+    /// (C23 6.4.4.6)
+    Nullptr,
+    /// `true` or `false`, constants of type `bool` rather than the integers they convert to
+    ///
+    /// (C23 6.4.4.6)
+    Bool(bool),
 }
 
 /// Integer number literal
 ///
 /// (C11 6.4.4.1)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Integer {
     pub base: IntegerBase,
+    /// Digits as written, without the base prefix and including C23 digit separators
     pub number: Box<str>,
     pub suffix: IntegerSuffix,
 }
 
+impl Integer {
+    /// Digits of `number` without digit separators
+    pub fn digits(&self) -> String {
+        self.number.replace('\'', "")
+    }
+}
+
 /// Base of the integer literal
 ///
 /// (C11 6.4.4.1)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IntegerBase {
     Decimal,
     Octal,
     Hexadecimal,
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Binary-constants.html)
     Binary,
+    /// Octal with an explicit `0o` or `0O` prefix (C2y, Clang extension)
+    OctalExplicit,
 }
 
 /// Suffix of an integer literal
 ///
 /// (C11 6.4.4.1)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IntegerSuffix {
     /// Minimum size of the integer literal
     pub size: IntegerSize,
@@ -86,6 +149,7 @@ pub struct IntegerSuffix {
 ///
 /// (C11 6.4.4.1)
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum IntegerSize {
     /// no `l` or `ll`
     Int = 0,
@@ -93,22 +157,37 @@ pub enum IntegerSize {
     Long,
     /// `ll`
     LongLong,
+    /// `wb`, a `_BitInt` just wide enough for the value
+    ///
+    /// (C23 6.4.4.1)
+    BitPrecise,
 }
 
 /// Floating point number literal
 ///
 /// (C11 6.4.4.2)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Float {
     pub base: FloatBase,
+    /// Significand and exponent as written, without the base prefix and including C23 digit
+    /// separators
     pub number: Box<str>,
     pub suffix: FloatSuffix,
 }
 
+impl Float {
+    /// Significand and exponent of `number` without digit separators
+    pub fn digits(&self) -> String {
+        self.number.replace('\'', "")
+    }
+}
+
 /// Floating point number base
 ///
 /// (C11 6.4.4.2)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FloatBase {
     Decimal,
     Hexadecimal,
@@ -118,6 +197,7 @@ pub enum FloatBase {
 ///
 /// (C11 6.4.4.2)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FloatSuffix {
     pub format: FloatFormat,
     /// Integer literal is an imaginary part of a complex number
@@ -130,6 +210,7 @@ pub struct FloatSuffix {
 ///
 /// (C11 6.4.4.2)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FloatFormat {
     /// `f` suffix
     Float,
@@ -143,12 +224,93 @@ pub enum FloatFormat {
     /// [ISO/IEC TS 18661-3:2015](http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1945.pdf)
     /// `fN`, `fNx`, `dN`, `dNx` suffixes
     TS18661Format(TS18661FloatType),
+    /// Fixed-point `r` and `k` suffixes with `u`, `h`, `l` and `ll` modifiers
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Fixed-Point.html), from ISO/IEC TR
+    /// 18037
+    FixedPoint(FixedPointFormat),
+}
+
+/// Fixed-point type of a literal
+///
+/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Fixed-Point.html)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FixedPointFormat {
+    pub kind: FixedPointKind,
+    pub size: FixedPointSize,
+    pub unsigned: bool,
+}
+
+/// `_Fract` or `_Accum`
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FixedPointKind {
+    /// `r` suffix
+    Fract,
+    /// `k` suffix
+    Accum,
+}
+
+/// Size modifier of a fixed-point literal
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FixedPointSize {
+    /// `h`
+    Short,
+    /// no modifier
+    Plain,
+    /// `l`
+    Long,
+    /// `ll`
+    LongLong,
 }
 
-/// String literal
+/// Character constant
+///
+/// (C11 6.4.4.4)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Character {
+    pub encoding: CharEncoding,
+    /// Constant as written, including the prefix and quotes
+    pub value: String,
+}
+
+/// Encoding of a character constant or string literal, given by its prefix
+///
+/// (C11 6.4.4.4, 6.4.5)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CharEncoding {
+    /// no prefix
+    Plain,
+    /// `L` prefix
+    Wide,
+    /// `u` prefix
+    Utf16,
+    /// `U` prefix
+    Utf32,
+    /// `u8` prefix, limited to a single character in character constants (C23 6.4.4.5)
+    Utf8,
+}
+
+/// String literal, made of adjacent pieces that are concatenated
 ///
 /// (C11 6.4.5)
-pub type StringLiteral = Vec<String>;
+pub type StringLiteral = Vec<StringPiece>;
+
+/// Single string literal token
+///
+/// Pieces with different prefixes can be concatenated, the encoding of the result is left to
+/// the consumer (C11 6.4.5p5). See `literal::DecodeString` for the contents of a literal.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StringPiece {
+    pub encoding: CharEncoding,
+    /// Literal as written, including the prefix and quotes
+    pub value: String,
+}
 
 // From 6.5 Expressions
 
@@ -156,6 +318,7 @@ pub type StringLiteral = Vec<String>;
 ///
 /// (C11 6.5)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Expression {
     /// Identifier
     ///
@@ -195,6 +358,13 @@ pub enum Expression {
     /// (C11 6.5.2)
     Call(Box<Node<CallExpression>>),
 
+    /// Kernel launch with an execution configuration
+    ///
+    /// `kernel<<<grid, block>>>(arguments)`
+    ///
+    /// [CUDA extension](https://docs.nvidia.com/cuda/cuda-c-programming-guide/#execution-configuration)
+    KernelCall(Box<Node<KernelCallExpression>>),
+
     /// Compound literal
     ///
     /// (C11 6.5.2)
@@ -210,6 +380,16 @@ pub enum Expression {
     /// (C11 6.5.3)
     SizeOfVal(Box<Node<SizeOfVal>>),
 
+    /// Number of elements of an array type
+    ///
+    /// (C2y 6.5.4.4)
+    CountOfTy(Box<Node<CountOfTy>>),
+
+    /// Number of elements of an array expression
+    ///
+    /// (C2y 6.5.4.4)
+    CountOfVal(Box<Node<CountOfVal>>),
+
     /// Alignment of a type
     ///
     /// (C11 6.5.3)
@@ -261,14 +441,49 @@ pub enum Expression {
     /// (C11 7.16.1.1).
     VaArg(Box<Node<VaArgExpression>>),
 
+    /// Test whether two types are compatible, ignoring top level qualifiers
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html#index-_005f_005fbuiltin_005ftypes_005fcompatible_005fp)
+    TypesCompatible(Box<Node<TypesCompatibleExpression>>),
+
+    /// Compile-time selection of one of two expressions
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html#index-_005f_005fbuiltin_005fchoose_005fexpr)
+    ChooseExpr(Box<Node<ChooseExpression>>),
+
     /// Statement expression
     ///
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Statement-Exprs.html)
     Statement(Box<Node<Statement>>),
+
+    /// Address of a label
+    ///
+    /// `&&label`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Labels-as-Values.html)
+    LabelAddress(Box<Node<Identifier>>),
+
+    /// Expression following `__extension__`, which suppresses pedantic warnings
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Alternate-Keywords.html)
+    Extension(Box<Node<Expression>>),
+
+    /// Block literal
+    ///
+    /// [Clang extension](https://clang.llvm.org/docs/BlockLanguageSpec.html)
+    Block(Box<Node<BlockExpression>>),
+
+    /// Platform version check
+    ///
+    /// `__builtin_available(macOS 12.0, *)`
+    ///
+    /// [Clang extension](https://clang.llvm.org/docs/LanguageExtensions.html#objective-c-available)
+    BuiltinAvailable(Box<Vec<Node<AvailabilityCondition>>>),
 }
 
 /// Struct or union member access
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MemberOperator {
     /// `expression.identifier`
     Direct,
@@ -280,6 +495,7 @@ pub enum MemberOperator {
 ///
 /// (C11 6.5.1.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GenericSelection {
     pub expression: Box<Node<Expression>>,
     pub associations: Vec<Node<GenericAssociation>>,
@@ -289,6 +505,7 @@ pub struct GenericSelection {
 ///
 /// (C11 6.5.1.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GenericAssociation {
     Type(Node<GenericAssociationType>),
     Default(Box<Node<Expression>>),
@@ -298,6 +515,7 @@ pub enum GenericAssociation {
 ///
 /// (C11 6.5.1.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GenericAssociationType {
     pub type_name: Node<TypeName>,
     pub expression: Box<Node<Expression>>,
@@ -309,6 +527,7 @@ pub struct GenericAssociationType {
 ///
 /// (C11 6.5.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MemberExpression {
     pub operator: Node<MemberOperator>,
     pub expression: Box<Node<Expression>>,
@@ -319,15 +538,29 @@ pub struct MemberExpression {
 ///
 /// (C11 6.5.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CallExpression {
     pub callee: Box<Node<Expression>>,
     pub arguments: Vec<Node<Expression>>,
 }
 
+/// Kernel launch expression
+///
+/// [CUDA extension](https://docs.nvidia.com/cuda/cuda-c-programming-guide/#execution-configuration)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KernelCallExpression {
+    pub callee: Box<Node<Expression>>,
+    /// Grid and block dimensions, optionally followed by shared memory size and stream
+    pub configuration: Vec<Node<Expression>>,
+    pub arguments: Vec<Node<Expression>>,
+}
+
 /// Compound literal
 ///
 /// (C11 6.5.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CompoundLiteral {
     pub type_name: Node<TypeName>,
     pub initializer_list: Vec<Node<InitializerListItem>>,
@@ -337,24 +570,42 @@ pub struct CompoundLiteral {
 ///
 /// (C11 6.5.3)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SizeOfTy(pub Node<TypeName>);
 
 /// Size of an unary expression
 ///
 /// (C11 6.5.3)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SizeOfVal(pub Box<Node<Expression>>);
 
+/// Number of elements of an array type
+///
+/// (C2y 6.5.4.4)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CountOfTy(pub Node<TypeName>);
+
+/// Number of elements of an array expression
+///
+/// (C2y 6.5.4.4)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CountOfVal(pub Box<Node<Expression>>);
+
 /// Alignment of a type
 ///
 /// (C11 6.5.3)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AlignOf(pub Box<Node<TypeName>>);
 
 /// All operators with one operand
 ///
 /// (C11 6.5)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnaryOperator {
     /// `operand++`
     PostIncrement,
@@ -376,6 +627,14 @@ pub enum UnaryOperator {
     Complement,
     /// `!operand`
     Negate,
+    /// `__real__ operand`, real part of a complex number
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Complex.html)
+    Real,
+    /// `__imag__ operand`, imaginary part of a complex number
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Complex.html)
+    Imag,
 }
 
 /// Unary operator expression
@@ -385,6 +644,7 @@ pub enum UnaryOperator {
 ///
 /// (C11 6.5.2, c11 6.5.3)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnaryOperatorExpression {
     pub operator: Node<UnaryOperator>,
     pub operand: Box<Node<Expression>>,
@@ -396,6 +656,7 @@ pub struct UnaryOperatorExpression {
 ///
 /// (C11 6.5.4)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CastExpression {
     pub type_name: Node<TypeName>,
     pub expression: Box<Node<Expression>>,
@@ -405,6 +666,7 @@ pub struct CastExpression {
 ///
 /// (C11 6.5)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BinaryOperator {
     /// `lhs[rhs]`
     Index,
@@ -474,6 +736,7 @@ pub enum BinaryOperator {
 ///
 /// (C11 6.5.5 -- 6.5.16)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BinaryOperatorExpression {
     pub operator: Node<BinaryOperator>,
     pub lhs: Box<Node<Expression>>,
@@ -484,6 +747,7 @@ pub struct BinaryOperatorExpression {
 ///
 /// (C11 6.5.15)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ConditionalExpression {
     pub condition: Box<Node<Expression>>,
     pub then_expression: Box<Node<Expression>>,
@@ -496,17 +760,59 @@ pub struct ConditionalExpression {
 ///
 /// (C11 7.16.1.1).
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VaArgExpression {
     pub va_list: Box<Node<Expression>>,
     pub type_name: Node<TypeName>,
 }
 
+/// Arguments of `__builtin_types_compatible_p`
+///
+/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html#index-_005f_005fbuiltin_005ftypes_005fcompatible_005fp)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypesCompatibleExpression {
+    pub first: Node<TypeName>,
+    pub second: Node<TypeName>,
+}
+
+/// Arguments of `__builtin_choose_expr`
+///
+/// The condition is an integer constant expression and the result has the type of the chosen
+/// expression, the other one is not evaluated. Calls with a different number of arguments are
+/// kept as `Expression::Call`.
+///
+/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html#index-_005f_005fbuiltin_005fchoose_005fexpr)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChooseExpression {
+    pub condition: Box<Node<Expression>>,
+    pub then_expression: Box<Node<Expression>>,
+    pub else_expression: Box<Node<Expression>>,
+}
+
+/// Block literal
+///
+/// `^ return-type ( parameters ) { ... }`, where both the return type and the parameters may be
+/// omitted, as in `^{ ... }`.
+///
+/// [Clang extension](https://clang.llvm.org/docs/BlockLanguageSpec.html)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockExpression {
+    pub return_type: Option<Node<TypeName>>,
+    pub parameters: Option<Node<FunctionDeclarator>>,
+    /// Compound statement
+    pub body: Box<Node<Statement>>,
+}
+
 /// Member offset expression
 ///
 /// Result of expansion of `offsetof` macro.
 ///
 /// (C11 7.19 §3).
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OffsetOfExpression {
     pub type_name: Node<TypeName>,
     pub designator: Node<OffsetDesignator>,
@@ -516,6 +822,7 @@ pub struct OffsetOfExpression {
 ///
 /// (C11 7.19 §3).
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OffsetDesignator {
     pub base: Node<Identifier>,
     pub members: Vec<Node<OffsetMember>>,
@@ -525,6 +832,7 @@ pub struct OffsetDesignator {
 ///
 /// (C11 7.19 §3).
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum OffsetMember {
     Member(Node<Identifier>),
     IndirectMember(Node<Identifier>),
@@ -535,8 +843,12 @@ pub enum OffsetMember {
 
 /// Variable, function or type declaration
 ///
-/// (C11 6.7)
+/// An attribute declaration like `[[fallthrough]];` or `__attribute__((fallthrough));` has a
+/// single `DeclarationSpecifier::Extension` holding the attributes and no declarators.
+///
+/// (C11 6.7, C23 6.7)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Declaration {
     pub specifiers: Vec<Node<DeclarationSpecifier>>,
     pub declarators: Vec<Node<InitDeclarator>>,
@@ -548,6 +860,7 @@ pub struct Declaration {
 ///
 /// (C11 6.7)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeclarationSpecifier {
     StorageClass(Node<StorageClassSpecifier>),
     TypeSpecifier(Node<TypeSpecifier>),
@@ -562,6 +875,7 @@ pub enum DeclarationSpecifier {
 ///
 /// (C11 6.7.6)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InitDeclarator {
     pub declarator: Node<Declarator>,
     pub initializer: Option<Node<Initializer>>,
@@ -573,6 +887,7 @@ pub struct InitDeclarator {
 ///
 /// (C11 6.7.1)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StorageClassSpecifier {
     /// `typedef`
     Typedef,
@@ -586,6 +901,12 @@ pub enum StorageClassSpecifier {
     Auto,
     /// `register`
     Register,
+    /// `constexpr` (C23 6.7.1)
+    Constexpr,
+    /// `__block`, variable captured by reference in blocks
+    ///
+    /// [Clang extension](https://clang.llvm.org/docs/BlockLanguageSpec.html)
+    Block,
 }
 
 // From 6.7.2
@@ -594,6 +915,7 @@ pub enum StorageClassSpecifier {
 ///
 /// (C11 6.7.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TypeSpecifier {
     /// `void`
     Void,
@@ -633,8 +955,12 @@ pub enum TypeSpecifier {
     TypedefName(Node<Identifier>),
     /// Specifies type of another type or expression
     ///
-    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
+    /// (C23 6.7.2.5), [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
     TypeOf(Node<TypeOf>),
+    /// `typeof_unqual`, type of another type or expression without qualifiers
+    ///
+    /// (C23 6.7.2.5)
+    TypeOfUnqual(Node<TypeOf>),
     /// Floating point types with guaranteed width and representation
     ///
     /// `_Float16`, `_Float32`, `_Float64`, `_Float128`
@@ -646,22 +972,97 @@ pub enum TypeSpecifier {
     /// `_Decimal16x`, `_Decimal32x`, `_Decimal64x`, `_Decimal128x`
     ///
     /// [ISO/IEC TS 18661-3:2015](http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1945.pdf)
+    ///
+    /// `_Decimal32`, `_Decimal64` and `_Decimal128` are standard since C23 (6.7.2), the other
+    /// decimal types require GNU extensions.
     TS18661Float(TS18661FloatType),
+    /// `__fp16`, `__float80`, `__float128` or `__ibm128`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Floating-Types.html)
+    TargetFloat(TargetFloat),
+    /// `__int128`, 128-bit integer on targets that support it
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/_005f_005fint128.html)
+    Int128,
+    /// `__int8`, `__int16`, `__int32` and `__int64`, sized integers
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/int8-int16-int32-int64)
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    /// `_Fract`, fixed-point type without integral part
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Fixed-Point.html)
+    Fract,
+    /// `_Accum`, fixed-point type with integral part
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Fixed-Point.html)
+    Accum,
+    /// `_Sat`, saturating fixed-point type
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Fixed-Point.html)
+    Sat,
+    /// `_BitInt(width)`, integer with the given number of bits
+    ///
+    /// `_ExtInt(width)` (Clang extension)
+    ///
+    /// (C23 6.7.2)
+    BitInt(Box<Node<Expression>>),
+    /// `auto` as the only type information in a declaration, the type is inferred from the
+    /// initializer
+    ///
+    /// `auto` alongside other type specifiers is `StorageClassSpecifier::Auto`.
+    ///
+    /// (C23 6.7.10)
+    Auto,
+    /// `__auto_type`, the type is inferred from the initializer
+    ///
+    /// Only accepted as the sole type specifier of a declaration whose declarators are plain
+    /// identifiers with initializers, pointer and array declarators are rejected like GCC does.
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
+    AutoType,
+    /// No type specifier in a legacy declaration, the type is `int`
+    ///
+    /// Only accepted with `Env::implicit_int`. The node has an empty span where the type
+    /// specifier would be written.
+    ///
+    /// (C89 3.5.2)
+    ImplicitInt,
 }
 
 /// Floating point type with guaranteed width and format
 ///
 /// [ISO/IEC TS 18661-3:2015](http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1945.pdf)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TS18661FloatType {
     pub format: TS18661FloatFormat,
     pub width: usize,
 }
 
+/// Floating point type only available on some targets
+///
+/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Floating-Types.html)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TargetFloat {
+    /// `__fp16`, half precision on ARM
+    Fp16,
+    /// `__float80`, x87 extended precision
+    Float80,
+    /// `__float128`, quadruple precision
+    Float128,
+    /// `__ibm128`, PowerPC double-double
+    Ibm128,
+}
+
 /// Floating point formats
 ///
 /// [ISO/IEC TS 18661-3:2015](http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1945.pdf)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TS18661FloatFormat {
     BinaryInterchange,
     BinaryExtended,
@@ -675,19 +1076,26 @@ pub enum TS18661FloatFormat {
 ///
 /// (C11 6.7.2.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StructType {
     pub kind: Node<StructKind>,
     pub identifier: Option<Node<Identifier>>,
+    /// Attributes between the `struct` or `union` keyword and the name
+    pub extensions: Vec<Node<Extension>>,
     /// List of structure of union members, when present.
     ///
     /// A [GNU extension](https://gcc.gnu.org/onlinedocs/gcc-8.1.0/gcc/Empty-Structures.html) allows the list to be empty.
     pub declarations: Option<Vec<Node<StructDeclaration>>>,
+    /// Attributes right after the closing brace, which apply to the type rather than to the
+    /// following declarator
+    pub trailing_extensions: Vec<Node<Extension>>,
 }
 
 /// The only difference between a `struct` and a `union`
 ///
 /// (C11 6.7.2.1)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StructKind {
     Struct,
     Union,
@@ -697,6 +1105,7 @@ pub enum StructKind {
 ///
 /// (C11 6.7.2.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StructDeclaration {
     Field(Node<StructField>),
     StaticAssert(Node<StaticAssert>),
@@ -704,9 +1113,15 @@ pub enum StructDeclaration {
 
 /// Struct field declaration
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StructField {
     pub specifiers: Vec<Node<SpecifierQualifier>>,
     pub declarators: Vec<Node<StructDeclarator>>,
+    /// Member without declarators whose type is a previously declared struct, union or
+    /// typedef, rather than an anonymous struct or union, see `Env::ms_extensions`
+    ///
+    /// [MSVC extension](https://gcc.gnu.org/onlinedocs/gcc/Unnamed-Fields.html)
+    pub ms_unnamed: bool,
 }
 
 /// Type and qualifiers for a struct declaration
@@ -715,6 +1130,7 @@ pub struct StructField {
 ///
 /// (C11 6.7.2.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SpecifierQualifier {
     TypeSpecifier(Node<TypeSpecifier>),
     TypeQualifier(Node<TypeQualifier>),
@@ -725,6 +1141,7 @@ pub enum SpecifierQualifier {
 ///
 /// (C11 6.7.2.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StructDeclarator {
     pub declarator: Option<Node<Declarator>>,
     pub bit_width: Option<Box<Node<Expression>>>,
@@ -736,15 +1153,24 @@ pub struct StructDeclarator {
 ///
 /// (C11 6.7.2.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EnumType {
     pub identifier: Option<Node<Identifier>>,
+    /// Attributes between the `enum` keyword and the name
+    pub extensions: Vec<Node<Extension>>,
+    /// Fixed underlying type after a colon (C23 6.7.2.2)
+    pub underlying: Option<Vec<Node<SpecifierQualifier>>>,
     pub enumerators: Vec<Node<Enumerator>>,
+    /// Attributes right after the closing brace, which apply to the type rather than to the
+    /// following declarator
+    pub trailing_extensions: Vec<Node<Extension>>,
 }
 
 /// Single constant inside a `enum` definition
 ///
 /// (C11 6.7.2.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Enumerator {
     pub identifier: Node<Identifier>,
     pub expression: Option<Box<Node<Expression>>>,
@@ -757,6 +1183,7 @@ pub struct Enumerator {
 ///
 /// (C11 6.7.3)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TypeQualifier {
     /// `const`
     ///
@@ -764,7 +1191,7 @@ pub enum TypeQualifier {
     Const,
     /// `restrict`
     ///
-    /// `__restrict`, `__restrict__` (GNU extension)
+    /// `__restrict`, `__restrict__` (GNU extension), `__restrict` (MSVC extension)
     Restrict,
     /// `volatile`
     ///
@@ -784,6 +1211,64 @@ pub enum TypeQualifier {
     Nullable,
     /// `_Atomic`
     Atomic,
+    /// `__unaligned`
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/unaligned)
+    Unaligned,
+    /// Named address space of the object
+    AddressSpace(AddressSpace),
+    /// Access of a kernel to an image parameter
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#access-qualifiers)
+    Access(AccessQualifier),
+}
+
+/// Named address space
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AddressSpace {
+    /// `__global`, `global`
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#address-space-qualifiers)
+    Global,
+    /// `__local`, `local`
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#address-space-qualifiers)
+    Local,
+    /// `__constant`, `constant`
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#address-space-qualifiers)
+    Constant,
+    /// `__private`, `private`
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#address-space-qualifiers)
+    Private,
+    /// `__generic`, `generic`
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#address-space-qualifiers)
+    Generic,
+    /// `__seg_fs`, relative to the `fs` segment on x86
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Named-Address-Spaces.html)
+    SegFs,
+    /// `__seg_gs`, relative to the `gs` segment on x86
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Named-Address-Spaces.html)
+    SegGs,
+}
+
+/// Image access qualifier
+///
+/// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#access-qualifiers)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AccessQualifier {
+    /// `__read_only`, `read_only`
+    ReadOnly,
+    /// `__write_only`, `write_only`
+    WriteOnly,
+    /// `__read_write`, `read_write`
+    ReadWrite,
 }
 
 // From 6.7.4
@@ -792,13 +1277,22 @@ pub enum TypeQualifier {
 ///
 /// (C11 6.7.4)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FunctionSpecifier {
     /// `inline`
     ///
-    /// `__inline`, `__inline__` (GNU extension)
+    /// `__inline`, `__inline__` (GNU extension), `__inline` (MSVC extension)
     Inline,
     /// `_Noreturn`
     Noreturn,
+    /// `__forceinline`
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/inline-functions-cpp)
+    ForceInline,
+    /// `__kernel`, `kernel`, function that can be enqueued from the host
+    ///
+    /// [OpenCL extension](https://registry.khronos.org/OpenCL/specs/3.0-unified/html/OpenCL_C.html#function-qualifiers)
+    Kernel,
 }
 
 // From 6.7.5
@@ -807,6 +1301,7 @@ pub enum FunctionSpecifier {
 ///
 /// (C11 6.7.5)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AlignmentSpecifier {
     /// `_Alignas(typename)`
     Type(Node<TypeName>),
@@ -822,6 +1317,7 @@ pub enum AlignmentSpecifier {
 ///
 /// (C11 6.7.6, 6.7.7)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Declarator {
     /// What is being declared
     pub kind: Node<DeclaratorKind>,
@@ -835,6 +1331,7 @@ pub struct Declarator {
 ///
 /// (C11 6.7.6, 6.7.7)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DeclaratorKind {
     /// Unnamed declarator
     ///
@@ -855,6 +1352,7 @@ pub enum DeclaratorKind {
 ///
 /// (C11 6.7.6)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DerivedDeclarator {
     /// `* qualifiers …`
     Pointer(Vec<Node<PointerQualifier>>),
@@ -872,6 +1370,7 @@ pub enum DerivedDeclarator {
 
 /// Array part of a declarator
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ArrayDeclarator {
     pub qualifiers: Vec<Node<TypeQualifier>>,
     pub size: ArraySize,
@@ -879,7 +1378,13 @@ pub struct ArrayDeclarator {
 
 /// Function parameter part of a declarator
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FunctionDeclarator {
+    /// Parameters declared ahead of `parameters` so that their sizes can refer to later ones,
+    /// `void f(int n; int a[n], int n)`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Variable-Length.html)
+    pub forward_parameters: Vec<Node<ParameterDeclaration>>,
     pub parameters: Vec<Node<ParameterDeclaration>>,
     pub ellipsis: Ellipsis,
 }
@@ -888,6 +1393,7 @@ pub struct FunctionDeclarator {
 ///
 /// (C11 6.7.6.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PointerQualifier {
     TypeQualifier(Node<TypeQualifier>),
     Extension(Vec<Node<Extension>>),
@@ -897,6 +1403,7 @@ pub enum PointerQualifier {
 ///
 /// (C11 6.7.6.2)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ArraySize {
     /// `[]`
     Unknown,
@@ -917,6 +1424,7 @@ pub enum ArraySize {
 ///
 /// (C11 6.7.6.3)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParameterDeclaration {
     pub specifiers: Vec<Node<DeclarationSpecifier>>,
     pub declarator: Option<Node<Declarator>>,
@@ -925,6 +1433,7 @@ pub struct ParameterDeclaration {
 
 /// Whether function signature ends with a `...`
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Ellipsis {
     Some,
     None,
@@ -938,6 +1447,7 @@ pub enum Ellipsis {
 ///
 /// (C11 6.7.7)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TypeName {
     pub specifiers: Vec<Node<SpecifierQualifier>>,
     pub declarator: Option<Node<Declarator>>,
@@ -949,6 +1459,7 @@ pub struct TypeName {
 ///
 /// (C11 6.7.9)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Initializer {
     Expression(Box<Node<Expression>>),
     List(Vec<Node<InitializerListItem>>),
@@ -958,6 +1469,7 @@ pub enum Initializer {
 ///
 /// (C11 6.7.9)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct InitializerListItem {
     pub designation: Vec<Node<Designator>>,
     pub initializer: Box<Node<Initializer>>,
@@ -965,6 +1477,7 @@ pub struct InitializerListItem {
 
 /// Single element of an designation in an initializer
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Designator {
     /// Array element
     ///
@@ -993,6 +1506,7 @@ pub enum Designator {
 ///
 /// ([GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Designated-Inits.html#Designated-Inits))
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RangeDesignator {
     pub from: Node<Expression>,
     pub to: Node<Expression>,
@@ -1004,9 +1518,11 @@ pub struct RangeDesignator {
 ///
 /// (C11 6.7.10)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StaticAssert {
     pub expression: Box<Node<Expression>>,
-    pub message: Node<StringLiteral>,
+    /// Message, which may be omitted since C23
+    pub message: Option<Node<StringLiteral>>,
 }
 
 // From 6.8 Statement
@@ -1015,6 +1531,7 @@ pub struct StaticAssert {
 ///
 /// (C11 6.8)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Statement {
     Labeled(Node<LabeledStatement>),
     Compound(Vec<Node<BlockItem>>),
@@ -1025,19 +1542,94 @@ pub enum Statement {
     DoWhile(Node<DoWhileStatement>),
     For(Node<ForStatement>),
     Goto(Node<Identifier>),
+    /// `goto *expr;` jumping to a label address
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Labels-as-Values.html)
+    GotoIndirect(Box<Node<Expression>>),
     Continue,
     Break,
     Return(Option<Box<Node<Expression>>>),
     /// Vendor specific inline assembly extensions
     Asm(Node<AsmStatement>),
+    /// `#pragma` line between block items
+    Pragma(Node<Pragma>),
+    /// OpenMP directive and the statement it applies to
+    Omp(Node<OmpStatement>),
+    /// Statement preceded by standard attributes
+    Attributed(Node<AttributedStatement>),
+    /// `__label__` declaration of labels local to the enclosing block, always at its start
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Local-Labels.html)
+    LocalLabels(Vec<Node<Identifier>>),
+    /// Function definition inside a function body
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Nested-Functions.html)
+    NestedFunction(Box<Node<FunctionDefinition>>),
+    /// `__try` block with an exception or termination handler
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/structured-exception-handling-c-cpp)
+    Try(Node<TryStatement>),
+    /// `__leave;` jumping to the end of the enclosing `__try` block
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/try-finally-statement)
+    Leave,
 }
 
 /// Labeled statement
 ///
 /// (C11 6.8.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LabeledStatement {
     pub label: Node<Label>,
+    /// Attributes after the colon of a goto label, like `out: __attribute__((cold));`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Label-Attributes.html)
+    pub attributes: Vec<Node<Extension>>,
+    pub statement: Box<Node<Statement>>,
+}
+
+/// Statement with attributes
+///
+/// Attributes before a label apply to the label, `statement` is then a `Statement::Labeled`.
+///
+/// (C23 6.8), `__attribute__((…))` before a statement like `__attribute__((musttail)) return
+/// f();` is a [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Statement-Attributes.html)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttributedStatement {
+    pub attributes: Vec<Node<Extension>>,
+    pub statement: Box<Node<Statement>>,
+}
+
+/// Structured exception handling statement
+///
+/// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/structured-exception-handling-c-cpp)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TryStatement {
+    /// Guarded compound statement
+    pub statement: Box<Node<Statement>>,
+    pub handler: TryHandler,
+}
+
+/// Handler of a `__try` block
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TryHandler {
+    /// `__except (filter) { ... }`
+    Except(ExceptHandler),
+    /// `__finally { ... }`, run however the guarded statement is left
+    Finally(Box<Node<Statement>>),
+}
+
+/// Exception handler run when `filter` evaluates to `EXCEPTION_EXECUTE_HANDLER`
+///
+/// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/try-except-statement)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExceptHandler {
+    pub filter: Box<Node<Expression>>,
     pub statement: Box<Node<Statement>>,
 }
 
@@ -1045,6 +1637,7 @@ pub struct LabeledStatement {
 ///
 /// (C11 6.8.4)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IfStatement {
     pub condition: Box<Node<Expression>>,
     pub then_statement: Box<Node<Statement>>,
@@ -1055,6 +1648,7 @@ pub struct IfStatement {
 ///
 /// (C11 6.8.4)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SwitchStatement {
     pub expression: Box<Node<Expression>>,
     pub statement: Box<Node<Statement>>,
@@ -1064,6 +1658,7 @@ pub struct SwitchStatement {
 ///
 /// (C11 6.8.5)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WhileStatement {
     pub expression: Box<Node<Expression>>,
     pub statement: Box<Node<Statement>>,
@@ -1073,6 +1668,7 @@ pub struct WhileStatement {
 ///
 /// (C11 6.8.5)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DoWhileStatement {
     pub statement: Box<Node<Statement>>,
     pub expression: Box<Node<Expression>>,
@@ -1082,6 +1678,7 @@ pub struct DoWhileStatement {
 ///
 /// (C11 6.8.5)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ForStatement {
     pub initializer: Node<ForInitializer>,
     pub condition: Option<Box<Node<Expression>>>,
@@ -1091,6 +1688,7 @@ pub struct ForStatement {
 
 /// Statement labels for `goto` and `switch`
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Label {
     /// Goto label
     ///
@@ -1118,6 +1716,7 @@ pub enum Label {
 ///
 /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Case-Ranges.html)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CaseRange {
     pub low: Box<Node<Expression>>,
     pub high: Box<Node<Expression>>,
@@ -1125,6 +1724,7 @@ pub struct CaseRange {
 
 /// First element of a `for` statement
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ForInitializer {
     /// `for(; …)`
     Empty,
@@ -1140,10 +1740,17 @@ pub enum ForInitializer {
 
 /// Element of a compound statement
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BlockItem {
     Declaration(Node<Declaration>),
     StaticAssert(Node<StaticAssert>),
     Statement(Node<Statement>),
+    /// Label not followed by a statement, before a declaration or at the end of the block
+    ///
+    /// A label followed by a statement is a `Statement::Labeled`.
+    ///
+    /// (C23 6.8.2)
+    Label(Node<Label>),
 }
 
 // From 6.9 External definitions
@@ -1152,22 +1759,33 @@ pub enum BlockItem {
 ///
 /// (C11 6.9)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TranslationUnit(pub Vec<Node<ExternalDeclaration>>);
 
 /// Top-level elements of a C program
 ///
 /// (C11 6.9)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExternalDeclaration {
     Declaration(Node<Declaration>),
     StaticAssert(Node<StaticAssert>),
     FunctionDefinition(Node<FunctionDefinition>),
+    /// `#pragma` line between external declarations
+    Pragma(Node<Pragma>),
+    /// Basic assembly at file scope, like `asm(".symver foo, foo@VERS_1");`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Basic-Asm.html)
+    Asm(Node<StringLiteral>),
+    /// Text skipped after a syntax error, see `driver::parse_preprocessed_recovering`
+    Error,
 }
 
 /// Function definition
 ///
 /// (C11 6.9.1)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FunctionDefinition {
     /// Return type of the function, possibly mixed with other specifiers
     pub specifiers: Vec<Node<DeclarationSpecifier>>,
@@ -1179,10 +1797,44 @@ pub struct FunctionDefinition {
     pub statement: Node<Statement>,
 }
 
+// From 6.10.6 Pragma directive
+
+/// Pragma directive left in the preprocessed source
+///
+/// Only pragmas between external declarations or block items are kept, elsewhere they are
+/// skipped like other directives.
+///
+/// (C11 6.10.6)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pragma {
+    pub kind: PragmaKind,
+    /// Text after `#pragma` up to the end of the line, or the operand of a pragma operator,
+    /// without surrounding whitespace
+    pub text: String,
+}
+
+/// Way a pragma is written
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PragmaKind {
+    /// `#pragma` line
+    Directive,
+    /// `_Pragma("...")` left by the preprocessor, `text` is the destringized literal
+    ///
+    /// (C11 6.10.9)
+    Operator,
+    /// `__pragma(...)`
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/preprocessor/pragma-directives-and-the-pragma-keyword)
+    Msvc,
+}
+
 // Syntax extensions
 
 /// Extended vendor-specific syntax that does not fit elsewhere
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Extension {
     /// Attributes
     ///
@@ -1196,21 +1848,109 @@ pub enum Extension {
     ///
     /// [Clang extension](https://clang.llvm.org/docs/AttributeReference.html#availability)
     AvailabilityAttribute(Node<AvailabilityAttribute>),
+    /// Attribute in double square brackets
+    ///
+    /// (C23 6.7.12)
+    StdAttribute(StdAttribute),
+    /// `__declspec( attributes )`
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/declspec)
+    Declspec(Vec<Node<Attribute>>),
+    /// Calling convention of a function or function pointer declarator
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/argument-passing-and-naming-conventions)
+    CallingConvention(CallingConvention),
+    /// `__extension__`, which suppresses pedantic warnings in the declaration
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Alternate-Keywords.html)
+    Marker,
+    /// Execution or memory space of a function or variable
+    ///
+    /// [CUDA extension](https://docs.nvidia.com/cuda/cuda-c-programming-guide/#c-cpp-language-extensions)
+    Cuda(CudaSpecifier),
+}
+
+/// CUDA declaration specifier
+///
+/// [CUDA extension](https://docs.nvidia.com/cuda/cuda-c-programming-guide/#c-cpp-language-extensions)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CudaSpecifier {
+    /// `__global__`, kernel run on the device and launched from the host
+    Global,
+    /// `__device__`, function or variable on the device
+    Device,
+    /// `__host__`, function run on the host
+    Host,
+    /// `__shared__`, variable in the shared memory of a thread block
+    Shared,
+    /// `__constant__`, variable in constant memory
+    Constant,
+    /// `__managed__`, variable accessible from both host and device
+    Managed,
+    /// `__launch_bounds__(max_threads, min_blocks)`, with one to three arguments
+    LaunchBounds(Vec<Node<Expression>>),
+}
+
+/// Calling convention keyword
+///
+/// [MSVC extension](https://learn.microsoft.com/en-us/cpp/cpp/argument-passing-and-naming-conventions)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CallingConvention {
+    /// `__cdecl`
+    Cdecl,
+    /// `__stdcall`
+    Stdcall,
+    /// `__fastcall`
+    Fastcall,
+    /// `__vectorcall`
+    Vectorcall,
+    /// `__thiscall`
+    Thiscall,
+    /// `__clrcall`
+    Clrcall,
 }
 
 /// Attributes
 ///
 /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Attribute-Syntax.html)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Attribute {
     pub name: Node<String>,
     pub arguments: Vec<Node<Expression>>,
 }
 
+/// Attribute in double square brackets, like `[[nodiscard]]` or `[[gnu::hot]]`
+///
+/// (C23 6.7.12.1)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StdAttribute {
+    /// Prefix before `::`, naming the vendor of the attribute
+    pub namespace: Option<Node<Identifier>>,
+    pub name: Node<Identifier>,
+    pub arguments: Option<Node<StdAttributeArguments>>,
+}
+
+/// Parenthesized arguments of a standard attribute
+///
+/// (C23 6.7.12.1)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StdAttributeArguments {
+    /// Comma-separated expressions, as in `[[deprecated("message")]]`
+    Expressions(Vec<Node<Expression>>),
+    /// Balanced tokens that do not form expressions, as written between the parenthesis
+    Tokens(String),
+}
+
 /// Platform availability attribute
 ///
 /// [Clang extension](https://clang.llvm.org/docs/AttributeReference.html#availability)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AvailabilityAttribute {
     pub platform: Node<Identifier>,
     pub clauses: Vec<Node<AvailabilityClause>>,
@@ -1220,6 +1960,7 @@ pub struct AvailabilityAttribute {
 ///
 /// [Clang extension](https://clang.llvm.org/docs/AttributeReference.html#availability)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AvailabilityClause {
     Introduced(Node<AvailabilityVersion>),
     Deprecated(Node<AvailabilityVersion>),
@@ -1233,14 +1974,38 @@ pub enum AvailabilityClause {
 ///
 /// [Clang extension](https://clang.llvm.org/docs/AttributeReference.html#availability)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AvailabilityVersion {
     pub major: String,
     pub minor: Option<String>,
     pub subminor: Option<String>,
 }
 
+/// Single argument of `__builtin_available`
+///
+/// [Clang extension](https://clang.llvm.org/docs/LanguageExtensions.html#objective-c-available)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AvailabilityCondition {
+    /// `platform version`
+    Platform(AvailabilityPlatform),
+    /// `*`, any other platform
+    Star,
+}
+
+/// Minimum version of a platform in `__builtin_available`
+///
+/// [Clang extension](https://clang.llvm.org/docs/LanguageExtensions.html#objective-c-available)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AvailabilityPlatform {
+    pub platform: Node<Identifier>,
+    pub version: Node<AvailabilityVersion>,
+}
+
 /// Inline assembler
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AsmStatement {
     /// Basic asm statement with just source code
     ///
@@ -1251,24 +2016,36 @@ pub enum AsmStatement {
     ///
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Extended-Asm.html)
     GnuExtended(GnuExtendedAsmStatement),
+
+    /// Unparsed source of an `__asm` block, including braces, or of a single `__asm` line
+    ///
+    /// [MSVC extension](https://learn.microsoft.com/en-us/cpp/assembler/inline/asm)
+    Msvc(String),
 }
 
 /// Extended statement that has access to C variables
 ///
 /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Extended-Asm.html)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GnuExtendedAsmStatement {
     pub qualifier: Option<Node<TypeQualifier>>,
+    /// `asm goto` that may jump to one of `labels`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Extended-Asm.html#GotoLabels)
+    pub goto: bool,
     pub template: Node<StringLiteral>,
     pub outputs: Vec<Node<GnuAsmOperand>>,
     pub inputs: Vec<Node<GnuAsmOperand>>,
     pub clobbers: Vec<Node<StringLiteral>>,
+    pub labels: Vec<Node<Identifier>>,
 }
 
 /// Single input or output operand specifier for GNU extended asm statement
 ///
 /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Extended-Asm.html#Output-Operands)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GnuAsmOperand {
     pub symbolic_name: Option<Node<Identifier>>,
     pub constraints: Node<StringLiteral>,
@@ -1277,9 +2054,24 @@ pub struct GnuAsmOperand {
 
 /// Type of an expression or type
 ///
-/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
+/// (C23 6.7.2.5), [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TypeOf {
     Expression(Node<Expression>),
     Type(Node<TypeName>),
 }
+
+#[test]
+fn test_identifier_decoded() {
+    let decoded = |name: &str| {
+        Identifier {
+            name: name.to_owned(),
+        }
+        .decoded()
+    };
+    assert_eq!(decoded("caf\\u00e9"), "caf\u{e9}");
+    assert_eq!(decoded("caf\\U000000E9"), "caf\u{e9}");
+    assert_eq!(decoded("caf\u{e9}"), "caf\u{e9}");
+    assert_eq!(decoded("\\u03c0_2"), "\u{3c0}_2");
+}
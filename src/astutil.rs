@@ -7,6 +7,7 @@ pub enum Operation {
     Unary(Node<UnaryOperator>),
     Binary(Node<BinaryOperator>, Node<Expression>),
     Call(Vec<Node<Expression>>),
+    KernelCall(Vec<Node<Expression>>, Vec<Node<Expression>>),
 }
 
 fn apply_op(a: Node<Expression>, op: Node<Operation>) -> Node<Expression> {
@@ -42,6 +43,14 @@ fn apply_op(a: Node<Expression>, op: Node<Operation>) -> Node<Expression> {
             },
             span,
         ))),
+        Operation::KernelCall(config, args) => Expression::KernelCall(Box::new(Node::new(
+            KernelCallExpression {
+                callee: Box::new(a),
+                configuration: config,
+                arguments: args,
+            },
+            span,
+        ))),
     };
 
     Node::new(expr, span)
@@ -83,6 +92,131 @@ pub fn with_ext(mut d: Node<Declarator>, e: Option<Vec<Node<Extension>>>) -> Nod
     d
 }
 
+pub fn has_constexpr(specifiers: &[Node<DeclarationSpecifier>]) -> bool {
+    specifiers.iter().any(|s| match s.node {
+        DeclarationSpecifier::StorageClass(ref c) => c.node == StorageClassSpecifier::Constexpr,
+        _ => false,
+    })
+}
+
+// Turn the `auto` storage class of a C23 declaration without type specifiers into the type
+// specifier of an inferred type
+pub fn auto_type(
+    mut specifiers: Vec<Node<DeclarationSpecifier>>,
+) -> Result<Vec<Node<DeclarationSpecifier>>, &'static str> {
+    let mut autos = specifiers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| match s.node {
+            DeclarationSpecifier::StorageClass(ref c) if c.node == StorageClassSpecifier::Auto => {
+                Some((i, c.span))
+            }
+            _ => None,
+        });
+    match (autos.next(), autos.next()) {
+        (Some((i, span)), None) => {
+            specifiers[i].node =
+                DeclarationSpecifier::TypeSpecifier(Node::new(TypeSpecifier::Auto, span));
+            Ok(specifiers)
+        }
+        _ => Err("auto"),
+    }
+}
+
+// Derived declarator applied first to the declared identifier, looking through parenthesized
+// declarators
+fn first_derived(d: &Declarator) -> Option<&DerivedDeclarator> {
+    if let DeclaratorKind::Declarator(ref inner) = d.kind.node {
+        if let Some(f) = first_derived(&inner.node) {
+            return Some(f);
+        }
+    }
+    let mut derived = d.derived.iter().map(|d| &d.node);
+    derived
+        .clone()
+        .find(|d| match **d {
+            DerivedDeclarator::Pointer(_) | DerivedDeclarator::Block(_) => false,
+            _ => true,
+        })
+        .or_else(|| derived.next())
+}
+
+// Whether the declared identifier is a function rather than a pointer or array
+pub fn declares_function(d: &Declarator) -> bool {
+    match first_derived(d) {
+        Some(&DerivedDeclarator::Function(_)) | Some(&DerivedDeclarator::KRFunction(_)) => true,
+        _ => false,
+    }
+}
+
+// Whether the specifiers define a struct, union or enum, so that a struct member without
+// declarators is an anonymous member rather than a useless declaration
+pub fn defines_type(s: &[Node<SpecifierQualifier>]) -> bool {
+    s.iter().any(|s| match s.node {
+        SpecifierQualifier::TypeSpecifier(ref t) => match t.node {
+            TypeSpecifier::Struct(ref s) => s.node.declarations.is_some(),
+            TypeSpecifier::Enum(_) => true,
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
+// Whether the specifiers name a struct, union or typedef declared elsewhere
+pub fn names_type(s: &[Node<SpecifierQualifier>]) -> bool {
+    s.iter().any(|s| match s.node {
+        SpecifierQualifier::TypeSpecifier(ref t) => match t.node {
+            TypeSpecifier::Struct(ref s) => s.node.declarations.is_none(),
+            TypeSpecifier::TypedefName(_) => true,
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
+// Array size written as `0`, allowed by GNU and MSVC for trailing flexible members
+pub fn is_zero_size(e: &Node<Expression>) -> bool {
+    match e.node {
+        Expression::Constant(ref c) => match c.node {
+            Constant::Integer(ref i) => i.number.chars().all(|c| c == '0'),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Whether a parameter of the declared function has no name, other than a lone `void`
+pub fn has_unnamed_parameters(d: &Declarator) -> bool {
+    fn named(d: &Declarator) -> bool {
+        match d.kind.node {
+            DeclaratorKind::Identifier(_) => true,
+            DeclaratorKind::Declarator(ref inner) => named(&inner.node),
+            DeclaratorKind::Abstract => false,
+        }
+    }
+
+    let f = match first_derived(d) {
+        Some(&DerivedDeclarator::Function(ref f)) => &f.node,
+        _ => return false,
+    };
+    let void = f.parameters.len() == 1
+        && f.ellipsis == Ellipsis::None
+        && f.parameters[0].node.declarator.is_none()
+        && f.parameters[0]
+            .node
+            .specifiers
+            .iter()
+            .any(|s| match s.node {
+                DeclarationSpecifier::TypeSpecifier(ref t) => t.node == TypeSpecifier::Void,
+                _ => false,
+            });
+    !void
+        && f.parameters.iter().any(|p| match p.node.declarator {
+            Some(ref d) => !named(&d.node),
+            None => true,
+        })
+}
+
 pub fn ts18661_float(binary: bool, width: usize, extended: bool) -> TS18661FloatType {
     TS18661FloatType {
         format: match (binary, extended) {
@@ -95,6 +229,114 @@ pub fn ts18661_float(binary: bool, width: usize, extended: bool) -> TS18661Float
     }
 }
 
+pub fn with_extension_markers(
+    e: Option<Node<Vec<Node<Extension>>>>,
+    d: StructDeclaration,
+) -> StructDeclaration {
+    match (e, d) {
+        (Some(e), StructDeclaration::Field(mut f)) => {
+            let s = Node::new(SpecifierQualifier::Extension(e.node), e.span);
+            f.node.specifiers.insert(0, s);
+            StructDeclaration::Field(f)
+        }
+        (_, d) => d,
+    }
+}
+
+pub fn char_constant(value: &str) -> Character {
+    Character {
+        encoding: encoding_prefix(value),
+        value: value.to_owned(),
+    }
+}
+
+pub fn string_piece(value: &str) -> StringPiece {
+    StringPiece {
+        encoding: encoding_prefix(value),
+        value: value.to_owned(),
+    }
+}
+
+fn encoding_prefix(value: &str) -> CharEncoding {
+    match value.as_bytes()[0] {
+        b'L' => CharEncoding::Wide,
+        b'U' => CharEncoding::Utf32,
+        b'u' if value.starts_with("u8") => CharEncoding::Utf8,
+        b'u' => CharEncoding::Utf16,
+        _ => CharEncoding::Plain,
+    }
+}
+
+// Whether a character outside of the basic character set can be part of an identifier, either
+// written directly or as a universal character name
+//
+// This is more permissive than the ranges of C11 Annex D and the XID properties used by C23,
+// only excluding control characters, spaces and the basic character set.
+pub fn is_extended_identifier_char(c: char) -> bool {
+    c >= '\u{a0}' && !c.is_whitespace()
+}
+
+// Two-character prefixes of punctuators and comments
+const JOINED_PUNCTUATORS: &'static [&'static str] = &[
+    "..", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "*=", "/=", "%=", "+=",
+    "-=", "&=", "^=", "|=", "##", "::", "<:", ":>", "<%", "%>", "%:", "//", "/*",
+];
+
+// Whether removing the line splice at byte `pos` of `source`, along with any adjacent ones,
+// would join the characters around it into a single token
+pub fn splice_joins(source: &str, pos: usize) -> bool {
+    let mut before = &source[..pos];
+    while before.ends_with("\\\n") || before.ends_with("\\\r\n") {
+        before = &before[..before.rfind('\\').expect("splice")];
+    }
+    let mut after = &source[pos..];
+    while after.starts_with("\\\n") || after.starts_with("\\\r\n") {
+        after = &after[after.find('\n').expect("splice") + 1..];
+    }
+    let (a, b) = match (before.chars().next_back(), after.chars().next()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+
+    let word = |c: char| {
+        c == '_' || c == '$' || c.is_ascii_alphanumeric() || is_extended_identifier_char(c)
+    };
+    let pair = format!("{}{}", a, b);
+    word(a) && word(b)
+        || a == '.' && b.is_ascii_digit()
+        || a.is_ascii_digit() && b == '.'
+        || JOINED_PUNCTUATORS.contains(&&pair[..])
+}
+
+// Universal character name in an identifier, from its hexadecimal digits
+pub fn identifier_ucn(hex: &str) -> Result<(), &'static str> {
+    match u32::from_str_radix(hex, 16).ok().and_then(::std::char::from_u32) {
+        Some(c) if is_extended_identifier_char(c) => Ok(()),
+        _ => Err("universal character name"),
+    }
+}
+
+// Operand of `_Pragma`, without encoding prefix and quotes and with `\"` and `\\` unescaped
+pub fn destringize(s: &str) -> String {
+    let start = s.find('"').map_or(0, |i| i + 1);
+    let mut text = String::new();
+    let mut chars = s[start..s.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(n) if n == '"' || n == '\\' => text.push(n),
+                Some(n) => {
+                    text.push(c);
+                    text.push(n);
+                }
+                None => text.push(c),
+            },
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
 pub fn int_suffix(mut s: &str) -> Result<IntegerSuffix, &'static str> {
     let mut l = IntegerSize::Int;
     let mut u = false;
@@ -107,6 +349,9 @@ pub fn int_suffix(mut s: &str) -> Result<IntegerSuffix, &'static str> {
         } else if l == IntegerSize::Int && (s.starts_with("l") || s.starts_with("L")) {
             l = IntegerSize::Long;
             s = &s[1..];
+        } else if l == IntegerSize::Int && (s.starts_with("wb") || s.starts_with("WB")) {
+            l = IntegerSize::BitPrecise;
+            s = &s[2..];
         } else if !u && (s.starts_with("u") || s.starts_with("U")) {
             u = true;
             s = &s[1..];
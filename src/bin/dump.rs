@@ -17,11 +17,13 @@ fn main() {
             config = Config::with_gcc();
         } else if opt == "-use-clang" {
             config = Config::with_clang();
+        } else if opt == "-use-msvc" {
+            config = Config::with_msvc();
         } else if opt == "-use-std" {
             config.flavor = Flavor::StdC11;
         } else if opt == "-q" {
             quiet = true;
-        } else if opt.starts_with("-") {
+        } else if opt.starts_with("-") && opt != "-" {
             config.cpp_options.push(opt);
         } else {
             if source.is_none() {
@@ -0,0 +1,210 @@
+//! Cache of preprocessed sources, see `Config::cache_dir`
+//!
+//! Each entry is a file named after a hash of the configuration, the source path and the
+//! source contents. It lists the files the source included, with their size and modification
+//! time, followed by preprocessor messages and the preprocessed text. An entry is only used if
+//! none of the included files changed since it was written.
+use std::fs::{self, File};
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::UNIX_EPOCH;
+
+use driver::{Config, Diagnostic};
+use loc;
+
+const HEADER: &'static str = "lang-c preprocessor cache 1";
+const EXTENSION: &'static str = "lang-c-cache";
+
+/// Entry for a source file in the cache directory
+pub struct Entry {
+    path: PathBuf,
+    dir: PathBuf,
+}
+
+impl Entry {
+    /// Find the entry for `source` preprocessed according to `config`
+    ///
+    /// Returns `None` if the source cannot be read.
+    pub fn new(config: &Config, cache_dir: &Path, source: &Path) -> Option<Entry> {
+        let mut contents = Vec::new();
+        let dir = config.cpp_current_dir.clone().unwrap_or_default();
+        if File::open(dir.join(source))
+            .and_then(|mut f| f.read_to_end(&mut contents))
+            .is_err()
+        {
+            return None;
+        }
+
+        let settings = format!(
+            "{:?}",
+            (
+                (HEADER, env!("CARGO_PKG_VERSION"), source),
+                (
+                    &config.cpp_command,
+                    &config.cpp_options,
+                    config.cpp_kind,
+                    &config.cpp_env,
+                    config.cpp_env_clear,
+                    &config.cpp_current_dir,
+                ),
+                (
+                    &config.include_dirs,
+                    &config.macros,
+                    config.flavor,
+                    config.no_std_flag,
+                    config.keep_comments,
+                    config.encoding,
+                ),
+            )
+        );
+        let mut hash = Fnv::new();
+        hash.write(settings.as_bytes());
+        hash.write(&[0]);
+        hash.write(&contents);
+
+        Some(Entry {
+            path: cache_dir.join(format!("{:016x}.{}", hash.0, EXTENSION)),
+            dir: dir,
+        })
+    }
+
+    /// Preprocessed text and messages, if the entry exists and is up to date
+    pub fn load(&self) -> Option<(String, Vec<Diagnostic>)> {
+        let mut text = String::new();
+        if File::open(&self.path)
+            .and_then(|mut f| f.read_to_string(&mut text))
+            .is_err()
+        {
+            return None;
+        }
+
+        let mut lines = text.split('\n');
+        if lines.next() != Some(HEADER) {
+            return None;
+        }
+        let mut diagnostics = Vec::new();
+        let mut size = None;
+        let mut length = 0;
+        for line in lines {
+            length += line.len() + 1;
+            if line == "" {
+                break;
+            } else if line.starts_with("size ") {
+                size = line[5..].parse::<usize>().ok();
+            } else if line.starts_with("diag ") {
+                diagnostics.push(Diagnostic::parse(&line[5..]));
+            } else if line.starts_with("dep ") {
+                let mut parts = line[4..].splitn(2, ' ');
+                let stamp = parts.next();
+                let path = match parts.next() {
+                    Some(path) => path,
+                    None => return None,
+                };
+                if stamp != stamp_of(&self.dir.join(path)).as_ref().map(|s| &s[..]) {
+                    return None;
+                }
+            } else {
+                return None;
+            }
+        }
+
+        let start = HEADER.len() + 1 + length;
+        if size != Some(text.len().saturating_sub(start)) {
+            return None;
+        }
+        Some((text[start..].to_owned(), diagnostics))
+    }
+
+    /// Save the result of preprocessing `source`
+    ///
+    /// Nothing is saved if an included file cannot be found, as it could not be checked for
+    /// changes later.
+    pub fn store(&self, source: &Path, text: &str, diagnostics: &[Diagnostic]) -> io::Result<()> {
+        let mut entry = String::new();
+        entry.push_str(HEADER);
+        entry.push('\n');
+        entry.push_str(&format!("size {}\n", text.len()));
+        for file in loc::get_included_files(text) {
+            if file.path == source {
+                continue;
+            }
+            let path = match file.path.to_str() {
+                Some(path) if !path.contains('\n') => path.to_owned(),
+                _ => return Ok(()),
+            };
+            let stamp = match stamp_of(&self.dir.join(&path)) {
+                Some(stamp) => stamp,
+                None => return Ok(()),
+            };
+            entry.push_str(&format!("dep {} {}\n", stamp, path));
+        }
+        for d in diagnostics {
+            entry.push_str(&format!("diag {}\n", d.to_string().replace('\n', " ")));
+        }
+        entry.push('\n');
+        entry.push_str(text);
+
+        // write to a temporary file first so that readers never see a partial entry
+        if let Some(parent) = self.path.parent() {
+            try!(fs::create_dir_all(parent));
+        }
+        let temp = self
+            .path
+            .with_extension(format!("{}.{}", process::id(), EXTENSION));
+        try!(File::create(&temp).and_then(|mut f| f.write_all(entry.as_bytes())));
+        fs::rename(&temp, &self.path).map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            e
+        })
+    }
+}
+
+/// Delete all entries in `cache_dir`, returning how many were removed
+pub fn purge(cache_dir: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    let entries = match fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let path = try!(entry).path();
+        if path.extension().map_or(false, |e| e == EXTENSION) {
+            try!(fs::remove_file(&path));
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+// Size and modification time of a file, as a string compared for equality
+fn stamp_of(path: &Path) -> Option<String> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(_) => return None,
+    };
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or((0, 0), |d| (d.as_secs(), d.subsec_nanos()));
+    Some(format!("{}:{}.{:09}", meta.len(), modified.0, modified.1))
+}
+
+// 64-bit FNV-1a, which unlike the standard library hasher is stable between releases
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Fnv {
+        Fnv(0xcbf29ce484222325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
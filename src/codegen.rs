@@ -0,0 +1,2532 @@
+//! C source emitter
+//!
+//! Writes a syntax tree back as C source that parses into the same tree, apart from spans.
+//! Declarations and statements are placed one per line and indented as set by `FormatOptions`,
+//! and expressions get the parentheses required by the precedence of their operators.
+//!
+//! Comments and the original layout are not kept, and keywords with several spellings are
+//! written in the standard one, like `restrict` for `__restrict__`. Literals are written as
+//! they were in the source. `type_to_string` and `type_name_to_string` spell a type on its own,
+//! as in diagnostics.
+//!
+//! ```
+//! use lang_c::codegen::{emit_translation_unit, FormatOptions};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//!
+//! let config = Config::default();
+//! let parse = parse_preprocessed(&config, "int(*f)(int x),y=1+2*3;".into()).unwrap();
+//! let options = FormatOptions::default();
+//! assert_eq!(
+//!     emit_translation_unit(&parse.unit, &options),
+//!     "int (*f)(int x), y = 1 + 2 * 3;\n"
+//! );
+//! ```
+
+use ast::*;
+use omp::*;
+use span::{Node, Span};
+
+/// Layout of the emitted source
+///
+/// Options only change whitespace and comments, the source parses into the same tree with any
+/// of them. The default is the style of K&R: four spaces of indentation, function braces on
+/// their own line and other braces at the end of the line.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Columns of indentation per nesting level
+    pub indent_width: usize,
+    /// Indent with one tab per nesting level instead of spaces
+    ///
+    /// Wrapped lines are aligned with spaces after the tabs. A tab counts as `indent_width`
+    /// columns towards `max_width`.
+    pub use_tabs: bool,
+    /// Placement of the opening brace of a function body
+    pub function_braces: BracePlacement,
+    /// Placement of the braces of compound statements controlled by `if`, `switch`, `while`,
+    /// `do`, `for` and `__try`
+    pub control_braces: BracePlacement,
+    /// Wrap parameter lists and initializer lists that would make a line longer than this
+    ///
+    /// Items that do not fit on the line go to the next one, aligned with the first item of the
+    /// list. Other constructs are never wrapped, so lines may still be longer. `None` never
+    /// wraps.
+    pub max_width: Option<usize>,
+    /// Write a space between a control keyword and its parenthesis, as in `if (x)`
+    pub space_before_paren: bool,
+    /// Syntax of comments written by the emitter, like the placeholder of a syntax error
+    pub comments: CommentStyle,
+}
+
+impl FormatOptions {
+    /// Style of LLVM: two spaces of indentation, all braces at the end of the line, lines of at
+    /// most 80 columns and `//` comments
+    pub fn llvm() -> FormatOptions {
+        FormatOptions {
+            indent_width: 2,
+            use_tabs: false,
+            function_braces: BracePlacement::SameLine,
+            control_braces: BracePlacement::SameLine,
+            max_width: Some(80),
+            space_before_paren: true,
+            comments: CommentStyle::Line,
+        }
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            indent_width: 4,
+            use_tabs: false,
+            function_braces: BracePlacement::NextLine,
+            control_braces: BracePlacement::SameLine,
+            max_width: Some(80),
+            space_before_paren: true,
+            comments: CommentStyle::Block,
+        }
+    }
+}
+
+/// Placement of an opening brace
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BracePlacement {
+    /// At the end of the line, `if (x) {`, with `} else {` after it
+    SameLine,
+    /// On a line of its own, with `else` on the line after the closing brace
+    NextLine,
+}
+
+/// Syntax of comments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentStyle {
+    /// `/* comment */`
+    Block,
+    /// `// comment`, ending the line
+    Line,
+}
+
+/// Source of a translation unit, each external declaration followed by a newline
+pub fn emit_translation_unit(unit: &TranslationUnit, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.translation_unit(unit);
+    e.out
+}
+
+/// Source of a single external declaration
+pub fn emit_external_declaration(
+    declaration: &ExternalDeclaration,
+    options: &FormatOptions,
+) -> String {
+    let mut e = Emitter::new(options);
+    e.external_declaration(declaration);
+    e.out
+}
+
+/// Source of a declaration, including the final `;`
+pub fn emit_declaration(declaration: &Declaration, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.declaration(declaration);
+    e.out
+}
+
+/// Source of a function definition
+pub fn emit_function_definition(
+    definition: &FunctionDefinition,
+    options: &FormatOptions,
+) -> String {
+    let mut e = Emitter::new(options);
+    e.function_definition(definition);
+    e.out
+}
+
+/// Source of a statement, on several lines for compound statements
+pub fn emit_statement(statement: &Statement, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.statement(statement);
+    e.out
+}
+
+/// Source of an expression
+pub fn emit_expression(expression: &Expression, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.expression(expression, PREC_COMMA);
+    e.out
+}
+
+/// Source of an initializer, as it follows the `=` of a declarator
+pub fn emit_initializer(initializer: &Initializer, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.initializer(initializer);
+    e.out
+}
+
+/// Source of a type name, as written in a cast or `sizeof`
+pub fn emit_type_name(type_name: &TypeName, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.type_name(type_name);
+    e.out
+}
+
+/// Source of a literal constant
+pub fn emit_constant(constant: &Constant, options: &FormatOptions) -> String {
+    let mut e = Emitter::new(options);
+    e.constant(constant);
+    e.out
+}
+
+/// C spelling of the type given by declaration specifiers and a declarator, like
+/// `const char *restrict names[4]`
+///
+/// Storage class, function and alignment specifiers are not part of the type and are left out,
+/// and so is the asm label of the declarator. An `identifier` is written in place of the name
+/// of the declarator, or where the name would go in an abstract one: `int (*)[3]` with `p` is
+/// `int (*p)[3]`. The type is written on one line, unless it defines a struct or an enum.
+pub fn type_to_string(
+    specifiers: &[Node<DeclarationSpecifier>],
+    declarator: Option<&Declarator>,
+    identifier: Option<&str>,
+) -> String {
+    let specifiers: Vec<_> = specifiers
+        .iter()
+        .filter_map(|s| specifier_qualifier(&s.node).map(|q| Node::new(q, s.span)))
+        .collect();
+    spelled_type(&specifiers, declarator, identifier)
+}
+
+/// C spelling of a type name, optionally declaring `identifier`, see `type_to_string`
+pub fn type_name_to_string(type_name: &TypeName, identifier: Option<&str>) -> String {
+    let declarator = type_name.declarator.as_ref().map(|d| &d.node);
+    spelled_type(&type_name.specifiers, declarator, identifier)
+}
+
+fn spelled_type(
+    specifiers: &[Node<SpecifierQualifier>],
+    declarator: Option<&Declarator>,
+    identifier: Option<&str>,
+) -> String {
+    let mut declarator = declarator.cloned();
+    if let Some(ref mut d) = declarator {
+        d.extensions.retain(|e| group(&e.node) != Group::AsmLabel);
+    }
+    if let Some(name) = identifier {
+        let name = Identifier { name: name.into() };
+        let kind = DeclaratorKind::Identifier(Node::new(name, Span::none()));
+        let kind = Node::new(kind, Span::none());
+        match declarator {
+            Some(ref mut d) => rename(d, kind),
+            None => {
+                declarator = Some(Declarator {
+                    kind: kind,
+                    derived: Vec::new(),
+                    extensions: Vec::new(),
+                })
+            }
+        }
+    }
+
+    let options = FormatOptions {
+        max_width: None,
+        ..FormatOptions::default()
+    };
+    let mut e = Emitter::new(&options);
+    e.declared_type(specifiers, declarator.as_ref());
+    e.out
+}
+
+// Precedence of expressions, higher binds tighter. Operands are written in parentheses when
+// their precedence is lower than what their position allows.
+const PREC_COMMA: u8 = 1;
+const PREC_ASSIGN: u8 = 2;
+const PREC_CONDITIONAL: u8 = 3;
+const PREC_LOGICAL_OR: u8 = 4;
+const PREC_CAST: u8 = 14;
+const PREC_UNARY: u8 = 15;
+const PREC_POSTFIX: u8 = 16;
+
+fn precedence(e: &Expression) -> u8 {
+    match *e {
+        Expression::UnaryOperator(ref u) => match u.node.operator.node {
+            UnaryOperator::PostIncrement | UnaryOperator::PostDecrement => PREC_POSTFIX,
+            _ => PREC_UNARY,
+        },
+        Expression::SizeOfTy(_)
+        | Expression::SizeOfVal(_)
+        | Expression::CountOfTy(_)
+        | Expression::CountOfVal(_)
+        | Expression::AlignOf(_)
+        | Expression::LabelAddress(_)
+        | Expression::Extension(_) => PREC_UNARY,
+        Expression::Cast(_) => PREC_CAST,
+        Expression::BinaryOperator(ref b) => binary_precedence(&b.node.operator.node),
+        Expression::Conditional(_) => PREC_CONDITIONAL,
+        Expression::Comma(_) => PREC_COMMA,
+        _ => PREC_POSTFIX,
+    }
+}
+
+fn binary_precedence(op: &BinaryOperator) -> u8 {
+    match *op {
+        BinaryOperator::Index => PREC_POSTFIX,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 13,
+        BinaryOperator::Plus | BinaryOperator::Minus => 12,
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 11,
+        BinaryOperator::Less
+        | BinaryOperator::Greater
+        | BinaryOperator::LessOrEqual
+        | BinaryOperator::GreaterOrEqual => 10,
+        BinaryOperator::Equals | BinaryOperator::NotEquals => 9,
+        BinaryOperator::BitwiseAnd => 8,
+        BinaryOperator::BitwiseXor => 7,
+        BinaryOperator::BitwiseOr => 6,
+        BinaryOperator::LogicalAnd => 5,
+        BinaryOperator::LogicalOr => PREC_LOGICAL_OR,
+        _ => PREC_ASSIGN,
+    }
+}
+
+fn binary_operator(op: &BinaryOperator) -> &'static str {
+    match *op {
+        BinaryOperator::Index => "[]",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::Plus => "+",
+        BinaryOperator::Minus => "-",
+        BinaryOperator::ShiftLeft => "<<",
+        BinaryOperator::ShiftRight => ">>",
+        BinaryOperator::Less => "<",
+        BinaryOperator::Greater => ">",
+        BinaryOperator::LessOrEqual => "<=",
+        BinaryOperator::GreaterOrEqual => ">=",
+        BinaryOperator::Equals => "==",
+        BinaryOperator::NotEquals => "!=",
+        BinaryOperator::BitwiseAnd => "&",
+        BinaryOperator::BitwiseXor => "^",
+        BinaryOperator::BitwiseOr => "|",
+        BinaryOperator::LogicalAnd => "&&",
+        BinaryOperator::LogicalOr => "||",
+        BinaryOperator::Assign => "=",
+        BinaryOperator::AssignMultiply => "*=",
+        BinaryOperator::AssignDivide => "/=",
+        BinaryOperator::AssignModulo => "%=",
+        BinaryOperator::AssignPlus => "+=",
+        BinaryOperator::AssignMinus => "-=",
+        BinaryOperator::AssignShiftLeft => "<<=",
+        BinaryOperator::AssignShiftRight => ">>=",
+        BinaryOperator::AssignBitwiseAnd => "&=",
+        BinaryOperator::AssignBitwiseXor => "^=",
+        BinaryOperator::AssignBitwiseOr => "|=",
+    }
+}
+
+fn unary_operator(op: &UnaryOperator) -> &'static str {
+    match *op {
+        UnaryOperator::PostIncrement | UnaryOperator::PreIncrement => "++",
+        UnaryOperator::PostDecrement | UnaryOperator::PreDecrement => "--",
+        UnaryOperator::Address => "&",
+        UnaryOperator::Indirection => "*",
+        UnaryOperator::Plus => "+",
+        UnaryOperator::Minus => "-",
+        UnaryOperator::Complement => "~",
+        UnaryOperator::Negate => "!",
+        UnaryOperator::Real => "__real__",
+        UnaryOperator::Imag => "__imag__",
+    }
+}
+
+// `sizeof (T){1}` is `sizeof` of a type followed by braces, so such operands need parentheses
+fn starts_with_compound_literal(e: &Expression) -> bool {
+    match *e {
+        Expression::CompoundLiteral(_) => true,
+        Expression::Member(ref m) => starts_with_compound_literal(&m.node.expression.node),
+        Expression::Call(ref c) => starts_with_compound_literal(&c.node.callee.node),
+        Expression::KernelCall(ref c) => starts_with_compound_literal(&c.node.callee.node),
+        Expression::UnaryOperator(ref u) => match u.node.operator.node {
+            UnaryOperator::PostIncrement | UnaryOperator::PostDecrement => {
+                starts_with_compound_literal(&u.node.operand.node)
+            }
+            _ => false,
+        },
+        Expression::BinaryOperator(ref b) => match b.node.operator.node {
+            BinaryOperator::Index => starts_with_compound_literal(&b.node.lhs.node),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Kind of an extension, consecutive GNU or standard attributes are written in one list
+#[derive(Clone, Copy, PartialEq)]
+enum Group {
+    Gnu,
+    Std,
+    Convention,
+    AsmLabel,
+    Other,
+}
+
+fn group(e: &Extension) -> Group {
+    match *e {
+        Extension::Attribute(_) | Extension::AvailabilityAttribute(_) => Group::Gnu,
+        Extension::StdAttribute(_) => Group::Std,
+        Extension::CallingConvention(_) => Group::Convention,
+        Extension::AsmLabel(_) => Group::AsmLabel,
+        _ => Group::Other,
+    }
+}
+
+// Part of the type of a declaration specifier
+fn specifier_qualifier(s: &DeclarationSpecifier) -> Option<SpecifierQualifier> {
+    match *s {
+        DeclarationSpecifier::TypeSpecifier(ref t) => {
+            Some(SpecifierQualifier::TypeSpecifier(t.clone()))
+        }
+        DeclarationSpecifier::TypeQualifier(ref q) => {
+            Some(SpecifierQualifier::TypeQualifier(q.clone()))
+        }
+        DeclarationSpecifier::Extension(ref e) => Some(SpecifierQualifier::Extension(e.clone())),
+        _ => None,
+    }
+}
+
+// Replace the name of a declarator, or fill the place of the name in an abstract one
+fn rename(d: &mut Declarator, kind: Node<DeclaratorKind>) {
+    if let DeclaratorKind::Declarator(ref mut d) = d.kind.node {
+        return rename(&mut d.node, kind);
+    }
+    d.kind = kind;
+}
+
+fn function_definition(d: &ExternalDeclaration) -> Option<&Node<FunctionDefinition>> {
+    match *d {
+        ExternalDeclaration::FunctionDefinition(ref f) => Some(f),
+        _ => None,
+    }
+}
+
+fn pointer(d: &DerivedDeclarator) -> Option<(&'static str, &[Node<PointerQualifier>])> {
+    match *d {
+        DerivedDeclarator::Pointer(ref q) => Some(("*", q)),
+        DerivedDeclarator::Block(ref q) => Some(("^", q)),
+        _ => None,
+    }
+}
+
+// Whether a declarator is written apart from the specifiers before it, `int *p` but `int[3]`
+fn declarator_spaced(d: &Declarator) -> bool {
+    match d.kind.node {
+        DeclaratorKind::Abstract if d.extensions.is_empty() => {
+            d.derived.first().and_then(|d| pointer(&d.node)).is_some()
+        }
+        _ => true,
+    }
+}
+
+// Tokens that would be read as a different token when written next to each other
+fn glues(a: char, b: char) -> bool {
+    fn word(c: char) -> bool {
+        c == '_' || c == '$' || c.is_alphanumeric()
+    }
+    if word(a) {
+        return word(b) || b == '\'' || b == '"';
+    }
+    let pair: String = [a, b].iter().collect();
+    [
+        "++", "--", "&&", "||", "<<", ">>", "/*", "//", "..", "->", "##",
+    ]
+    .contains(&&*pair)
+}
+
+// Where the attributes at the end of a declarator's extensions are written
+#[derive(Clone, Copy, PartialEq)]
+enum Trailing {
+    // Everything before the kind of the declarator
+    None,
+    // GNU attributes after the declarator, as in a struct declarator
+    Attributes,
+    // An asm label and GNU attributes after the declarator, as in an init declarator
+    AsmLabel,
+}
+
+fn trailing_start(extensions: &[Node<Extension>], trailing: Trailing) -> usize {
+    let mut start = extensions.len();
+    if trailing == Trailing::None {
+        return start;
+    }
+    while start > 0 && group(&extensions[start - 1].node) == Group::Gnu {
+        start -= 1;
+    }
+    if trailing == Trailing::AsmLabel && start > 0 {
+        if let Extension::AsmLabel(_) = extensions[start - 1].node {
+            start -= 1;
+        }
+    }
+    start
+}
+
+struct Emitter<'a> {
+    options: &'a FormatOptions,
+    out: String,
+    level: usize,
+    // Nothing written on the current line yet, not even the indentation
+    line_start: bool,
+    // The current line ends with a `//` comment
+    line_comment: bool,
+}
+
+impl<'a> Emitter<'a> {
+    fn new(options: &'a FormatOptions) -> Emitter<'a> {
+        Emitter {
+            options: options,
+            out: String::new(),
+            level: 0,
+            line_start: true,
+            line_comment: false,
+        }
+    }
+
+    fn push(&mut self, s: &str) {
+        let first = match s.chars().next() {
+            Some(c) => c,
+            None => return,
+        };
+        if self.line_comment {
+            self.line();
+        }
+        if self.line_start {
+            self.indent(0);
+        } else if let Some(last) = self.out.chars().next_back() {
+            if glues(last, first) {
+                self.out.push(' ');
+            }
+        }
+        self.out.push_str(s);
+    }
+
+    fn line(&mut self) {
+        self.out.push('\n');
+        self.line_start = true;
+        self.line_comment = false;
+    }
+
+    // Indentation of the current level, followed by spaces up to the column `align`
+    fn indent(&mut self, align: usize) {
+        let width = self.options.indent_width;
+        if self.options.use_tabs {
+            for _ in 0..self.level {
+                self.out.push('\t');
+            }
+        } else {
+            for _ in 0..self.level * width {
+                self.out.push(' ');
+            }
+        }
+        for _ in self.level * width..align {
+            self.out.push(' ');
+        }
+        self.line_start = false;
+    }
+
+    // Width of the line around the byte offset `at`
+    fn width(&self, at: usize) -> usize {
+        let start = self.out[..at].rfind('\n').map_or(0, |i| i + 1);
+        let end = self.out[at..].find('\n').map_or(self.out.len(), |i| at + i);
+        self.columns(&self.out[start..end])
+    }
+
+    fn columns(&self, s: &str) -> usize {
+        s.chars()
+            .map(|c| match c {
+                '\t' => self.options.indent_width,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    // Preprocessing directive, on a line of its own and not indented
+    fn directive(&mut self, s: &str) {
+        if !self.line_start {
+            self.line();
+        }
+        self.out.push_str(s);
+        self.line_start = false;
+    }
+
+    fn comment(&mut self, text: &str) {
+        match self.options.comments {
+            CommentStyle::Block => self.push(&format!("/* {} */", text)),
+            CommentStyle::Line => {
+                self.push(&format!("// {}", text));
+                self.line_comment = true;
+            }
+        }
+    }
+
+    // Space or line break between a block and the code before or after it on the same line
+    fn brace_space(&mut self, placement: BracePlacement) {
+        match placement {
+            BracePlacement::SameLine => self.push(" "),
+            BracePlacement::NextLine => self.line(),
+        }
+    }
+
+    // Control keyword and the opening parenthesis after it
+    fn keyword_paren(&mut self, keyword: &str) {
+        self.push(keyword);
+        self.push(if self.options.space_before_paren {
+            " ("
+        } else {
+            "("
+        });
+    }
+
+    // Items separated by commas, after the opening parenthesis or brace of a list. An item whose
+    // first line would go past `max_width` starts a new line, aligned with the first item.
+    // Whether it fits is decided before wrapping the lists inside it.
+    fn wrapped<T, F>(&mut self, items: &[T], mut f: F)
+    where
+        F: FnMut(&mut Emitter, &T),
+    {
+        let align = self.width(self.out.len());
+        let unwrapped = FormatOptions {
+            max_width: None,
+            ..self.options.clone()
+        };
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.push(",");
+                let fits = match self.options.max_width {
+                    Some(max) => {
+                        let mut e = Emitter::new(&unwrapped);
+                        e.level = self.level;
+                        e.line_start = false;
+                        f(&mut e, item);
+                        let first = e.out.split('\n').next().unwrap_or("");
+                        self.width(self.out.len()) + 1 + self.columns(first) <= max
+                    }
+                    None => true,
+                };
+                if fits {
+                    self.push(" ");
+                } else {
+                    self.line();
+                    self.indent(align);
+                }
+            }
+            f(self, item);
+        }
+    }
+
+    fn separated<T, F>(&mut self, items: &[T], separator: &str, mut f: F)
+    where
+        F: FnMut(&mut Emitter, &T),
+    {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.push(separator);
+            }
+            f(self, item);
+        }
+    }
+
+    fn identifier(&mut self, i: &Node<Identifier>) {
+        self.push(&i.node.name);
+    }
+
+    // Character constants and string literals are written as they were, only characters
+    // that may not appear in them are escaped
+    fn literal(&mut self, value: &str) {
+        let mut s = String::with_capacity(value.len());
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    s.push(c);
+                    match chars.next() {
+                        Some('\r') if chars.peek() == Some(&'\n') => {
+                            s.push('\r');
+                            s.push(chars.next().unwrap());
+                        }
+                        Some(c) => s.push(c),
+                        None => {}
+                    }
+                }
+                '\n' => s.push_str("\\n"),
+                '\r' => s.push_str("\\r"),
+                '\t' => s.push(c),
+                c if (c as u32) < 0x20 || c == '\x7f' => {
+                    s.push_str(&format!("\\{:03o}", c as u32));
+                }
+                c => s.push(c),
+            }
+        }
+        self.push(&s);
+    }
+
+    fn string_literal(&mut self, s: &Node<StringLiteral>) {
+        for (i, piece) in s.node.iter().enumerate() {
+            if i > 0 {
+                self.push(" ");
+            }
+            self.literal(&piece.value);
+        }
+    }
+
+    // 6.9 External definitions
+
+    fn translation_unit(&mut self, unit: &TranslationUnit) {
+        for (i, d) in unit.0.iter().enumerate() {
+            if i > 0
+                && (function_definition(&d.node).is_some()
+                    || function_definition(&unit.0[i - 1].node).is_some())
+            {
+                self.line();
+            }
+            self.external_declaration(&d.node);
+            self.line();
+        }
+    }
+
+    fn external_declaration(&mut self, d: &ExternalDeclaration) {
+        match *d {
+            ExternalDeclaration::Declaration(ref d) => self.declaration(&d.node),
+            ExternalDeclaration::StaticAssert(ref s) => self.static_assert(&s.node),
+            ExternalDeclaration::FunctionDefinition(ref f) => self.function_definition(&f.node),
+            ExternalDeclaration::Pragma(ref p) => self.pragma(&p.node),
+            ExternalDeclaration::Asm(ref s) => {
+                self.push("__asm__(");
+                self.string_literal(s);
+                self.push(");");
+            }
+            ExternalDeclaration::Error => self.comment("syntax error"),
+        }
+    }
+
+    fn function_definition(&mut self, f: &FunctionDefinition) {
+        if self.declaration_specifiers(&f.specifiers) {
+            self.push(" ");
+        }
+        self.declarator(&f.declarator.node, Trailing::None);
+        if f.declarations.is_empty() {
+            self.brace_space(self.options.function_braces);
+        } else {
+            self.line();
+        }
+        for d in &f.declarations {
+            self.level += 1;
+            self.declaration(&d.node);
+            self.level -= 1;
+            self.line();
+        }
+        self.statement(&f.statement.node);
+    }
+
+    fn pragma(&mut self, p: &Pragma) {
+        match p.kind {
+            PragmaKind::Directive => {
+                let mut s = String::from("#pragma");
+                if !p.text.is_empty() {
+                    s.push(' ');
+                    s.push_str(&p.text);
+                }
+                self.directive(&s);
+            }
+            PragmaKind::Operator => {
+                let mut s = String::from("_Pragma(\"");
+                for c in p.text.chars() {
+                    if c == '"' || c == '\\' {
+                        s.push('\\');
+                    }
+                    s.push(c);
+                }
+                s.push_str("\")");
+                self.push(&s);
+            }
+            PragmaKind::Msvc => {
+                self.push("__pragma(");
+                self.push(&p.text);
+                self.push(")");
+            }
+        }
+    }
+
+    // 6.7 Declarations
+
+    fn declaration(&mut self, d: &Declaration) {
+        let specifiers = self.declaration_specifiers(&d.specifiers);
+        for (i, d) in d.declarators.iter().enumerate() {
+            if i > 0 {
+                self.push(", ");
+            } else if specifiers && declarator_spaced(&d.node.declarator.node) {
+                self.push(" ");
+            }
+            self.declarator(&d.node.declarator.node, Trailing::AsmLabel);
+            if let Some(ref i) = d.node.initializer {
+                self.push(" = ");
+                self.initializer(&i.node);
+            }
+        }
+        self.push(";");
+    }
+
+    // Returns whether anything was written
+    fn declaration_specifiers(&mut self, specifiers: &[Node<DeclarationSpecifier>]) -> bool {
+        let mut any = false;
+        for s in specifiers {
+            if let DeclarationSpecifier::TypeSpecifier(ref t) = s.node {
+                if t.node == TypeSpecifier::ImplicitInt {
+                    continue;
+                }
+            }
+            if any {
+                self.push(" ");
+            }
+            any = true;
+            match s.node {
+                DeclarationSpecifier::StorageClass(ref s) => self.storage_class(&s.node),
+                DeclarationSpecifier::TypeSpecifier(ref t) => self.type_specifier(&t.node),
+                DeclarationSpecifier::TypeQualifier(ref q) => self.type_qualifier(&q.node),
+                DeclarationSpecifier::Function(ref f) => self.function_specifier(&f.node),
+                DeclarationSpecifier::Alignment(ref a) => self.alignment_specifier(&a.node),
+                DeclarationSpecifier::Extension(ref e) => self.extension_specifier(e),
+            }
+        }
+        any
+    }
+
+    fn specifier_qualifiers(&mut self, specifiers: &[Node<SpecifierQualifier>]) -> bool {
+        let mut any = false;
+        for s in specifiers {
+            if let SpecifierQualifier::TypeSpecifier(ref t) = s.node {
+                if t.node == TypeSpecifier::ImplicitInt {
+                    continue;
+                }
+            }
+            if any {
+                self.push(" ");
+            }
+            any = true;
+            match s.node {
+                SpecifierQualifier::TypeSpecifier(ref t) => self.type_specifier(&t.node),
+                SpecifierQualifier::TypeQualifier(ref q) => self.type_qualifier(&q.node),
+                SpecifierQualifier::Extension(ref e) => self.extension_specifier(e),
+            }
+        }
+        any
+    }
+
+    fn storage_class(&mut self, s: &StorageClassSpecifier) {
+        self.push(match *s {
+            StorageClassSpecifier::Typedef => "typedef",
+            StorageClassSpecifier::Extern => "extern",
+            StorageClassSpecifier::Static => "static",
+            StorageClassSpecifier::ThreadLocal => "_Thread_local",
+            StorageClassSpecifier::Auto => "auto",
+            StorageClassSpecifier::Register => "register",
+            StorageClassSpecifier::Constexpr => "constexpr",
+            StorageClassSpecifier::Block => "__block",
+        });
+    }
+
+    fn type_specifier(&mut self, t: &TypeSpecifier) {
+        let keyword = match *t {
+            TypeSpecifier::Void => "void",
+            TypeSpecifier::Char => "char",
+            TypeSpecifier::Short => "short",
+            TypeSpecifier::Int => "int",
+            TypeSpecifier::Long => "long",
+            TypeSpecifier::Float => "float",
+            TypeSpecifier::Double => "double",
+            TypeSpecifier::Signed => "signed",
+            TypeSpecifier::Unsigned => "unsigned",
+            TypeSpecifier::Bool => "_Bool",
+            TypeSpecifier::Complex => "_Complex",
+            TypeSpecifier::Atomic(ref t) => {
+                self.push("_Atomic(");
+                self.type_name(&t.node);
+                return self.push(")");
+            }
+            TypeSpecifier::Struct(ref s) => return self.struct_type(&s.node),
+            TypeSpecifier::Enum(ref e) => return self.enum_type(&e.node),
+            TypeSpecifier::TypedefName(ref i) => return self.identifier(i),
+            TypeSpecifier::TypeOf(ref t) => return self.type_of("typeof(", &t.node),
+            TypeSpecifier::TypeOfUnqual(ref t) => return self.type_of("typeof_unqual(", &t.node),
+            TypeSpecifier::TS18661Float(ref f) => {
+                let name = match f.format {
+                    TS18661FloatFormat::BinaryInterchange => format!("_Float{}", f.width),
+                    TS18661FloatFormat::BinaryExtended => format!("_Float{}x", f.width),
+                    TS18661FloatFormat::DecimalInterchange => format!("_Decimal{}", f.width),
+                    TS18661FloatFormat::DecimalExtended => format!("_Decimal{}x", f.width),
+                };
+                return self.push(&name);
+            }
+            TypeSpecifier::TargetFloat(ref f) => match *f {
+                TargetFloat::Fp16 => "__fp16",
+                TargetFloat::Float80 => "__float80",
+                TargetFloat::Float128 => "__float128",
+                TargetFloat::Ibm128 => "__ibm128",
+            },
+            TypeSpecifier::Int128 => "__int128",
+            TypeSpecifier::Int8 => "__int8",
+            TypeSpecifier::Int16 => "__int16",
+            TypeSpecifier::Int32 => "__int32",
+            TypeSpecifier::Int64 => "__int64",
+            TypeSpecifier::Fract => "_Fract",
+            TypeSpecifier::Accum => "_Accum",
+            TypeSpecifier::Sat => "_Sat",
+            TypeSpecifier::BitInt(ref e) => {
+                self.push("_BitInt(");
+                self.expression(&e.node, PREC_CONDITIONAL);
+                return self.push(")");
+            }
+            TypeSpecifier::Auto => "auto",
+            TypeSpecifier::AutoType => "__auto_type",
+            TypeSpecifier::ImplicitInt => "",
+        };
+        self.push(keyword);
+    }
+
+    fn type_of(&mut self, keyword: &str, t: &TypeOf) {
+        self.push(keyword);
+        match *t {
+            TypeOf::Expression(ref e) => self.expression(&e.node, PREC_COMMA),
+            TypeOf::Type(ref t) => self.type_name(&t.node),
+        }
+        self.push(")");
+    }
+
+    fn type_qualifier(&mut self, q: &TypeQualifier) {
+        self.push(match *q {
+            TypeQualifier::Const => "const",
+            TypeQualifier::Restrict => "restrict",
+            TypeQualifier::Volatile => "volatile",
+            TypeQualifier::Nonnull => "_Nonnull",
+            TypeQualifier::NullUnspecified => "_Null_unspecified",
+            TypeQualifier::Nullable => "_Nullable",
+            TypeQualifier::Atomic => "_Atomic",
+            TypeQualifier::Unaligned => "__unaligned",
+            TypeQualifier::AddressSpace(ref a) => match *a {
+                AddressSpace::Global => "__global",
+                AddressSpace::Local => "__local",
+                AddressSpace::Constant => "__constant",
+                AddressSpace::Private => "__private",
+                AddressSpace::Generic => "__generic",
+                AddressSpace::SegFs => "__seg_fs",
+                AddressSpace::SegGs => "__seg_gs",
+            },
+            TypeQualifier::Access(ref a) => match *a {
+                AccessQualifier::ReadOnly => "__read_only",
+                AccessQualifier::WriteOnly => "__write_only",
+                AccessQualifier::ReadWrite => "__read_write",
+            },
+        });
+    }
+
+    fn function_specifier(&mut self, f: &FunctionSpecifier) {
+        self.push(match *f {
+            FunctionSpecifier::Inline => "inline",
+            FunctionSpecifier::Noreturn => "_Noreturn",
+            FunctionSpecifier::ForceInline => "__forceinline",
+            FunctionSpecifier::Kernel => "__kernel",
+        });
+    }
+
+    fn alignment_specifier(&mut self, a: &AlignmentSpecifier) {
+        self.push("_Alignas(");
+        match *a {
+            AlignmentSpecifier::Type(ref t) => self.type_name(&t.node),
+            AlignmentSpecifier::Constant(ref e) => self.expression(&e.node, PREC_CONDITIONAL),
+        }
+        self.push(")");
+    }
+
+    fn struct_type(&mut self, s: &StructType) {
+        self.push(match s.kind.node {
+            StructKind::Struct => "struct",
+            StructKind::Union => "union",
+        });
+        if !s.extensions.is_empty() {
+            self.push(" ");
+            self.extensions(&s.extensions);
+        }
+        if let Some(ref i) = s.identifier {
+            self.push(" ");
+            self.identifier(i);
+        }
+        if let Some(ref declarations) = s.declarations {
+            self.push(" {");
+            if !declarations.is_empty() {
+                self.line();
+                self.level += 1;
+                for d in declarations {
+                    self.struct_declaration(&d.node);
+                    self.line();
+                }
+                self.level -= 1;
+            }
+            self.push("}");
+            if !s.trailing_extensions.is_empty() {
+                self.push(" ");
+                self.extensions(&s.trailing_extensions);
+            }
+        }
+    }
+
+    fn struct_declaration(&mut self, d: &StructDeclaration) {
+        let f = match *d {
+            StructDeclaration::Field(ref f) => &f.node,
+            StructDeclaration::StaticAssert(ref s) => return self.static_assert(&s.node),
+        };
+        let specifiers = self.specifier_qualifiers(&f.specifiers);
+        for (i, d) in f.declarators.iter().enumerate() {
+            if i > 0 {
+                self.push(", ");
+            }
+            let extensions = match d.node.declarator {
+                Some(ref declarator) => {
+                    let declarator = &declarator.node;
+                    if i == 0 && specifiers && declarator_spaced(declarator) {
+                        self.push(" ");
+                    }
+                    let start = trailing_start(&declarator.extensions, Trailing::Attributes);
+                    self.declarator_head(declarator, &declarator.extensions[..start]);
+                    &declarator.extensions[start..]
+                }
+                None => &[],
+            };
+            // Attributes of a bit-field follow its width
+            if let Some(ref w) = d.node.bit_width {
+                if d.node.declarator.is_none() && i > 0 {
+                    self.push(": ");
+                } else {
+                    self.push(" : ");
+                }
+                self.expression(&w.node, PREC_CONDITIONAL);
+            }
+            self.trailing_extensions(extensions);
+        }
+        self.push(";");
+    }
+
+    fn enum_type(&mut self, e: &EnumType) {
+        self.push("enum");
+        if !e.extensions.is_empty() {
+            self.push(" ");
+            self.extensions(&e.extensions);
+        }
+        if let Some(ref i) = e.identifier {
+            self.push(" ");
+            self.identifier(i);
+        }
+        if let Some(ref u) = e.underlying {
+            self.push(" : ");
+            self.specifier_qualifiers(u);
+        }
+        if !e.enumerators.is_empty() {
+            self.push(" {");
+            self.line();
+            self.level += 1;
+            for (i, n) in e.enumerators.iter().enumerate() {
+                self.identifier(&n.node.identifier);
+                if !n.node.extensions.is_empty() {
+                    self.push(" ");
+                    self.extensions(&n.node.extensions);
+                }
+                if let Some(ref e) = n.node.expression {
+                    self.push(" = ");
+                    self.expression(&e.node, PREC_CONDITIONAL);
+                }
+                if i + 1 < e.enumerators.len() {
+                    self.push(",");
+                }
+                self.line();
+            }
+            self.level -= 1;
+            self.push("}");
+            if !e.trailing_extensions.is_empty() {
+                self.push(" ");
+                self.extensions(&e.trailing_extensions);
+            }
+        }
+    }
+
+    fn static_assert(&mut self, s: &StaticAssert) {
+        self.push("_Static_assert(");
+        self.expression(&s.expression.node, PREC_CONDITIONAL);
+        if let Some(ref m) = s.message {
+            self.push(", ");
+            self.string_literal(m);
+        }
+        self.push(");");
+    }
+
+    // 6.7.6 Declarators
+
+    fn declarator(&mut self, d: &Declarator, trailing: Trailing) {
+        let start = trailing_start(&d.extensions, trailing);
+        self.declarator_head(d, &d.extensions[..start]);
+        self.trailing_extensions(&d.extensions[start..]);
+    }
+
+    fn trailing_extensions(&mut self, extensions: &[Node<Extension>]) {
+        if !extensions.is_empty() {
+            self.push(" ");
+            self.extensions(extensions);
+        }
+    }
+
+    // GNU attributes and calling conventions go before the pointers, other extensions after
+    // the identifier. Pointers are written in order before the identifier, arrays and
+    // functions after it.
+    fn declarator_head(&mut self, d: &Declarator, extensions: &[Node<Extension>]) {
+        let attributes = extensions.iter().filter(|e| group(&e.node) == Group::Gnu);
+        let conventions = extensions
+            .iter()
+            .filter(|e| group(&e.node) == Group::Convention);
+        let others = extensions
+            .iter()
+            .filter(|e| group(&e.node) != Group::Gnu && group(&e.node) != Group::Convention);
+
+        let mut space = false;
+        if attributes.clone().next().is_some() {
+            self.extensions(attributes);
+            space = true;
+        }
+        for c in conventions {
+            if space {
+                self.push(" ");
+            }
+            self.extensions(Some(c));
+            space = true;
+        }
+
+        for derived in &d.derived {
+            let (token, qualifiers) = match pointer(&derived.node) {
+                Some(p) => p,
+                None => continue,
+            };
+            if space {
+                self.push(" ");
+            }
+            self.push(token);
+            for (i, q) in qualifiers.iter().enumerate() {
+                if i > 0 {
+                    self.push(" ");
+                }
+                match q.node {
+                    PointerQualifier::TypeQualifier(ref q) => self.type_qualifier(&q.node),
+                    PointerQualifier::Extension(ref e) => self.extension_specifier(e),
+                }
+            }
+            space = !qualifiers.is_empty();
+        }
+
+        match d.kind.node {
+            DeclaratorKind::Abstract => {}
+            DeclaratorKind::Identifier(ref i) => {
+                if space {
+                    self.push(" ");
+                }
+                self.identifier(i);
+            }
+            DeclaratorKind::Declarator(ref d) => {
+                if space {
+                    self.push(" ");
+                }
+                self.push("(");
+                self.declarator(&d.node, Trailing::None);
+                self.push(")");
+            }
+        }
+
+        if others.clone().next().is_some() {
+            self.push(" ");
+            self.extensions(others);
+        }
+
+        for derived in &d.derived {
+            match derived.node {
+                DerivedDeclarator::Pointer(_) | DerivedDeclarator::Block(_) => {}
+                DerivedDeclarator::Array(ref a) => self.array_declarator(&a.node),
+                DerivedDeclarator::Function(ref f) => {
+                    self.push("(");
+                    self.function_declarator(&f.node);
+                    self.push(")");
+                }
+                DerivedDeclarator::KRFunction(ref p) => {
+                    self.push("(");
+                    self.wrapped(p, |e, i| e.identifier(i));
+                    self.push(")");
+                }
+            }
+        }
+    }
+
+    fn array_declarator(&mut self, a: &ArrayDeclarator) {
+        self.push("[");
+        let mut space = false;
+        if let ArraySize::StaticExpression(_) = a.size {
+            self.push("static");
+            space = true;
+        }
+        for q in &a.qualifiers {
+            if space {
+                self.push(" ");
+            }
+            self.type_qualifier(&q.node);
+            space = true;
+        }
+        match a.size {
+            ArraySize::Unknown => {}
+            ArraySize::VariableUnknown => {
+                if space {
+                    self.push(" ");
+                }
+                self.push("*");
+            }
+            ArraySize::VariableExpression(ref e) | ArraySize::StaticExpression(ref e) => {
+                if space {
+                    self.push(" ");
+                }
+                self.expression(&e.node, PREC_ASSIGN);
+            }
+        }
+        self.push("]");
+    }
+
+    fn function_declarator(&mut self, f: &FunctionDeclarator) {
+        if !f.forward_parameters.is_empty() {
+            self.separated(&f.forward_parameters, ", ", |e, p| {
+                e.parameter_declaration(&p.node)
+            });
+            self.push("; ");
+        }
+        // The ellipsis is wrapped like parameters, as `None`
+        let mut parameters: Vec<_> = f.parameters.iter().map(Some).collect();
+        if f.ellipsis == Ellipsis::Some {
+            parameters.push(None);
+        }
+        self.wrapped(&parameters, |e, p| match *p {
+            Some(p) => e.parameter_declaration(&p.node),
+            None => e.push("..."),
+        });
+    }
+
+    fn parameter_declaration(&mut self, p: &ParameterDeclaration) {
+        let specifiers = self.declaration_specifiers(&p.specifiers);
+        if let Some(ref d) = p.declarator {
+            if specifiers && declarator_spaced(&d.node) {
+                self.push(" ");
+            }
+            self.declarator(&d.node, Trailing::None);
+        }
+        self.trailing_extensions(&p.extensions);
+    }
+
+    // 6.7.7 Type names
+
+    fn type_name(&mut self, t: &TypeName) {
+        self.declared_type(&t.specifiers, t.declarator.as_ref().map(|d| &d.node));
+    }
+
+    fn declared_type(&mut self, specifiers: &[Node<SpecifierQualifier>], d: Option<&Declarator>) {
+        let specifiers = self.specifier_qualifiers(specifiers);
+        if let Some(d) = d {
+            if specifiers && declarator_spaced(d) {
+                self.push(" ");
+            }
+            self.declarator(d, Trailing::None);
+        }
+    }
+
+    // 6.7.9 Initialization
+
+    fn initializer(&mut self, i: &Initializer) {
+        match *i {
+            Initializer::Expression(ref e) => self.expression(&e.node, PREC_ASSIGN),
+            Initializer::List(ref items) => self.initializer_list(items),
+        }
+    }
+
+    fn initializer_list(&mut self, items: &[Node<InitializerListItem>]) {
+        self.push("{");
+        self.wrapped(items, |e, item| {
+            for d in &item.node.designation {
+                match d.node {
+                    Designator::Index(ref i) => {
+                        e.push("[");
+                        e.expression(&i.node, PREC_CONDITIONAL);
+                        e.push("]");
+                    }
+                    Designator::Member(ref m) => {
+                        e.push(".");
+                        e.identifier(m);
+                    }
+                    Designator::Range(ref r) => {
+                        e.push("[");
+                        e.expression(&r.node.from.node, PREC_CONDITIONAL);
+                        e.push(" ... ");
+                        e.expression(&r.node.to.node, PREC_CONDITIONAL);
+                        e.push("]");
+                    }
+                }
+            }
+            if !item.node.designation.is_empty() {
+                e.push(" = ");
+            }
+            e.initializer(&item.node.initializer.node);
+        });
+        self.push("}");
+    }
+
+    // Extensions
+
+    // Specifier made of extensions, an empty one is written as an empty attribute list
+    fn extension_specifier(&mut self, extensions: &[Node<Extension>]) {
+        if extensions.is_empty() {
+            self.push("[[]]");
+        } else {
+            self.extensions(extensions);
+        }
+    }
+
+    // Consecutive GNU or standard attributes are written in one `__attribute__((...))` or
+    // `[[...]]`, other extensions one by one
+    fn extensions<'b, I>(&mut self, extensions: I)
+    where
+        I: IntoIterator<Item = &'b Node<Extension>>,
+    {
+        let mut open = None;
+        let mut first = true;
+        for e in extensions {
+            let next = match group(&e.node) {
+                g @ Group::Gnu | g @ Group::Std => Some(g),
+                _ => None,
+            };
+            if next.is_some() && next == open {
+                self.push(", ");
+            } else {
+                self.close_group(open);
+                if !first {
+                    self.push(" ");
+                }
+                match next {
+                    Some(Group::Gnu) => self.push("__attribute__(("),
+                    Some(Group::Std) => self.push("[["),
+                    _ => {}
+                }
+            }
+            open = next;
+            first = false;
+            self.extension(&e.node);
+        }
+        self.close_group(open);
+    }
+
+    fn close_group(&mut self, group: Option<Group>) {
+        match group {
+            Some(Group::Gnu) => self.push("))"),
+            Some(Group::Std) => self.push("]]"),
+            _ => {}
+        }
+    }
+
+    fn extension(&mut self, e: &Extension) {
+        match *e {
+            Extension::Attribute(ref a) => self.attribute(a),
+            Extension::AsmLabel(ref s) => {
+                self.push("__asm__(");
+                self.string_literal(s);
+                self.push(")");
+            }
+            Extension::AvailabilityAttribute(ref a) => {
+                self.push("availability(");
+                self.identifier(&a.node.platform);
+                for c in &a.node.clauses {
+                    self.push(", ");
+                    match c.node {
+                        AvailabilityClause::Introduced(ref v) => {
+                            self.push("introduced=");
+                            self.availability_version(&v.node);
+                        }
+                        AvailabilityClause::Deprecated(ref v) => {
+                            self.push("deprecated=");
+                            self.availability_version(&v.node);
+                        }
+                        AvailabilityClause::Obsoleted(ref v) => {
+                            self.push("obsoleted=");
+                            self.availability_version(&v.node);
+                        }
+                        AvailabilityClause::Unavailable => self.push("unavailable"),
+                        AvailabilityClause::Message(ref s) => {
+                            self.push("message=");
+                            self.string_literal(s);
+                        }
+                        AvailabilityClause::Replacement(ref s) => {
+                            self.push("replacement=");
+                            self.string_literal(s);
+                        }
+                    }
+                }
+                self.push(")");
+            }
+            Extension::StdAttribute(ref a) => {
+                if let Some(ref n) = a.namespace {
+                    self.identifier(n);
+                    self.push("::");
+                }
+                self.identifier(&a.name);
+                if let Some(ref arguments) = a.arguments {
+                    self.push("(");
+                    match arguments.node {
+                        StdAttributeArguments::Expressions(ref e) => {
+                            self.separated(e, ", ", |e, a| e.expression(&a.node, PREC_ASSIGN))
+                        }
+                        StdAttributeArguments::Tokens(ref t) => self.push(t),
+                    }
+                    self.push(")");
+                }
+            }
+            Extension::Declspec(ref a) => {
+                self.push("__declspec(");
+                self.separated(a, " ", |e, a| e.attribute(&a.node));
+                self.push(")");
+            }
+            Extension::CallingConvention(ref c) => self.push(match *c {
+                CallingConvention::Cdecl => "__cdecl",
+                CallingConvention::Stdcall => "__stdcall",
+                CallingConvention::Fastcall => "__fastcall",
+                CallingConvention::Vectorcall => "__vectorcall",
+                CallingConvention::Thiscall => "__thiscall",
+                CallingConvention::Clrcall => "__clrcall",
+            }),
+            Extension::Marker => self.push("__extension__"),
+            Extension::Cuda(ref c) => match *c {
+                CudaSpecifier::Global => self.push("__global__"),
+                CudaSpecifier::Device => self.push("__device__"),
+                CudaSpecifier::Host => self.push("__host__"),
+                CudaSpecifier::Shared => self.push("__shared__"),
+                CudaSpecifier::Constant => self.push("__constant__"),
+                CudaSpecifier::Managed => self.push("__managed__"),
+                CudaSpecifier::LaunchBounds(ref e) => {
+                    self.push("__launch_bounds__(");
+                    self.separated(e, ", ", |e, a| e.expression(&a.node, PREC_ASSIGN));
+                    self.push(")");
+                }
+            },
+        }
+    }
+
+    fn attribute(&mut self, a: &Attribute) {
+        self.push(&a.name.node);
+        if !a.arguments.is_empty() {
+            self.push("(");
+            self.separated(&a.arguments, ", ", |e, a| {
+                e.expression(&a.node, PREC_ASSIGN)
+            });
+            self.push(")");
+        }
+    }
+
+    fn availability_version(&mut self, v: &AvailabilityVersion) {
+        let mut s = v.major.clone();
+        for part in v.minor.iter().chain(v.subminor.iter()) {
+            s.push('.');
+            s.push_str(part);
+        }
+        self.push(&s);
+    }
+
+    // 6.8 Statements and blocks
+
+    fn statement(&mut self, s: &Statement) {
+        match *s {
+            Statement::Labeled(ref l) => {
+                self.label(&l.node.label.node);
+                if !l.node.attributes.is_empty() {
+                    self.push(" ");
+                    self.extensions(&l.node.attributes);
+                }
+                self.line();
+                self.statement(&l.node.statement.node);
+            }
+            Statement::Compound(ref items) => self.block(items),
+            Statement::Expression(ref e) => {
+                if let Some(ref e) = *e {
+                    self.expression(&e.node, PREC_COMMA);
+                }
+                self.push(";");
+            }
+            Statement::If(ref i) => {
+                let i = &i.node;
+                self.keyword_paren("if");
+                self.expression(&i.condition.node, PREC_COMMA);
+                self.push(")");
+                let braced = self.body(&i.then_statement.node);
+                if let Some(ref e) = i.else_statement {
+                    if braced {
+                        self.brace_space(self.options.control_braces);
+                    } else {
+                        self.line();
+                    }
+                    self.push("else");
+                    match e.node {
+                        Statement::If(_) => {
+                            self.push(" ");
+                            self.statement(&e.node);
+                        }
+                        _ => {
+                            self.body(&e.node);
+                        }
+                    }
+                }
+            }
+            Statement::Switch(ref s) => {
+                self.keyword_paren("switch");
+                self.expression(&s.node.expression.node, PREC_COMMA);
+                self.push(")");
+                self.body(&s.node.statement.node);
+            }
+            Statement::While(ref w) => {
+                self.keyword_paren("while");
+                self.expression(&w.node.expression.node, PREC_COMMA);
+                self.push(")");
+                self.body(&w.node.statement.node);
+            }
+            Statement::DoWhile(ref d) => {
+                self.push("do");
+                if self.body(&d.node.statement.node) {
+                    self.push(" ");
+                } else {
+                    self.line();
+                }
+                self.keyword_paren("while");
+                self.expression(&d.node.expression.node, PREC_COMMA);
+                self.push(");");
+            }
+            Statement::For(ref f) => {
+                let f = &f.node;
+                self.keyword_paren("for");
+                match f.initializer.node {
+                    ForInitializer::Empty => self.push(";"),
+                    ForInitializer::Expression(ref e) => {
+                        self.expression(&e.node, PREC_COMMA);
+                        self.push(";");
+                    }
+                    ForInitializer::Declaration(ref d) => self.declaration(&d.node),
+                    ForInitializer::StaticAssert(ref s) => self.static_assert(&s.node),
+                }
+                if let Some(ref c) = f.condition {
+                    self.push(" ");
+                    self.expression(&c.node, PREC_COMMA);
+                }
+                self.push(";");
+                if let Some(ref s) = f.step {
+                    self.push(" ");
+                    self.expression(&s.node, PREC_COMMA);
+                }
+                self.push(")");
+                self.body(&f.statement.node);
+            }
+            Statement::Goto(ref l) => {
+                self.push("goto ");
+                self.identifier(l);
+                self.push(";");
+            }
+            Statement::GotoIndirect(ref e) => {
+                self.push("goto *");
+                self.expression(&e.node, PREC_COMMA);
+                self.push(";");
+            }
+            Statement::Continue => self.push("continue;"),
+            Statement::Break => self.push("break;"),
+            Statement::Return(ref e) => {
+                self.push("return");
+                if let Some(ref e) = *e {
+                    self.push(" ");
+                    self.expression(&e.node, PREC_COMMA);
+                }
+                self.push(";");
+            }
+            Statement::Asm(ref a) => self.asm_statement(&a.node),
+            Statement::Pragma(ref p) => self.pragma(&p.node),
+            Statement::Omp(ref o) => {
+                self.omp_directive(&o.node.directive.node);
+                if let Some(ref s) = o.node.statement {
+                    self.line();
+                    self.statement(&s.node);
+                }
+            }
+            Statement::Attributed(ref a) => {
+                self.extension_specifier(&a.node.attributes);
+                self.push(" ");
+                self.statement(&a.node.statement.node);
+            }
+            Statement::LocalLabels(ref l) => {
+                self.push("__label__ ");
+                self.separated(l, ", ", |e, i| e.identifier(i));
+                self.push(";");
+            }
+            Statement::NestedFunction(ref f) => self.function_definition(&f.node),
+            Statement::Try(ref t) => {
+                let braces = self.options.control_braces;
+                self.push("__try");
+                self.brace_space(braces);
+                self.statement(&t.node.statement.node);
+                self.brace_space(braces);
+                match t.node.handler {
+                    TryHandler::Except(ref h) => {
+                        self.keyword_paren("__except");
+                        self.expression(&h.filter.node, PREC_COMMA);
+                        self.push(")");
+                        self.brace_space(braces);
+                        self.statement(&h.statement.node);
+                    }
+                    TryHandler::Finally(ref s) => {
+                        self.push("__finally");
+                        self.brace_space(braces);
+                        self.statement(&s.node);
+                    }
+                }
+            }
+            Statement::Leave => self.push("__leave;"),
+        }
+    }
+
+    // Statement controlled by a selection or iteration statement, a compound statement placed
+    // by `control_braces` and others indented on the next line. Returns whether it was a
+    // compound statement.
+    fn body(&mut self, s: &Statement) -> bool {
+        if let Statement::Compound(ref items) = *s {
+            self.brace_space(self.options.control_braces);
+            self.block(items);
+            true
+        } else {
+            self.line();
+            self.level += 1;
+            self.statement(s);
+            self.level -= 1;
+            false
+        }
+    }
+
+    fn block(&mut self, items: &[Node<BlockItem>]) {
+        self.push("{");
+        if items.is_empty() {
+            return self.push("}");
+        }
+        self.line();
+        self.level += 1;
+        for item in items {
+            match item.node {
+                BlockItem::Declaration(ref d) => self.declaration(&d.node),
+                BlockItem::StaticAssert(ref s) => self.static_assert(&s.node),
+                BlockItem::Statement(ref s) => self.statement(&s.node),
+                BlockItem::Label(ref l) => self.label(&l.node),
+            }
+            self.line();
+        }
+        self.level -= 1;
+        self.push("}");
+    }
+
+    // Labels are outdented by one level when they start a line
+    fn label(&mut self, l: &Label) {
+        let level = self.level;
+        if self.line_start && level > 0 {
+            self.level -= 1;
+        }
+        match *l {
+            Label::Identifier(ref i) => self.identifier(i),
+            Label::Case(ref e) => {
+                self.push("case ");
+                self.expression(&e.node, PREC_CONDITIONAL);
+            }
+            Label::CaseRange(ref r) => {
+                self.push("case ");
+                self.expression(&r.node.low.node, PREC_CONDITIONAL);
+                self.push(" ... ");
+                self.expression(&r.node.high.node, PREC_CONDITIONAL);
+            }
+            Label::Default => self.push("default"),
+        }
+        self.push(":");
+        self.level = level;
+    }
+
+    fn asm_statement(&mut self, a: &AsmStatement) {
+        match *a {
+            AsmStatement::GnuBasic(ref s) => {
+                self.push("__asm__(");
+                self.string_literal(s);
+                self.push(");");
+            }
+            AsmStatement::GnuExtended(ref a) => {
+                self.push("__asm__");
+                if let Some(ref q) = a.qualifier {
+                    self.push(" ");
+                    self.type_qualifier(&q.node);
+                }
+                if a.goto {
+                    self.push(" goto");
+                }
+                self.push("(");
+                self.string_literal(&a.template);
+                // Sections are written up to the last non-empty one
+                let sections = if !a.labels.is_empty() {
+                    4
+                } else if !a.clobbers.is_empty() {
+                    3
+                } else if !a.inputs.is_empty() {
+                    2
+                } else if !a.outputs.is_empty() || !a.goto {
+                    1
+                } else {
+                    0
+                };
+                if sections >= 1 {
+                    self.asm_operands(&a.outputs);
+                }
+                if sections >= 2 {
+                    self.asm_operands(&a.inputs);
+                }
+                if sections >= 3 {
+                    self.push(" :");
+                    for (i, c) in a.clobbers.iter().enumerate() {
+                        self.push(if i > 0 { ", " } else { " " });
+                        self.string_literal(c);
+                    }
+                }
+                if sections >= 4 {
+                    self.push(" :");
+                    for (i, l) in a.labels.iter().enumerate() {
+                        self.push(if i > 0 { ", " } else { " " });
+                        self.identifier(l);
+                    }
+                }
+                self.push(");");
+            }
+            AsmStatement::Msvc(ref s) => {
+                self.push("__asm ");
+                self.push(s);
+            }
+        }
+    }
+
+    fn asm_operands(&mut self, operands: &[Node<GnuAsmOperand>]) {
+        self.push(" :");
+        for (i, o) in operands.iter().enumerate() {
+            self.push(if i > 0 { ", " } else { " " });
+            if let Some(ref n) = o.node.symbolic_name {
+                self.push("[");
+                self.identifier(n);
+                self.push("] ");
+            }
+            self.string_literal(&o.node.constraints);
+            self.push("(");
+            self.expression(&o.node.variable_name.node, PREC_COMMA);
+            self.push(")");
+        }
+    }
+
+    fn omp_directive(&mut self, d: &OmpDirective) {
+        // A directive ends at the end of the line, it is never wrapped
+        let options = FormatOptions {
+            max_width: None,
+            ..self.options.clone()
+        };
+        let mut s = Emitter::new(&options);
+        s.push("#pragma omp ");
+        match d.kind {
+            OmpDirectiveKind::Parallel => s.push("parallel"),
+            OmpDirectiveKind::For => s.push("for"),
+            OmpDirectiveKind::ParallelFor => s.push("parallel for"),
+            OmpDirectiveKind::Sections => s.push("sections"),
+            OmpDirectiveKind::Single => s.push("single"),
+            OmpDirectiveKind::Task => s.push("task"),
+            OmpDirectiveKind::Critical(ref n) => {
+                s.push("critical");
+                if let Some(ref n) = *n {
+                    s.push("(");
+                    s.identifier(n);
+                    s.push(")");
+                }
+            }
+            OmpDirectiveKind::Atomic => s.push("atomic"),
+            OmpDirectiveKind::Barrier => s.push("barrier"),
+        }
+        for c in &d.clauses {
+            s.push(" ");
+            s.omp_clause(&c.node);
+        }
+        self.directive(&s.out);
+    }
+
+    fn omp_clause(&mut self, c: &OmpClause) {
+        let (name, list) = match *c {
+            OmpClause::Private(ref l) => ("private(", l),
+            OmpClause::Shared(ref l) => ("shared(", l),
+            OmpClause::FirstPrivate(ref l) => ("firstprivate(", l),
+            OmpClause::Reduction(ref r) => {
+                self.push("reduction(");
+                match r.operator.node {
+                    OmpReductionOperator::Plus => self.push("+"),
+                    OmpReductionOperator::Minus => self.push("-"),
+                    OmpReductionOperator::Multiply => self.push("*"),
+                    OmpReductionOperator::BitwiseAnd => self.push("&"),
+                    OmpReductionOperator::BitwiseOr => self.push("|"),
+                    OmpReductionOperator::BitwiseXor => self.push("^"),
+                    OmpReductionOperator::LogicalAnd => self.push("&&"),
+                    OmpReductionOperator::LogicalOr => self.push("||"),
+                    OmpReductionOperator::Identifier(ref i) => self.identifier(i),
+                }
+                self.push(": ");
+                self.separated(&r.variables, ", ", |e, i| e.identifier(i));
+                return self.push(")");
+            }
+            OmpClause::NumThreads(ref e) => {
+                self.push("num_threads(");
+                self.expression(&e.node, PREC_COMMA);
+                return self.push(")");
+            }
+            OmpClause::Schedule(ref s) => {
+                self.push("schedule(");
+                self.push(match s.kind.node {
+                    OmpScheduleKind::Static => "static",
+                    OmpScheduleKind::Dynamic => "dynamic",
+                    OmpScheduleKind::Guided => "guided",
+                    OmpScheduleKind::Auto => "auto",
+                    OmpScheduleKind::Runtime => "runtime",
+                });
+                if let Some(ref c) = s.chunk_size {
+                    self.push(", ");
+                    self.expression(&c.node, PREC_ASSIGN);
+                }
+                return self.push(")");
+            }
+            OmpClause::Collapse(ref e) => {
+                self.push("collapse(");
+                self.expression(&e.node, PREC_CONDITIONAL);
+                return self.push(")");
+            }
+        };
+        self.push(name);
+        self.separated(list, ", ", |e, i| e.identifier(i));
+        self.push(")");
+    }
+
+    // 6.5 Expressions
+
+    fn expression(&mut self, e: &Expression, min: u8) {
+        if precedence(e) < min {
+            self.push("(");
+            self.expression(e, PREC_COMMA);
+            return self.push(")");
+        }
+        match *e {
+            Expression::Identifier(ref i) => self.identifier(i),
+            Expression::Constant(ref c) => self.constant(&c.node),
+            Expression::StringLiteral(ref s) => self.string_literal(s),
+            Expression::GenericSelection(ref g) => {
+                self.push("_Generic(");
+                self.expression(&g.node.expression.node, PREC_ASSIGN);
+                for a in &g.node.associations {
+                    self.push(", ");
+                    match a.node {
+                        GenericAssociation::Type(ref t) => {
+                            self.type_name(&t.node.type_name.node);
+                            self.push(": ");
+                            self.expression(&t.node.expression.node, PREC_ASSIGN);
+                        }
+                        GenericAssociation::Default(ref e) => {
+                            self.push("default: ");
+                            self.expression(&e.node, PREC_ASSIGN);
+                        }
+                    }
+                }
+                self.push(")");
+            }
+            Expression::Member(ref m) => {
+                self.expression(&m.node.expression.node, PREC_POSTFIX);
+                self.push(match m.node.operator.node {
+                    MemberOperator::Direct => ".",
+                    MemberOperator::Indirect => "->",
+                });
+                self.identifier(&m.node.identifier);
+            }
+            Expression::Call(ref c) => {
+                self.expression(&c.node.callee.node, PREC_POSTFIX);
+                self.arguments(&c.node.arguments);
+            }
+            Expression::KernelCall(ref c) => {
+                self.expression(&c.node.callee.node, PREC_POSTFIX);
+                self.push("<<<");
+                self.separated(&c.node.configuration, ", ", |e, a| {
+                    e.expression(&a.node, PREC_ASSIGN)
+                });
+                self.push(">>>");
+                self.arguments(&c.node.arguments);
+            }
+            Expression::CompoundLiteral(ref c) => {
+                self.push("(");
+                self.type_name(&c.node.type_name.node);
+                self.push(")");
+                self.initializer_list(&c.node.initializer_list);
+            }
+            Expression::SizeOfTy(ref s) => {
+                self.push("sizeof(");
+                self.type_name(&s.node.0.node);
+                self.push(")");
+            }
+            Expression::SizeOfVal(ref s) => self.unary_keyword("sizeof", &s.node.0.node),
+            Expression::CountOfTy(ref s) => {
+                self.push("_Countof(");
+                self.type_name(&s.node.0.node);
+                self.push(")");
+            }
+            Expression::CountOfVal(ref s) => self.unary_keyword("_Countof", &s.node.0.node),
+            Expression::AlignOf(ref a) => {
+                self.push("_Alignof(");
+                self.type_name(&a.node.0.node);
+                self.push(")");
+            }
+            Expression::UnaryOperator(ref u) => {
+                let op = &u.node.operator.node;
+                let operand = &u.node.operand.node;
+                match *op {
+                    UnaryOperator::PostIncrement | UnaryOperator::PostDecrement => {
+                        self.expression(operand, PREC_POSTFIX);
+                        self.push(unary_operator(op));
+                    }
+                    UnaryOperator::PreIncrement | UnaryOperator::PreDecrement => {
+                        self.push(unary_operator(op));
+                        self.expression(operand, PREC_UNARY);
+                    }
+                    _ => {
+                        self.push(unary_operator(op));
+                        self.expression(operand, PREC_CAST);
+                    }
+                }
+            }
+            Expression::Cast(ref c) => {
+                self.push("(");
+                self.type_name(&c.node.type_name.node);
+                self.push(")");
+                self.expression(&c.node.expression.node, PREC_CAST);
+            }
+            Expression::BinaryOperator(ref b) => {
+                let op = &b.node.operator.node;
+                let (lhs, rhs) = (&b.node.lhs.node, &b.node.rhs.node);
+                match binary_precedence(op) {
+                    PREC_POSTFIX => {
+                        self.expression(lhs, PREC_POSTFIX);
+                        self.push("[");
+                        self.expression(rhs, PREC_COMMA);
+                        self.push("]");
+                    }
+                    PREC_ASSIGN => {
+                        self.expression(lhs, PREC_UNARY);
+                        self.push(" ");
+                        self.push(binary_operator(op));
+                        self.push(" ");
+                        self.expression(rhs, PREC_ASSIGN);
+                    }
+                    p => {
+                        self.expression(lhs, p);
+                        self.push(" ");
+                        self.push(binary_operator(op));
+                        self.push(" ");
+                        self.expression(rhs, p + 1);
+                    }
+                }
+            }
+            Expression::Conditional(ref c) => {
+                self.expression(&c.node.condition.node, PREC_LOGICAL_OR);
+                self.push(" ? ");
+                self.expression(&c.node.then_expression.node, PREC_COMMA);
+                self.push(" : ");
+                self.expression(&c.node.else_expression.node, PREC_CONDITIONAL);
+            }
+            Expression::Comma(ref e) => {
+                self.separated(e, ", ", |e, a| e.expression(&a.node, PREC_ASSIGN));
+            }
+            Expression::OffsetOf(ref o) => {
+                self.push("__builtin_offsetof(");
+                self.type_name(&o.node.type_name.node);
+                self.push(", ");
+                self.identifier(&o.node.designator.node.base);
+                for m in &o.node.designator.node.members {
+                    match m.node {
+                        OffsetMember::Member(ref i) => {
+                            self.push(".");
+                            self.identifier(i);
+                        }
+                        OffsetMember::IndirectMember(ref i) => {
+                            self.push("->");
+                            self.identifier(i);
+                        }
+                        OffsetMember::Index(ref e) => {
+                            self.push("[");
+                            self.expression(&e.node, PREC_COMMA);
+                            self.push("]");
+                        }
+                    }
+                }
+                self.push(")");
+            }
+            Expression::VaArg(ref v) => {
+                self.push("__builtin_va_arg(");
+                self.expression(&v.node.va_list.node, PREC_ASSIGN);
+                self.push(", ");
+                self.type_name(&v.node.type_name.node);
+                self.push(")");
+            }
+            Expression::TypesCompatible(ref t) => {
+                self.push("__builtin_types_compatible_p(");
+                self.type_name(&t.node.first.node);
+                self.push(", ");
+                self.type_name(&t.node.second.node);
+                self.push(")");
+            }
+            Expression::ChooseExpr(ref c) => {
+                self.push("__builtin_choose_expr(");
+                self.expression(&c.node.condition.node, PREC_ASSIGN);
+                self.push(", ");
+                self.expression(&c.node.then_expression.node, PREC_ASSIGN);
+                self.push(", ");
+                self.expression(&c.node.else_expression.node, PREC_ASSIGN);
+                self.push(")");
+            }
+            Expression::Statement(ref s) => {
+                self.push("(");
+                self.statement(&s.node);
+                self.push(")");
+            }
+            Expression::LabelAddress(ref l) => {
+                self.push("&&");
+                self.identifier(l);
+            }
+            Expression::Extension(ref e) => {
+                self.push("__extension__ ");
+                self.expression(&e.node, PREC_CAST);
+            }
+            Expression::Block(ref b) => {
+                self.push("^");
+                if let Some(ref t) = b.node.return_type {
+                    self.type_name(&t.node);
+                }
+                if let Some(ref p) = b.node.parameters {
+                    self.push("(");
+                    self.function_declarator(&p.node);
+                    self.push(")");
+                }
+                if b.node.return_type.is_some() || b.node.parameters.is_some() {
+                    self.push(" ");
+                }
+                self.statement(&b.node.body.node);
+            }
+            Expression::BuiltinAvailable(ref c) => {
+                self.push("__builtin_available(");
+                self.separated(c, ", ", |e, c| match c.node {
+                    AvailabilityCondition::Platform(ref p) => {
+                        e.identifier(&p.platform);
+                        e.push(" ");
+                        e.availability_version(&p.version.node);
+                    }
+                    AvailabilityCondition::Star => e.push("*"),
+                });
+                self.push(")");
+            }
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Node<Expression>]) {
+        self.push("(");
+        self.separated(arguments, ", ", |e, a| e.expression(&a.node, PREC_ASSIGN));
+        self.push(")");
+    }
+
+    // `sizeof` and `_Countof` of an expression
+    fn unary_keyword(&mut self, keyword: &str, operand: &Expression) {
+        self.push(keyword);
+        if starts_with_compound_literal(operand) {
+            self.push("(");
+            self.expression(operand, PREC_COMMA);
+            self.push(")");
+        } else {
+            self.push(" ");
+            self.expression(operand, PREC_UNARY);
+        }
+    }
+
+    // 6.4.4 Constants
+
+    fn constant(&mut self, c: &Constant) {
+        match *c {
+            Constant::Integer(ref i) => {
+                let mut s = String::from(match i.base {
+                    IntegerBase::Decimal => "",
+                    IntegerBase::Octal => "0",
+                    IntegerBase::Hexadecimal => "0x",
+                    IntegerBase::Binary => "0b",
+                    IntegerBase::OctalExplicit => "0o",
+                });
+                s.push_str(&i.number);
+                if i.suffix.unsigned {
+                    s.push('U');
+                }
+                s.push_str(match i.suffix.size {
+                    IntegerSize::Int => "",
+                    IntegerSize::Long => "L",
+                    IntegerSize::LongLong => "LL",
+                    IntegerSize::BitPrecise => "WB",
+                });
+                if i.suffix.imaginary {
+                    s.push('i');
+                }
+                self.push(&s);
+            }
+            Constant::Float(ref f) => {
+                let mut s = String::from(match f.base {
+                    FloatBase::Decimal => "",
+                    FloatBase::Hexadecimal => "0x",
+                });
+                s.push_str(&f.number);
+                match f.suffix.format {
+                    FloatFormat::Float => s.push('f'),
+                    FloatFormat::Double => {}
+                    FloatFormat::LongDouble => s.push('L'),
+                    FloatFormat::TS18661Format(ref t) => match t.format {
+                        TS18661FloatFormat::BinaryInterchange => {
+                            s.push_str(&format!("f{}", t.width))
+                        }
+                        TS18661FloatFormat::BinaryExtended => s.push_str(&format!("f{}x", t.width)),
+                        TS18661FloatFormat::DecimalInterchange => s.push_str(match t.width {
+                            32 => "df",
+                            64 => "dd",
+                            _ => "dl",
+                        }),
+                        TS18661FloatFormat::DecimalExtended => {
+                            s.push_str(&format!("d{}x", t.width))
+                        }
+                    },
+                    FloatFormat::FixedPoint(ref p) => {
+                        if p.unsigned {
+                            s.push('u');
+                        }
+                        s.push_str(match p.size {
+                            FixedPointSize::Short => "h",
+                            FixedPointSize::Plain => "",
+                            FixedPointSize::Long => "l",
+                            FixedPointSize::LongLong => "ll",
+                        });
+                        s.push(match p.kind {
+                            FixedPointKind::Fract => 'r',
+                            FixedPointKind::Accum => 'k',
+                        });
+                    }
+                }
+                if f.suffix.imaginary {
+                    s.push('i');
+                }
+                self.push(&s);
+            }
+            Constant::Character(ref c) => self.literal(&c.value),
+            Constant::Nullptr => self.push("nullptr"),
+            Constant::Bool(b) => self.push(if b { "true" } else { "false" }),
+        }
+    }
+}
+
+#[cfg(test)]
+fn parse_unit(source: &str) -> TranslationUnit {
+    use driver::{parse_preprocessed, Config};
+    parse_preprocessed(&Config::default(), source.into())
+        .expect("parse failed")
+        .unit
+}
+
+#[cfg(test)]
+fn emit_parsed_expression(source: &str) -> String {
+    use env::Env;
+    use parser;
+    let e = parser::expression(source, &mut Env::with_gnu()).expect("parse failed");
+    emit_expression(&e.node, &FormatOptions::default())
+}
+
+#[test]
+fn test_emit_precedence() {
+    let cases = [
+        ("(a+b)*c", "(a + b) * c"),
+        ("a-(b-c)", "a - (b - c)"),
+        ("(a-b)-c", "a - b - c"),
+        ("a=b=c", "a = b = c"),
+        ("(a=b)=c", "(a = b) = c"),
+        ("a?b:(c?d:e)", "a ? b : c ? d : e"),
+        ("(a?b:c)?d:e", "(a ? b : c) ? d : e"),
+        ("a?(b,c):(d,e)", "a ? b, c : (d, e)"),
+        ("f((a,b),c)", "f((a, b), c)"),
+        ("-(-x)", "- -x"),
+        ("a-(-b)", "a - -b"),
+        ("&(&&l)", "& &&l"),
+        ("(*p).x[1]++", "(*p).x[1]++"),
+        ("(int)(char)-x", "(int)(char)-x"),
+        ("sizeof((int){1}.x)", "sizeof((int){1}.x)"),
+        ("sizeof(x+1)", "sizeof (x + 1)"),
+        ("(a,b)[c]", "(a, b)[c]"),
+    ];
+    for &(source, expected) in &cases {
+        assert_eq!(emit_parsed_expression(source), expected, "{}", source);
+    }
+}
+
+#[test]
+fn test_emit_declarators() {
+    let source = concat!(
+        "int (*(*f)(void))[3];\n",
+        "void (*signal(int, void (*)(int)))(int);\n",
+        "char *const *volatile p[2], (*q)[4] __asm__(\"q\") __attribute__((unused));\n",
+        "int x = sizeof(int (*)[3]) + sizeof(long[2]);\n",
+    );
+    let options = FormatOptions::default();
+    assert_eq!(emit_translation_unit(&parse_unit(source), &options), source);
+}
+
+#[test]
+fn test_emit_layout() {
+    let unit = parse_unit(concat!(
+        "struct s{int a:3,:2;};int f(int n){if(n)return 1;else if(n>2){n--;}else n++;",
+        "do n--;while(n);for(;;)break;switch(n){case 1:{}default:;}",
+        "__asm__ volatile(\"\"::\"r\"(n):\"memory\");return({n;});}",
+    ));
+    assert_eq!(
+        emit_translation_unit(&unit, &FormatOptions::default()),
+        concat!(
+            "struct s {\n",
+            "    int a : 3, : 2;\n",
+            "};\n",
+            "\n",
+            "int f(int n)\n",
+            "{\n",
+            "    if (n)\n",
+            "        return 1;\n",
+            "    else if (n > 2) {\n",
+            "        n--;\n",
+            "    } else\n",
+            "        n++;\n",
+            "    do\n",
+            "        n--;\n",
+            "    while (n);\n",
+            "    for (;;)\n",
+            "        break;\n",
+            "    switch (n) {\n",
+            "    case 1:\n",
+            "        {}\n",
+            "    default:\n",
+            "        ;\n",
+            "    }\n",
+            "    __asm__ volatile(\"\" : : \"r\"(n) : \"memory\");\n",
+            "    return ({\n",
+            "        n;\n",
+            "    });\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn test_emit_literal_escapes() {
+    let piece = StringPiece {
+        encoding: CharEncoding::Plain,
+        value: "\"a\nb\\\n\x01\"".into(),
+    };
+    let e = Expression::StringLiteral(Box::new(Node::new(vec![piece], Span::none())));
+    assert_eq!(
+        emit_expression(&e, &FormatOptions::default()),
+        "\"a\\nb\\\n\\001\""
+    );
+}
+
+#[cfg(test)]
+const STYLE_SOURCE: &'static str = concat!(
+    "int sum(int count, const int *values, int (*filter)(int value, void *context), void *context);",
+    "int primes[] = {2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71};",
+    "int f(int n) { while (n > 10) n /= 2; if (n) { return primes[n]; } else { do { n++; } ",
+    "while (n < 0); } for (;;) { switch (n) { case 1: break; } } return 0; }",
+);
+
+#[cfg(test)]
+fn print_unit(unit: &TranslationUnit) -> String {
+    use print::Printer;
+    use visit::Visit;
+    let mut s = String::new();
+    Printer::new(&mut s).visit_translation_unit(unit);
+    s
+}
+
+#[test]
+fn test_emit_options_keep_tree() {
+    let unit = parse_unit(STYLE_SOURCE);
+    let expected = print_unit(&unit);
+    for bits in 0..1 << 7 {
+        let bit = |i: u32| bits & 1 << i != 0;
+        let braces = |i| {
+            if bit(i) {
+                BracePlacement::NextLine
+            } else {
+                BracePlacement::SameLine
+            }
+        };
+        let options = FormatOptions {
+            indent_width: if bit(0) { 2 } else { 4 },
+            use_tabs: bit(1),
+            function_braces: braces(2),
+            control_braces: braces(3),
+            max_width: if bit(4) { Some(24) } else { None },
+            space_before_paren: bit(5),
+            comments: if bit(6) {
+                CommentStyle::Line
+            } else {
+                CommentStyle::Block
+            },
+        };
+        let source = emit_translation_unit(&unit, &options);
+        let actual = print_unit(&parse_unit(&source));
+        assert!(actual == expected, "{:?}:\n{}", options, source);
+    }
+}
+
+// Unit of `STYLE_SOURCE` with a syntax error, written as a comment
+#[cfg(test)]
+fn style_unit() -> TranslationUnit {
+    let mut unit = parse_unit(STYLE_SOURCE);
+    let error = Node::new(ExternalDeclaration::Error, Span::none());
+    unit.0.insert(1, error);
+    unit
+}
+
+#[test]
+fn test_emit_style_knr() {
+    assert_eq!(
+        emit_translation_unit(&style_unit(), &FormatOptions::default()),
+        concat!(
+            "int sum(int count, const int *values, int (*filter)(int value, void *context),\n",
+            "        void *context);\n",
+            "/* syntax error */\n",
+            "int primes[] = {2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59,\n",
+            "                61, 67, 71};\n",
+            "\n",
+            "int f(int n)\n",
+            "{\n",
+            "    while (n > 10)\n",
+            "        n /= 2;\n",
+            "    if (n) {\n",
+            "        return primes[n];\n",
+            "    } else {\n",
+            "        do {\n",
+            "            n++;\n",
+            "        } while (n < 0);\n",
+            "    }\n",
+            "    for (;;) {\n",
+            "        switch (n) {\n",
+            "        case 1:\n",
+            "            break;\n",
+            "        }\n",
+            "    }\n",
+            "    return 0;\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn test_emit_style_llvm() {
+    assert_eq!(
+        emit_translation_unit(&style_unit(), &FormatOptions::llvm()),
+        concat!(
+            "int sum(int count, const int *values, int (*filter)(int value, void *context),\n",
+            "        void *context);\n",
+            "// syntax error\n",
+            "int primes[] = {2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59,\n",
+            "                61, 67, 71};\n",
+            "\n",
+            "int f(int n) {\n",
+            "  while (n > 10)\n",
+            "    n /= 2;\n",
+            "  if (n) {\n",
+            "    return primes[n];\n",
+            "  } else {\n",
+            "    do {\n",
+            "      n++;\n",
+            "    } while (n < 0);\n",
+            "  }\n",
+            "  for (;;) {\n",
+            "    switch (n) {\n",
+            "    case 1:\n",
+            "      break;\n",
+            "    }\n",
+            "  }\n",
+            "  return 0;\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn test_emit_style_allman() {
+    let options = FormatOptions {
+        use_tabs: true,
+        function_braces: BracePlacement::NextLine,
+        control_braces: BracePlacement::NextLine,
+        max_width: Some(60),
+        space_before_paren: false,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        emit_translation_unit(&style_unit(), &options),
+        concat!(
+            "int sum(int count, const int *values,\n",
+            "        int (*filter)(int value, void *context),\n",
+            "        void *context);\n",
+            "/* syntax error */\n",
+            "int primes[] = {2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37,\n",
+            "                41, 43, 47, 53, 59, 61, 67, 71};\n",
+            "\n",
+            "int f(int n)\n",
+            "{\n",
+            "\twhile(n > 10)\n",
+            "\t\tn /= 2;\n",
+            "\tif(n)\n",
+            "\t{\n",
+            "\t\treturn primes[n];\n",
+            "\t}\n",
+            "\telse\n",
+            "\t{\n",
+            "\t\tdo\n",
+            "\t\t{\n",
+            "\t\t\tn++;\n",
+            "\t\t} while(n < 0);\n",
+            "\t}\n",
+            "\tfor(;;)\n",
+            "\t{\n",
+            "\t\tswitch(n)\n",
+            "\t\t{\n",
+            "\t\tcase 1:\n",
+            "\t\t\tbreak;\n",
+            "\t\t}\n",
+            "\t}\n",
+            "\treturn 0;\n",
+            "}\n",
+        )
+    );
+}
+
+#[cfg(test)]
+fn parse_declaration(source: &str) -> Node<Declaration> {
+    use driver::{parse_preprocessed, Config};
+    let unit = parse_preprocessed(&Config::with_clang(), source.into())
+        .expect("parse failed")
+        .unit;
+    match unit.0.into_iter().next().map(|d| d.node) {
+        Some(ExternalDeclaration::Declaration(d)) => d,
+        d => panic!("not a declaration: {:?}", d),
+    }
+}
+
+// Type of the declarator of `source`, checked to declare the same when parsed again
+#[cfg(test)]
+fn declarator_type(source: &str, identifier: Option<&str>) -> String {
+    use print::Printer;
+    use visit::Visit;
+    fn dump(d: &Node<Declarator>) -> String {
+        let mut s = String::new();
+        Printer::new(&mut s).visit_declarator(&d.node, &d.span);
+        s
+    }
+    let d = parse_declaration(source);
+    let declarator = &d.node.declarators[0].node.declarator;
+    let s = type_to_string(&d.node.specifiers, Some(&declarator.node), identifier);
+
+    let reparsed = parse_declaration(&format!("{};", s));
+    let again = &reparsed.node.declarators[0].node.declarator;
+    assert_eq!(
+        type_to_string(&reparsed.node.specifiers, Some(&again.node), None),
+        s
+    );
+    if identifier.is_none() {
+        assert_eq!(dump(again), dump(declarator), "{}", s);
+    }
+    s
+}
+
+#[test]
+fn test_type_to_string() {
+    let cases = [
+        ("int (*(*f)(void))[3];", "int (*(*f)(void))[3]"),
+        (
+            "void (*signal(int, void (*)(int)))(int);",
+            "void (*signal(int, void (*)(int)))(int)",
+        ),
+        (
+            "static const char *restrict names[4];",
+            "const char *restrict names[4]",
+        ),
+        ("char *const *volatile p[2];", "char *const *volatile p[2]"),
+        (
+            "extern inline int (*a[2])(int *_Nonnull);",
+            "int (*a[2])(int *_Nonnull)",
+        ),
+        (
+            "int __seg_gs *const *_Nullable q;",
+            "int __seg_gs *const *_Nullable q",
+        ),
+    ];
+    for &(source, expected) in &cases {
+        assert_eq!(declarator_type(source, None), expected);
+    }
+
+    assert_eq!(
+        declarator_type("int (*(*f)(void))[3];", Some("g")),
+        "int (*(*g)(void))[3]"
+    );
+    assert_eq!(
+        declarator_type(
+            "register char *x[] __asm__(\"r\") __attribute__((unused));",
+            Some("y")
+        ),
+        "char __attribute__((unused)) *y[]"
+    );
+}
+
+#[test]
+fn test_type_name_to_string() {
+    use env::Env;
+    use parser;
+    let type_name = |source: &str| {
+        let e = parser::expression(source, &mut Env::with_gnu()).expect("parse failed");
+        match e.node {
+            Expression::SizeOfTy(s) => s.node.0.node,
+            e => panic!("not sizeof: {:?}", e),
+        }
+    };
+    let cases = [
+        ("void (*)(int)", "handler", "void (*handler)(int)"),
+        ("char *const *", "p", "char *const *p"),
+        ("int (*[2])(void)", "table", "int (*table[2])(void)"),
+        ("unsigned long", "n", "unsigned long n"),
+    ];
+    for &(source, identifier, declared) in &cases {
+        let t = type_name(&format!("sizeof({})", source));
+        let s = type_name_to_string(&t, None);
+        assert_eq!(s, source);
+        assert_eq!(type_name(&format!("sizeof({})", s)), t);
+        let s = type_name_to_string(&t, Some(identifier));
+        assert_eq!(s, declared);
+        let d = parse_declaration(&format!("{};", s));
+        let declarator = &d.node.declarators[0].node.declarator.node;
+        assert_eq!(
+            type_to_string(&d.node.specifiers, Some(declarator), None),
+            s
+        );
+    }
+}
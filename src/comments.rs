@@ -0,0 +1,335 @@
+//! Comments retained in the preprocessed source
+//!
+//! Comments are normally removed by the preprocessor. With `Config::keep_comments` they are
+//! kept, skipped over by the parser like whitespace, and collected into `Parse::comments`.
+
+use std::collections::HashMap;
+
+use ast::*;
+use driver::Parse;
+use span::{Node, Span};
+use visit::*;
+
+/// Comment text, without the delimiters
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Comment {
+    pub kind: CommentKind,
+    pub text: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum CommentKind {
+    /// `/* ... */`
+    Block,
+    /// `// ...`
+    Line,
+}
+
+/// Find all comments in a preprocessed source
+///
+/// String and character literals are skipped, so comment delimiters inside them are ignored.
+/// Spans are byte offsets of the whole comment, delimiters included.
+pub fn collect(src: &str) -> Vec<Node<Comment>> {
+    let bytes = src.as_bytes();
+    let mut comments = Vec::new();
+    let mut p = 0;
+
+    while p < bytes.len() {
+        match bytes[p] {
+            b'/' if bytes.get(p + 1) == Some(&b'*') => {
+                let (text, end) = match src[p + 2..].find("*/") {
+                    Some(n) => (&src[p + 2..p + 2 + n], p + 2 + n + 2),
+                    None => (&src[p + 2..], bytes.len()),
+                };
+                comments.push(comment(CommentKind::Block, text, p, end));
+                p = end;
+            }
+            b'/' if bytes.get(p + 1) == Some(&b'/') => {
+                let mut end = p + src[p..].find('\n').unwrap_or(bytes.len() - p);
+                if end > p && bytes[end - 1] == b'\r' {
+                    end -= 1;
+                }
+                comments.push(comment(CommentKind::Line, &src[p + 2..end], p, end));
+                p = end;
+            }
+            b'"' | b'\'' => p = skip_literal(bytes, p),
+            c if is_ident(c) => {
+                // Skip whole identifiers and pp-numbers, so that a digit separator `'` is not
+                // mistaken for the start of a character constant.
+                let number = c.is_ascii_digit();
+                p += 1;
+                while p < bytes.len() && (is_ident(bytes[p]) || number && is_number(bytes, p)) {
+                    p += 1;
+                }
+            }
+            b'.' if bytes.get(p + 1).map_or(false, u8::is_ascii_digit) => {
+                p += 1;
+                while p < bytes.len() && (is_ident(bytes[p]) || is_number(bytes, p)) {
+                    p += 1;
+                }
+            }
+            _ => p += 1,
+        }
+    }
+
+    comments
+}
+
+/// Attach comments to the declarations they document
+///
+/// Returns comments keyed by the start offset of the documented node: an
+/// `ExternalDeclaration`, a `StructField` or an `Enumerator`. For each node these are the
+/// comments on the lines immediately preceding it, followed by a trailing `//!<`, `///<`,
+/// `/*!<` or `/**<` comment on the line it ends on.
+///
+/// A blank line or a preprocessor line marker between a comment and the node breaks the
+/// association, so comments before an `#include` don't attach to the included declarations.
+pub fn associate(parse: &Parse) -> HashMap<usize, Vec<Node<Comment>>> {
+    let mut items = Items(Vec::new());
+    items.visit_translation_unit(&parse.unit);
+
+    let mut docs = HashMap::new();
+    for span in items.0 {
+        let mut found = preceding(&parse.source, &parse.comments, span.start);
+        let end = content_end(&parse.source, &parse.comments, span);
+        found.extend(trailing(&parse.source, &parse.comments, end));
+        if !found.is_empty() {
+            docs.insert(span.start, found.into_iter().cloned().collect());
+        }
+    }
+    docs
+}
+
+struct Items(Vec<Span>);
+
+impl<'ast> Visit<'ast> for Items {
+    fn visit_external_declaration(&mut self, n: &'ast ExternalDeclaration, span: &'ast Span) {
+        self.0.push(*span);
+        visit_external_declaration(self, n, span)
+    }
+
+    fn visit_struct_field(&mut self, n: &'ast StructField, span: &'ast Span) {
+        self.0.push(*span);
+        visit_struct_field(self, n, span)
+    }
+
+    fn visit_enumerator(&mut self, n: &'ast Enumerator, span: &'ast Span) {
+        self.0.push(*span);
+        visit_enumerator(self, n, span)
+    }
+}
+
+// Spans may include trailing whitespace and comments, find where the node's own text ends
+fn content_end(src: &str, comments: &[Node<Comment>], span: Span) -> usize {
+    let mut end = span.end;
+    loop {
+        let trimmed = span.start + src[span.start..end].trim_right().len();
+        match comments
+            .iter()
+            .find(|c| c.span.end == trimmed && c.span.start >= span.start)
+        {
+            Some(c) => end = c.span.start,
+            None => return trimmed,
+        }
+    }
+}
+
+fn is_trailing_doc(c: &Comment) -> bool {
+    match c.kind {
+        CommentKind::Block => c.text.starts_with("*<") || c.text.starts_with("!<"),
+        CommentKind::Line => c.text.starts_with("/<") || c.text.starts_with("!<"),
+    }
+}
+
+// Comments on the lines directly above `pos`, each starting its own line
+fn preceding<'a>(src: &str, comments: &'a [Node<Comment>], pos: usize) -> Vec<&'a Node<Comment>> {
+    let mut n = comments.iter().take_while(|c| c.span.end <= pos).count();
+    let mut found = Vec::new();
+    let mut end = pos;
+
+    while n > 0 {
+        let c = &comments[n - 1];
+        let gap = &src[c.span.end..end];
+        let lines = gap.matches('\n').count();
+        if is_trailing_doc(&c.node) || !gap.trim().is_empty() || lines > 1 {
+            break;
+        }
+        if lines == 0 && end == pos {
+            // Comment on the same line, in front of the node
+            break;
+        }
+
+        let line_start = src[..c.span.start].rfind('\n').map_or(0, |p| p + 1);
+        let prefix = &src[line_start..c.span.start];
+        let after_comment = n > 1 && {
+            let p = &comments[n - 2];
+            p.span.end > line_start && src[p.span.end..c.span.start].trim().is_empty()
+        };
+        if !prefix.trim().is_empty() && !after_comment {
+            break;
+        }
+
+        found.push(c);
+        end = c.span.start;
+        n -= 1;
+    }
+
+    found.reverse();
+    found
+}
+
+// Trailing documentation comment on the line ending at `pos`
+fn trailing<'a>(src: &str, comments: &'a [Node<Comment>], pos: usize) -> Option<&'a Node<Comment>> {
+    let c = match comments.iter().find(|c| c.span.start >= pos) {
+        Some(c) => c,
+        None => return None,
+    };
+    let gap = &src[pos..c.span.start];
+    if is_trailing_doc(&c.node)
+        && gap
+            .chars()
+            .all(|c| c == ' ' || c == '\t' || c == ',' || c == ';')
+    {
+        Some(c)
+    } else {
+        None
+    }
+}
+
+fn comment(kind: CommentKind, text: &str, start: usize, end: usize) -> Node<Comment> {
+    Node::new(
+        Comment {
+            kind: kind,
+            text: text.to_owned(),
+        },
+        Span::span(start, end),
+    )
+}
+
+fn is_ident(c: u8) -> bool {
+    c == b'_' || c.is_ascii_alphanumeric() || c >= 0x80
+}
+
+// Characters other than identifier ones that can continue a pp-number
+fn is_number(bytes: &[u8], p: usize) -> bool {
+    match bytes[p] {
+        b'.' => true,
+        b'\'' => bytes.get(p + 1).map_or(false, |&c| is_ident(c)),
+        b'+' | b'-' => match bytes[p - 1] {
+            b'e' | b'E' | b'p' | b'P' => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Position just after a string or character literal starting at `p`
+fn skip_literal(bytes: &[u8], mut p: usize) -> usize {
+    let quote = bytes[p];
+    p += 1;
+    while p < bytes.len() {
+        match bytes[p] {
+            b'\\' => p += 2,
+            b'\n' => return p,
+            c if c == quote => return p + 1,
+            _ => p += 1,
+        }
+    }
+    bytes.len()
+}
+
+#[test]
+fn test_collect() {
+    fn t(src: &str, expected: &[(CommentKind, &str, &str)]) {
+        let comments = collect(src);
+        assert_eq!(comments.len(), expected.len(), "{:?}", comments);
+        for (c, &(kind, text, span)) in comments.iter().zip(expected) {
+            assert_eq!(c.node.kind, kind);
+            assert_eq!(c.node.text, text);
+            assert_eq!(&src[c.span.start..c.span.end], span);
+        }
+    }
+
+    use self::CommentKind::*;
+
+    t("int x;", &[]);
+    t(
+        "/* a */ int x; // b\r\n",
+        &[(Block, " a ", "/* a */"), (Line, " b", "// b")],
+    );
+    t(
+        "char *s = \"/* no */\", c = '\"'; /**/",
+        &[(Block, "", "/**/")],
+    );
+    t("int x = 1'000; // c", &[(Line, " c", "// c")]);
+    t("x = u8'/'; /* d", &[(Block, " d", "/* d")]);
+    t(
+        "/* e\n * f\n */\n# 1 \"it's.h\" 1\n//g",
+        &[
+            (Block, " e\n * f\n ", "/* e\n * f\n */"),
+            (Line, "g", "//g"),
+        ],
+    );
+}
+
+#[test]
+fn test_associate() {
+    use driver::{parse_preprocessed, Config};
+
+    let source = "\
+# 1 \"main.c\"
+/** Before include */
+# 1 \"inc.h\" 1
+int included;
+# 3 \"main.c\" 2
+
+/// Point in space.
+/// Second line.
+struct point {
+    /** Horizontal */
+    int x; //!< X coordinate
+    int y; ///< Y coordinate
+
+    /* Not attached, blank line follows */
+
+    int z;
+};
+
+enum color {
+    RED, /**< Red */
+    // Green
+    GREEN,
+    BLUE /*!< Blue */
+};
+
+int a; // Plain trailing comment
+int b;
+";
+
+    let mut config = Config::with_gcc();
+    config.keep_comments = true;
+    let parse = parse_preprocessed(&config, source.into()).expect("parse failed");
+    let docs = associate(&parse);
+
+    let texts = |needle: &str| -> Vec<String> {
+        let start = source.find(needle).expect("needle not found");
+        docs.get(&start)
+            .map(|v| v.iter().map(|c| c.node.text.clone()).collect())
+            .unwrap_or_default()
+    };
+
+    assert_eq!(texts("int included"), Vec::<String>::new());
+    assert_eq!(
+        texts("struct point"),
+        vec!["/ Point in space.", "/ Second line."]
+    );
+    assert_eq!(texts("int x"), vec!["* Horizontal ", "!< X coordinate"]);
+    assert_eq!(texts("int y"), vec!["/< Y coordinate"]);
+    assert_eq!(texts("int z"), Vec::<String>::new());
+    assert_eq!(texts("RED"), vec!["*< Red "]);
+    assert_eq!(texts("GREEN"), vec![" Green"]);
+    assert_eq!(texts("BLUE"), vec!["!< Blue "]);
+    assert_eq!(texts("int a"), Vec::<String>::new());
+    assert_eq!(texts("int b"), Vec::<String>::new());
+    assert_eq!(docs.len(), 6);
+}
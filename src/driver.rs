@@ -1,16 +1,39 @@
+#![allow(unknown_lints)]
+#![allow(bare_trait_objects)]
+
 //! Preprocess and parse C source file into an abstract syntax tree
 
+use std::cmp;
 use std::collections::HashSet;
 use std::error;
+use std::ffi::OsString;
 use std::fmt;
+use std::fs;
+use std::fs::File;
 use std::io;
-use std::path::Path;
-use std::process::Command;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::str;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use ast::TranslationUnit;
-use env::Env;
+use ast::{Declaration, Expression, ExternalDeclaration, Statement, TranslationUnit, TypeName};
+use cache;
+use comments::{self, Comment};
+use env::{Env, Standard, DEFAULT_MAX_NESTING, NESTING_TOO_DEEP};
 use loc;
-use parser::translation_unit;
+use parser::{
+    fragment_declaration, fragment_expression, fragment_statement, fragment_type_name,
+    translation_unit, translation_unit_from, ParseError,
+};
+#[cfg(feature = "internal-preprocessor")]
+use preprocessor::{
+    preprocess_file as internal_preprocess_file, preprocess_str as internal_preprocess_str,
+};
+use span::{Node, Span};
 
 /// Parser configuration
 #[derive(Clone, Debug)]
@@ -19,8 +42,59 @@ pub struct Config {
     pub cpp_command: String,
     /// Options to pass to the preprocessor program
     pub cpp_options: Vec<String>,
+    /// Command line and output conventions of the preprocessor program
+    pub cpp_kind: PreprocessorKind,
+    /// Kill the preprocessor if it runs for longer than this
+    pub cpp_timeout: Option<Duration>,
+    /// Handle to abort the preprocessor from another thread
+    pub cancel: Option<CancelToken>,
+    /// Environment variables to set for the preprocessor program
+    pub cpp_env: Vec<(String, String)>,
+    /// Start the preprocessor program with an empty environment, apart from `cpp_env`
+    pub cpp_env_clear: bool,
+    /// Working directory of the preprocessor program
+    ///
+    /// Relative source paths and include directories are resolved against it.
+    pub cpp_current_dir: Option<PathBuf>,
+    /// Directories to search for included files, in order
+    pub include_dirs: Vec<PathBuf>,
+    /// Macros to define or undefine before preprocessing, in order
+    pub macros: Vec<Macro>,
     /// Language flavor to parse
+    ///
+    /// The preprocessor is asked for the matching language standard, unless `cpp_options`
+    /// already selects one or `no_std_flag` is set.
     pub flavor: Flavor,
+    /// Do not pass a language standard option derived from `flavor` to the preprocessor
+    ///
+    /// Needed for preprocessors that reject `-std=`.
+    pub no_std_flag: bool,
+    /// Reject digraphs like `<:` in place of `[`, see `Env::digraphs`
+    pub no_digraphs: bool,
+    /// Accept declarations without a type specifier, see `Env::implicit_int`
+    pub implicit_int: bool,
+    /// Accept members that are only a tag or typedef name, see `Env::ms_extensions`
+    ///
+    /// Always on with `Flavor::MsvcC11`.
+    pub ms_extensions: bool,
+    /// Keep comments in the preprocessed source and collect them into `Parse::comments`
+    pub keep_comments: bool,
+    /// Character encoding of the preprocessed source
+    pub encoding: Encoding,
+    /// Deepest nesting of expressions, declarators, initializers and statements to parse
+    ///
+    /// Deeper input is rejected with a syntax error rather than overflowing the stack. A
+    /// construct counts one or more levels depending on how much stack it needs: the default
+    /// fits in the 2 MiB stack of a spawned thread even in a debug build, raise it only when
+    /// parsing on a larger stack.
+    pub max_nesting: usize,
+    /// Directory to keep preprocessed sources in, to skip the preprocessor for files that did
+    /// not change since they were last parsed
+    ///
+    /// Only `parse` and `parse_all` use the cache. An entry is reused if the configuration, the
+    /// source file contents, and the size and modification time of all included files are
+    /// unchanged. Unreadable or outdated entries are silently replaced.
+    pub cache_dir: Option<PathBuf>,
 }
 
 impl Config {
@@ -29,7 +103,23 @@ impl Config {
         Config {
             cpp_command: "gcc".into(),
             cpp_options: vec!["-E".into()],
+            cpp_kind: PreprocessorKind::Gcc,
+            cpp_timeout: None,
+            cancel: None,
+            cpp_env: Vec::new(),
+            cpp_env_clear: false,
+            cpp_current_dir: None,
+            include_dirs: Vec::new(),
+            macros: Vec::new(),
             flavor: Flavor::GnuC11,
+            no_std_flag: false,
+            no_digraphs: false,
+            implicit_int: false,
+            ms_extensions: false,
+            keep_comments: false,
+            encoding: Encoding::Utf8,
+            max_nesting: DEFAULT_MAX_NESTING,
+            cache_dir: None,
         }
     }
 
@@ -38,8 +128,167 @@ impl Config {
         Config {
             cpp_command: "clang".into(),
             cpp_options: vec!["-E".into()],
+            cpp_kind: PreprocessorKind::Gcc,
+            cpp_timeout: None,
+            cancel: None,
+            cpp_env: Vec::new(),
+            cpp_env_clear: false,
+            cpp_current_dir: None,
+            include_dirs: Vec::new(),
+            macros: Vec::new(),
             flavor: Flavor::ClangC11,
+            no_std_flag: false,
+            no_digraphs: false,
+            implicit_int: false,
+            ms_extensions: false,
+            keep_comments: false,
+            encoding: Encoding::Utf8,
+            max_nesting: DEFAULT_MAX_NESTING,
+            cache_dir: None,
+        }
+    }
+
+    /// Use MSVC `cl.exe` as a pre-processor and enable MSVC extensions
+    pub fn with_msvc() -> Config {
+        Config {
+            cpp_command: "cl.exe".into(),
+            cpp_options: vec!["/E".into(), "/nologo".into()],
+            cpp_kind: PreprocessorKind::Msvc,
+            cpp_timeout: None,
+            cancel: None,
+            cpp_env: Vec::new(),
+            cpp_env_clear: false,
+            cpp_current_dir: None,
+            include_dirs: Vec::new(),
+            macros: Vec::new(),
+            flavor: Flavor::MsvcC11,
+            no_std_flag: false,
+            no_digraphs: false,
+            implicit_int: false,
+            ms_extensions: false,
+            keep_comments: false,
+            encoding: Encoding::Utf8,
+            max_nesting: DEFAULT_MAX_NESTING,
+            cache_dir: None,
+        }
+    }
+
+    /// Use the built-in preprocessor and parse standard C11
+    ///
+    /// No external program is run. System headers are not searched, so their directories must
+    /// be added with `include_dir` if the source needs them.
+    #[cfg(feature = "internal-preprocessor")]
+    pub fn with_internal_preprocessor() -> Config {
+        Config {
+            cpp_command: String::new(),
+            cpp_options: Vec::new(),
+            cpp_kind: PreprocessorKind::Internal,
+            cpp_timeout: None,
+            cancel: None,
+            cpp_env: Vec::new(),
+            cpp_env_clear: false,
+            cpp_current_dir: None,
+            include_dirs: Vec::new(),
+            macros: Vec::new(),
+            flavor: Flavor::StdC11,
+            no_std_flag: false,
+            no_digraphs: false,
+            implicit_int: false,
+            ms_extensions: false,
+            keep_comments: false,
+            encoding: Encoding::Utf8,
+            max_nesting: DEFAULT_MAX_NESTING,
+            cache_dir: None,
+        }
+    }
+
+    /// Use the compiler named by the `CPP` or `CC` environment variable
+    ///
+    /// The value is split on whitespace, so wrappers like `ccache gcc` work. Command line
+    /// conventions and language flavor are guessed from the compiler name. `CC` is run with the
+    /// option to only preprocess. Falls back to `Config::default()` if neither is set.
+    pub fn from_env() -> Config {
+        Config::from_vars(|name| ::std::env::var(name).ok())
+    }
+
+    // `from_env` with the variables looked up by `var`
+    fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> Config {
+        for &(name, is_cc) in &[("CPP", false), ("CC", true)] {
+            if let Some(value) = var(name) {
+                if let Some(config) = Config::from_command(&value, is_cc) {
+                    return config;
+                }
+            }
+        }
+        Config::default()
+    }
+
+    fn from_command(command: &str, is_cc: bool) -> Option<Config> {
+        const WRAPPERS: &'static [&'static str] = &["ccache", "sccache", "distcc", "icecc"];
+
+        let words = command.split_whitespace().collect::<Vec<_>>();
+        let name = |w: &&str| {
+            Path::new(w)
+                .file_stem()
+                .map_or(String::new(), |s| s.to_string_lossy().to_lowercase())
+        };
+        // Skip compiler wrappers to find the name of the compiler
+        let compiler = match words.iter().map(name).find(|n| !WRAPPERS.contains(&&n[..])) {
+            Some(n) => n,
+            None => return None,
+        };
+
+        let mut config = if compiler == "cl" || compiler.ends_with("clang-cl") {
+            Config::with_msvc()
+        } else if compiler.contains("clang") {
+            Config::with_clang()
+        } else {
+            Config::with_gcc()
+        };
+        let preprocess_only = config.cpp_options.clone();
+        config.cpp_command = words[0].into();
+        config.cpp_options = words[1..].iter().map(|&w| w.into()).collect();
+        if is_cc {
+            config.cpp_options.extend(preprocess_only);
         }
+        Some(config)
+    }
+
+    /// Set an environment variable for the preprocessor program
+    pub fn env_var<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> &mut Config {
+        self.cpp_env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Do not pass the environment of this process to the preprocessor program
+    pub fn clear_env(&mut self, clear: bool) -> &mut Config {
+        self.cpp_env_clear = clear;
+        self
+    }
+
+    /// Run the preprocessor program in directory `dir`
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Config {
+        self.cpp_current_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add a directory to the include search path (`-I`)
+    pub fn include_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Config {
+        self.include_dirs.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Define a macro, optionally with a value (`-D name` or `-D name=value`)
+    pub fn define<S: Into<String>>(&mut self, name: S, value: Option<S>) -> &mut Config {
+        self.macros
+            .push(Macro::Define(name.into(), value.map(Into::into)));
+        self
+    }
+
+    /// Remove any previous definition of a macro (`-U name`)
+    pub fn undefine<S: Into<String>>(&mut self, name: S) -> &mut Config {
+        self.macros.push(Macro::Undefine(name.into()));
+        self
     }
 }
 
@@ -55,15 +304,95 @@ impl Default for Config {
     }
 }
 
+/// Macro definition passed to the preprocessor
+#[derive(Clone, Debug, PartialEq)]
+pub enum Macro {
+    /// `-D name` or `-D name=value`
+    Define(String, Option<String>),
+    /// `-U name`
+    Undefine(String),
+}
+
+/// Cancellation handle for a preprocessor run
+///
+/// Clones share the same state, so a clone stored in `Config` can be tripped from another
+/// thread, for example by a GUI that wants to abort a long preprocessing run.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// Request the preprocessor to be killed
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Preprocessor program conventions
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PreprocessorKind {
+    /// GCC compatible preprocessor (`gcc`, `clang`, `cpp`)
+    Gcc,
+    /// Microsoft `cl.exe`, which cannot read standard input and reports errors on stdout
+    Msvc,
+    /// Built-in preprocessor, does not run `cpp_command`
+    ///
+    /// Preprocessing fails with an error when the crate is built without the
+    /// `internal-preprocessor` feature.
+    Internal,
+}
+
 /// C language flavors
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Flavor {
+    /// Strict standard C89
+    StdC89,
+    /// Strict standard C99
+    StdC99,
     /// Strict standard C11
     StdC11,
+    /// Strict standard C17
+    StdC17,
+    /// Strict standard C23
+    StdC23,
     /// Standard C11 with GNU extensions
     GnuC11,
+    /// Standard C23 with GNU extensions
+    GnuC23,
     /// Standard C11 with Clang extensions
     ClangC11,
+    /// Standard C23 with Clang extensions
+    ClangC23,
+    /// Standard C11 with MSVC extensions
+    MsvcC11,
+    /// C subset of CUDA with Clang extensions, as output by `nvcc -E` or `clang -x cuda -E`
+    Cuda,
+    /// OpenCL C, based on C99 with Clang extensions
+    OpenCl,
+}
+
+/// Character encoding of the preprocessor output
+///
+/// Except in strict `Utf8` mode, bytes in string and character literals that are not valid
+/// UTF-8 are replaced with octal escape sequences, so that literals keep their original values.
+/// For `Latin1` and `Windows1252` this applies to all bytes above 0x7f.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Encoding {
+    /// UTF-8, invalid sequences are an error
+    Utf8,
+    /// UTF-8, invalid sequences outside of literals are replaced with U+FFFD
+    Utf8Lossy,
+    /// ISO 8859-1
+    Latin1,
+    /// Windows code page 1252, a superset of printable ISO 8859-1 characters
+    Windows1252,
 }
 
 /// Result of a successful parse
@@ -73,13 +402,168 @@ pub struct Parse {
     pub source: String,
     /// Root of the abstract syntax tree
     pub unit: TranslationUnit,
+    /// Comments in the pre-processed source, if `Config::keep_comments` is set
+    pub comments: Vec<Node<Comment>>,
+    /// Warnings and other messages printed by the preprocessor
+    pub cpp_diagnostics: Vec<Diagnostic>,
+    /// Use of `Config::cache_dir` while preprocessing
+    pub cache: CacheStats,
+}
+
+/// Number of preprocessed sources found in or missing from `Config::cache_dir`
+///
+/// Both counts are zero if the cache is disabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl Parse {
+    /// Files named in line markers of the preprocessed source, main source file first
+    ///
+    /// Each file is listed once, in the order it was first entered. Pseudo-files such as
+    /// `<built-in>` are left out.
+    pub fn included_files(&self) -> Vec<loc::IncludedFile> {
+        loc::get_included_files(&self.source)
+    }
+}
+
+/// Message printed by the preprocessor
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// File the message refers to, if the message starts with a location
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Rest of the message, for example `warning: "FOO" redefined`
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Split location from a message in `file:line:column: ...` or `file(line): ...` format
+    pub fn parse(text: &str) -> Diagnostic {
+        let digits = |s: &str| s.bytes().take_while(|c| c.is_ascii_digit()).count();
+
+        // Include stack printed by gcc before a message is not a message location
+        let context = text.trim_left();
+        let mut start =
+            if context.starts_with("In file included from ") || context.starts_with("from ") {
+                text.len()
+            } else {
+                0
+            };
+        while let Some(n) = text[start..].find(|c| c == ':' || c == '(') {
+            let p = start + n;
+            let rest = &text[p + 1..];
+            let d = digits(rest);
+            let end = if text[p..].starts_with('(') {
+                "): "
+            } else {
+                ":"
+            };
+            if p > 0 && d > 0 && rest[d..].starts_with(end) {
+                let mut rest = &rest[d + end.len()..];
+                let mut column = None;
+                let c = digits(rest);
+                if end == ":" && c > 0 && rest[c..].starts_with(':') {
+                    column = rest[..c].parse().ok();
+                    rest = &rest[c + 1..];
+                }
+                return Diagnostic {
+                    file: Some(text[..p].into()),
+                    line: text[p + 1..p + 1 + d].parse().ok(),
+                    column: column,
+                    message: rest.trim().into(),
+                };
+            }
+            start = p + 1;
+        }
+
+        Diagnostic {
+            file: None,
+            line: None,
+            column: None,
+            message: text.trim().into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref file) = self.file {
+            try!(write!(fmt, "{}:", file));
+            if let Some(line) = self.line {
+                try!(write!(fmt, "{}:", line));
+            }
+            if let Some(column) = self.column {
+                try!(write!(fmt, "{}:", column));
+            }
+            try!(write!(fmt, " "));
+        }
+        write!(fmt, "{}", self.message)
+    }
+}
+
+fn diagnostics(text: &str) -> Vec<Diagnostic> {
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Diagnostic::parse)
+        .collect()
 }
 
 #[derive(Debug)]
 /// Error type returned from `parse`
+#[non_exhaustive]
 pub enum Error {
+    /// Preprocessor command could not be found
+    PreprocessorMissing {
+        command: String,
+        source: io::Error,
+    },
     PreprocessorError(io::Error),
+    PreprocessorTimeout(PartialOutput),
+    PreprocessorCancelled(PartialOutput),
     SyntaxError(SyntaxError),
+    /// Input of `parse_preprocessed_reader` is not valid UTF-8
+    InvalidEncoding {
+        /// Offset of the first byte of the invalid sequence
+        byte_offset: usize,
+    },
+}
+
+impl Error {
+    fn from_preprocessor(e: io::Error) -> Error {
+        let kind = e.kind();
+        if kind == io::ErrorKind::NotFound
+            && e.get_ref().map_or(false, |e| e.is::<MissingCommand>())
+        {
+            let missing = *e
+                .into_inner()
+                .and_then(|e| e.downcast::<MissingCommand>().ok())
+                .expect("checked above");
+            return Error::PreprocessorMissing {
+                command: missing.command,
+                source: missing.error,
+            };
+        }
+        if kind != io::ErrorKind::TimedOut && kind != io::ErrorKind::Interrupted {
+            return Error::PreprocessorError(e);
+        }
+        if !e.get_ref().map_or(false, |e| e.is::<PartialOutput>()) {
+            return Error::PreprocessorError(e);
+        }
+
+        let partial = *e
+            .into_inner()
+            .and_then(|e| e.downcast::<PartialOutput>().ok())
+            .expect("checked above");
+        if kind == io::ErrorKind::TimedOut {
+            Error::PreprocessorTimeout(partial)
+        } else {
+            Error::PreprocessorCancelled(partial)
+        }
+    }
 }
 
 impl From<SyntaxError> for Error {
@@ -91,21 +575,76 @@ impl From<SyntaxError> for Error {
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            &Error::PreprocessorMissing {
+                ref command,
+                ref source,
+            } => write!(
+                fmt,
+                "preprocessor \"{}\" not found ({}), set Config::cpp_command to the C preprocessor to use",
+                command, source
+            ),
             &Error::PreprocessorError(ref e) => write!(fmt, "preprocessor error: {}", e),
+            &Error::PreprocessorTimeout(ref p) => write!(fmt, "preprocessor timed out{}", p),
+            &Error::PreprocessorCancelled(ref p) => write!(fmt, "preprocessor cancelled{}", p),
             &Error::SyntaxError(ref e) => write!(fmt, "syntax error: {}", e),
+            &Error::InvalidEncoding { byte_offset } => {
+                write!(fmt, "invalid UTF-8 at byte offset {}", byte_offset)
+            }
         }
     }
 }
 
 impl error::Error for Error {
-    fn description(&self) -> &str {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
         match self {
-            &Error::PreprocessorError(_) => "preprocessor error",
-            &Error::SyntaxError(_) => "syntax error",
+            &Error::PreprocessorMissing { ref source, .. } => Some(source),
+            &Error::PreprocessorError(ref e) => Some(e),
+            &Error::PreprocessorTimeout(ref p) => Some(p),
+            &Error::PreprocessorCancelled(ref p) => Some(p),
+            &Error::SyntaxError(ref e) => Some(e),
+            &Error::InvalidEncoding { .. } => None,
+        }
+    }
+}
+
+// Failure to start the preprocessor command, carried inside an `io::Error` of kind `NotFound`
+#[derive(Debug)]
+struct MissingCommand {
+    command: String,
+    error: io::Error,
+}
+
+impl fmt::Display for MissingCommand {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}: {}", self.command, self.error)
+    }
+}
+
+impl error::Error for MissingCommand {}
+
+/// Output collected from a preprocessor that was killed before it finished
+///
+/// A `Preprocessor` reports a timeout or cancellation by returning an `io::Error` of kind
+/// `TimedOut` or `Interrupted` that wraps this type.
+#[derive(Debug, Clone)]
+pub struct PartialOutput {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl fmt::Display for PartialOutput {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let stderr = self.stderr.trim_right();
+        if stderr.is_empty() {
+            Ok(())
+        } else {
+            write!(fmt, ": {}", stderr)
         }
     }
 }
 
+impl error::Error for PartialOutput {}
+
 /// Syntax error during parsing
 #[derive(Debug, Clone)]
 pub struct SyntaxError {
@@ -113,12 +652,25 @@ pub struct SyntaxError {
     pub source: String,
     /// Line number in the preprocessed source
     pub line: usize,
-    /// Column number in the preprocessed source
+    /// Column number in the preprocessed source, counted in bytes
     pub column: usize,
+    /// Column number in the preprocessed source, counted in characters
+    pub column_utf8: usize,
     /// Byte position in the preproccessed source
     pub offset: usize,
     /// Tokens expected at the error location
     pub expected: HashSet<&'static str>,
+    /// Original file containing the error, as named by line markers
+    pub file: String,
+    /// Line number in the original file
+    pub original_line: usize,
+    /// Column number in the original line, counted in characters
+    ///
+    /// Same as `column_utf8` unless the original file could be read and its line contains the
+    /// same tokens as the preprocessed one, spaced differently.
+    pub original_column: usize,
+    /// File name and line of `#include` directives leading to `file`, outermost first
+    pub included_from: Vec<(String, usize)>,
 }
 
 impl SyntaxError {
@@ -139,79 +691,2701 @@ impl SyntaxError {
     pub fn get_location(&self) -> (loc::Location, Vec<loc::Location>) {
         loc::get_location_for_offset(&self.source, self.offset)
     }
+
+    /// Input was nested deeper than `Config::max_nesting`
+    pub fn is_nesting_too_deep(&self) -> bool {
+        self.expected.len() == 1 && self.expected.contains(NESTING_TOO_DEEP)
+    }
 }
 
+impl error::Error for SyntaxError {}
+
 impl fmt::Display for SyntaxError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let (loc, inc) = self.get_location();
-        try!(write!(
-            fmt,
-            "unexpected token at \"{}\" line {} column {}, expected ",
-            loc.file, loc.line, self.column
-        ));
-        try!(self.format_expected(fmt));
-        for loc in inc {
-            try!(write!(fmt, "\n  included from {}:{}", loc.file, loc.line));
+        try!(self.format_message(fmt));
+        self.format_included_from(fmt)
+    }
+}
+
+/// Longest part of a source line shown by `SyntaxError::render`
+const SNIPPET_WIDTH: usize = 100;
+
+/// Number of columns a tab is expanded to by `SyntaxError::render`
+const SNIPPET_TAB_WIDTH: usize = 4;
+
+impl SyntaxError {
+    fn format_message(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_nesting_too_deep() {
+            write!(
+                fmt,
+                "nesting too deep at \"{}\" line {} column {}",
+                self.file, self.original_line, self.original_column
+            )
+        } else {
+            try!(write!(
+                fmt,
+                "unexpected token at \"{}\" line {} column {}, expected ",
+                self.file, self.original_line, self.original_column
+            ));
+            self.format_expected(fmt)
+        }
+    }
+
+    fn format_included_from(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for &(ref file, line) in &self.included_from {
+            try!(write!(fmt, "\n  included from {}:{}", file, line));
         }
         Ok(())
     }
+
+    /// Format the error like `Display`, followed by the offending source line with the
+    /// unexpected token underlined
+    pub fn render(&self) -> String {
+        Snippet(self).to_string()
+    }
+
+    /// Write the same text as `render`
+    pub fn fmt_with_snippet(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        try!(self.format_message(fmt));
+
+        let offset = cmp::min(self.offset, self.source.len());
+        let (_, start) = loc::get_line_start(&self.source, offset);
+        let end = loc::get_line_end(&self.source, offset);
+        let line = &self.source[start..end];
+        let chars = line.chars().collect::<Vec<_>>();
+        let column = self.source[start..offset].chars().count();
+        let token = self
+            .source
+            .get(offset..start + line.len())
+            .map_or(0, token_length);
+
+        // show a window of the line around the error
+        let (from, to) = if chars.len() <= SNIPPET_WIDTH {
+            (0, chars.len())
+        } else {
+            let from = column.saturating_sub(SNIPPET_WIDTH / 2);
+            let from = cmp::min(from, chars.len() - SNIPPET_WIDTH);
+            (from, from + SNIPPET_WIDTH)
+        };
+
+        let mut text = String::new();
+        let mut marker = String::new();
+        if from > 0 {
+            text.push_str("...");
+            marker.push_str("   ");
+        }
+        for (i, &c) in chars[from..to].iter().enumerate() {
+            let i = from + i;
+            let width = if c == '\t' { SNIPPET_TAB_WIDTH } else { 1 };
+            if c == '\t' {
+                text.push_str(&" ".repeat(width));
+            } else {
+                text.push(c);
+            }
+            let m = if i < column {
+                ' '
+            } else if i == column {
+                '^'
+            } else if i < column + token {
+                '~'
+            } else {
+                continue;
+            };
+            marker.push(m);
+            for _ in 1..width {
+                marker.push(if m == '^' { '~' } else { m });
+            }
+        }
+        if column >= to {
+            marker.push('^');
+        }
+        if to < chars.len() {
+            text.push_str("...");
+        }
+
+        let number = self.original_line.to_string();
+        let gutter = " ".repeat(number.len());
+        try!(write!(fmt, "\n{} |", gutter));
+        try!(write!(fmt, "\n{} | {}", number, text.trim_right()));
+        try!(write!(fmt, "\n{} | {}", gutter, marker.trim_right()));
+        self.format_included_from(fmt)
+    }
+}
+
+// Length in characters of the token at the start of `s`, as far as the snippet is concerned
+fn token_length(s: &str) -> usize {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => 0,
+        Some(q) if q == '"' || q == '\'' => {
+            let mut n = 1;
+            let mut escape = false;
+            for c in chars {
+                n += 1;
+                if escape {
+                    escape = false;
+                } else if c == '\\' {
+                    escape = true;
+                } else if c == q {
+                    break;
+                }
+            }
+            n
+        }
+        Some(c) if c.is_alphanumeric() || c == '_' => s
+            .chars()
+            .take_while(|&c| c.is_alphanumeric() || c == '_')
+            .count(),
+        Some(_) => 1,
+    }
+}
+
+struct Snippet<'a>(&'a SyntaxError);
+
+impl<'a> fmt::Display for Snippet<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_with_snippet(fmt)
+    }
+}
+
+/// Source of preprocessed C text
+///
+/// Implement this trait to plug in a preprocessor other than an external command, for
+/// example an in-process implementation or a mock used in tests.
+pub trait Preprocessor {
+    /// Preprocess the file at `source`
+    fn preprocess(&self, source: &Path) -> io::Result<String>;
+
+    /// Preprocess the file at `source`, also returning messages printed by a successful run
+    ///
+    /// The default implementation calls `preprocess` and returns no messages.
+    fn preprocess_with_diagnostics(&self, source: &Path) -> io::Result<(String, Vec<Diagnostic>)> {
+        self.preprocess(source).map(|s| (s, Vec::new()))
+    }
+
+    /// Preprocess the text `source` held in memory, reported as file `name`
+    ///
+    /// The default implementation returns an error.
+    fn preprocess_str(&self, name: &str, source: &str) -> io::Result<String> {
+        let _ = source;
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!(
+                "cannot preprocess \"{}\": in-memory source not supported",
+                name
+            ),
+        ))
+    }
+
+    /// Preprocess the text `source` held in memory, also returning messages printed by a
+    /// successful run
+    ///
+    /// The default implementation calls `preprocess_str` and returns no messages.
+    fn preprocess_str_with_diagnostics(
+        &self,
+        name: &str,
+        source: &str,
+    ) -> io::Result<(String, Vec<Diagnostic>)> {
+        self.preprocess_str(name, source).map(|s| (s, Vec::new()))
+    }
+}
+
+/// Preprocessor running an external command as described by `Config`
+#[derive(Clone, Debug)]
+pub struct CommandPreprocessor {
+    config: Config,
+}
+
+impl CommandPreprocessor {
+    pub fn new(config: &Config) -> CommandPreprocessor {
+        CommandPreprocessor {
+            config: config.clone(),
+        }
+    }
+}
+
+impl Preprocessor for CommandPreprocessor {
+    fn preprocess(&self, source: &Path) -> io::Result<String> {
+        preprocess(&self.config, source).map(|p| p.0)
+    }
+
+    fn preprocess_str(&self, name: &str, source: &str) -> io::Result<String> {
+        preprocess_str(&self.config, name, source).map(|p| p.0)
+    }
+
+    fn preprocess_with_diagnostics(&self, source: &Path) -> io::Result<(String, Vec<Diagnostic>)> {
+        preprocess(&self.config, source)
+    }
+
+    fn preprocess_str_with_diagnostics(
+        &self,
+        name: &str,
+        source: &str,
+    ) -> io::Result<(String, Vec<Diagnostic>)> {
+        preprocess_str(&self.config, name, source)
+    }
 }
 
+/// Name of the source read from standard input, as used in diagnostics
+pub const STDIN_NAME: &'static str = "<stdin>";
+
 /// Parse a C file
+///
+/// A `source` of `-` reads the standard input of the current process, see `parse_stdin`.
 pub fn parse<P: AsRef<Path>>(config: &Config, source: P) -> Result<Parse, Error> {
-    let processed = match preprocess(config, source.as_ref()) {
-        Ok(s) => s,
-        Err(e) => return Err(Error::PreprocessorError(e)),
+    let source = source.as_ref();
+    if source == Path::new("-") {
+        return parse_stdin(config);
+    }
+    let entry = config
+        .cache_dir
+        .as_ref()
+        .and_then(|dir| cache::Entry::new(config, dir, source));
+    let entry = match entry {
+        Some(entry) => entry,
+        None => return parse_with(&CommandPreprocessor::new(config), config, source),
+    };
+
+    let (processed, diagnostics, stats) = match entry.load() {
+        Some((processed, diagnostics)) => {
+            (processed, diagnostics, CacheStats { hits: 1, misses: 0 })
+        }
+        None => {
+            let (processed, diagnostics) = match preprocess(config, source) {
+                Ok(p) => p,
+                Err(e) => return Err(Error::from_preprocessor(e)),
+            };
+            // failing to save the entry only costs time on the next run
+            let _ = entry.store(source, &processed, &diagnostics);
+            (processed, diagnostics, CacheStats { hits: 0, misses: 1 })
+        }
     };
 
-    Ok(try!(parse_preprocessed(config, processed)))
+    let mut parse = try!(parse_preprocessed(config, processed));
+    parse.cpp_diagnostics = diagnostics;
+    parse.cache = stats;
+    Ok(parse)
 }
 
-pub fn parse_preprocessed(config: &Config, source: String) -> Result<Parse, SyntaxError> {
-    let mut env = match config.flavor {
-        Flavor::StdC11 => Env::with_core(),
-        Flavor::GnuC11 => Env::with_gnu(),
-        Flavor::ClangC11 => Env::with_clang(),
+/// Delete preprocessed sources saved in `cache_dir` by `parse`, returning how many were removed
+pub fn purge_cache<P: AsRef<Path>>(cache_dir: P) -> io::Result<usize> {
+    cache::purge(cache_dir.as_ref())
+}
+
+/// Stack size of the `parse_all` worker threads
+const WORKER_STACK_SIZE: usize = 8 << 20;
+
+/// Parse many C files using a pool of `jobs` worker threads
+///
+/// Results are returned in the order of `files`. At most `jobs` preprocessor processes run at
+/// the same time, and a failure in one file does not affect the others.
+pub fn parse_all<I: IntoIterator<Item = PathBuf>>(
+    config: &Config,
+    files: I,
+    jobs: usize,
+) -> Vec<(PathBuf, Result<Parse, Error>)> {
+    let files = files.into_iter().collect::<Vec<_>>();
+    let count = files.len();
+    let jobs = cmp::max(1, cmp::min(jobs, count));
+
+    let config = Arc::new(config.clone());
+    let queue = Arc::new(Mutex::new(files.into_iter().enumerate()));
+    let (tx, rx) = mpsc::channel();
+
+    let workers = (0..jobs)
+        .map(|_| {
+            let config = config.clone();
+            let queue = queue.clone();
+            let tx = tx.clone();
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn(move || loop {
+                    let next = queue.lock().unwrap().next();
+                    let (i, path) = match next {
+                        Some(next) => next,
+                        None => return,
+                    };
+                    let result = parse(&config, &path);
+                    if tx.send((i, path, result)).is_err() {
+                        return;
+                    }
+                })
+                .expect("failed to spawn parser thread")
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+    for (i, path, result) in rx {
+        results[i] = Some((path, result));
+    }
+
+    for worker in workers {
+        if let Err(e) = worker.join() {
+            ::std::panic::resume_unwind(e);
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|r| r.expect("every file is parsed"))
+        .collect()
+}
+
+/// Parse C source read from the standard input of the current process
+///
+/// All of the input is read and decoded according to `config.encoding` before running the
+/// preprocessor, which gets it over a pipe like `parse_str`. Diagnostics name the source
+/// `<stdin>`.
+pub fn parse_stdin(config: &Config) -> Result<Parse, Error> {
+    parse_stdin_with(&CommandPreprocessor::new(config), config)
+}
+
+/// Parse C source read from the standard input of the current process using a custom
+/// preprocessor
+pub fn parse_stdin_with(preprocessor: &Preprocessor, config: &Config) -> Result<Parse, Error> {
+    let stdin = io::stdin();
+    let input = stdin.lock();
+    parse_reader_with(preprocessor, config, input)
+}
+
+fn parse_reader_with<R: Read>(
+    preprocessor: &Preprocessor,
+    config: &Config,
+    input: R,
+) -> Result<Parse, Error> {
+    let source = try!(read_source(config, input));
+    parse_str_with(preprocessor, config, STDIN_NAME, &source)
+}
+
+/// Parse C source text held in memory
+///
+/// The text is piped to the preprocessor on its standard input. `name` is used as the file
+/// name in line markers, and thus in diagnostics, instead of `<stdin>`.
+pub fn parse_str(config: &Config, name: &str, source: &str) -> Result<Parse, Error> {
+    parse_str_with(&CommandPreprocessor::new(config), config, name, source)
+}
+
+/// Parse a C file using a custom preprocessor
+///
+/// The preprocessor command options in `config` are ignored, only the flavor is used. A
+/// `source` of `-` reads the standard input, see `parse_stdin_with`.
+pub fn parse_with<P: AsRef<Path>>(
+    preprocessor: &Preprocessor,
+    config: &Config,
+    source: P,
+) -> Result<Parse, Error> {
+    if source.as_ref() == Path::new("-") {
+        return parse_stdin_with(preprocessor, config);
+    }
+    let (processed, diagnostics) = match preprocessor.preprocess_with_diagnostics(source.as_ref()) {
+        Ok(p) => p,
+        Err(e) => return Err(Error::from_preprocessor(e)),
+    };
+
+    let mut parse = try!(parse_preprocessed(config, processed));
+    parse.cpp_diagnostics = diagnostics;
+    Ok(parse)
+}
+
+/// Parse C source text held in memory using a custom preprocessor
+pub fn parse_str_with(
+    preprocessor: &Preprocessor,
+    config: &Config,
+    name: &str,
+    source: &str,
+) -> Result<Parse, Error> {
+    let (processed, diagnostics) = match preprocessor.preprocess_str_with_diagnostics(name, source)
+    {
+        Ok(p) => p,
+        Err(e) => return Err(Error::from_preprocessor(e)),
     };
 
+    let mut parse = try!(parse_preprocessed(config, processed));
+    parse.cpp_diagnostics = diagnostics;
+    Ok(parse)
+}
+
+/// Parse a preprocessed source
+///
+/// Line splices left in `source` are accepted between tokens and inside literals. A splice
+/// inside an identifier, constant or punctuator is a syntax error.
+pub fn parse_preprocessed(config: &Config, source: String) -> Result<Parse, SyntaxError> {
+    let mut env = flavor_env(config);
+
     match translation_unit(&source, &mut env) {
         Ok(unit) => Ok(Parse {
+            comments: if config.keep_comments {
+                comments::collect(&source)
+            } else {
+                Vec::new()
+            },
             source: source,
             unit: unit,
+            cpp_diagnostics: Vec::new(),
+            cache: CacheStats::default(),
         }),
-        Err(err) => Err(SyntaxError {
-            source: source,
-            line: err.line,
-            column: err.column,
-            offset: err.offset,
-            expected: err.expected,
-        }),
+        Err(err) => Err(syntax_error(
+            &source,
+            err,
+            &mut env,
+            Some(&original_dir(config)),
+        )),
     }
 }
 
-fn preprocess(config: &Config, source: &Path) -> io::Result<String> {
-    let mut cmd = Command::new(&config.cpp_command);
+/// Parse a preprocessed source read from `reader`
+///
+/// The input is decoded according to `config.encoding`; in strict `Utf8` mode an invalid
+/// sequence is reported as `Error::InvalidEncoding`. `name` is used as the file name of syntax
+/// errors when the input has no line markers.
+pub fn parse_preprocessed_reader<R: Read>(
+    config: &Config,
+    name: &str,
+    reader: R,
+) -> Result<Parse, Error> {
+    let source = try!(read_source(config, reader));
+    parse_preprocessed(config, source).map_err(|mut e| {
+        if e.file.is_empty() && e.included_from.is_empty() {
+            e.file = name.to_owned();
+        }
+        Error::SyntaxError(e)
+    })
+}
 
-    for item in &config.cpp_options {
-        cmd.arg(item);
+// Read all of `reader`, decoded according to `config.encoding`
+fn read_source<R: Read>(config: &Config, mut reader: R) -> Result<String, Error> {
+    if config.encoding == Encoding::Utf8 {
+        return read_utf8(reader);
+    }
+    let mut bytes = Vec::new();
+    if let Err(e) = reader.read_to_end(&mut bytes) {
+        return Err(Error::PreprocessorError(e));
+    }
+    decode(bytes, config.encoding).map_err(Error::PreprocessorError)
+}
+
+// Read all of `reader`, checking each chunk for valid UTF-8 as it arrives
+fn read_utf8<R: Read>(mut reader: R) -> Result<String, Error> {
+    let mut text = String::new();
+    let mut buf = [0; 8192];
+    // incomplete sequence at the end of the previous chunk
+    let mut pending = Vec::new();
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::PreprocessorError(e)),
+        };
+        pending.extend_from_slice(&buf[..n]);
+        let valid = match str::from_utf8(&pending) {
+            Ok(s) => s.len(),
+            Err(e) => match e.error_len() {
+                Some(_) => {
+                    return Err(Error::InvalidEncoding {
+                        byte_offset: text.len() + e.valid_up_to(),
+                    })
+                }
+                None => e.valid_up_to(),
+            },
+        };
+        text.push_str(str::from_utf8(&pending[..valid]).expect("validated above"));
+        pending.drain(..valid);
     }
+    if !pending.is_empty() {
+        return Err(Error::InvalidEncoding {
+            byte_offset: text.len(),
+        });
+    }
+    Ok(text)
+}
 
-    cmd.arg(source);
+/// Maximum number of syntax errors reported by `parse_preprocessed_recovering`
+pub const MAX_RECOVERED_ERRORS: usize = 100;
 
-    let output = try!(cmd.output());
+/// Parse a preprocessed source, continuing after syntax errors
+///
+/// When an external declaration fails to parse, text is skipped up to the next `;` or the `}`
+/// closing a brace at file scope, whichever comes first after the error. Skipped text is
+/// represented by `ExternalDeclaration::Error` in the returned unit. After
+/// `MAX_RECOVERED_ERRORS` errors the rest of the input is skipped as a whole.
+pub fn parse_preprocessed_recovering(config: &Config, source: String) -> (Parse, Vec<SyntaxError>) {
+    let mut env = flavor_env(config);
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    let mut pos = 0;
 
-    if output.status.success() {
-        match String::from_utf8(output.stdout) {
-            Ok(s) => Ok(s),
-            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+    while pos < source.len() {
+        // each part of the source is parsed once, up to the declaration that fails
+        env.nesting_exceeded = None;
+        let (decls, end, err) = translation_unit_from(&source, pos, &mut env);
+        items.extend(decls);
+        pos = cmp::max(pos, end);
+        let err = match err {
+            Some(err) => err,
+            None => break,
+        };
+        let err = syntax_error(&source, err, &mut env, Some(&original_dir(config)));
+        let resume = if errors.len() + 1 == MAX_RECOVERED_ERRORS {
+            source.len()
+        } else {
+            resume_offset(&source, pos, err.offset)
+        };
+        errors.push(err);
+        items.push(Node::new(
+            ExternalDeclaration::Error,
+            Span::span(pos, resume),
+        ));
+        pos = resume;
+    }
+
+    let parse = Parse {
+        comments: if config.keep_comments {
+            comments::collect(&source)
+        } else {
+            Vec::new()
+        },
+        source: source,
+        unit: TranslationUnit(items),
+        cpp_diagnostics: Vec::new(),
+        cache: CacheStats::default(),
+    };
+    (parse, errors)
+}
+
+// Offset just past the first `;` or file-scope `}` at or after `error`, scanning from `start`
+fn resume_offset(source: &str, start: usize, error: usize) -> usize {
+    let b = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < b.len() {
+        match b[i] {
+            b'"' | b'\'' => {
+                let quote = b[i];
+                i += 1;
+                while i < b.len() && b[i] != quote && b[i] != b'\n' {
+                    i += if b[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'/' if b[i..].starts_with(b"/*") => {
+                i += b[i..]
+                    .windows(2)
+                    .position(|w| w == b"*/")
+                    .map_or(b.len(), |n| n + 1);
+            }
+            b'/' if b[i..].starts_with(b"//") => {
+                i += b[i..]
+                    .iter()
+                    .position(|&c| c == b'\n')
+                    .unwrap_or(b.len() - i);
+            }
+            b'{' | b'(' | b'[' => depth += 1,
+            b'}' | b')' | b']' => {
+                depth = depth.saturating_sub(1);
+                if b[i] == b'}' && depth == 0 && i >= error {
+                    return i + 1;
+                }
+            }
+            b';' if depth == 0 && i >= error => return i + 1,
+            _ => {}
         }
-    } else {
-        match String::from_utf8(output.stderr) {
-            Ok(s) => Err(io::Error::new(io::ErrorKind::Other, s)),
-            Err(_) => Err(io::Error::new(
-                io::ErrorKind::Other,
-                "cpp error contains invalid utf-8",
-            )),
+        i += 1;
+    }
+    b.len()
+}
+
+// Directory that relative file names in line markers are relative to
+fn original_dir(config: &Config) -> PathBuf {
+    config.cpp_current_dir.clone().unwrap_or_default()
+}
+
+fn flavor_env(config: &Config) -> Env {
+    let mut env = env_for_flavor(config.flavor);
+    env.max_nesting = config.max_nesting;
+    env.digraphs = !config.no_digraphs;
+    env.implicit_int = config.implicit_int;
+    env.ms_extensions |= config.ms_extensions;
+    env
+}
+
+/// Parser state with the language options and keywords of `flavor`
+#[doc(hidden)]
+pub fn env_for_flavor(flavor: Flavor) -> Env {
+    match flavor {
+        Flavor::StdC89 => Env::with_standard(Standard::C89),
+        Flavor::StdC99 => Env::with_standard(Standard::C99),
+        Flavor::StdC11 => Env::with_core(),
+        Flavor::StdC17 => Env::with_standard(Standard::C17),
+        Flavor::StdC23 => Env::with_standard(Standard::C23),
+        Flavor::GnuC11 => Env::with_gnu(),
+        Flavor::GnuC23 => Env::new(Standard::C23, true, false),
+        Flavor::ClangC11 => Env::with_clang(),
+        Flavor::ClangC23 => Env::new(Standard::C23, true, true),
+        Flavor::MsvcC11 => Env::with_msvc(),
+        Flavor::Cuda => Env::with_cuda(),
+        Flavor::OpenCl => Env::with_opencl(),
+    }
+}
+
+/// Parse a single expression, such as the body of an object-like macro
+///
+/// The whole of `source` must be consumed, apart from surrounding whitespace. Identifiers
+/// declared as typedef names in `env` are treated as types, so `(foo)(x)` is a cast or a call
+/// depending on its contents.
+pub fn parse_expression(source: &str, env: &mut Env) -> Result<Node<Expression>, SyntaxError> {
+    parse_fragment(source, env, fragment_expression).map(|e| *e)
+}
+
+/// Parse a single statement
+///
+/// See `parse_expression` for how `source` and `env` are treated.
+pub fn parse_statement(source: &str, env: &mut Env) -> Result<Node<Statement>, SyntaxError> {
+    parse_fragment(source, env, fragment_statement).map(|s| *s)
+}
+
+/// Parse a single declaration, including the terminating semicolon
+///
+/// Typedef names declared by `source` are added to `env`. See `parse_expression` for how
+/// `source` is treated.
+pub fn parse_declaration(source: &str, env: &mut Env) -> Result<Node<Declaration>, SyntaxError> {
+    parse_fragment(source, env, fragment_declaration)
+}
+
+/// Parse a type name, as found in a cast or `sizeof`
+///
+/// See `parse_expression` for how `source` and `env` are treated.
+pub fn parse_type_name(source: &str, env: &mut Env) -> Result<Node<TypeName>, SyntaxError> {
+    parse_fragment(source, env, fragment_type_name)
+}
+
+fn parse_fragment<T>(
+    source: &str,
+    env: &mut Env,
+    rule: fn(&str, &mut Env) -> Result<T, ParseError>,
+) -> Result<T, SyntaxError> {
+    env.nesting_exceeded = None;
+    rule(source, env).map_err(|err| syntax_error(source, err, env, None))
+}
+
+// Input nested too deeply is reported where the limit was first hit, not where parsing gave up.
+// Original files are looked up relative to `dir` to find the original column, if given.
+fn syntax_error(source: &str, err: ParseError, env: &mut Env, dir: Option<&Path>) -> SyntaxError {
+    let (offset, expected) = match env.nesting_exceeded.take() {
+        Some(offset) => (offset, Some(NESTING_TOO_DEEP).into_iter().collect()),
+        None => (err.offset, err.expected),
+    };
+    let (line, start) = loc::get_line_start(source, offset);
+    let column_utf8 = source[start..offset].chars().count() + 1;
+    let (loc, inc) = loc::get_location_for_offset(source, offset);
+    let file = loc::unescape_file_name(loc.file);
+
+    let end = loc::get_line_end(source, offset);
+    let original_column = match dir {
+        Some(dir) if !file.is_empty() => {
+            original_column(&dir.join(&file), loc.line, &source[start..end], column_utf8)
         }
+        _ => None,
+    };
+
+    SyntaxError {
+        source: source.to_owned(),
+        line: line,
+        column: offset - start + 1,
+        column_utf8: column_utf8,
+        offset: offset,
+        expected: expected,
+        original_line: loc.line,
+        original_column: original_column.unwrap_or(column_utf8),
+        file: file,
+        included_from: inc
+            .iter()
+            .map(|l| (loc::unescape_file_name(l.file), l.line))
+            .collect(),
+    }
+}
+
+// Largest original file read to find the original column of a syntax error
+const MAX_ORIGINAL_SIZE: u64 = 16 << 20;
+
+// Column in line `number` of file `path` corresponding to `column` in the preprocessed `line`,
+// provided both lines consist of the same tokens. Line markers can name any file, so only the
+// start of a regular file is read: a FIFO or device would block or never end.
+fn original_column(path: &Path, number: usize, line: &str, column: usize) -> Option<usize> {
+    match fs::metadata(path) {
+        Ok(ref meta) if meta.is_file() => (),
+        _ => return None,
+    }
+    let mut bytes = Vec::new();
+    if File::open(path)
+        .and_then(|f| f.take(MAX_ORIGINAL_SIZE).read_to_end(&mut bytes))
+        .is_err()
+    {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    let original = match text.lines().nth(number.saturating_sub(1)) {
+        Some(l) => l,
+        None => return None,
+    };
+
+    let tokens = line_tokens(line);
+    let original_tokens = line_tokens(original);
+    if tokens.len() != original_tokens.len()
+        || tokens.iter().zip(&original_tokens).any(|(a, b)| a.1 != b.1)
+    {
+        return None;
+    }
+    match tokens.iter().position(|t| t.0 + 1 >= column) {
+        Some(i) => Some(original_tokens[i].0 + 1),
+        None => Some(original.trim_right().chars().count() + 1),
+    }
+}
+
+// Tokens of a line with their character positions, roughly as seen by the preprocessor
+fn line_tokens(line: &str) -> Vec<(usize, String)> {
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            break;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            continue;
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i += 1;
+        } else if c.is_alphanumeric() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+        let end = cmp::min(i, chars.len());
+        tokens.push((start, chars[start..end].iter().cloned().collect()));
+    }
+    tokens
+}
+
+fn preprocess(config: &Config, source: &Path) -> io::Result<(String, Vec<Diagnostic>)> {
+    if config.cpp_kind == PreprocessorKind::Internal {
+        return internal_preprocess_file(config, source);
+    }
+
+    let mut cmd = command(config);
+
+    cmd.args(&preprocessor_args(config));
+    cmd.arg(source);
+
+    let output = try!(run_preprocessor(config, cmd, None));
+
+    if config.cpp_kind == PreprocessorKind::Msvc {
+        msvc_output(config, output)
+    } else {
+        preprocessor_output(config, output)
+    }
+}
+
+fn preprocess_str(
+    config: &Config,
+    name: &str,
+    source: &str,
+) -> io::Result<(String, Vec<Diagnostic>)> {
+    if config.cpp_kind == PreprocessorKind::Internal {
+        return internal_preprocess_str(config, name, source);
+    }
+
+    if config.cpp_kind == PreprocessorKind::Msvc {
+        return preprocess_temp_file(config, name, source);
+    }
+
+    let mut cmd = command(config);
+
+    cmd.args(&preprocessor_args(config));
+    cmd.arg("-x").arg("c").arg("-");
+
+    let input = format!("#line 1 \"{}\"\n{}", escape_line_marker(name), source);
+    let output = try!(run_preprocessor(config, cmd, Some(input)));
+
+    preprocessor_output(config, output)
+}
+
+#[cfg(not(feature = "internal-preprocessor"))]
+fn internal_preprocess_file(_: &Config, _: &Path) -> io::Result<(String, Vec<Diagnostic>)> {
+    Err(internal_preprocessor_missing())
+}
+
+#[cfg(not(feature = "internal-preprocessor"))]
+fn internal_preprocess_str(_: &Config, _: &str, _: &str) -> io::Result<(String, Vec<Diagnostic>)> {
+    Err(internal_preprocessor_missing())
+}
+
+#[cfg(not(feature = "internal-preprocessor"))]
+fn internal_preprocessor_missing() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "built without internal-preprocessor")
+}
+
+/// Preprocessor command with the environment and working directory from `config`
+fn command(config: &Config) -> Command {
+    let mut cmd = Command::new(&config.cpp_command);
+    if config.cpp_env_clear {
+        cmd.env_clear();
+    }
+    for &(ref key, ref value) in &config.cpp_env {
+        cmd.env(key, value);
+    }
+    if let Some(ref dir) = config.cpp_current_dir {
+        cmd.current_dir(dir);
+    }
+    cmd
+}
+
+/// Run the preprocessor, optionally feeding `input` to its stdin
+///
+/// The process is killed when `config.cpp_timeout` expires or `config.cancel` is tripped.
+fn run_preprocessor(
+    config: &Config,
+    mut cmd: Command,
+    input: Option<String>,
+) -> io::Result<Output> {
+    cmd.stdin(if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    });
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            // a missing working directory is reported the same way as a missing command
+            let dir_exists = config.cpp_current_dir.as_ref().map_or(true, |d| d.is_dir());
+            if e.kind() != io::ErrorKind::NotFound || !dir_exists {
+                return Err(e);
+            }
+            let missing = MissingCommand {
+                command: config.cpp_command.clone(),
+                error: e,
+            };
+            return Err(io::Error::new(io::ErrorKind::NotFound, missing));
+        }
+    };
+
+    // Feed the input from a separate thread, so that a preprocessor that fills up its output
+    // pipe before consuming all of its input does not deadlock us.
+    let writer = input.map(|input| {
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        thread::spawn(move || stdin.write_all(input.as_bytes()))
+    });
+    let stdout = Drain::new(child.stdout.take().expect("stdout is piped"));
+    let stderr = Drain::new(child.stderr.take().expect("stderr is piped"));
+
+    let deadline = config.cpp_timeout.map(|t| Instant::now() + t);
+    let status = if deadline.is_none() && config.cancel.is_none() {
+        try!(child.wait())
+    } else {
+        loop {
+            if let Some(status) = try!(child.try_wait()) {
+                break status;
+            }
+
+            let kind = if config.cancel.as_ref().map_or(false, |c| c.is_cancelled()) {
+                io::ErrorKind::Interrupted
+            } else if deadline.map_or(false, |d| Instant::now() >= d) {
+                io::ErrorKind::TimedOut
+            } else {
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            };
+
+            let _ = child.kill();
+            let _ = child.wait();
+
+            // Don't wait for the readers to finish, the pipes may still be held open by
+            // processes the preprocessor has spawned.
+            let partial = PartialOutput {
+                stdout: String::from_utf8_lossy(&stdout.snapshot()).into_owned(),
+                stderr: String::from_utf8_lossy(&stderr.snapshot()).into_owned(),
+            };
+            return Err(io::Error::new(kind, partial));
+        }
+    };
+
+    let stdout = try!(stdout.finish());
+    let stderr = try!(stderr.finish());
+
+    if let Some(writer) = writer {
+        match writer.join() {
+            Ok(Ok(())) => {}
+            // Preprocessor exited early, its own diagnostics explain why.
+            Ok(Err(ref e)) if e.kind() == io::ErrorKind::BrokenPipe => {}
+            Ok(Err(e)) => return Err(e),
+            Err(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "stdin writer panicked",
+                ))
+            }
+        }
+    }
+
+    Ok(Output {
+        status: status,
+        stdout: stdout,
+        stderr: stderr,
+    })
+}
+
+/// Background thread collecting everything written to a pipe
+struct Drain {
+    buf: Arc<Mutex<Vec<u8>>>,
+    thread: thread::JoinHandle<io::Result<()>>,
+}
+
+impl Drain {
+    fn new<R: Read + Send + 'static>(mut pipe: R) -> Drain {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let shared = buf.clone();
+        let thread = thread::spawn(move || {
+            let mut chunk = [0; 4096];
+            loop {
+                match pipe.read(&mut chunk) {
+                    Ok(0) => return Ok(()),
+                    Ok(n) => shared.lock().unwrap().extend_from_slice(&chunk[..n]),
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+        });
+        Drain {
+            buf: buf,
+            thread: thread,
+        }
+    }
+
+    /// Data read so far
+    fn snapshot(&self) -> Vec<u8> {
+        self.buf.lock().unwrap().clone()
+    }
+
+    /// Wait until the pipe is closed and return all data
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self.thread.join() {
+            Ok(r) => try!(r),
+            Err(_) => return Err(io::Error::new(io::ErrorKind::Other, "reader panicked")),
+        }
+        let buf = self.buf.lock().unwrap();
+        Ok(buf.clone())
+    }
+}
+
+/// Preprocess in-memory text through a temporary file, for preprocessors that can't read stdin
+fn preprocess_temp_file(
+    config: &Config,
+    name: &str,
+    source: &str,
+) -> io::Result<(String, Vec<Diagnostic>)> {
+    use std::fs::{remove_file, File};
+    use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+    static COUNTER: AtomicUsize = ATOMIC_USIZE_INIT;
+
+    let path = ::std::env::temp_dir().join(format!(
+        "lang-c-{}-{}.c",
+        ::std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+
+    let input = format!("#line 1 \"{}\"\n{}", escape_line_marker(name), source);
+    let result = File::create(&path)
+        .and_then(|mut f| f.write_all(input.as_bytes()))
+        .and_then(|_| preprocess(config, &path));
+    let _ = remove_file(&path);
+    result
+}
+
+fn escape_line_marker(name: &str) -> String {
+    let mut s = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == '"' || c == '\\' {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+    s
+}
+
+fn preprocessor_output(config: &Config, output: Output) -> io::Result<(String, Vec<Diagnostic>)> {
+    if output.status.success() {
+        let text = try!(decode(output.stdout, config.encoding));
+        Ok((text, diagnostics(&String::from_utf8_lossy(&output.stderr))))
+    } else {
+        match String::from_utf8(output.stderr) {
+            Ok(s) => Err(io::Error::new(io::ErrorKind::Other, s)),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "cpp error contains invalid utf-8",
+            )),
+        }
+    }
+}
+
+fn msvc_output(config: &Config, output: Output) -> io::Result<(String, Vec<Diagnostic>)> {
+    let stdout = try!(decode(output.stdout, config.encoding));
+    let (text, mut messages) = split_msvc_output(&stdout);
+    messages.push_str(&String::from_utf8_lossy(&output.stderr));
+
+    if output.status.success() {
+        Ok((text, diagnostics(&messages)))
+    } else {
+        Err(io::Error::new(io::ErrorKind::Other, messages))
+    }
+}
+
+/// Convert preprocessed source to a string according to `encoding`
+///
+/// Byte values in literals are preserved as described in `Encoding`.
+#[doc(hidden)]
+pub fn decode(bytes: Vec<u8>, encoding: Encoding) -> io::Result<String> {
+    let bytes = match String::from_utf8(bytes) {
+        Ok(s) => {
+            if s.is_ascii() || encoding == Encoding::Utf8 || encoding == Encoding::Utf8Lossy {
+                return Ok(s);
+            }
+            s.into_bytes()
+        }
+        Err(e) => {
+            if encoding == Encoding::Utf8 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+            e.into_bytes()
+        }
+    };
+
+    let mut text = String::with_capacity(bytes.len() + bytes.len() / 4);
+    let mut quote = None;
+    let mut escaped = false;
+    // Inside a pp-number, where `'` is a digit separator
+    let mut number = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if b >= 0x80 {
+            let (c, n) = decode_char(&bytes[i..], encoding);
+            match (c, quote) {
+                (Some(c), None) => text.push(c),
+                (Some(c), Some(_)) if encoding == Encoding::Utf8Lossy => text.push(c),
+                (_, Some(_)) => {
+                    for &b in &bytes[i..i + n] {
+                        text.push_str(&format!("\\{:03o}", b));
+                    }
+                }
+                (None, None) => text.push('\u{fffd}'),
+            }
+            i += n;
+            escaped = false;
+            continue;
+        }
+
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == q || b == b'\n' {
+                quote = None;
+            }
+        } else if b == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            let n = bytes[i + 2..]
+                .windows(2)
+                .position(|w| w == b"*/")
+                .map_or(bytes.len(), |n| i + n + 4);
+            decode_text(&mut text, &bytes[i..n], encoding);
+            i = n;
+            continue;
+        } else if b == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            let n = bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |n| i + n);
+            decode_text(&mut text, &bytes[i..n], encoding);
+            i = n;
+            continue;
+        } else if b == b'"' || (b == b'\'' && !number) {
+            quote = Some(b);
+        } else if b.is_ascii_digit() && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+            number = true;
+        } else if !is_ident_byte(b) && b != b'.' && b != b'\'' {
+            number = false;
+        }
+
+        text.push(b as char);
+        i += 1;
+    }
+
+    Ok(text)
+}
+
+// Decode text outside of literals, such as a comment
+fn decode_text(text: &mut String, mut bytes: &[u8], encoding: Encoding) {
+    while !bytes.is_empty() {
+        if bytes[0] < 0x80 {
+            text.push(bytes[0] as char);
+            bytes = &bytes[1..];
+        } else {
+            let (c, n) = decode_char(bytes, encoding);
+            text.push(c.unwrap_or('\u{fffd}'));
+            bytes = &bytes[n..];
+        }
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+// Decode one non-ASCII character, returning `None` if it is not valid, and its length in bytes
+fn decode_char(bytes: &[u8], encoding: Encoding) -> (Option<char>, usize) {
+    const WINDOWS_1252: [u16; 32] = [
+        0x20ac, 0x81, 0x201a, 0x192, 0x201e, 0x2026, 0x2020, 0x2021, 0x2c6, 0x2030, 0x160, 0x2039,
+        0x152, 0x8d, 0x17d, 0x8f, 0x90, 0x2018, 0x2019, 0x201c, 0x201d, 0x2022, 0x2013, 0x2014,
+        0x2dc, 0x2122, 0x161, 0x203a, 0x153, 0x9d, 0x17e, 0x178,
+    ];
+
+    let b = bytes[0];
+    match encoding {
+        Encoding::Latin1 => (Some(b as char), 1),
+        Encoding::Windows1252 if b < 0xa0 => (
+            ::std::char::from_u32(WINDOWS_1252[b as usize - 0x80] as u32),
+            1,
+        ),
+        Encoding::Windows1252 => (Some(b as char), 1),
+        _ => {
+            let n = match b {
+                0xc2...0xdf => 2,
+                0xe0...0xef => 3,
+                0xf0...0xf4 => 4,
+                _ => return (None, 1),
+            };
+            match bytes.get(..n).map(::std::str::from_utf8) {
+                Some(Ok(s)) => (s.chars().next(), n),
+                _ => (None, 1),
+            }
+        }
+    }
+}
+
+/// Separate preprocessed text from the banner and diagnostics `cl.exe` mixes into its stdout
+fn split_msvc_output(stdout: &str) -> (String, String) {
+    let mut text = String::with_capacity(stdout.len());
+    let mut diagnostics = String::new();
+    let mut started = false;
+
+    for line in stdout.split_terminator('\n') {
+        if is_msvc_diagnostic(line) {
+            diagnostics.push_str(line.trim_right());
+            diagnostics.push('\n');
+        } else if started || line.starts_with("#line ") {
+            started = true;
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    (text, diagnostics)
+}
+
+// `file.c(12): fatal error C1083: ...` or `cl : Command line warning D9002 : ...`
+fn is_msvc_diagnostic(line: &str) -> bool {
+    if line.starts_with("cl : ") {
+        return true;
+    }
+
+    let n = match line.find("): ") {
+        Some(n) => n,
+        None => return false,
+    };
+    let pos = match line[..n].rfind('(') {
+        Some(p) => &line[p + 1..n],
+        None => return false,
+    };
+    if pos.is_empty() || !pos.bytes().all(|c| c.is_ascii_digit() || c == b',') {
+        return false;
+    }
+
+    let rest = &line[n + 3..];
+    rest.starts_with("error ")
+        || rest.starts_with("fatal error ")
+        || rest.starts_with("warning ")
+        || rest.starts_with("note: ")
+}
+
+/// Command line options for the preprocessor, excluding the source file
+///
+/// Each path and macro is passed as a separate argument, so no quoting is necessary.
+fn preprocessor_args(config: &Config) -> Vec<OsString> {
+    let mut args: Vec<OsString> = Vec::new();
+
+    for item in &config.cpp_options {
+        args.push(item.into());
+    }
+
+    if let Some(std) = std_option(config) {
+        let prefix = &std[..5];
+        if !config.no_std_flag && !config.cpp_options.iter().any(|o| o.starts_with(prefix)) {
+            args.push(std.into());
+        }
+    }
+
+    if config.keep_comments {
+        // `-CC` also keeps comments from macro definitions in their expansions
+        args.push(if config.cpp_kind == PreprocessorKind::Msvc {
+            "/C".into()
+        } else {
+            "-CC".into()
+        });
+    }
+
+    for dir in &config.include_dirs {
+        args.push("-I".into());
+        args.push(dir.into());
+    }
+
+    for m in &config.macros {
+        match *m {
+            Macro::Define(ref name, None) => {
+                args.push("-D".into());
+                args.push(name.into());
+            }
+            Macro::Define(ref name, Some(ref value)) => {
+                args.push("-D".into());
+                args.push(format!("{}={}", name, value).into());
+            }
+            Macro::Undefine(ref name) => {
+                args.push("-U".into());
+                args.push(name.into());
+            }
+        }
+    }
+
+    args
+}
+
+/// Option selecting the language standard of a flavor
+///
+/// gcc and clang accept the same spellings. C23 is spelled `c2x`, which unlike `c23` is also
+/// understood by gcc before 14 and clang before 18. cl.exe has no options for C89, C99 and the
+/// GNU flavors, so nothing is passed. The MSVC flavor keeps the default dialect of cl.exe, as
+/// `/std:c11` also switches to the conforming preprocessor that older SDK headers break with.
+/// CUDA sources are C++ to the preprocessor and OpenCL selects its version with `-cl-std=`, so
+/// no C standard is passed for them either.
+fn std_option(config: &Config) -> Option<&'static str> {
+    match (config.cpp_kind, config.flavor) {
+        (PreprocessorKind::Gcc, Flavor::StdC89) => Some("-std=c89"),
+        (PreprocessorKind::Gcc, Flavor::StdC99) => Some("-std=c99"),
+        (PreprocessorKind::Gcc, Flavor::StdC11) => Some("-std=c11"),
+        (PreprocessorKind::Gcc, Flavor::StdC17) => Some("-std=c17"),
+        (PreprocessorKind::Gcc, Flavor::StdC23) => Some("-std=c2x"),
+        (PreprocessorKind::Gcc, Flavor::GnuC11) => Some("-std=gnu11"),
+        (PreprocessorKind::Gcc, Flavor::GnuC23) => Some("-std=gnu2x"),
+        (PreprocessorKind::Gcc, Flavor::ClangC11) => Some("-std=gnu11"),
+        (PreprocessorKind::Gcc, Flavor::ClangC23) => Some("-std=gnu2x"),
+        (PreprocessorKind::Msvc, Flavor::StdC11) => Some("/std:c11"),
+        (PreprocessorKind::Msvc, Flavor::StdC17) => Some("/std:c17"),
+        (PreprocessorKind::Msvc, Flavor::StdC23) => Some("/std:clatest"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+fn test_dir(name: &str) -> PathBuf {
+    use std::fs;
+    let dir = ::std::env::temp_dir().join(format!("lang-c-{}-{}", name, ::std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("failed to create test directory");
+    dir
+}
+
+#[test]
+fn test_parse_str() {
+    let mut source = String::from("#include <stddef.h>\n");
+    for i in 0..2000 {
+        source.push_str(&format!("size_t var_{} = sizeof(int) * {};\n", i, i));
+    }
+
+    let config = Config::with_gcc();
+    let unit = parse_str(&config, "generated.c", &source)
+        .expect("parse failed")
+        .unit;
+    assert!(unit.0.len() > 2000);
+
+    source.push_str("int broken = ;\n");
+    let err = match parse_str(&config, "generated \"source\".c", &source) {
+        Err(Error::SyntaxError(e)) => e,
+        r => panic!("unexpected result: {:?}", r.map(|p| p.unit)),
+    };
+    let (loc, inc) = err.get_location();
+    assert_eq!(loc.file, "generated \\\"source\\\".c");
+    assert_eq!(loc.line, 2002);
+    assert!(inc.is_empty());
+}
+
+#[cfg(feature = "dev-serde")]
+#[test]
+fn test_serde_round_trip() {
+    let mut source = String::new();
+    for header in &[
+        "stdio.h", "stdlib.h", "string.h", "math.h", "signal.h", "time.h",
+    ] {
+        source.push_str(&format!("#include <{}>\n", header));
+    }
+    source.push_str("int main(int argc, char **argv) { return argc > 1 ? atoi(argv[1]) : 0; }\n");
+
+    let unit = parse_str(&Config::with_gcc(), "round-trip.c", &source)
+        .expect("parse failed")
+        .unit;
+    assert!(unit.0.len() > 500);
+
+    let json = ::serde_json::to_string(&unit).expect("serialize failed");
+    let back: TranslationUnit = ::serde_json::from_str(&json).expect("deserialize failed");
+    assert_eq!(back, unit);
+    assert_eq!(::serde_json::to_string(&back).unwrap(), json);
+}
+
+#[cfg(test)]
+struct StripComments;
+
+#[cfg(test)]
+impl Preprocessor for StripComments {
+    fn preprocess(&self, source: &Path) -> io::Result<String> {
+        use std::fs::File;
+
+        let mut text = String::new();
+        try!(try!(File::open(source)).read_to_string(&mut text));
+        self.preprocess_str(&source.to_string_lossy(), &text)
+    }
+
+    fn preprocess_str(&self, _name: &str, source: &str) -> io::Result<String> {
+        let mut out = String::with_capacity(source.len());
+        let mut rest = source;
+        while let Some(i) = rest.find("/*") {
+            out.push_str(&rest[..i]);
+            out.push(' ');
+            rest = match rest[i + 2..].find("*/") {
+                Some(j) => &rest[i + 2 + j + 2..],
+                None => return Err(io::Error::new(io::ErrorKind::Other, "unterminated comment")),
+            };
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+#[test]
+fn test_parse_with() {
+    use std::fs::File;
+
+    let config = Config::with_gcc();
+    let source = "int /* first */ a;\n/* second */ int b;\n";
+
+    let parse = parse_str_with(&StripComments, &config, "test.c", source).expect("parse failed");
+    assert_eq!(parse.source, "int   a;\n  int b;\n");
+    assert_eq!(parse.unit.0.len(), 2);
+
+    let path = test_dir("parse-with").join("test.c");
+    File::create(&path)
+        .and_then(|mut f| f.write_all(source.as_bytes()))
+        .unwrap();
+    let parse = parse_with(&StripComments, &config, &path).expect("parse failed");
+    assert_eq!(parse.unit.0.len(), 2);
+
+    match parse_str_with(&StripComments, &config, "test.c", "int /* a;") {
+        Err(Error::PreprocessorError(_)) => {}
+        r => panic!("unexpected result: {:?}", r.map(|p| p.unit)),
+    }
+}
+
+#[test]
+fn test_split_msvc_output() {
+    const STDOUT: &'static str = "main.c\r\n\
+        #line 1 \"C:\\\\src\\\\main.c\"\r\n\
+        int x;\r\n\
+        C:\\src\\main.c(3): warning C4068: unknown pragma 'foo'\r\n\
+        int y;\r\n\
+        C:\\src\\main.c(4): fatal error C1083: Cannot open include file: 'missing.h'\r\n";
+
+    let (text, diagnostics) = split_msvc_output(STDOUT);
+    assert_eq!(
+        text,
+        "#line 1 \"C:\\\\src\\\\main.c\"\r\nint x;\r\nint y;\r\n"
+    );
+    assert_eq!(
+        diagnostics,
+        "C:\\src\\main.c(3): warning C4068: unknown pragma 'foo'\n\
+         C:\\src\\main.c(4): fatal error C1083: Cannot open include file: 'missing.h'\n"
+    );
+
+    assert!(is_msvc_diagnostic(
+        "cl : Command line warning D9002 : ignoring unknown option '/foo'"
+    ));
+    assert!(!is_msvc_diagnostic("int f(x): warning;"));
+    assert!(!is_msvc_diagnostic("x = f(a): error ? 1 : 2;"));
+}
+
+#[test]
+fn test_preprocessor_errors() {
+    use std::error::Error as StdError;
+
+    let mut config = Config::with_gcc();
+    config.cpp_command = "lang-c-no-such-preprocessor".into();
+    let err = parse_str(&config, "t.c", "int x;").unwrap_err();
+    match err {
+        Error::PreprocessorMissing {
+            ref command,
+            ref source,
+        } => {
+            assert_eq!(command, "lang-c-no-such-preprocessor");
+            assert_eq!(source.kind(), io::ErrorKind::NotFound);
+        }
+        ref e => panic!("expected missing preprocessor, got {:?}", e),
+    }
+    let text = err.to_string();
+    assert!(
+        text.contains("\"lang-c-no-such-preprocessor\" not found"),
+        "{}",
+        text
+    );
+    assert!(text.contains("Config::cpp_command"), "{}", text);
+    assert!(err.source().is_some());
+
+    if cfg!(unix) {
+        config.cpp_command = "false".into();
+        match parse_str(&config, "t.c", "int x;").unwrap_err() {
+            Error::PreprocessorError(_) => {}
+            ref e => panic!("expected preprocessor error, got {:?}", e),
+        }
+    }
+
+    let err = Error::from(parse_preprocessed(&config, "int".into()).unwrap_err());
+    assert!(err.source().unwrap().is::<SyntaxError>());
+}
+
+#[test]
+fn test_parse_preprocessed_msvc() {
+    let source = split_msvc_output(
+        "#line 1 \"C:\\\\src\\\\main.c\"\r\n\
+         typedef int foo;\r\n\
+         foo x = ;\r\n",
+    )
+    .0;
+    let err = parse_preprocessed(&Config::with_msvc(), source).unwrap_err();
+    let (loc, inc) = err.get_location();
+    assert_eq!(loc.file, "C:\\\\src\\\\main.c");
+    assert_eq!(loc.line, 2);
+    assert!(inc.is_empty());
+}
+
+#[cfg(windows)]
+#[test]
+fn test_parse_msvc() {
+    let config = Config::with_msvc();
+    let source = "#include <stddef.h>\nsize_t x = sizeof(int);\n";
+    match parse_str(&config, "main.c", source) {
+        Ok(parse) => assert!(parse.unit.0.len() > 1),
+        // cl.exe is only available from a developer command prompt
+        Err(Error::PreprocessorError(ref e)) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => panic!("parse failed: {}", e),
+    }
+}
+
+#[cfg(all(unix, test))]
+fn sleeping_preprocessor() -> Config {
+    let mut config = Config::with_gcc();
+    config.cpp_command = "sh".into();
+    config.cpp_options = vec![
+        "-c".into(),
+        "echo '# 1 \"partial.c\"'; echo 'still working' >&2; sleep 10".into(),
+        "sh".into(),
+    ];
+    config
+}
+
+#[cfg(unix)]
+#[test]
+fn test_preprocessor_timeout() {
+    let mut config = sleeping_preprocessor();
+    config.cpp_timeout = Some(Duration::from_millis(500));
+
+    let start = Instant::now();
+    match parse(&config, "test.c") {
+        Err(Error::PreprocessorTimeout(p)) => {
+            assert_eq!(p.stdout, "# 1 \"partial.c\"\n");
+            assert_eq!(p.stderr, "still working\n");
+        }
+        r => panic!("unexpected result: {:?}", r.map(|p| p.unit)),
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_preprocessor_cancel() {
+    let mut config = sleeping_preprocessor();
+    let token = CancelToken::new();
+    config.cancel = Some(token.clone());
+
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        token.cancel();
+    });
+
+    let start = Instant::now();
+    match parse_str(&config, "test.c", "int x;") {
+        Err(Error::PreprocessorCancelled(_)) => {}
+        r => panic!("unexpected result: {:?}", r.map(|p| p.unit)),
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+    canceller.join().unwrap();
+}
+
+#[test]
+fn test_parse_nested_omp() {
+    // the parallel blocks are parsed once, not once for every enclosing block
+    let depth = 20;
+    let source = format!(
+        "void f(void) {{\n{}x = 1;\n{}}}\n",
+        "#pragma omp parallel\n{ int x;\n".repeat(depth),
+        "}\n".repeat(depth)
+    );
+    let mut config = Config::with_gcc();
+    config.flavor = Flavor::StdC89;
+    let start = Instant::now();
+    let parse = parse_preprocessed(&config, source).expect("parse failed");
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(parse.unit.0.len(), 1);
+}
+
+#[test]
+fn test_parse_preprocessed_flavor() {
+    // length of the unit, or offset and column of the error
+    fn t(flavor: Flavor, source: &str) -> Result<usize, (usize, usize)> {
+        let mut config = Config::with_gcc();
+        config.flavor = flavor;
+        match parse_preprocessed(&config, source.into()) {
+            Ok(parse) => Ok(parse.unit.0.len()),
+            Err(err) => Err((err.offset, err.column)),
+        }
+    }
+
+    // errors point at the start of the offending token
+    let mixed = "void f(void) { f(); int x; }";
+    assert_eq!(t(Flavor::StdC99, mixed), Ok(1));
+    assert_eq!(t(Flavor::StdC89, mixed), Err((20, 21)));
+
+    let long_long = "unsigned long long x;";
+    assert_eq!(t(Flavor::StdC99, long_long), Ok(1));
+    assert_eq!(t(Flavor::StdC89, long_long), Err((14, 15)));
+
+    let assert = "int x; _Static_assert(1, \"x\");";
+    assert_eq!(t(Flavor::StdC11, assert), Ok(2));
+    assert_eq!(t(Flavor::StdC99, assert), Err((7, 8)));
+
+    // `_Generic` is an identifier before C11, so the type name is unexpected
+    let generic = "int x = _Generic(1, int: 2);";
+    assert_eq!(t(Flavor::StdC11, generic), Ok(1));
+    assert_eq!(t(Flavor::StdC99, generic), Err((20, 21)));
+
+    for keyword in &["true", "false", "nullptr"] {
+        let decl = format!("int {};", keyword);
+        for flavor in &[
+            Flavor::StdC11,
+            Flavor::StdC17,
+            Flavor::GnuC11,
+            Flavor::ClangC11,
+        ] {
+            assert_eq!(t(*flavor, &decl), Ok(1), "{} in {:?}", keyword, flavor);
+        }
+        for flavor in &[Flavor::StdC23, Flavor::GnuC23, Flavor::ClangC23] {
+            assert!(t(*flavor, &decl).is_err(), "{} in {:?}", keyword, flavor);
+        }
+    }
+}
+
+#[test]
+fn test_syntax_error_render() {
+    fn t(source: &str) -> String {
+        parse_preprocessed(&Config::default(), source.into())
+            .unwrap_err()
+            .render()
+    }
+
+    let err = t("# 1 \"a.c\"\nint x = 1;\nint y = foo bar;\n");
+    assert!(err.starts_with("unexpected token at \"a.c\" line 2 column 13, expected '!='"));
+    assert!(err.ends_with("\n  |\n2 | int y = foo bar;\n  |             ^~~"));
+
+    // tabs are expanded, multi-byte characters count once
+    let err = t("# 1 \"b.c\"\n\tchar *s = \"\u{e9}t\u{e9}\" \"\u{2192}\"\t);\n");
+    assert!(err.starts_with("unexpected token at \"b.c\" line 1 column 22,"));
+    assert!(err.ends_with(&format!(
+        "\n1 |     char *s = \"\u{e9}t\u{e9}\" \"\u{2192}\"    );\n  | {}^",
+        " ".repeat(27)
+    )));
+
+    let long = format!("int {} = 1 1;", "x".repeat(200));
+    let err = t(&long);
+    let lines = err.lines().collect::<Vec<_>>();
+    assert_eq!(lines[2].len(), 4 + 3 + 100);
+    assert!(lines[2].ends_with("xxx = 1 1;"));
+    assert!(lines[3].ends_with("  ^"));
+    assert_eq!(lines[3].len(), lines[2].len() - 1);
+
+    assert_eq!(
+        t("# 1 \"main.c\"\n# 1 \"inc.h\" 1\nint (x;\n# 2 \"main.c\" 2\n"),
+        r#"unexpected token at "inc.h" line 1 column 7, expected '(', ')', '[', '[_a-zA-Z0-9]'
+  |
+1 | int (x;
+  |       ^
+  included from main.c:1"#
+    );
+
+    assert!(t("int f(void) {").ends_with("\n1 | int f(void) {\n  |              ^"));
+}
+
+#[test]
+fn test_parse_bom() {
+    let parse = parse_preprocessed(&Config::default(), "\u{feff}int x;\n".into()).unwrap();
+    assert_eq!(parse.unit.0[0].span.start, 3);
+
+    let source =
+        "# 1 \"main.c\"\n\u{feff}int x;\n# 1 \"a.h\" 1\n\u{feff}int y;\n# 2 \"main.c\" 2\n";
+    let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+    assert_eq!(parse.unit.0.len(), 2);
+    assert_eq!(
+        &parse.source[parse.unit.0[1].span.start..],
+        "int y;\n# 2 \"main.c\" 2\n"
+    );
+
+    let err = parse_preprocessed(&Config::default(), "\u{feff}int x y;".into()).unwrap_err();
+    assert_eq!((err.offset, err.column, err.column_utf8), (9, 10, 8));
+
+    let mut env = Env::with_core();
+    let expr = parse_expression("\u{feff}a + 1", &mut env).unwrap();
+    assert_eq!((expr.span.start, expr.span.end), (3, 8));
+    let err = parse_expression("\u{feff}a b", &mut env).unwrap_err();
+    assert_eq!(err.offset, 5);
+    assert!(parse_statement("\u{feff}return;", &mut env).is_ok());
+    assert!(parse_expression("a \u{feff}+ 1", &mut env).is_err());
+}
+
+#[test]
+fn test_parse_line_splicing() {
+    let source = "static int max(int a, int b) \\\n{ \\\r\n  return a > b ? a : b; \\\n}\nchar *s = \"a\\\nb\";\n";
+    let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+    assert_eq!(parse.unit.0.len(), 2);
+    let span = parse.unit.0[0].span;
+    assert_eq!(
+        &source[span.start..span.end],
+        &source[..source.find("}").unwrap() + 1]
+    );
+
+    let err = parse_preprocessed(&Config::default(), "int x = \\\n  1 2;\n".into()).unwrap_err();
+    assert_eq!((err.line, err.column, err.original_line), (2, 5, 2));
+
+    // a splice inside a token is an error rather than a token separator
+    let t = |source: &str| parse_preprocessed(&Config::default(), source.into()).map(|_| ());
+    assert_eq!(t("int x = b +\\\n+ c;").unwrap_err().offset, 11);
+    assert_eq!(t("int x = b +\\\r\n\\\n= c;").unwrap_err().offset, 11);
+    assert_eq!(t("int x = lo\\\nng;").unwrap_err().offset, 10);
+    assert_eq!(t("double x = 1.\\\n5;").unwrap_err().offset, 13);
+    assert!(t("int x = b + \\\n+c;").is_ok());
+    assert!(t("int x = b +\\\n-c;").is_ok());
+    assert!(t("int x = (b)\\\n+c;").is_ok());
+}
+
+#[test]
+fn test_parse_ms_extensions() {
+    let source = "struct inner { int a; }; struct outer { struct inner; int b; };";
+    let mut config = Config::default();
+    assert!(parse_preprocessed(&config, source.into()).is_err());
+    config.ms_extensions = true;
+    assert!(parse_preprocessed(&config, source.into()).is_ok());
+
+    let mut config = Config::with_msvc();
+    assert!(parse_preprocessed(&config, source.into()).is_ok());
+    config.ms_extensions = false;
+    assert!(parse_preprocessed(&config, source.into()).is_ok());
+}
+
+#[test]
+fn test_syntax_error_column() {
+    fn t(source: &str) -> (usize, usize, usize, usize) {
+        let err = parse_preprocessed(&Config::default(), source.into()).unwrap_err();
+        (err.line, err.column, err.column_utf8, err.original_line)
+    }
+
+    // CRLF line endings count as one line break
+    assert_eq!(t("# 1 \"a.c\"\r\nint x;\r\nint y z;\r\n"), (3, 7, 7, 2));
+    assert!(
+        parse_preprocessed(&Config::default(), "int x;\r\nint y z;\r\n".into())
+            .unwrap_err()
+            .render()
+            .ends_with("\n2 | int y z;\n  |       ^")
+    );
+
+    // tabs are a single byte and character
+    assert_eq!(t("int x;\n\t\tint y z;\n"), (2, 9, 9, 2));
+
+    // multi-byte characters are counted once in `column_utf8`
+    assert_eq!(t("char *s = \"\u{e9}t\u{e9}\" 1;\n"), (1, 19, 17, 1));
+}
+
+#[test]
+fn test_parse_fragments() {
+    use ast::{DeclaratorKind, Statement};
+
+    let mut env = Env::with_core();
+    match parse_expression(" (foo)(x) ", &mut env).unwrap().node {
+        Expression::Call(_) => {}
+        e => panic!("expected call, got {:?}", e),
+    }
+    env.add_typename("foo");
+    match parse_expression("(foo)(x)", &mut env).unwrap().node {
+        Expression::Cast(_) => {}
+        e => panic!("expected cast, got {:?}", e),
+    }
+
+    let mut env = Env::with_core();
+    let decl = parse_declaration("typedef int bar;", &mut env).unwrap();
+    assert_eq!(decl.node.declarators.len(), 1);
+    assert!(env.is_typename("bar"));
+    let ty = parse_type_name("bar *", &mut env).unwrap();
+    match ty.node.declarator.unwrap().node.kind.node {
+        DeclaratorKind::Abstract => {}
+        k => panic!("expected abstract declarator, got {:?}", k),
+    }
+
+    match parse_statement("do { x++; } while (0)\n", &mut env) {
+        Err(err) => assert_eq!((err.line, err.column, err.offset), (2, 1, 22)),
+        Ok(s) => panic!("expected error, got {:?}", s),
+    }
+    match parse_statement("do { x++; } while (0);", &mut env)
+        .unwrap()
+        .node
+    {
+        Statement::DoWhile(_) => {}
+        s => panic!("expected do-while, got {:?}", s),
+    }
+
+    let err = parse_expression("a + b c", &mut env).unwrap_err();
+    assert_eq!(err.offset, 6);
+    assert_eq!(err.source, "a + b c");
+}
+
+#[test]
+fn test_parse_preprocessed_recovering() {
+    let source = r#"# 1 "broken.c"
+int f(void) { return 1 +; }
+int ok1(void) { return 1; }
+int g(void) { int x = ; return x; }
+typedef int T;
+T ok2(void) { return (T)0; }
+int h(void) { if (1) { } else }
+"#;
+    let (parse, errors) = parse_preprocessed_recovering(&Config::default(), source.into());
+    let lines = errors.iter().map(|e| e.line).collect::<Vec<_>>();
+    assert_eq!(lines, [2, 4, 7]);
+    assert_eq!(errors[0].get_location().0.line, 1);
+    assert_eq!(&errors[0].source, source);
+
+    let names = parse
+        .unit
+        .0
+        .iter()
+        .map(|d| match d.node {
+            ExternalDeclaration::Error => "error".to_owned(),
+            _ => parse.source[d.span.start..d.span.end]
+                .split_whitespace()
+                .nth(1)
+                .unwrap()
+                .to_owned(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["error", "ok1(void)", "error", "int", "ok2(void)", "error"]
+    );
+
+    let (parse, errors) = parse_preprocessed_recovering(&Config::default(), "int x;\n".into());
+    assert!(errors.is_empty());
+    assert_eq!(parse.unit.0.len(), 1);
+
+    let source = "}".repeat(2 * MAX_RECOVERED_ERRORS);
+    let (parse, errors) = parse_preprocessed_recovering(&Config::default(), source);
+    assert_eq!(errors.len(), MAX_RECOVERED_ERRORS);
+    assert_eq!(parse.unit.0.len(), MAX_RECOVERED_ERRORS);
+}
+
+#[test]
+fn test_parse_deeply_nested() {
+    // Parsed on the test thread, whose stack is the 2 MiB default of a spawned thread
+    fn t(config: &Config, source: String) -> Result<(), (usize, String)> {
+        match parse_preprocessed(config, source) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                assert!(err.is_nesting_too_deep());
+                Err((err.offset, err.to_string()))
+            }
+        }
+    }
+    fn nest(open: &str, inner: &str, close: &str, n: usize) -> String {
+        format!("{}{}{}", open.repeat(n), inner, close.repeat(n))
+    }
+
+    let config = Config::default();
+    let deep = 30000;
+    let start = Instant::now();
+    let paren = format!("int x = {};", nest("(", "1", ")", deep));
+    assert_eq!(
+        t(&config, paren),
+        Err((51, "nesting too deep at \"\" line 1 column 52".to_owned()))
+    );
+    let init = format!("int x[] = {};", nest("{", "1", "}", deep));
+    assert!(t(&config, init).is_err());
+    let decl = format!("int {};", nest("(", "x", ")", deep));
+    assert!(t(&config, decl).is_err());
+    let casts = format!("int x = {}1;", "(long)".repeat(deep));
+    assert!(t(&config, casts).is_err());
+    let unary = format!("int x = {}1;", "-".repeat(deep));
+    assert!(t(&config, unary).is_err());
+    let ternary = format!("int x = {}1;", "1 ? 1 : ".repeat(deep));
+    assert!(t(&config, ternary).is_err());
+    let block = format!("void f(void) {}", nest("{", "", "}", deep));
+    assert!(t(&config, block).is_err());
+    let ifs = format!("void f(void) {{ {}; }}", "if (1) ".repeat(deep));
+    assert!(t(&config, ifs).is_err());
+    let binary = format!("int x = {};", nest("1 + (", "1", ")", deep));
+    assert!(t(&config, binary).is_err());
+    let calls = format!("int x = {};", nest("f(", "1", ")", deep));
+    assert!(t(&config, calls).is_err());
+    let stmt_exprs = format!("int x = {};", nest("({ ", "1;", " })", deep));
+    assert!(t(&config, stmt_exprs).is_err());
+    let labels = format!("void f(void) {{ {}; }}", "l: ".repeat(deep));
+    assert!(t(&config, labels).is_err());
+    let structs = format!("{}x;", nest("struct { ", "int", " x; }", deep));
+    assert!(t(&config, structs).is_err());
+    let typeofs = format!("{}x;", nest("typeof(", "int", ")", deep));
+    assert!(t(&config, typeofs).is_err());
+    let fnptrs = format!("void f({});", nest("void (*)(", "void", ")", deep));
+    assert!(t(&config, fnptrs).is_err());
+    let omp = format!(
+        "void f(void) {{ {}; }}",
+        "#pragma omp parallel\n".repeat(deep)
+    );
+    assert!(t(&config, omp).is_err());
+    assert!(start.elapsed() < Duration::from_secs(10));
+
+    // A parenthesized expression counts three levels
+    let mut config = Config::default();
+    config.max_nesting = 13;
+    assert_eq!(
+        t(&config, format!("int x = {};", nest("(", "1", ")", 3))),
+        Ok(())
+    );
+    assert!(t(&config, format!("int x = {};", nest("(", "1", ")", 4))).is_err());
+
+    let mut env = Env::with_core();
+    env.max_nesting = 8;
+    assert!(parse_expression("((1))", &mut env).is_err());
+    assert!(parse_expression("(1)", &mut env).is_ok());
+}
+
+#[test]
+fn test_parse_keep_comments() {
+    use comments::CommentKind;
+
+    let source = "/* leading */\n\
+                  #define ZERO /* in macro */ 0\n\
+                  int x = ZERO; // trailing\n\
+                  int /* inline */ y;\n";
+
+    let mut config = Config::with_gcc();
+    config.keep_comments = true;
+    let parse = parse_str(&config, "comments.c", source).expect("parse failed");
+    assert_eq!(parse.unit.0.len(), 2);
+
+    // Comments from implicitly included headers come first
+    let comments = parse
+        .comments
+        .iter()
+        .filter(|c| {
+            loc::get_location_for_offset(&parse.source, c.span.start)
+                .0
+                .file
+                == "comments.c"
+        })
+        .collect::<Vec<_>>();
+    for c in &comments {
+        let delimiter = match c.node.kind {
+            CommentKind::Block => "/*",
+            CommentKind::Line => "//",
+        };
+        assert!(parse.source[c.span.start..].starts_with(delimiter));
+    }
+
+    let x = &parse.unit.0[0].span;
+    assert_eq!(&parse.source[x.start..x.end], "int x = /* in macro */ 0;");
+    assert!(comments[2].span.start > x.end);
+
+    let comments = comments
+        .iter()
+        .map(|c| (c.node.kind, &c.node.text[..]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        comments,
+        vec![
+            (CommentKind::Block, " leading "),
+            (CommentKind::Block, " in macro "),
+            (CommentKind::Line, " trailing"),
+            (CommentKind::Block, " inline "),
+        ]
+    );
+
+    config.keep_comments = false;
+    let parse = parse_str(&config, "comments.c", source).expect("parse failed");
+    assert!(parse.comments.is_empty());
+}
+
+#[test]
+fn test_parse_all() {
+    use std::fs::File;
+
+    fn assert_send<T: Send>() {}
+    assert_send::<Config>();
+    assert_send::<Parse>();
+    assert_send::<Error>();
+
+    let dir = test_dir("parse-all");
+    let files = (0..10)
+        .map(|i| {
+            let path = dir.join(format!("file{}.c", i));
+            let text = if i == 3 {
+                "int broken = ;\n".to_string()
+            } else {
+                format!("int x{} = {};\n", i, i)
+            };
+            File::create(&path)
+                .and_then(|mut f| f.write_all(text.as_bytes()))
+                .unwrap();
+            path
+        })
+        .collect::<Vec<_>>();
+
+    let results = parse_all(&Config::with_gcc(), files.clone(), 4);
+    assert_eq!(results.len(), files.len());
+    for (i, &(ref path, ref result)) in results.iter().enumerate() {
+        assert_eq!(path, &files[i]);
+        match *result {
+            Ok(ref parse) => {
+                assert!(i != 3);
+                assert!(parse.source.contains(&format!("int x{} = {};", i, i)));
+            }
+            Err(Error::SyntaxError(_)) => assert_eq!(i, 3),
+            Err(ref e) => panic!("{}: {}", path.display(), e),
+        }
+    }
+
+    assert!(parse_all(&Config::with_gcc(), Vec::new(), 0).is_empty());
+}
+
+#[test]
+fn test_preprocessor_args() {
+    let mut config = Config::with_gcc();
+    config
+        .include_dir("/opt/my headers")
+        .define("FOO", None)
+        .define("BAR", Some("a=b"))
+        .undefine("BAZ");
+
+    let args = preprocessor_args(&config);
+    let expected: Vec<OsString> = vec![
+        "-E".into(),
+        "-std=gnu11".into(),
+        "-I".into(),
+        "/opt/my headers".into(),
+        "-D".into(),
+        "FOO".into(),
+        "-D".into(),
+        "BAR=a=b".into(),
+        "-U".into(),
+        "BAZ".into(),
+    ];
+    assert_eq!(args, expected);
+
+    config.flavor = Flavor::StdC23;
+    assert_eq!(preprocessor_args(&config)[1], "-std=c2x");
+    config.flavor = Flavor::GnuC23;
+    assert_eq!(preprocessor_args(&config)[1], "-std=gnu2x");
+    config.flavor = Flavor::StdC17;
+    assert_eq!(preprocessor_args(&config)[1], "-std=c17");
+}
+
+#[test]
+fn test_preprocessor_std_option() {
+    fn t(mut config: Config, flavor: Flavor, expected: &[&str]) {
+        config.flavor = flavor;
+        let args = preprocessor_args(&config);
+        assert_eq!(args, expected, "{:?}", flavor);
+    }
+
+    let gcc = Config::with_gcc;
+    t(gcc(), Flavor::StdC89, &["-E", "-std=c89"]);
+    t(gcc(), Flavor::StdC99, &["-E", "-std=c99"]);
+    t(gcc(), Flavor::StdC11, &["-E", "-std=c11"]);
+    t(gcc(), Flavor::StdC17, &["-E", "-std=c17"]);
+    t(gcc(), Flavor::StdC23, &["-E", "-std=c2x"]);
+    t(gcc(), Flavor::GnuC11, &["-E", "-std=gnu11"]);
+    t(gcc(), Flavor::GnuC23, &["-E", "-std=gnu2x"]);
+    t(
+        Config::with_clang(),
+        Flavor::ClangC11,
+        &["-E", "-std=gnu11"],
+    );
+    t(
+        Config::with_clang(),
+        Flavor::ClangC23,
+        &["-E", "-std=gnu2x"],
+    );
+    t(Config::with_clang(), Flavor::Cuda, &["-E"]);
+    t(Config::with_clang(), Flavor::OpenCl, &["-E"]);
+
+    let msvc = Config::with_msvc;
+    t(msvc(), Flavor::StdC99, &["/E", "/nologo"]);
+    t(msvc(), Flavor::StdC11, &["/E", "/nologo", "/std:c11"]);
+    t(msvc(), Flavor::StdC17, &["/E", "/nologo", "/std:c17"]);
+    t(msvc(), Flavor::StdC23, &["/E", "/nologo", "/std:clatest"]);
+    t(msvc(), Flavor::MsvcC11, &["/E", "/nologo"]);
+
+    let mut config = gcc();
+    config.cpp_options.push("-std=gnu99".into());
+    t(config, Flavor::GnuC11, &["-E", "-std=gnu99"]);
+    let mut config = msvc();
+    config.cpp_options.push("/std:c11".into());
+    t(config, Flavor::StdC17, &["/E", "/nologo", "/std:c11"]);
+
+    let mut config = gcc();
+    config.no_std_flag = true;
+    t(config, Flavor::StdC23, &["-E"]);
+}
+
+#[test]
+fn test_parse_include_dir_and_define() {
+    use std::fs::{create_dir, File};
+
+    let dir = test_dir("include-dir");
+    let inc = dir.join("my include");
+    create_dir(&inc).unwrap();
+    File::create(inc.join("header.h"))
+        .and_then(|mut f| f.write_all(b"typedef int my_int;\n"))
+        .unwrap();
+    let source = dir.join("main.c");
+    File::create(&source)
+        .and_then(|mut f| f.write_all(b"#include <header.h>\nmy_int x = VALUE;\n"))
+        .unwrap();
+
+    let mut config = Config::with_gcc();
+    config.include_dir(&inc).define("VALUE", Some("42"));
+    let parse = parse(&config, &source).expect("parse failed");
+    assert_eq!(parse.unit.0.len(), 2);
+}
+
+#[test]
+fn test_syntax_error_location() {
+    use std::fs::{create_dir, File};
+
+    let dir = test_dir("error-location");
+    create_dir(dir.join("sub")).unwrap();
+    File::create(dir.join("sub").join("inner.h"))
+        .and_then(|mut f| f.write_all(b"// inner\n\n\t\tint   y  =  ) ;\n"))
+        .unwrap();
+    File::create(dir.join("outer.h"))
+        .and_then(|mut f| f.write_all(b"int x;\n#include \"sub/inner.h\"\n"))
+        .unwrap();
+    File::create(dir.join("main.c"))
+        .and_then(|mut f| f.write_all(b"#define X 1\n\n#include \"outer.h\"\n"))
+        .unwrap();
+
+    let mut config = Config::with_gcc();
+    config.current_dir(&dir);
+    let err = match parse(&config, "main.c") {
+        Err(Error::SyntaxError(err)) => err,
+        r => panic!("expected syntax error, got {:?}", r),
+    };
+    assert_eq!(err.file, "sub/inner.h");
+    assert_eq!((err.original_line, err.original_column), (3, 15));
+    assert_eq!(
+        (err.column, err.source.lines().nth(err.line - 1)),
+        (11, Some("  int y = ) ;"))
+    );
+    assert_eq!(
+        err.included_from,
+        [("main.c".to_owned(), 3), ("outer.h".to_owned(), 2)]
+    );
+    let text = err.to_string();
+    assert!(text.starts_with("unexpected token at \"sub/inner.h\" line 3 column 15, expected "));
+    assert!(text.ends_with("\n  included from main.c:3\n  included from outer.h:2"));
+
+    // the error stays self-describing without the preprocessed source
+    let mut err = err;
+    err.source = String::new();
+    assert_eq!(err.to_string(), text);
+
+    // a line changed by macro expansion keeps the preprocessed column
+    File::create(dir.join("main.c"))
+        .and_then(|mut f| f.write_all(b"#define X 1 +\nint  z = X ;\n"))
+        .unwrap();
+    let err = match parse(&config, "main.c") {
+        Err(Error::SyntaxError(err)) => err,
+        r => panic!("expected syntax error, got {:?}", r),
+    };
+    assert_eq!(err.file, "main.c");
+    assert_eq!(err.original_line, 2);
+    assert_eq!(err.original_column, err.column);
+}
+
+#[test]
+fn test_parse_error_location_special_files() {
+    // only regular files named by line markers are read for the original column
+    let dir = test_dir("error-location-special");
+    let mut config = Config::with_gcc();
+    config.current_dir(&dir);
+    let mut names = vec![dir.to_string_lossy().into_owned()];
+    if cfg!(unix) {
+        names.push("/dev/zero".to_owned());
+    }
+    for name in names {
+        let source = format!("# 1 \"{}\"\n  int  x = ) ;\n", name);
+        let err = parse_preprocessed(&config, source).unwrap_err();
+        assert_eq!((err.original_line, err.original_column), (1, err.column));
+    }
+}
+
+#[cfg(not(feature = "internal-preprocessor"))]
+#[test]
+fn test_parse_internal_preprocessor_missing() {
+    let mut config = Config::default();
+    config.cpp_kind = PreprocessorKind::Internal;
+    match parse_str(&config, "a.c", "int x;") {
+        Err(Error::PreprocessorError(e)) => {
+            assert_eq!(e.to_string(), "built without internal-preprocessor")
+        }
+        other => panic!("expected preprocessor error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "internal-preprocessor")]
+#[test]
+fn test_parse_internal_preprocessor() {
+    use std::fs::{create_dir, File};
+
+    let dir = test_dir("internal-cpp");
+    let inc = dir.join("include");
+    create_dir(&inc).unwrap();
+    let files: &[(&Path, &str, &str)] = &[
+        (
+            &inc,
+            "types.h",
+            "#ifndef TYPES_H\n#define TYPES_H\ntypedef unsigned long size_t;\n#endif\n",
+        ),
+        (
+            &inc,
+            "list.h",
+            "#pragma once\n#include <types.h>\n#define LIST(T) struct list_##T { T *items; size_t len; }\n",
+        ),
+        (
+            &dir,
+            "config.h",
+            "#include <types.h>\n#define VERSION ((MAJOR) * 100 + 2)\n#if VERSION >= 300\n#define HAVE_LIST 1\n#endif\n",
+        ),
+        (
+            &dir,
+            "main.c",
+            "#include \"config.h\"\n#include <list.h>\n#include <list.h>\n\n#ifdef HAVE_LIST\nLIST(int) numbers;\n#else\n#error missing list\n#endif\nsize_t line = __LINE__;\nconst char *file = __FILE__;\nint bad = ;\n",
+        ),
+    ];
+    for &(dir, name, text) in files {
+        File::create(dir.join(name))
+            .and_then(|mut f| f.write_all(text.as_bytes()))
+            .unwrap();
+    }
+
+    let mut config = Config::with_internal_preprocessor();
+    config.include_dir(&inc).define("MAJOR", Some("3"));
+    config.cpp_command = "/nonexistent/cpp".into();
+
+    let source = dir.join("main.c");
+    let err = match parse(&config, &source) {
+        Err(Error::SyntaxError(err)) => err,
+        other => panic!("expected syntax error, got {:?}", other),
+    };
+    let (loc, includes) = err.get_location();
+    assert_eq!(loc.file, source.to_str().unwrap());
+    assert_eq!(loc.line, 12);
+    assert!(includes.is_empty());
+
+    let fixed = err.source.replace("int bad = ;", "");
+    let unit = parse_preprocessed(&config, fixed).expect("parse failed");
+    assert_eq!(unit.unit.0.len(), 4);
+    assert!(unit
+        .source
+        .contains("struct list_int { int *items; size_t len; } numbers;"));
+    assert!(unit.source.contains("size_t line = 10;"));
+
+    let offset = unit.source.find("typedef").unwrap();
+    let (loc, includes) = loc::get_location_for_offset(&unit.source, offset);
+    assert_eq!(loc.file, inc.join("types.h").to_str().unwrap());
+    assert_eq!(loc.line, 3);
+    assert_eq!(includes.len(), 2);
+    assert_eq!(includes[1].line, 1);
+
+    config.macros.clear();
+    match parse(&config, &source) {
+        Err(Error::PreprocessorError(e)) => assert!(e.to_string().contains("#error missing list")),
+        other => panic!("expected preprocessor error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode() {
+    let t = |bytes: &[u8], encoding: Encoding| decode(bytes.to_vec(), encoding).ok();
+
+    assert_eq!(t(b"int x;", Encoding::Utf8), Some("int x;".into()));
+    assert_eq!(t(b"\"caf\xe9\"", Encoding::Utf8), None);
+    assert_eq!(
+        t(
+            b"\"caf\xe9\" 'x' 1'000 '\xe9' /* caf\xe9's */ caf\xe9",
+            Encoding::Latin1
+        ),
+        Some("\"caf\\351\" 'x' 1'000 '\\351' /* café's */ café".into())
+    );
+    assert_eq!(
+        t(b"\"\x80\\\"\x80\" // \x80\n\x80", Encoding::Windows1252),
+        Some("\"\\200\\\"\\200\" // \u{20ac}\n\u{20ac}".into())
+    );
+    assert_eq!(
+        t(b"\"caf\xc3\xa9 \xe9\" \xe9", Encoding::Utf8Lossy),
+        Some("\"café \\351\" \u{fffd}".into())
+    );
+}
+
+#[test]
+fn test_parse_latin1() {
+    use ast::StringLiteral;
+    use span::Span;
+    use std::fs::File;
+    use visit::{self, Visit};
+
+    struct Strings(Vec<String>);
+
+    impl<'ast> Visit<'ast> for Strings {
+        fn visit_string_literal(&mut self, s: &'ast StringLiteral, span: &'ast Span) {
+            self.0.extend(s.iter().map(|p| p.value.clone()));
+            visit::visit_string_literal(self, s, span);
+        }
+    }
+
+    let dir = test_dir("latin1");
+    let source = dir.join("latin1.c");
+    File::create(&source)
+        .and_then(|mut f| f.write_all(b"/* caf\xe9 */\nchar *s = \"caf\xe9\";\n"))
+        .unwrap();
+
+    let mut config = Config::with_gcc();
+    config.keep_comments = true;
+    assert!(parse(&config, &source).is_err());
+
+    config.encoding = Encoding::Latin1;
+    let parse = parse(&config, &source).expect("parse failed");
+    assert!(parse.comments.iter().any(|c| c.node.text == " café "));
+
+    let mut strings = Strings(Vec::new());
+    strings.visit_translation_unit(&parse.unit);
+    assert_eq!(strings.0, ["\"caf\\351\""]);
+    assert_eq!(u8::from_str_radix("351", 8), Ok(0xe9));
+}
+
+#[test]
+fn test_diagnostic_parse() {
+    let d = |file: Option<&str>, line, column, message: &str| Diagnostic {
+        file: file.map(Into::into),
+        line: line,
+        column: column,
+        message: message.into(),
+    };
+
+    assert_eq!(
+        Diagnostic::parse("a.c:3:9: warning: \"FOO\" redefined"),
+        d(Some("a.c"), Some(3), Some(9), "warning: \"FOO\" redefined")
+    );
+    assert_eq!(
+        Diagnostic::parse("<command-line>: warning: \"X\" redefined"),
+        d(None, None, None, "<command-line>: warning: \"X\" redefined")
+    );
+    assert_eq!(
+        Diagnostic::parse("C:\\src\\a.c:12: warning: ignoring pragma"),
+        d(
+            Some("C:\\src\\a.c"),
+            Some(12),
+            None,
+            "warning: ignoring pragma"
+        )
+    );
+    assert_eq!(
+        Diagnostic::parse("C:\\src\\a.c(7): warning C4068: unknown pragma 'x'"),
+        d(
+            Some("C:\\src\\a.c"),
+            Some(7),
+            None,
+            "warning C4068: unknown pragma 'x'"
+        )
+    );
+    assert_eq!(
+        Diagnostic::parse("a.c:3:9: warning: x").to_string(),
+        "a.c:3:9: warning: x"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_preprocessor_diagnostics() {
+    let mut config = Config::with_gcc();
+    config.cpp_command = "sh".into();
+    config.cpp_options = vec![
+        "-c".into(),
+        "echo 'int x;'; echo 'In file included from x.h:2,' >&2; \
+         echo 'a.c:1:9: warning: ignoring #pragma foo' >&2"
+            .into(),
+        "sh".into(),
+    ];
+
+    let unit = parse(&config, "a.c").expect("parse failed");
+    assert_eq!(unit.unit.0.len(), 1);
+    assert_eq!(unit.cpp_diagnostics.len(), 2);
+    assert_eq!(unit.cpp_diagnostics[0].file, None);
+    assert_eq!(
+        unit.cpp_diagnostics[0].message,
+        "In file included from x.h:2,"
+    );
+    assert_eq!(
+        unit.cpp_diagnostics[1],
+        Diagnostic {
+            file: Some("a.c".into()),
+            line: Some(1),
+            column: Some(9),
+            message: "warning: ignoring #pragma foo".into(),
+        }
+    );
+
+    config.cpp_options[1] = "echo 'int x;'; echo 'a.c:1:1: error: bad' >&2; exit 1".into();
+    match parse(&config, "a.c") {
+        Err(Error::PreprocessorError(e)) => assert_eq!(e.to_string(), "a.c:1:1: error: bad\n"),
+        other => panic!("expected preprocessor error, got {:?}", other),
+    }
+    let unit = parse_str(&Config::with_gcc(), "w.c", "int x;\n#warning careful\n").unwrap();
+    let warning = unit.cpp_diagnostics.last().expect("no warning");
+    assert_eq!(warning.file, Some("w.c".into()));
+    assert_eq!(warning.line, Some(2));
+    assert!(warning.message.starts_with("warning: #warning careful"));
+}
+
+#[test]
+fn test_config_from_env() {
+    // the process environment is shared with tests running in parallel, so it is left alone
+    fn from_vars(cpp: Option<&str>, cc: Option<&str>) -> Config {
+        Config::from_vars(|name| {
+            let value = match name {
+                "CPP" => cpp,
+                "CC" => cc,
+                _ => None,
+            };
+            value.map(String::from)
+        })
+    }
+
+    let config = from_vars(None, Some("ccache clang -m32"));
+    assert_eq!(config.cpp_command, "ccache");
+    assert_eq!(config.cpp_options, ["clang", "-m32", "-E"]);
+    assert_eq!(config.cpp_kind, PreprocessorKind::Gcc);
+    assert_eq!(config.flavor, Flavor::ClangC11);
+
+    let config = from_vars(
+        Some("/usr/bin/x86_64-linux-gnu-cpp-12 -P"),
+        Some("ccache clang -m32"),
+    );
+    assert_eq!(config.cpp_command, "/usr/bin/x86_64-linux-gnu-cpp-12");
+    assert_eq!(config.cpp_options, ["-P"]);
+    assert_eq!(config.flavor, Flavor::GnuC11);
+
+    let config = from_vars(Some(" "), Some("cl.exe"));
+    assert_eq!(config.cpp_command, "cl.exe");
+    assert_eq!(config.cpp_options, ["/E", "/nologo"]);
+    assert_eq!(config.cpp_kind, PreprocessorKind::Msvc);
+
+    assert_eq!(
+        from_vars(None, None).cpp_command,
+        Config::default().cpp_command
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_preprocessor_environment() {
+    use std::fs::File;
+
+    let dir = test_dir("environment");
+    File::create(dir.join("marker.h")).unwrap();
+
+    let mut config = Config::with_gcc();
+    config.cpp_command = "sh".into();
+    config.cpp_options = vec![
+        "-c".into(),
+        "test -f marker.h && echo \"int ${LANG_C_VAR:-unset}, ${HOME:-no_home};\"".into(),
+        "sh".into(),
+    ];
+    config
+        .env_var("LANG_C_VAR", "from_config")
+        .clear_env(true)
+        .current_dir(&dir);
+
+    let unit = parse(&config, "test.c").expect("parse failed");
+    assert_eq!(unit.source, "int from_config, no_home;\n");
+
+    config.cpp_current_dir = None;
+    assert!(parse(&config, "test.c").is_err());
+}
+
+#[test]
+fn test_parse_included_files() {
+    use std::fs::{create_dir, File};
+
+    let dir = test_dir("included-files");
+    create_dir(dir.join("sub")).unwrap();
+    let files: &[(&str, &str)] = &[
+        (
+            "main.c",
+            "#include \"a.h\"\n#include <stddef.h>\n#include \"a.h\"\n",
+        ),
+        ("a.h", "#include \"sub/b.h\"\n"),
+        ("sub/b.h", "typedef int b;\n"),
+    ];
+    for &(name, text) in files {
+        File::create(dir.join(name))
+            .and_then(|mut f| f.write_all(text.as_bytes()))
+            .unwrap();
+    }
+
+    let source = dir.join("main.c");
+    let unit = parse(&Config::with_gcc(), &source).expect("parse failed");
+    let files = unit.included_files();
+
+    let user = files
+        .iter()
+        .filter(|f| !f.system)
+        .map(|f| f.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(user, [source, dir.join("a.h"), dir.join("sub/b.h")]);
+    assert!(files
+        .iter()
+        .any(|f| f.system && f.path.ends_with("stddef.h")));
+}
+
+#[test]
+fn test_parse_cache() {
+    use std::fs;
+
+    fn write(path: &Path, text: &str) {
+        File::create(path)
+            .and_then(|mut f| f.write_all(text.as_bytes()))
+            .unwrap();
+    }
+    fn read(path: &Path) -> String {
+        let mut text = String::new();
+        File::open(path)
+            .and_then(|mut f| f.read_to_string(&mut text))
+            .unwrap();
+        text
+    }
+
+    if !cfg!(unix) {
+        return;
+    }
+
+    let dir = test_dir("cache");
+    let cache_dir = dir.join("cache");
+    write(&dir.join("inc.h"), "typedef int T;\n");
+    write(&dir.join("main.c"), "#include \"inc.h\"\nT x;\n");
+
+    // count preprocessor runs in a file next to the sources
+    let mut config = Config::with_gcc();
+    config.cpp_command = "sh".into();
+    config.cpp_options = vec![
+        "-c".into(),
+        "echo run >> runs; exec gcc \"$@\"".into(),
+        "sh".into(),
+        "-E".into(),
+    ];
+    config.cpp_current_dir = Some(dir.clone());
+    config.cache_dir = Some(cache_dir.clone());
+    let runs = || read(&dir.join("runs")).lines().count();
+
+    let first = parse(&config, "main.c").expect("parse failed");
+    assert_eq!(first.cache, CacheStats { hits: 0, misses: 1 });
+    assert_eq!(runs(), 1);
+
+    let second = parse(&config, "main.c").expect("parse failed");
+    assert_eq!(second.cache, CacheStats { hits: 1, misses: 0 });
+    assert_eq!(second.source, first.source);
+    assert_eq!(second.unit, first.unit);
+    assert_eq!(runs(), 1);
+
+    // a different size is noticed even if the modification time has a coarse resolution
+    write(&dir.join("inc.h"), "typedef long T;\n");
+    let changed = parse(&config, "main.c").expect("parse failed");
+    assert_eq!(changed.cache.misses, 1);
+    assert!(changed.source.contains("typedef long T;"));
+    assert_eq!(runs(), 2);
+
+    for entry in fs::read_dir(&cache_dir).unwrap() {
+        let path = entry.unwrap().path();
+        let text = read(&path);
+        write(&path, &text[..text.len() - 3]);
+    }
+    let corrupted = parse(&config, "main.c").expect("parse failed");
+    assert_eq!(corrupted.cache.misses, 1);
+    assert_eq!(runs(), 3);
+
+    config.macros.push(Macro::Define("UNUSED".into(), None));
+    assert_eq!(parse(&config, "main.c").unwrap().cache.misses, 1);
+    assert_eq!(runs(), 4);
+
+    assert_eq!(purge_cache(&cache_dir).unwrap(), 2);
+    assert_eq!(purge_cache(&cache_dir).unwrap(), 0);
+    assert_eq!(parse(&config, "main.c").unwrap().cache.misses, 1);
+    assert_eq!(runs(), 5);
+
+    config.cache_dir = None;
+    assert_eq!(
+        parse(&config, "main.c").unwrap().cache,
+        CacheStats::default()
+    );
+    assert_eq!(runs(), 6);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_parse_reader() {
+    use std::io::Cursor;
+
+    let config = Config::with_gcc();
+    let preprocessor = CommandPreprocessor::new(&config);
+    let input = Cursor::new("#define N 3\nint x[N];\n");
+    let parse = parse_reader_with(&preprocessor, &config, input).expect("parse failed");
+    assert!(parse.source.contains("int x[3];"));
+    assert_eq!(parse.unit.0.len(), 1);
+
+    let input = Cursor::new("int x = ;\n");
+    match parse_reader_with(&preprocessor, &config, input).unwrap_err() {
+        Error::SyntaxError(ref e) => {
+            assert_eq!(e.file, STDIN_NAME);
+            assert_eq!(e.original_line, 1);
+        }
+        ref e => panic!("expected syntax error, got {:?}", e),
+    }
+
+    let input = Cursor::new(vec![b'i', 0xff, b';']);
+    match parse_reader_with(&StripComments, &config, input).unwrap_err() {
+        Error::InvalidEncoding { byte_offset } => assert_eq!(byte_offset, 1),
+        ref e => panic!("expected encoding error, got {:?}", e),
+    }
+
+    // the input is decoded like preprocessor output
+    let mut config = Config::with_gcc();
+    config.encoding = Encoding::Latin1;
+    let input = Cursor::new(b"char c = '\xe9';\n".to_vec());
+    let parse = parse_reader_with(&StripComments, &config, input).expect("parse failed");
+    assert!(parse.source.contains("char c = '\\351';"));
+}
+
+#[test]
+fn test_parse_preprocessed_reader() {
+    use std::io::Cursor;
+
+    // split a multi-byte character across reads
+    struct Chunks(Vec<Vec<u8>>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Ok(0);
+            }
+            let chunk = self.0.remove(0);
+            buf[..chunk.len()].copy_from_slice(&chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    let config = Config::default();
+    let input = Cursor::new("char *s = \"caf\u{e9}\";\n".as_bytes().to_vec());
+    let parse = parse_preprocessed_reader(&config, "in.c", input).expect("parse failed");
+    assert_eq!(parse.unit.0.len(), 1);
+
+    let text = "int x; // \u{e9}\n".as_bytes();
+    let input = Chunks(vec![text[..11].to_vec(), text[11..].to_vec()]);
+    let parse = parse_preprocessed_reader(&config, "in.c", input).expect("parse failed");
+    assert_eq!(parse.source, "int x; // \u{e9}\n");
+
+    let input = Cursor::new(b"int x;\nint \xff;\n".to_vec());
+    match parse_preprocessed_reader(&config, "in.c", input).unwrap_err() {
+        Error::InvalidEncoding { byte_offset } => assert_eq!(byte_offset, 11),
+        ref e => panic!("expected invalid encoding, got {:?}", e),
+    }
+    let input = Cursor::new(b"int x; \xc3".to_vec());
+    match parse_preprocessed_reader(&config, "in.c", input).unwrap_err() {
+        Error::InvalidEncoding { byte_offset } => assert_eq!(byte_offset, 7),
+        ref e => panic!("expected invalid encoding, got {:?}", e),
+    }
+
+    let mut latin1 = Config::default();
+    latin1.encoding = Encoding::Latin1;
+    let input = Cursor::new(b"char c = '\xe9';\n".to_vec());
+    let parse = parse_preprocessed_reader(&latin1, "in.c", input).expect("parse failed");
+    assert!(parse.source.contains("'\\351'"), "{}", parse.source);
+
+    let input = Cursor::new(b"int x = ;\n".to_vec());
+    match parse_preprocessed_reader(&config, "in.c", input).unwrap_err() {
+        Error::SyntaxError(ref e) => assert_eq!(e.file, "in.c"),
+        ref e => panic!("expected syntax error, got {:?}", e),
+    }
+    let input = Cursor::new(b"# 1 \"real.c\"\nint x = ;\n".to_vec());
+    match parse_preprocessed_reader(&config, "in.c", input).unwrap_err() {
+        Error::SyntaxError(ref e) => assert_eq!(e.file, "real.c"),
+        ref e => panic!("expected syntax error, got {:?}", e),
     }
 }
@@ -1,69 +1,241 @@
+//! Parser state: language options and identifiers known to name types
 use std::collections::{HashMap, HashSet};
 
 use ast::*;
 use span::Node;
 use strings;
 
+/// Kind of an identifier declared in a scope
 #[derive(Clone, Copy, Debug, PartialEq, Hash)]
 pub enum Symbol {
     Typename,
     Identifier,
 }
 
+/// Revision of the C standard, used to reject constructs that appeared only later
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Standard {
+    C89,
+    C99,
+    C11,
+    C17,
+    C23,
+}
+
+/// Default for `Env::max_nesting`
+///
+/// Low enough to parse on a 2 MiB thread stack in a debug build.
+pub const DEFAULT_MAX_NESTING: usize = 128;
+
+/// Expected token reported when input is nested deeper than `Env::max_nesting`
+pub const NESTING_TOO_DEEP: &'static str = "nesting too deep";
+
+/// State threaded through the parser
+///
+/// C grammar is ambiguous without knowing which identifiers are typedef names: `(foo)(x)` is a
+/// cast if `foo` names a type and a call otherwise.
 pub struct Env {
+    /// Declared identifiers, innermost scope last
     pub symbols: Vec<HashMap<String, Symbol>>,
+    /// Tags of defined enums, in the same scopes as `symbols`
+    pub enum_tags: Vec<HashSet<String>>,
+    pub standard: Standard,
     pub extensions_gnu: bool,
     pub extensions_clang: bool,
+    pub extensions_msvc: bool,
+    pub extensions_cuda: bool,
+    pub extensions_opencl: bool,
     pub reserved: HashSet<&'static str>,
+    /// Accept `<%`, `%>`, `<:`, `:>` and `%:` as spellings of `{`, `}`, `[`, `]` and `#`
+    ///
+    /// On by default in all standards, as C89 compilers and preprocessors implement the 1995
+    /// amendment that introduced them.
+    pub digraphs: bool,
+    /// Accept attributes in double square brackets, like `[[nodiscard]]`
+    ///
+    /// On by default in C23 and with GNU extensions.
+    pub std_attributes: bool,
+    /// Accept struct and union members that are only a tag or typedef name, whose members are
+    /// then accessed as members of the enclosing type, like `-fms-extensions`
+    ///
+    /// On by default with MSVC extensions.
+    pub ms_extensions: bool,
+    /// Accept `$` in identifiers, like `sys$open`
+    ///
+    /// On by default with GNU, Clang and MSVC extensions.
+    pub dollar_identifiers: bool,
+    /// Accept declarations and function definitions without a type specifier, like `static x;`
+    /// or `main(argc, argv) char **argv; { … }`, whose type is then `int` as in C89
+    ///
+    /// Off by default in all standards and flavors.
+    pub implicit_int: bool,
+    /// Accept the x86 named address spaces `__seg_fs` and `__seg_gs` as type qualifiers
+    ///
+    /// On by default with GNU extensions. They are not reserved, so when this is off they are
+    /// ordinary identifiers.
+    pub seg_address_spaces: bool,
+    /// Current nesting of expressions, declarators, initializers and statements
+    pub nesting: usize,
+    /// Deepest nesting to accept before failing the parse
+    pub max_nesting: usize,
+    /// Offset at which nesting first exceeded `max_nesting`
+    pub nesting_exceeded: Option<usize>,
+    // Depth and contents of the parameter scope kept for a function body, see
+    // `leave_parameter_scope`
+    parameter_scope: Option<(usize, HashMap<String, Symbol>, HashSet<String>)>,
 }
 
 impl Env {
     pub fn with_core() -> Env {
-        let mut reserved = HashSet::default();
-        reserved.extend(strings::RESERVED_C11.iter());
-        Env {
-            extensions_gnu: false,
-            extensions_clang: false,
-            symbols: vec![HashMap::default()],
-            reserved: reserved,
-        }
+        Env::with_standard(Standard::C11)
+    }
+
+    pub fn with_standard(standard: Standard) -> Env {
+        Env::new(standard, false, false)
     }
 
     pub fn with_gnu() -> Env {
-        let mut symbols = HashMap::default();
-        let mut reserved = HashSet::default();
-        symbols.insert("__builtin_va_list".to_owned(), Symbol::Typename);
-        reserved.extend(strings::RESERVED_C11.iter());
-        reserved.extend(strings::RESERVED_GNU.iter());
-        Env {
-            extensions_gnu: true,
-            extensions_clang: false,
-            symbols: vec![symbols],
-            reserved: reserved,
-        }
+        Env::new(Standard::C11, true, false)
     }
 
     pub fn with_clang() -> Env {
+        Env::new(Standard::C11, true, true)
+    }
+
+    pub fn with_msvc() -> Env {
+        let mut env = Env::with_core();
+        env.extensions_msvc = true;
+        env.ms_extensions = true;
+        env.dollar_identifiers = true;
+        env.reserved.extend(strings::RESERVED_MSVC.iter());
+        env
+    }
+
+    /// CUDA declaration specifiers and kernel launches on top of Clang extensions
+    pub fn with_cuda() -> Env {
+        let mut env = Env::with_clang();
+        env.extensions_cuda = true;
+        env.reserved.extend(strings::RESERVED_CUDA.iter());
+        env
+    }
+
+    /// OpenCL C qualifiers on top of C99 with Clang extensions
+    ///
+    /// The unprefixed spellings like `global` are not reserved, they are only recognized as
+    /// qualifiers when followed by the rest of a declaration.
+    pub fn with_opencl() -> Env {
+        let mut env = Env::new(Standard::C99, true, true);
+        env.extensions_opencl = true;
+        env.reserved.extend(strings::RESERVED_OPENCL.iter());
+        env
+    }
+
+    pub fn new(standard: Standard, gnu: bool, clang: bool) -> Env {
         let mut symbols = HashMap::default();
         let mut reserved = HashSet::default();
-        symbols.insert("__builtin_va_list".to_owned(), Symbol::Typename);
-        reserved.extend(strings::RESERVED_C11.iter());
-        reserved.extend(strings::RESERVED_GNU.iter());
-        reserved.extend(strings::RESERVED_CLANG.iter());
+        reserved.extend(strings::RESERVED_C89.iter());
+        if standard >= Standard::C99 || gnu {
+            reserved.extend(strings::RESERVED_C99.iter());
+        }
+        if standard >= Standard::C11 || gnu {
+            reserved.extend(strings::RESERVED_C11.iter());
+        }
+        if standard >= Standard::C23 {
+            reserved.extend(strings::RESERVED_C23.iter());
+        }
+        if gnu {
+            symbols.insert("__builtin_va_list".to_owned(), Symbol::Typename);
+            reserved.extend(strings::RESERVED_GNU.iter());
+        }
+        if clang {
+            reserved.extend(strings::RESERVED_CLANG.iter());
+        }
         Env {
-            extensions_gnu: true,
-            extensions_clang: true,
+            standard: standard,
+            extensions_gnu: gnu,
+            extensions_clang: clang,
+            extensions_msvc: false,
+            extensions_cuda: false,
+            extensions_opencl: false,
             symbols: vec![symbols],
+            enum_tags: vec![HashSet::default()],
             reserved: reserved,
+            digraphs: true,
+            std_attributes: standard >= Standard::C23 || gnu,
+            ms_extensions: false,
+            dollar_identifiers: gnu || clang,
+            implicit_int: false,
+            seg_address_spaces: gnu,
+            nesting: 0,
+            max_nesting: DEFAULT_MAX_NESTING,
+            nesting_exceeded: None,
+            parameter_scope: None,
+        }
+    }
+
+    /// Enter a nested construct at offset `pos`
+    ///
+    /// Once the limit is exceeded all further nesting fails, so that backtracking out of a
+    /// deeply nested input does not try every alternative at every level.
+    pub fn enter_nesting(&mut self, pos: usize) -> Result<(), &'static str> {
+        if self.nesting_exceeded.is_some() {
+            return Err(NESTING_TOO_DEEP);
+        }
+        if self.nesting >= self.max_nesting {
+            self.nesting_exceeded = Some(pos);
+            return Err(NESTING_TOO_DEEP);
         }
+        self.nesting += 1;
+        Ok(())
+    }
+
+    pub fn leave_nesting(&mut self) {
+        self.nesting -= 1;
     }
 
     pub fn enter_scope(&mut self) {
         self.symbols.push(HashMap::new());
+        self.enum_tags.push(HashSet::new());
     }
 
     pub fn leave_scope(&mut self) {
         self.symbols.pop().expect("more scope pops than pushes");
+        self.enum_tags.pop();
+    }
+
+    /// Leave the scope of a parameter list, keeping its declarations for a function body
+    ///
+    /// The parameters of a function definition are those of the outermost list, or of the
+    /// first one when the function returns a function pointer.
+    pub fn leave_parameter_scope(&mut self) {
+        let symbols = self.symbols.pop().expect("more scope pops than pushes");
+        let tags = self.enum_tags.pop().unwrap_or_default();
+        let depth = self.symbols.len();
+        let keep = match self.parameter_scope {
+            Some((d, _, _)) => d <= depth,
+            None => false,
+        };
+        if !keep {
+            self.parameter_scope = Some((depth, symbols, tags));
+        }
+    }
+
+    /// Forget the parameter scope kept by `leave_parameter_scope`
+    pub fn clear_parameter_scope(&mut self) {
+        self.parameter_scope = None;
+    }
+
+    /// Add the declarations of the kept parameter scope to the current scope, when it was left
+    /// at this depth
+    pub fn reopen_parameter_scope(&mut self) {
+        if let Some((depth, symbols, tags)) = self.parameter_scope.take() {
+            if depth == self.symbols.len() {
+                self.symbols.last_mut().expect("scope").extend(symbols);
+                if let Some(scope) = self.enum_tags.last_mut() {
+                    scope.extend(tags);
+                }
+            }
+        }
     }
 
     pub fn is_typename(&self, ident: &str) -> bool {
@@ -89,10 +261,22 @@ impl Env {
         scope.insert(s.to_string(), symbol);
     }
 
-    #[cfg(test)]
+    /// Declare `s` as a typedef name in the current scope
     pub fn add_typename(&mut self, s: &str) {
         self.add_symbol(s, Symbol::Typename)
     }
+
+    /// Record that the enum tagged `s` is complete in the current scope
+    pub fn add_enum_tag(&mut self, s: &str) {
+        if let Some(scope) = self.enum_tags.last_mut() {
+            scope.insert(s.to_string());
+        }
+    }
+
+    /// Whether an enum tagged `s` is complete in any enclosing scope
+    pub fn is_enum_tag(&self, s: &str) -> bool {
+        self.enum_tags.iter().any(|scope| scope.contains(s))
+    }
 }
 
 fn find_declarator_name(d: &DeclaratorKind) -> Option<&str> {
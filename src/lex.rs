@@ -0,0 +1,544 @@
+//! Split a preprocessed source into tokens
+//!
+//! ```
+//! use lang_c::driver::Flavor;
+//! use lang_c::lex::{tokens, TokenKind};
+//!
+//! let kinds = tokens("typeof(x) y;", Flavor::GnuC11)
+//!     .map(|t| t.unwrap().kind)
+//!     .filter(|&k| k != TokenKind::Whitespace)
+//!     .collect::<Vec<_>>();
+//! assert_eq!(kinds[0], TokenKind::Keyword);
+//! ```
+//!
+//! Tokens cover the whole input, so concatenating their text gives back the source. Spans are
+//! byte offsets and match the spans of the corresponding nodes produced by the parser.
+//! Identifiers are classified as keywords using the same tables as the parser, so `typeof` is
+//! a keyword in GNU and C23 flavors only.
+
+use std::error;
+use std::fmt;
+
+use ast::Constant;
+use astutil::{identifier_ucn, is_extended_identifier_char};
+use driver::{env_for_flavor, Flavor};
+use env::{Env, Standard};
+use parser;
+use span::Span;
+
+/// Classification of a token
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Identifier,
+    Keyword,
+    /// Integer constant, including suffix
+    Integer,
+    /// Floating constant, including suffix
+    Float,
+    /// Character constant, including prefix and quotes
+    Character,
+    /// Single string literal, including prefix and quotes
+    String,
+    Punctuator,
+    /// Line marker or other directive left by the preprocessor, without the final newline
+    LineMarker,
+    /// `#pragma` line, without the final newline
+    Pragma,
+    /// Spaces, tabs and newlines, or a byte order mark at the start of a line
+    Whitespace,
+    /// Block or line comment, only present when the preprocessor keeps comments
+    Comment,
+}
+
+/// Token of a preprocessed source
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// Text of the token, `&source[span.start..span.end]`
+    pub text: &'a str,
+}
+
+/// Text that does not form a valid token
+///
+/// Lexing continues after the offending text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LexError {
+    pub span: Span,
+    pub message: &'static str,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} at offset {}", self.message, self.span.start)
+    }
+}
+
+impl error::Error for LexError {}
+
+// Longest first, so that the first match is the longest one.
+const PUNCTUATORS: &'static [&'static str] = &[
+    "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||", "*=",
+    "/=", "%=", "+=", "-=", "&=", "^=", "|=", "[", "]", "(", ")", "{", "}", ".", "&", "*", "+",
+    "-", "~", "!", "/", "%", "<", ">", "^", "|", "?", ":", ";", "=", ",",
+];
+
+// Alternative spellings of `{`, `}`, `[`, `]` and `#`, see `Env::digraphs`
+const DIGRAPHS: &'static [&'static str] = &["<%", "%>", "<:", ":>", "%:"];
+
+/// Iterate over tokens of a preprocessed `source`, as parsed in `flavor`
+pub fn tokens<'a>(source: &'a str, flavor: Flavor) -> Tokens<'a> {
+    Tokens {
+        source: source,
+        pos: 0,
+        env: env_for_flavor(flavor),
+    }
+}
+
+/// Iterator returned by `tokens`
+pub struct Tokens<'a> {
+    source: &'a str,
+    pos: usize,
+    env: Env,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.source.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        let (kind, end) = match self.scan(start) {
+            Ok(t) => t,
+            Err((message, end)) => {
+                self.pos = end;
+                return Some(Err(LexError {
+                    span: Span::span(start, end),
+                    message: message,
+                }));
+            }
+        };
+        self.pos = end;
+
+        Some(Ok(Token {
+            kind: kind,
+            span: Span::span(start, end),
+            text: &self.source[start..end],
+        }))
+    }
+}
+
+impl<'a> Tokens<'a> {
+    // Kind and end of the token at `start`, or a message and the end of the text to skip
+    fn scan(&mut self, start: usize) -> Result<(TokenKind, usize), (&'static str, usize)> {
+        let s = &self.source[start..];
+        let b = s.as_bytes();
+        // directives may be indented, and included files may start with a byte order mark
+        let before =
+            self.source[..start].trim_right_matches(|c| c == ' ' || c == '\t' || c == '\u{feff}');
+        let at_line_start = before.is_empty() || before.ends_with('\n');
+
+        if at_line_start && s.starts_with('\u{feff}') {
+            return Ok((TokenKind::Whitespace, start + '\u{feff}'.len_utf8()));
+        }
+
+        let hash = b[0] == b'#' || self.env.digraphs && s.starts_with("%:");
+        if hash && at_line_start {
+            let end = start + directive_length(s);
+            let kind = if is_pragma(s) {
+                TokenKind::Pragma
+            } else {
+                TokenKind::LineMarker
+            };
+            return Ok((kind, end));
+        }
+
+        if whitespace_length(b) > 0 {
+            let mut n = 0;
+            while n < b.len() && whitespace_length(&b[n..]) > 0 {
+                n += whitespace_length(&b[n..]);
+            }
+            return Ok((TokenKind::Whitespace, start + n));
+        }
+
+        if s.starts_with("/*") {
+            return match s[2..].find("*/") {
+                Some(n) => Ok((TokenKind::Comment, start + 2 + n + 2)),
+                None => Err(("unterminated comment", self.source.len())),
+            };
+        }
+        if s.starts_with("//") {
+            let n = s.find('\n').unwrap_or(s.len());
+            return Ok((TokenKind::Comment, start + n));
+        }
+
+        let dollar = self.env.dollar_identifiers;
+        if !b[0].is_ascii_digit() && identifier_char_length(s, dollar) > 0 {
+            let mut n = 0;
+            while n < s.len() {
+                match identifier_char_length(&s[n..], dollar) {
+                    0 => break,
+                    l => n += l,
+                }
+            }
+            let word = &s[..n];
+            if n < b.len() && (b[n] == b'"' || b[n] == b'\'') {
+                if let "u8" | "u" | "U" | "L" = word {
+                    return self.quoted(start, n);
+                }
+            }
+            let kind = if self.env.reserved.contains(word) {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Identifier
+            };
+            return Ok((kind, start + n));
+        }
+
+        if b[0].is_ascii_digit() || (b[0] == b'.' && b.len() > 1 && b[1].is_ascii_digit()) {
+            let n = number_length(b, self.env.standard >= Standard::C23);
+            return match parser::constant(&s[..n], &mut self.env) {
+                Ok(Constant::Integer(_)) => Ok((TokenKind::Integer, start + n)),
+                Ok(Constant::Float(_)) => Ok((TokenKind::Float, start + n)),
+                _ => Err(("invalid numeric constant", start + n)),
+            };
+        }
+
+        if b[0] == b'"' || b[0] == b'\'' {
+            return self.quoted(start, 0);
+        }
+
+        let digraph = DIGRAPHS
+            .iter()
+            .find(|p| self.env.digraphs && s.starts_with(*p));
+        if let Some(p) = digraph {
+            return Ok((TokenKind::Punctuator, start + p.len()));
+        }
+        if let Some(p) = PUNCTUATORS.iter().find(|p| s.starts_with(*p)) {
+            return Ok((TokenKind::Punctuator, start + p.len()));
+        }
+
+        let n = s.chars().next().map_or(1, |c| c.len_utf8());
+        Err(("unexpected character", start + n))
+    }
+
+    // String literal or character constant with a prefix of `prefix` bytes
+    fn quoted(
+        &self,
+        start: usize,
+        prefix: usize,
+    ) -> Result<(TokenKind, usize), (&'static str, usize)> {
+        let b = &self.source.as_bytes()[start..];
+        let quote = b[prefix];
+        let mut n = prefix + 1;
+        while n < b.len() && b[n] != quote && b[n] != b'\n' {
+            n += if b[n] == b'\\' && b[n + 1..].starts_with(b"\r\n") {
+                3
+            } else if b[n] == b'\\' && n + 1 < b.len() {
+                2
+            } else {
+                1
+            };
+        }
+        if n >= b.len() || b[n] != quote {
+            return Err(("unterminated literal", start + n));
+        }
+        let kind = if quote == b'"' {
+            TokenKind::String
+        } else {
+            TokenKind::Character
+        };
+        Ok((kind, start + n + 1))
+    }
+}
+
+fn is_identifier_char(c: u8) -> bool {
+    c == b'_' || c.is_ascii_alphanumeric()
+}
+
+// Length of the identifier character, universal character name or extended character at the
+// start of `s`, if any
+fn identifier_char_length(s: &str, dollar: bool) -> usize {
+    let b = s.as_bytes();
+    match b[0] {
+        c if is_identifier_char(c) => 1,
+        b'$' if dollar => 1,
+        b'\\' => {
+            let len = match b.get(1) {
+                Some(&b'u') => 6,
+                Some(&b'U') => 10,
+                _ => return 0,
+            };
+            match s.get(2..len) {
+                Some(h)
+                    if h.bytes().all(|c| c.is_ascii_hexdigit()) && identifier_ucn(h).is_ok() =>
+                {
+                    len
+                }
+                _ => 0,
+            }
+        }
+        c if c >= 0x80 => s
+            .chars()
+            .next()
+            .filter(|&c| is_extended_identifier_char(c))
+            .map_or(0, char::len_utf8),
+        _ => 0,
+    }
+}
+
+// Length of the space, line break or backslash-newline at the start of `b`, if any
+fn whitespace_length(b: &[u8]) -> usize {
+    match b[0] {
+        b' ' | b'\t' | b'\n' => 1,
+        b'\r' if b.get(1) == Some(&b'\n') => 2,
+        b'\\' if b.get(1) == Some(&b'\n') => 2,
+        b'\\' if b.get(1) == Some(&b'\r') && b.get(2) == Some(&b'\n') => 3,
+        _ => 0,
+    }
+}
+
+// Directive up to the end of the line, where block comments may span lines
+fn directive_length(s: &str) -> usize {
+    let mut n = 0;
+    while n < s.len() {
+        if s[n..].starts_with("/*") {
+            match s[n + 2..].find("*/") {
+                Some(m) => n += 2 + m + 2,
+                None => return s.len(),
+            }
+        } else if s.as_bytes()[n] == b'\n' {
+            break;
+        } else {
+            n += 1;
+        }
+    }
+    n
+}
+
+fn is_pragma(s: &str) -> bool {
+    let rest =
+        s[if s.starts_with('#') { 1 } else { 2 }..].trim_left_matches(|c| c == ' ' || c == '\t');
+    rest.starts_with("pragma") && !rest[6..].bytes().next().map_or(false, is_identifier_char)
+}
+
+// Digits, period, exponent and suffix of a numeric constant, with digit separators if
+// `separators` is set
+fn number_length(b: &[u8], separators: bool) -> usize {
+    let hex = b.len() > 1 && b[0] == b'0' && (b[1] == b'x' || b[1] == b'X');
+    let mut n = if hex { 2 } else { 0 };
+    while n < b.len() {
+        let c = b[n];
+        let exponent = if hex {
+            c == b'p' || c == b'P'
+        } else {
+            c == b'e' || c == b'E'
+        };
+        if exponent && n + 1 < b.len() && (b[n + 1] == b'+' || b[n + 1] == b'-') {
+            n += 2;
+        } else if c == b'.' || is_identifier_char(c) {
+            n += 1;
+        } else if separators && c == b'\'' && b.get(n + 1).map_or(false, |&c| is_identifier_char(c))
+        {
+            n += 2;
+        } else {
+            break;
+        }
+    }
+    n
+}
+
+#[test]
+fn test_tokens() {
+    fn t(source: &str, flavor: Flavor, expected: &[(TokenKind, &str)]) {
+        let actual = tokens(source, flavor)
+            .map(|t| t.expect("lex failed"))
+            .filter(|t| t.kind != TokenKind::Whitespace)
+            .map(|t| (t.kind, t.text))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+    use self::TokenKind::*;
+
+    t(
+        "# 1 \"a.c\"\n#pragma pack(1)\nint x = 0x1p-2f+1e+3, *p; // done",
+        Flavor::StdC11,
+        &[
+            (LineMarker, "# 1 \"a.c\""),
+            (Pragma, "#pragma pack(1)"),
+            (Keyword, "int"),
+            (Identifier, "x"),
+            (Punctuator, "="),
+            (Float, "0x1p-2f"),
+            (Punctuator, "+"),
+            (Float, "1e+3"),
+            (Punctuator, ","),
+            (Punctuator, "*"),
+            (Identifier, "p"),
+            (Punctuator, ";"),
+            (Comment, "// done"),
+        ],
+    );
+    t(
+        "%:pragma once\nint a<:1:> = <%1%>;",
+        Flavor::StdC89,
+        &[
+            (Pragma, "%:pragma once"),
+            (Keyword, "int"),
+            (Identifier, "a"),
+            (Punctuator, "<:"),
+            (Integer, "1"),
+            (Punctuator, ":>"),
+            (Punctuator, "="),
+            (Punctuator, "<%"),
+            (Integer, "1"),
+            (Punctuator, "%>"),
+            (Punctuator, ";"),
+        ],
+    );
+    t(
+        "int \\\n x = \"a\\\r\nb\";",
+        Flavor::StdC11,
+        &[
+            (Keyword, "int"),
+            (Identifier, "x"),
+            (Punctuator, "="),
+            (String, "\"a\\\r\nb\""),
+            (Punctuator, ";"),
+        ],
+    );
+    t(
+        "a->b>>=.5e1+0x1e+u8\"s\\\"\"L'\\''",
+        Flavor::StdC11,
+        &[
+            (Identifier, "a"),
+            (Punctuator, "->"),
+            (Identifier, "b"),
+            (Punctuator, ">>="),
+            (Float, ".5e1"),
+            (Punctuator, "+"),
+            (Integer, "0x1e"),
+            (Punctuator, "+"),
+            (String, "u8\"s\\\"\""),
+            (Character, "L'\\''"),
+        ],
+    );
+    t(
+        "1'000+0x1'fp0",
+        Flavor::StdC23,
+        &[(Integer, "1'000"), (Punctuator, "+"), (Float, "0x1'fp0")],
+    );
+    t(
+        "1'0'",
+        Flavor::StdC11,
+        &[(Integer, "1"), (Character, "'0'")],
+    );
+    t("typeof", Flavor::GnuC11, &[(Keyword, "typeof")]);
+    t("typeof", Flavor::StdC11, &[(Identifier, "typeof")]);
+    t("typeof", Flavor::StdC23, &[(Keyword, "typeof")]);
+    t(
+        "typeof_unqual",
+        Flavor::GnuC11,
+        &[(Identifier, "typeof_unqual")],
+    );
+    t(
+        "typeof_unqual",
+        Flavor::StdC23,
+        &[(Keyword, "typeof_unqual")],
+    );
+    t("__stdcall", Flavor::MsvcC11, &[(Keyword, "__stdcall")]);
+    t("__stdcall", Flavor::StdC11, &[(Identifier, "__stdcall")]);
+    t("sys$open", Flavor::GnuC11, &[(Identifier, "sys$open")]);
+    t("caf\\u00e9", Flavor::StdC11, &[(Identifier, "caf\\u00e9")]);
+    t("int\u{e9}", Flavor::StdC11, &[(Identifier, "int\u{e9}")]);
+    t("$start", Flavor::MsvcC11, &[(Identifier, "$start")]);
+
+    let errors = tokens("int 1x @ \"a\nb", Flavor::StdC11)
+        .filter_map(|t| t.err())
+        .map(|e| (e.message, e.span.start, e.span.end))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        vec![
+            ("invalid numeric constant", 4, 6),
+            ("unexpected character", 7, 8),
+            ("unterminated literal", 9, 11),
+        ]
+    );
+}
+
+#[test]
+fn test_tokens_match_parse() {
+    use driver::{parse_preprocessed, Config};
+    use visit::{self, Visit};
+
+    struct Spans(Vec<Span>);
+
+    impl<'ast> Visit<'ast> for Spans {
+        fn visit_identifier(&mut self, n: &'ast ::ast::Identifier, span: &'ast Span) {
+            self.0.push(*span);
+            visit::visit_identifier(self, n, span);
+        }
+        fn visit_constant(&mut self, n: &'ast Constant, span: &'ast Span) {
+            self.0.push(*span);
+            visit::visit_constant(self, n, span);
+        }
+        fn visit_string_literal(&mut self, n: &'ast ::ast::StringLiteral, span: &'ast Span) {
+            self.0.push(*span);
+            visit::visit_string_literal(self, n, span);
+        }
+    }
+
+    let source = concat!(
+        "\u{feff}",
+        r#"# 1 "t.c"
+#pragma once
+typedef struct { unsigned long long n; char *s; } item;
+static const item items[] = { { 10ULL, "ten" }, { 0x7fu, "x" "y" } };
+int count(const item *it, double scale) {
+    /* sum */
+    int total = 0;
+    #pragma GCC unroll 2
+    for (int i = 0; i < 2 && it[i].n != 0; ++i)
+        total += (int) (it[i].n * scale * 1.5e-1f) + 'a';
+    return total >= 0 ? total : -1;
+}
+"#
+    );
+    let mut config = Config::with_gcc();
+    config.keep_comments = true;
+    let parse = parse_preprocessed(&config, source.into()).expect("parse failed");
+    let mut spans = Spans(Vec::new());
+    spans.visit_translation_unit(&parse.unit);
+
+    let tokens = tokens(source, config.flavor)
+        .map(|t| t.expect("lex failed"))
+        .collect::<Vec<_>>();
+    assert_eq!(tokens.iter().map(|t| t.text).collect::<String>(), source);
+    let directives = tokens
+        .iter()
+        .filter(|t| t.kind == TokenKind::LineMarker || t.kind == TokenKind::Pragma)
+        .map(|t| t.text)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        directives,
+        ["# 1 \"t.c\"", "#pragma once", "#pragma GCC unroll 2"]
+    );
+
+    for span in spans.0 {
+        let text = &source[span.start..span.end];
+        let first = tokens.iter().position(|t| t.span.start == span.start);
+        let last = tokens.iter().position(|t| t.span.end == span.end);
+        match (first, last) {
+            (Some(first), Some(last)) => {
+                // adjacent string literals are a single node
+                let kind = tokens[first].kind;
+                assert!(first == last || kind == TokenKind::String, "{:?}", text);
+            }
+            _ => panic!("no token boundary at {:?} {:?}", span, text),
+        }
+    }
+}
@@ -12,16 +12,30 @@
 #![allow(deprecated)]
 #![allow(ellipsis_inclusive_range_patterns)]
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "dev-serde"))]
+extern crate serde_json;
+
 pub mod ast;
+pub mod codegen;
+pub mod comments;
 pub mod driver;
+pub mod env;
+pub mod lex;
+pub mod literal;
 pub mod loc;
+pub mod omp;
 pub mod print;
+pub mod roundtrip;
 pub mod span;
 pub mod visit;
 
 mod astutil;
-mod env;
+mod cache;
 mod parser;
+#[cfg(feature = "internal-preprocessor")]
+mod preprocessor;
 mod strings;
 
 #[cfg(test)]
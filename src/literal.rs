@@ -0,0 +1,1067 @@
+//! Values of constants
+//!
+//! The AST keeps constants as they are written in the source. The functions here interpret
+//! them the way GCC does on its usual targets, where `char` is a signed 8-bit byte and `int`
+//! and `wchar_t` are 32 bits wide. The width of `long` and `long long` is given by a
+//! `DataModel`.
+
+use ast::{
+    CharEncoding, Character, Float, FloatBase, FloatFormat, Integer, IntegerBase, IntegerSize,
+};
+use ast::{StringLiteral, TS18661FloatFormat, TS18661FloatType};
+
+/// Error computing the value of a character constant
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CharError {
+    /// Octal or hexadecimal escape sequence larger than a code unit of the encoding
+    EscapeOutOfRange,
+    /// Character that does not fit in a single code unit of the encoding
+    NotEncodable,
+    /// Universal character name of a surrogate, a character beyond U+10FFFF or a character of
+    /// the basic character set (C11 6.4.3)
+    InvalidUcn,
+}
+
+/// Value of a character constant
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CharValue {
+    pub value: i64,
+    /// Implementation-defined interpretation of a constant with several characters
+    pub multichar: Option<MultiChar>,
+}
+
+/// How the value of a constant with more than one character is computed
+///
+/// These are the choices of GCC for implementation-defined values (C11 6.4.4.4p10).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MultiChar {
+    /// Bytes of a plain constant packed big-endian into an `int`
+    Packed,
+    /// Plain constant with more than four bytes, of which only the last four are kept
+    Truncated,
+    /// Prefixed constant, which takes the value of the last character
+    LastChar,
+}
+
+impl Character {
+    /// Value of the character constant
+    ///
+    /// A single character without prefix has type `int` but the value of a `char`, so `'\xff'`
+    /// is `-1`. Multi-character constants like `'abcd'` pack their bytes big-endian, so `'ab'`
+    /// is `'a' * 256 + 'b'`. Only the last four bytes are kept, as GCC does.
+    ///
+    /// Prefixed constants with several characters take the value of the last one. Characters
+    /// outside the basic character set take as many bytes in a plain constant as in UTF-8.
+    pub fn value(&self) -> Result<i64, CharError> {
+        self.evaluate().map(|v| v.value)
+    }
+
+    /// Value of the character constant, with how several characters were combined
+    ///
+    /// See `value` for the interpretation.
+    pub fn evaluate(&self) -> Result<CharValue, CharError> {
+        let body = &self.value[self.value.find('\'').unwrap_or(0) + 1..];
+        let body = body.rfind('\'').map_or(body, |e| &body[..e]);
+        let units = try!(escaped_units(body.chars()));
+
+        if self.encoding != CharEncoding::Plain {
+            let multichar = if units.len() > 1 {
+                Some(MultiChar::LastChar)
+            } else {
+                None
+            };
+            let value = match self.encoding {
+                CharEncoding::Utf8 => last_unit(units, 0x7f, 0xff),
+                CharEncoding::Utf16 => last_unit(units, 0xffff, 0xffff),
+                CharEncoding::Utf32 => last_unit(units, 0x10ffff, 0xffff_ffff),
+                _ => last_unit(units, 0x10ffff, 0xffff_ffff).map(|v| v as i32 as i64),
+            };
+            return value.map(|v| CharValue {
+                value: v,
+                multichar: multichar,
+            });
+        }
+
+        let mut bytes = Vec::new();
+        for unit in units {
+            match unit {
+                Unit::Char(c) => {
+                    let mut buf = [0; 4];
+                    bytes.extend(c.encode_utf8(&mut buf).bytes());
+                }
+                Unit::Code(v) if v <= 0xff => bytes.push(v as u8),
+                Unit::Code(_) => return Err(CharError::EscapeOutOfRange),
+            }
+        }
+        if bytes.len() == 1 {
+            return Ok(CharValue {
+                value: bytes[0] as i8 as i64,
+                multichar: None,
+            });
+        }
+        let packed = bytes.iter().fold(0u32, |v, &b| (v << 8) | b as u32);
+        Ok(CharValue {
+            value: packed as i32 as i64,
+            multichar: if bytes.len() > 4 {
+                Some(MultiChar::Truncated)
+            } else {
+                Some(MultiChar::Packed)
+            },
+        })
+    }
+}
+
+/// Error decoding a string literal
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StringError {
+    /// Octal or hexadecimal escape sequence larger than a code unit of the encoding
+    EscapeOutOfRange,
+    /// Universal character name of a surrogate, a character beyond U+10FFFF or a character of
+    /// the basic character set (C11 6.4.3)
+    InvalidUcn,
+    /// Concatenation of pieces with different encoding prefixes, like `u"a" U"b"`
+    IncompatibleEncodings,
+}
+
+/// Contents of a string literal
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DecodedString {
+    /// Encoding of the concatenated literal
+    pub encoding: CharEncoding,
+    /// Code units, without the terminating null character
+    pub units: CodeUnits,
+}
+
+/// Code units of a string literal
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CodeUnits {
+    /// Bytes of a plain or `u8` literal, with UTF-8 as execution character set
+    Bytes(Vec<u8>),
+    /// UTF-16 code units of a `u` literal
+    Utf16(Vec<u16>),
+    /// Code units of a `U` or `L` literal
+    Utf32(Vec<u32>),
+}
+
+/// Contents of a string literal
+pub trait DecodeString {
+    /// Concatenate the pieces of the literal and decode their escape sequences
+    ///
+    /// A piece without prefix takes the encoding of the other pieces, while pieces with
+    /// different prefixes can not be concatenated (C11 6.4.5p2). Escape sequences end at the
+    /// end of their piece, so `"\x41" "1"` is `"A1"`.
+    fn decode(&self) -> Result<DecodedString, StringError>;
+}
+
+impl DecodeString for StringLiteral {
+    fn decode(&self) -> Result<DecodedString, StringError> {
+        let mut encoding = CharEncoding::Plain;
+        for piece in self {
+            if piece.encoding == CharEncoding::Plain || piece.encoding == encoding {
+                continue;
+            }
+            if encoding != CharEncoding::Plain {
+                return Err(StringError::IncompatibleEncodings);
+            }
+            encoding = piece.encoding.clone();
+        }
+
+        let mut units = Vec::new();
+        for piece in self {
+            let body = &piece.value[piece.value.find('"').unwrap_or(0) + 1..];
+            let body = body.rfind('"').map_or(body, |e| &body[..e]);
+            units.extend(try!(escaped_units(body.chars()).map_err(|e| match e {
+                CharError::InvalidUcn => StringError::InvalidUcn,
+                _ => StringError::EscapeOutOfRange,
+            })));
+        }
+
+        let units = match encoding {
+            CharEncoding::Plain | CharEncoding::Utf8 => {
+                let mut bytes = Vec::new();
+                for unit in units {
+                    match unit {
+                        Unit::Char(c) => {
+                            let mut buf = [0; 4];
+                            bytes.extend(c.encode_utf8(&mut buf).bytes());
+                        }
+                        Unit::Code(v) if v <= 0xff => bytes.push(v as u8),
+                        Unit::Code(_) => return Err(StringError::EscapeOutOfRange),
+                    }
+                }
+                CodeUnits::Bytes(bytes)
+            }
+            CharEncoding::Utf16 => {
+                let mut code = Vec::new();
+                for unit in units {
+                    match unit {
+                        Unit::Char(c) => {
+                            let mut buf = [0; 2];
+                            code.extend(c.encode_utf16(&mut buf).iter());
+                        }
+                        Unit::Code(v) if v <= 0xffff => code.push(v as u16),
+                        Unit::Code(_) => return Err(StringError::EscapeOutOfRange),
+                    }
+                }
+                CodeUnits::Utf16(code)
+            }
+            CharEncoding::Utf32 | CharEncoding::Wide => CodeUnits::Utf32(
+                units
+                    .into_iter()
+                    .map(|unit| match unit {
+                        Unit::Char(c) => c as u32,
+                        Unit::Code(v) => v,
+                    })
+                    .collect(),
+            ),
+        };
+
+        Ok(DecodedString {
+            encoding: encoding,
+            units: units,
+        })
+    }
+}
+
+/// Error computing the value or the type of an integer constant
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IntError {
+    /// Digit not allowed in the base of the constant
+    InvalidDigit,
+    /// Value does not fit in 128 bits
+    Overflow,
+    /// Value does not fit in any of the types allowed by the suffix
+    TooLarge,
+}
+
+/// Widths in bits of the integer types that depend on the target
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DataModel {
+    pub int: u32,
+    pub long: u32,
+    pub long_long: u32,
+}
+
+impl DataModel {
+    /// 32-bit `int`, `long` and pointers
+    pub const ILP32: DataModel = DataModel {
+        int: 32,
+        long: 32,
+        long_long: 64,
+    };
+    /// 64-bit `long`, used by Unix-like systems
+    pub const LP64: DataModel = DataModel {
+        int: 32,
+        long: 64,
+        long_long: 64,
+    };
+    /// 32-bit `long` with 64-bit pointers, used by 64-bit Windows
+    pub const LLP64: DataModel = DataModel {
+        int: 32,
+        long: 32,
+        long_long: 64,
+    };
+}
+
+/// Type of an integer constant
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct IntegerType {
+    /// `Int`, `Long` or `LongLong` for the standard types, `BitPrecise` for `_BitInt(width)`
+    pub size: IntegerSize,
+    pub unsigned: bool,
+    /// Width in bits, including the sign bit
+    pub width: u32,
+}
+
+impl IntegerType {
+    /// Largest value of the type
+    pub fn max_value(&self) -> u128 {
+        let bits = if self.unsigned {
+            self.width
+        } else {
+            self.width - 1
+        };
+        if bits >= 128 {
+            u128::MAX
+        } else {
+            (1 << bits) - 1
+        }
+    }
+}
+
+impl Integer {
+    /// Value of the integer constant
+    ///
+    /// Digit separators are skipped. Values that need more than 128 bits are an error.
+    pub fn value(&self) -> Result<u128, IntError> {
+        let radix = match self.base {
+            IntegerBase::Decimal => 10,
+            IntegerBase::Octal | IntegerBase::OctalExplicit => 8,
+            IntegerBase::Hexadecimal => 16,
+            IntegerBase::Binary => 2,
+        };
+        let mut value = 0u128;
+        for c in self.number.chars().filter(|&c| c != '\'') {
+            let d = try!(c.to_digit(radix).ok_or(IntError::InvalidDigit));
+            value = try!(value
+                .checked_mul(radix as u128)
+                .and_then(|v| v.checked_add(d as u128))
+                .ok_or(IntError::Overflow));
+        }
+        Ok(value)
+    }
+
+    /// Type of the integer constant
+    ///
+    /// This is the first type that can represent the value from the candidates allowed by
+    /// the suffix (C11 6.4.4.1). Decimal constants without `u` suffix are only candidates for
+    /// signed types, other constants also for the unsigned type of each size. A constant with
+    /// `wb` suffix has the smallest `_BitInt` type that can represent it (C23 6.4.4.1).
+    pub fn integer_type(&self, model: &DataModel) -> Result<IntegerType, IntError> {
+        let value = try!(self.value());
+        let unsigned = self.suffix.unsigned;
+
+        if self.suffix.size == IntegerSize::BitPrecise {
+            let bits = 128 - value.leading_zeros();
+            // `unsigned _BitInt(1)` and `_BitInt(2)` are the narrowest types
+            let width = if unsigned {
+                bits.max(1)
+            } else {
+                bits.max(1) + 1
+            };
+            return Ok(IntegerType {
+                size: IntegerSize::BitPrecise,
+                unsigned: unsigned,
+                width: width,
+            });
+        }
+
+        let sizes = [
+            (IntegerSize::Int, model.int),
+            (IntegerSize::Long, model.long),
+            (IntegerSize::LongLong, model.long_long),
+        ];
+        let decimal = self.base == IntegerBase::Decimal;
+        for &(size, width) in sizes.iter().filter(|s| s.0 >= self.suffix.size) {
+            for &u in &[false, true] {
+                if (u && !unsigned && decimal) || (!u && unsigned) {
+                    continue;
+                }
+                let t = IntegerType {
+                    size: size,
+                    unsigned: u,
+                    width: width,
+                };
+                if value <= t.max_value() {
+                    return Ok(t);
+                }
+            }
+        }
+        Err(IntError::TooLarge)
+    }
+}
+
+/// Error computing the value of a floating constant
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FloatError {
+    /// Number that is not a floating constant
+    InvalidSyntax,
+    /// Value too large for the format, that would be rounded to infinity
+    Overflow,
+}
+
+/// Exact value of a floating constant, before rounding to a format
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FloatParts {
+    /// Base of the significand digits, 10 or 16
+    pub radix: u32,
+    /// Significand digits without the radix point and without leading zeros, empty for zero
+    pub significand: String,
+    /// Power of ten for decimal constants, power of two for hexadecimal constants
+    ///
+    /// The value of the constant is the `significand` read as an integer, times the base to
+    /// this power.
+    pub exponent: i64,
+}
+
+impl Float {
+    /// Value of the floating constant
+    ///
+    /// The value is correctly rounded to the precision of `float` for the `f` and `f32`
+    /// suffixes and of `double` otherwise, even for suffixes denoting wider types. The range
+    /// of fixed-point types is not checked.
+    pub fn value(&self) -> Result<f64, FloatError> {
+        let float32 = FloatFormat::TS18661Format(TS18661FloatType {
+            format: TS18661FloatFormat::BinaryInterchange,
+            width: 32,
+        });
+        let single = self.suffix.format == FloatFormat::Float || self.suffix.format == float32;
+
+        let value = match self.base {
+            FloatBase::Decimal => {
+                try!(self.parts());
+                let digits = self.digits();
+                if single {
+                    try!(digits.parse::<f32>().map_err(|_| FloatError::InvalidSyntax)) as f64
+                } else {
+                    try!(digits.parse::<f64>().map_err(|_| FloatError::InvalidSyntax))
+                }
+            }
+            FloatBase::Hexadecimal => {
+                let parts = try!(self.parts());
+                if single {
+                    binary_float(&parts, 24, -126)
+                } else {
+                    binary_float(&parts, 53, -1022)
+                }
+            }
+        };
+
+        if value.is_infinite() || (single && (value as f32).is_infinite()) {
+            Err(FloatError::Overflow)
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Significand and exponent of the floating constant
+    pub fn parts(&self) -> Result<FloatParts, FloatError> {
+        let digits = self.digits();
+        let (radix, marker, scale) = match self.base {
+            FloatBase::Decimal => (10, 'e', 1),
+            FloatBase::Hexadecimal => (16, 'p', 4),
+        };
+        let (mantissa, exponent) = match digits.find(|c: char| c.to_ascii_lowercase() == marker) {
+            Some(i) => (&digits[..i], &digits[i + 1..]),
+            None => (&digits[..], ""),
+        };
+        let (whole, fraction) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+            None => (mantissa, ""),
+        };
+        let valid = |s: &str| s.chars().all(|c| c.is_digit(radix));
+        if whole.len() + fraction.len() == 0 || !valid(whole) || !valid(fraction) {
+            return Err(FloatError::InvalidSyntax);
+        }
+        if self.base == FloatBase::Hexadecimal && exponent.is_empty() {
+            return Err(FloatError::InvalidSyntax);
+        }
+
+        let exponent = if exponent.is_empty() {
+            0
+        } else {
+            let (negative, e) = match exponent.as_bytes()[0] {
+                b'-' => (true, &exponent[1..]),
+                b'+' => (false, &exponent[1..]),
+                _ => (false, exponent),
+            };
+            if e.is_empty() || !e.chars().all(|c| c.is_ascii_digit()) {
+                return Err(FloatError::InvalidSyntax);
+            }
+            // Exponents beyond the range of `i64` only overflow or underflow
+            let e = e.parse::<i64>().unwrap_or(i64::MAX / 2);
+            if negative {
+                -e
+            } else {
+                e
+            }
+        };
+
+        let all = format!("{}{}", whole, fraction);
+        let significand = all.trim_left_matches('0').trim_right_matches('0');
+        let exponent = if significand.is_empty() {
+            0
+        } else {
+            let trailing = all.len() - all.trim_right_matches('0').len();
+            exponent
+                .saturating_sub(fraction.len() as i64 * scale)
+                .saturating_add(trailing as i64 * scale)
+        };
+
+        Ok(FloatParts {
+            radix: radix,
+            significand: significand.to_owned(),
+            exponent: exponent,
+        })
+    }
+}
+
+// Value of a hexadecimal constant, rounded to nearest even with `precision` bits in the
+// significand and `min_exp` the exponent of the smallest normal number
+fn binary_float(parts: &FloatParts, precision: i64, min_exp: i64) -> f64 {
+    // Leading 16 hexadecimal digits, and whether any digit after them is not zero
+    let digits = parts.significand.as_bytes();
+    let kept = digits.len().min(16);
+    let mut m = 0u64;
+    for &d in &digits[..kept] {
+        m = m << 4 | (d as char).to_digit(16).unwrap() as u64;
+    }
+    if m == 0 {
+        return 0.0;
+    }
+    let sticky = digits.len() > kept;
+    let exp = parts
+        .exponent
+        .saturating_add((digits.len() - kept) as i64 * 4);
+
+    // m * 2^exp with the leading bit of m at position 63
+    let shift = m.leading_zeros() as i64;
+    let m = m << shift;
+    let exp = exp.saturating_sub(shift);
+    let leading = exp.saturating_add(63);
+
+    // Subnormal results have fewer bits of precision
+    let bits = if leading < min_exp {
+        precision - min_exp.saturating_sub(leading).min(precision + 1)
+    } else {
+        precision
+    };
+    let drop = 64 - bits;
+    if drop > 64 {
+        return 0.0;
+    }
+    let m = m as u128;
+    let mut result = (m >> drop) as u64;
+    let rest = m & ((1 << drop) - 1);
+    let half = 1 << (drop - 1);
+    if rest > half || (rest == half && (sticky || result & 1 == 1)) {
+        result += 1;
+    }
+
+    // Scaling by a power of two is exact until the result is reached
+    let mut value = result as f64;
+    let mut e = exp.saturating_add(drop);
+    while e > 0 && value.is_finite() {
+        let step = e.min(1000);
+        value *= 2f64.powi(step as i32);
+        e -= step;
+    }
+    while e < 0 && value != 0.0 {
+        let step = e.max(-1000);
+        value *= 2f64.powi(step as i32);
+        e -= step;
+    }
+    value
+}
+
+// Character as written or given by a universal character name, or code unit given by an
+// escape sequence
+enum Unit {
+    Char(char),
+    Code(u32),
+}
+
+// Value of the last unit in a prefixed constant
+fn last_unit(units: Vec<Unit>, max_char: u32, max_code: u32) -> Result<i64, CharError> {
+    match units.into_iter().last() {
+        Some(Unit::Char(c)) if c as u32 <= max_char => Ok(c as u32 as i64),
+        Some(Unit::Char(_)) => Err(CharError::NotEncodable),
+        Some(Unit::Code(v)) if v <= max_code => Ok(v as i64),
+        Some(Unit::Code(_)) => Err(CharError::EscapeOutOfRange),
+        None => Ok(0),
+    }
+}
+
+// Split the body of a character constant or string literal into units, decoding escape
+// sequences and skipping line splices
+fn escaped_units<I: Iterator<Item = char>>(body: I) -> Result<Vec<Unit>, CharError> {
+    let mut units = Vec::new();
+    let mut chars = body.peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            units.push(Unit::Char(c));
+            continue;
+        }
+        let e = match chars.next() {
+            Some(e) => e,
+            None => break,
+        };
+        let simple = match e {
+            '\r' | '\n' => {
+                if e == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            'a' => 7,
+            'b' => 8,
+            'e' | 'E' => 27,
+            'f' => 12,
+            'n' => 10,
+            'r' => 13,
+            't' => 9,
+            'v' => 11,
+            '0'...'7' => {
+                let mut v = e as u32 - '0' as u32;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) => v = v * 8 + d,
+                        None => break,
+                    }
+                    chars.next();
+                }
+                v
+            }
+            'x' => {
+                let mut v = 0u32;
+                while let Some(d) = chars.peek().and_then(|c| c.to_digit(16)) {
+                    v = try!(v
+                        .checked_mul(16)
+                        .map(|v| v + d)
+                        .ok_or(CharError::EscapeOutOfRange));
+                    chars.next();
+                }
+                v
+            }
+            'u' | 'U' => {
+                let len = if e == 'u' { 4 } else { 8 };
+                let mut v = 0u32;
+                for _ in 0..len {
+                    match chars.next().and_then(|c| c.to_digit(16)) {
+                        Some(d) => v = v * 16 + d,
+                        None => return Err(CharError::InvalidUcn),
+                    }
+                }
+                units.push(Unit::Char(try!(ucn_char(v))));
+                continue;
+            }
+            e => e as u32,
+        };
+        units.push(Unit::Code(simple));
+    }
+    Ok(units)
+}
+
+// Character named by a universal character name (C11 6.4.3p2)
+fn ucn_char(v: u32) -> Result<char, CharError> {
+    let basic = v < 0xa0 && v != 0x24 && v != 0x40 && v != 0x60;
+    match ::std::char::from_u32(v) {
+        Some(c) if !basic => Ok(c),
+        _ => Err(CharError::InvalidUcn),
+    }
+}
+
+#[cfg(test)]
+fn char_value(s: &str) -> Result<i64, CharError> {
+    let encoding = match s.find('\'') {
+        Some(0) => CharEncoding::Plain,
+        Some(1) if s.starts_with('L') => CharEncoding::Wide,
+        Some(1) if s.starts_with('u') => CharEncoding::Utf16,
+        Some(1) => CharEncoding::Utf32,
+        _ => CharEncoding::Utf8,
+    };
+    Character {
+        encoding: encoding,
+        value: s.to_owned(),
+    }
+    .value()
+}
+
+#[cfg(test)]
+fn integer(base: IntegerBase, number: &str, suffix: &str) -> Integer {
+    Integer {
+        base: base,
+        number: number.to_owned().into_boxed_str(),
+        suffix: ::astutil::int_suffix(suffix).unwrap(),
+    }
+}
+
+#[cfg(test)]
+fn int_type(
+    base: IntegerBase,
+    number: &str,
+    suffix: &str,
+) -> Result<(IntegerSize, bool), IntError> {
+    integer(base, number, suffix)
+        .integer_type(&DataModel::LP64)
+        .map(|t| (t.size, t.unsigned))
+}
+
+#[test]
+fn test_integer_value() {
+    use self::IntegerBase::*;
+    assert_eq!(integer(Decimal, "0", "").value(), Ok(0));
+    assert_eq!(integer(Decimal, "1'000'000", "").value(), Ok(1000000));
+    assert_eq!(integer(Octal, "777", "").value(), Ok(0o777));
+    assert_eq!(integer(OctalExplicit, "17", "").value(), Ok(15));
+    assert_eq!(integer(Binary, "1010", "").value(), Ok(10));
+    assert_eq!(
+        integer(Hexadecimal, "FFFFFFFFFFFFFFFF", "").value(),
+        Ok(0xFFFFFFFFFFFFFFFF)
+    );
+    assert_eq!(
+        integer(Decimal, "18446744073709551616", "").value(),
+        Ok(18446744073709551616)
+    );
+    assert_eq!(
+        integer(Hexadecimal, "ffffffff'ffffffff'ffffffff'ffffffff", "").value(),
+        Ok(u128::MAX)
+    );
+    assert_eq!(
+        integer(Hexadecimal, "1'00000000'00000000'00000000'00000000", "").value(),
+        Err(IntError::Overflow)
+    );
+    assert_eq!(
+        integer(Decimal, "340282366920938463463374607431768211456", "").value(),
+        Err(IntError::Overflow)
+    );
+    assert_eq!(integer(Octal, "8", "").value(), Err(IntError::InvalidDigit));
+}
+
+#[test]
+fn test_integer_type() {
+    use self::IntegerBase::*;
+    use self::IntegerSize::*;
+    assert_eq!(int_type(Decimal, "2147483647", ""), Ok((Int, false)));
+    assert_eq!(int_type(Decimal, "2147483648", ""), Ok((Long, false)));
+    assert_eq!(int_type(Hexadecimal, "80000000", ""), Ok((Int, true)));
+    assert_eq!(int_type(Decimal, "1", "u"), Ok((Int, true)));
+    assert_eq!(int_type(Decimal, "1", "ll"), Ok((LongLong, false)));
+    assert_eq!(int_type(Decimal, "4294967296", "u"), Ok((Long, true)));
+    assert_eq!(
+        int_type(Decimal, "9223372036854775807", ""),
+        Ok((Long, false))
+    );
+    assert_eq!(
+        int_type(Decimal, "9223372036854775808", ""),
+        Err(IntError::TooLarge)
+    );
+    assert_eq!(
+        int_type(Hexadecimal, "FFFFFFFFFFFFFFFF", ""),
+        Ok((Long, true))
+    );
+    assert_eq!(
+        int_type(Hexadecimal, "FFFFFFFFFFFFFFFF", "ll"),
+        Ok((LongLong, true))
+    );
+    assert_eq!(
+        int_type(Decimal, "18446744073709551615", "ull"),
+        Ok((LongLong, true))
+    );
+    assert_eq!(
+        int_type(Decimal, "18446744073709551616", "u"),
+        Err(IntError::TooLarge)
+    );
+
+    let llp64 = |number: &str, suffix: &str| {
+        integer(Decimal, number, suffix)
+            .integer_type(&DataModel::LLP64)
+            .map(|t| (t.size, t.unsigned))
+    };
+    assert_eq!(llp64("2147483648", ""), Ok((LongLong, false)));
+    assert_eq!(llp64("2147483648", "lu"), Ok((Long, true)));
+
+    let width = |number: &str, suffix: &str| {
+        integer(Decimal, number, suffix)
+            .integer_type(&DataModel::LP64)
+            .map(|t| t.width)
+    };
+    assert_eq!(width("0", "wb"), Ok(2));
+    assert_eq!(width("0", "uwb"), Ok(1));
+    assert_eq!(width("255", "wb"), Ok(9));
+    assert_eq!(width("255", "uwb"), Ok(8));
+    assert_eq!(width("18446744073709551616", "wb"), Ok(66));
+}
+
+#[cfg(test)]
+fn float_value(s: &str) -> Result<f64, FloatError> {
+    use ast::FloatSuffix;
+    let (base, number) = if s.starts_with("0x") {
+        (FloatBase::Hexadecimal, &s[2..])
+    } else {
+        (FloatBase::Decimal, s)
+    };
+    // `f` is a hexadecimal digit before the exponent
+    let suffixed = base == FloatBase::Decimal || number.contains('p');
+    let (number, format) = if suffixed && number.ends_with('f') {
+        (&number[..number.len() - 1], FloatFormat::Float)
+    } else if number.ends_with('L') {
+        (&number[..number.len() - 1], FloatFormat::LongDouble)
+    } else {
+        (number, FloatFormat::Double)
+    };
+    Float {
+        base: base,
+        number: number.to_owned().into_boxed_str(),
+        suffix: FloatSuffix {
+            format: format,
+            imaginary: false,
+        },
+    }
+    .value()
+}
+
+// Expected values are from `strtod` and `strtof`
+#[test]
+fn test_decimal_float_value() {
+    let bits = |s: &str| float_value(s).map(f64::to_bits);
+    assert_eq!(bits("1e-5"), Ok(0x3ee4f8b588e368f1));
+    assert_eq!(bits(".5"), Ok(0x3fe0000000000000));
+    assert_eq!(bits(".5L"), Ok(0x3fe0000000000000));
+    assert_eq!(bits("0.1"), Ok(0x3fb999999999999a));
+    assert_eq!(bits("1'000.0"), Ok(1000f64.to_bits()));
+    assert_eq!(bits("5."), Ok(5f64.to_bits()));
+    assert_eq!(bits("1.e3"), Ok(1000f64.to_bits()));
+    assert_eq!(bits("2.2250738585072011e-308"), Ok(0x000fffffffffffff));
+    assert_eq!(bits("4.9e-324"), Ok(0x0000000000000001));
+    assert_eq!(bits("1.7976931348623157e308"), Ok(0x7fefffffffffffff));
+    assert_eq!(
+        bits("123456789012345678901234567890"),
+        Ok(0x45f8ee90ff6c373e)
+    );
+    assert_eq!(bits("1e-5f"), Ok((1e-5f32 as f64).to_bits()));
+    assert_eq!(float_value("1e400"), Err(FloatError::Overflow));
+    assert_eq!(float_value("1e39f"), Err(FloatError::Overflow));
+    assert_eq!(float_value("1e-400"), Ok(0.0));
+    assert_eq!(float_value("1e+"), Err(FloatError::InvalidSyntax));
+    assert_eq!(float_value("inf"), Err(FloatError::InvalidSyntax));
+}
+
+#[test]
+fn test_hexadecimal_float_value() {
+    let bits = |s: &str| float_value(s).map(f64::to_bits);
+    assert_eq!(bits("0x1.8p3"), Ok(0x4028000000000000));
+    assert_eq!(bits("0x10p0"), Ok(0x4030000000000000));
+    assert_eq!(bits("0x.8p1"), Ok(0x3ff0000000000000));
+    assert_eq!(bits("0xABCp-4"), Ok(0x4065780000000000));
+    assert_eq!(bits("0xA'BCp-4"), Ok(0x4065780000000000));
+    assert_eq!(bits("0x1p-1022"), Ok(0x0010000000000000));
+    assert_eq!(bits("0x0.8p-1022"), Ok(0x0008000000000000));
+    assert_eq!(bits("0x1p-1074"), Ok(0x0000000000000001));
+    assert_eq!(bits("0x1p-1075"), Ok(0x0000000000000000));
+    assert_eq!(bits("0x1.0000000000001p-1075"), Ok(0x0000000000000001));
+    assert_eq!(bits("0x1.00000000000008p0"), Ok(0x3ff0000000000000));
+    assert_eq!(bits("0x1.000000000000080001p0"), Ok(0x3ff0000000000001));
+    assert_eq!(bits("0x1.00000000000018p0"), Ok(0x3ff0000000000002));
+    assert_eq!(bits("0x1.fffffffffffff7ffp1023"), Ok(0x7fefffffffffffff));
+    assert_eq!(
+        float_value("0x1.fffffffffffff8p1023"),
+        Err(FloatError::Overflow)
+    );
+    assert_eq!(bits("0x1.ffffffp-1f"), Ok(1f64.to_bits()));
+    assert_eq!(bits("0x1p127f"), Ok(2f64.powi(127).to_bits()));
+    assert_eq!(float_value("0x1p128f"), Err(FloatError::Overflow));
+    assert_eq!(float_value("0x0p99999999999999999999"), Ok(0.0));
+    assert_eq!(float_value("0x1p"), Err(FloatError::InvalidSyntax));
+}
+
+#[test]
+fn test_float_parts() {
+    let parts = |base: FloatBase, number: &str| {
+        use ast::FloatSuffix;
+        Float {
+            base: base,
+            number: number.to_owned().into_boxed_str(),
+            suffix: FloatSuffix {
+                format: FloatFormat::Double,
+                imaginary: false,
+            },
+        }
+        .parts()
+        .map(|p| (p.radix, p.significand, p.exponent))
+    };
+    let p = |r: u32, s: &str, e: i64| Ok((r, s.to_owned(), e));
+    assert_eq!(parts(FloatBase::Decimal, "12.50e3"), p(10, "125", 2));
+    assert_eq!(parts(FloatBase::Decimal, "0.001"), p(10, "1", -3));
+    assert_eq!(parts(FloatBase::Decimal, "00.0"), p(10, "", 0));
+    assert_eq!(parts(FloatBase::Hexadecimal, "1.8p3"), p(16, "18", -1));
+    assert_eq!(parts(FloatBase::Hexadecimal, "10p0"), p(16, "1", 4));
+}
+
+#[cfg(test)]
+fn decode(pieces: &[&str]) -> Result<DecodedString, StringError> {
+    let literal: StringLiteral = pieces.iter().map(|p| ::astutil::string_piece(p)).collect();
+    literal.decode()
+}
+
+#[cfg(test)]
+fn decode_bytes(pieces: &[&str]) -> Vec<u8> {
+    match decode(pieces).map(|d| d.units) {
+        Ok(CodeUnits::Bytes(b)) => b,
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_string_escapes() {
+    assert_eq!(decode_bytes(&["\"abc\""]), b"abc");
+    assert_eq!(decode_bytes(&["\"\""]), b"");
+    assert_eq!(
+        decode_bytes(&["\"\\'\\\"\\?\\\\\\a\\b\\e\\f\\n\\r\\t\\v\""]),
+        b"'\"?\\\x07\x08\x1b\x0c\n\r\t\x0b"
+    );
+    assert_eq!(decode_bytes(&["\"\\0a\\0\\1234\\377\""]), b"\0a\0\x534\xff");
+    assert_eq!(decode_bytes(&["\"\\x41\\x000042\""]), b"AB");
+    assert_eq!(
+        decode_bytes(&["\"\\u00e9\\U0001F600\""]),
+        "\u{e9}\u{1f600}".as_bytes()
+    );
+    assert_eq!(decode_bytes(&["\"\u{e9}\""]), "\u{e9}".as_bytes());
+    assert_eq!(decode_bytes(&["\"a\\\nb\""]), b"ab");
+    assert_eq!(decode(&["\"\\x100\""]), Err(StringError::EscapeOutOfRange));
+    assert_eq!(decode(&["\"\\ud800\""]), Err(StringError::InvalidUcn));
+}
+
+#[test]
+fn test_string_concatenation() {
+    assert_eq!(decode_bytes(&["\"\\x41\"", "\"1\""]), b"A1");
+    assert_eq!(decode(&["\"\\x411\""]), Err(StringError::EscapeOutOfRange));
+    assert_eq!(decode_bytes(&["\"\\1\"", "\"23\""]), b"\x0123");
+    assert_eq!(decode_bytes(&["\"a\\0\"", "\"b\""]), b"a\0b");
+    assert_eq!(decode_bytes(&["\"a\"", "u8\"b\"", "\"c\""]), b"abc");
+    assert_eq!(
+        decode(&["\"a\"", "u8\"b\""]).map(|d| d.encoding),
+        Ok(CharEncoding::Utf8)
+    );
+    assert_eq!(
+        decode(&["\"a\"", "u\"\u{1f600}\"", "\"\\x41\""]),
+        Ok(DecodedString {
+            encoding: CharEncoding::Utf16,
+            units: CodeUnits::Utf16(vec![97, 0xd83d, 0xde00, 0x41]),
+        })
+    );
+    assert_eq!(
+        decode(&["L\"a\"", "\"\\xffffffff\"", "L\"\""]),
+        Ok(DecodedString {
+            encoding: CharEncoding::Wide,
+            units: CodeUnits::Utf32(vec![97, 0xffffffff]),
+        })
+    );
+    assert_eq!(
+        decode(&["U\"\\U0001F600\"", "\"b\""]).map(|d| d.units),
+        Ok(CodeUnits::Utf32(vec![0x1f600, 98]))
+    );
+    assert_eq!(
+        decode(&["u\"a\"", "U\"b\""]),
+        Err(StringError::IncompatibleEncodings)
+    );
+    assert_eq!(
+        decode(&["u8\"a\"", "L\"b\""]),
+        Err(StringError::IncompatibleEncodings)
+    );
+    assert_eq!(
+        decode(&["u\"\\x10000\""]),
+        Err(StringError::EscapeOutOfRange)
+    );
+}
+
+#[test]
+fn test_single_char_value() {
+    assert_eq!(char_value("'a'"), Ok(97));
+    assert_eq!(char_value("'\\n'"), Ok(10));
+    assert_eq!(char_value("'\\0'"), Ok(0));
+    assert_eq!(char_value("'\\''"), Ok(39));
+    assert_eq!(char_value("'\\377'"), Ok(-1));
+    assert_eq!(char_value("'\\x80'"), Ok(-128));
+    assert_eq!(char_value("'\\x100'"), Err(CharError::EscapeOutOfRange));
+}
+
+#[test]
+fn test_multichar_value() {
+    assert_eq!(char_value("'ab'"), Ok(0x6162));
+    assert_eq!(char_value("'RIFF'"), Ok(0x52494646));
+    assert_eq!(char_value("'\\0\\1'"), Ok(1));
+    assert_eq!(char_value("'\\101B'"), Ok(0x4142));
+    assert_eq!(char_value("'\\x41B'"), Err(CharError::EscapeOutOfRange));
+    assert_eq!(char_value("'\\x41\\x42'"), Ok(0x4142));
+    assert_eq!(char_value("'a\\tb'"), Ok(0x610962));
+    assert_eq!(char_value("'\\377\\377\\377\\377'"), Ok(-1));
+    assert_eq!(char_value("'abcde'"), Ok(0x62636465));
+    assert_eq!(char_value("'\u{e9}'"), Ok(0xc3a9));
+}
+
+#[cfg(test)]
+fn char_multichar(s: &str) -> Option<MultiChar> {
+    let encoding = if s.starts_with('L') {
+        CharEncoding::Wide
+    } else {
+        CharEncoding::Plain
+    };
+    Character {
+        encoding: encoding,
+        value: s.to_owned(),
+    }
+    .evaluate()
+    .unwrap()
+    .multichar
+}
+
+#[test]
+fn test_escape_value() {
+    let escapes = [
+        ("'\\''", 39),
+        ("'\\\"'", 34),
+        ("'\\?'", 63),
+        ("'\\\\'", 92),
+        ("'\\a'", 7),
+        ("'\\b'", 8),
+        ("'\\e'", 27),
+        ("'\\f'", 12),
+        ("'\\n'", 10),
+        ("'\\r'", 13),
+        ("'\\t'", 9),
+        ("'\\v'", 11),
+        ("'\\7'", 7),
+        ("'\\77'", 63),
+        ("'\\177'", 127),
+        ("'\\200'", -128),
+        ("'\\1234'", 0x5334),
+        ("'\\x0'", 0),
+        ("'\\x7f'", 127),
+        ("'\\x000000041'", 65),
+        ("'\\u00e9'", 0xc3a9),
+        ("'\\U0001F600'", 0xf09f9880u32 as i32 as i64),
+        ("'\\u0024'", 36),
+        ("'a\\\nb'", 0x6162),
+    ];
+    for &(s, v) in escapes.iter() {
+        assert_eq!(char_value(s), Ok(v), "{}", s);
+    }
+    assert_eq!(char_value("'\\u0041'"), Err(CharError::InvalidUcn));
+    assert_eq!(char_value("'\\400'"), Err(CharError::EscapeOutOfRange));
+    assert_eq!(
+        char_value("'\\xfffffffff'"),
+        Err(CharError::EscapeOutOfRange)
+    );
+    assert_eq!(char_value("'\\ud800'"), Err(CharError::InvalidUcn));
+    assert_eq!(char_value("'\\U00110000'"), Err(CharError::InvalidUcn));
+    assert_eq!(char_value("'\\u12'"), Err(CharError::InvalidUcn));
+}
+
+#[test]
+fn test_wide_char_value() {
+    assert_eq!(char_value("L'\\u00e9'"), Ok(0xe9));
+    assert_eq!(char_value("L'\\U0010FFFF'"), Ok(0x10ffff));
+    assert_eq!(char_value("L'\\x80000000'"), Ok(-0x80000000));
+    assert_eq!(char_value("u'\\xffff'"), Ok(0xffff));
+    assert_eq!(char_value("u'\\x10000'"), Err(CharError::EscapeOutOfRange));
+    assert_eq!(char_value("u'\\uffff'"), Ok(0xffff));
+    assert_eq!(char_value("u'\\U00010000'"), Err(CharError::NotEncodable));
+    assert_eq!(char_value("U'\\xffffffff'"), Ok(0xffffffff));
+    assert_eq!(char_value("U'\\U0001F600'"), Ok(0x1f600));
+    assert_eq!(char_value("u8'\\xff'"), Ok(0xff));
+    assert_eq!(char_value("u8'\\x100'"), Err(CharError::EscapeOutOfRange));
+    assert_eq!(char_value("u8'\\u00e9'"), Err(CharError::NotEncodable));
+}
+
+#[test]
+fn test_multichar_kind() {
+    assert_eq!(char_multichar("'a'"), None);
+    assert_eq!(char_multichar("'\\377'"), None);
+    assert_eq!(char_multichar("'ab'"), Some(MultiChar::Packed));
+    assert_eq!(char_multichar("'\\u00e9'"), Some(MultiChar::Packed));
+    assert_eq!(char_multichar("'abcde'"), Some(MultiChar::Truncated));
+    assert_eq!(char_multichar("L'a'"), None);
+    assert_eq!(char_multichar("L'ab'"), Some(MultiChar::LastChar));
+}
+
+#[test]
+fn test_prefixed_char_value() {
+    assert_eq!(char_value("L'ab'"), Ok(98));
+    assert_eq!(char_value("L'\\xffffffff'"), Ok(-1));
+    assert_eq!(char_value("u'\u{e9}'"), Ok(0xe9));
+    assert_eq!(char_value("u'\u{1f600}'"), Err(CharError::NotEncodable));
+    assert_eq!(char_value("U'\u{1f600}'"), Ok(0x1f600));
+    assert_eq!(char_value("u8'a'"), Ok(97));
+    assert_eq!(char_value("u8'\u{e9}'"), Err(CharError::NotEncodable));
+}
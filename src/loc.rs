@@ -1,6 +1,10 @@
 //! Convert byte offsets into line numbers
+use std::cmp;
+use std::path::PathBuf;
+
 const F_NEW: u32 = 1;
 const F_RET: u32 = 2;
+const F_SYS: u32 = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Location<'a> {
@@ -19,8 +23,9 @@ pub fn get_location_for_offset<'a>(src: &'a str, pos: usize) -> (Location<'a>, V
     let mut loc = Location { file: "", line: 1 };
 
     while p < pos {
-        let n = p + src[p..].find("\n").unwrap_or(src[p..].len());
-        if pos <= n {
+        let n = p + src[p..].find(&['\r', '\n'][..]).unwrap_or(src[p..].len());
+        let next = n + terminator_length(&src[n..]);
+        if pos < next || pos <= n {
             break;
         }
 
@@ -32,15 +37,67 @@ pub fn get_location_for_offset<'a>(src: &'a str, pos: usize) -> (Location<'a>, V
                 inc.pop();
             }
             loc = l;
+        } else if let Some(l) = parse_msvc_line_directive(&src[p..n]) {
+            // cl.exe does not mark include boundaries, so infer them from file names
+            if let Some(i) = inc.iter().rposition(|i: &Location| i.file == l.file) {
+                inc.truncate(i);
+            } else if l.line == 1 && !loc.file.is_empty() && loc.file != l.file {
+                inc.push(loc);
+            }
+            loc = l;
         } else {
             loc.line += 1;
         }
 
-        p = n + 1;
+        p = next;
     }
     (loc, inc)
 }
 
+/// Find line number and column of an offset in a source, both starting from 1.
+///
+/// Column is counted in characters. Lines end with `\n`, `\r\n` or a lone `\r`.
+pub fn get_line_column_for_offset(src: &str, pos: usize) -> (usize, usize) {
+    let (line, start) = get_line_start(src, pos);
+    (line, src[start..pos].chars().count() + 1)
+}
+
+// Line number and byte offset of the start of the line containing `pos`
+#[doc(hidden)]
+pub fn get_line_start(src: &str, pos: usize) -> (usize, usize) {
+    let b = src.as_bytes();
+    let mut line = 1;
+    let mut start = 0;
+    for i in 0..pos {
+        if b[i] == b'\n' || b[i] == b'\r' && b.get(i + 1) != Some(&b'\n') {
+            line += 1;
+            start = i + 1;
+        }
+    }
+    (line, start)
+}
+
+// Byte offset of the end of the line containing `pos`, excluding the line terminator
+#[doc(hidden)]
+pub fn get_line_end(src: &str, pos: usize) -> usize {
+    if src[..pos].ends_with('\r') && src[pos..].starts_with('\n') {
+        return pos - 1;
+    }
+    src[pos..]
+        .find(&['\r', '\n'][..])
+        .map_or(src.len(), |n| pos + n)
+}
+
+fn terminator_length(s: &str) -> usize {
+    if s.starts_with("\r\n") {
+        2
+    } else if s.starts_with('\r') || s.starts_with('\n') {
+        1
+    } else {
+        0
+    }
+}
+
 #[test]
 fn test_get_location_for_offset() {
     fn t(src: &str, pos: usize, file: &str, line: usize, includes: &[(&str, usize)]) {
@@ -87,6 +144,228 @@ fn test_get_location_for_offset() {
     t(T, 98, "foo", 16, &[]);
     t(T, 102, "foo", 17, &[]);
     t(T, 114, "ook", 2, &[("foo", 17)]);
+
+    const M: &'static str = "#line 1 \"C:\\\\src\\\\main.c\"\r
+\r
+#line 1 \"C:\\\\include\\\\stddef.h\"\r
+typedef unsigned size_t;\r
+#line 2 \"C:\\\\src\\\\main.c\"\r
+size_t x;\r
+";
+    t(M, 27, "C:\\\\src\\\\main.c", 1, &[]);
+    t(
+        M,
+        75,
+        "C:\\\\include\\\\stddef.h",
+        1,
+        &[("C:\\\\src\\\\main.c", 2)],
+    );
+    t(M, 125, "C:\\\\src\\\\main.c", 2, &[]);
+
+    t("a\r\nb\rc\n", 1, "", 1, &[]);
+    t("a\r\nb\rc\n", 2, "", 1, &[]);
+    t("a\r\nb\rc\n", 3, "", 2, &[]);
+    t("a\r\nb\rc\n", 5, "", 3, &[]);
+    t("# 7 \"crlf\"\r\na\r\nb", 15, "crlf", 8, &[]);
+}
+
+#[test]
+fn test_get_line_column_for_offset() {
+    let src = "ab\r\n\tc\rd\u{e9}f\ng";
+    assert_eq!(get_line_column_for_offset(src, 0), (1, 1));
+    assert_eq!(get_line_column_for_offset(src, 2), (1, 3));
+    assert_eq!(get_line_column_for_offset(src, 3), (1, 4));
+    assert_eq!(get_line_column_for_offset(src, 4), (2, 1));
+    assert_eq!(get_line_column_for_offset(src, 5), (2, 2));
+    assert_eq!(get_line_column_for_offset(src, 7), (3, 1));
+    assert_eq!(get_line_column_for_offset(src, 10), (3, 3));
+    assert_eq!(get_line_column_for_offset(src, 13), (4, 2));
+    assert_eq!(get_line_end(src, 1), 2);
+    assert_eq!(get_line_end(src, 3), 2);
+    assert_eq!(get_line_end(src, 5), 6);
+}
+
+/// File named in the line markers of a preprocessed source
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludedFile {
+    pub path: PathBuf,
+    /// File is a system header (flag 3 in gcc line markers)
+    pub system: bool,
+}
+
+/// List distinct files named in line markers of a preprocessed source, in first-seen order.
+///
+/// First entry is normally the main source file. Pseudo-files like `<built-in>` and
+/// `<command-line>` are skipped.
+pub fn get_included_files(src: &str) -> Vec<IncludedFile> {
+    let mut files: Vec<IncludedFile> = Vec::new();
+
+    let mut p = 0;
+    while p < src.len() {
+        let n = p + src[p..].find(&['\r', '\n'][..]).unwrap_or(src[p..].len());
+        let line = &src[p..n];
+        p = n + terminator_length(&src[n..]);
+        if !line.starts_with('#') {
+            continue;
+        }
+
+        let (loc, flags) = match parse_line_directive(line) {
+            Some(d) => d,
+            None => match parse_msvc_line_directive(line) {
+                Some(l) => (l, 0),
+                None => continue,
+            },
+        };
+        if loc.file.starts_with('<') && loc.file.ends_with('>') {
+            continue;
+        }
+
+        let path = PathBuf::from(unescape_file_name(loc.file));
+        let system = flags & F_SYS == F_SYS;
+        match files.iter_mut().find(|f| f.path == path) {
+            Some(f) => f.system |= system,
+            None => files.push(IncludedFile {
+                path: path,
+                system: system,
+            }),
+        }
+    }
+
+    files
+}
+
+// Undo escaping of `\\`, `\"` and octal escapes in a file name from a line marker
+#[doc(hidden)]
+pub fn unescape_file_name(s: &str) -> String {
+    let b = s.as_bytes();
+    let mut bytes = Vec::with_capacity(b.len());
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] != b'\\' || i + 1 == b.len() {
+            bytes.push(b[i]);
+            i += 1;
+            continue;
+        }
+        let n = b[i + 1..]
+            .iter()
+            .take(3)
+            .take_while(|&&c| c >= b'0' && c <= b'7')
+            .count();
+        if n > 0 {
+            // `\400` and above do not fit a byte, they are clamped to `\377`
+            let value = b[i + 1..i + 1 + n]
+                .iter()
+                .fold(0u32, |a, &c| a * 8 + (c - b'0') as u32);
+            bytes.push(cmp::min(value, 0xff) as u8);
+            i += 1 + n;
+        } else {
+            bytes.push(b[i + 1]);
+            i += 2;
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[test]
+fn test_unescape_file_name() {
+    assert_eq!(unescape_file_name(r#"a\\b\"c"#), "a\\b\"c");
+    assert_eq!(unescape_file_name(r"caf\303\251\0619"), "caf\u{e9}19");
+    assert_eq!(unescape_file_name(r"\777\400"), unescape_file_name(r"\377\377"));
+    assert_eq!(unescape_file_name(r"x\"), "x\\");
+}
+
+#[test]
+fn test_get_included_files() {
+    fn t(src: &str, expected: &[(&str, bool)]) {
+        let files = get_included_files(src);
+        let files = files
+            .iter()
+            .map(|f| (f.path.to_str().unwrap(), f.system))
+            .collect::<Vec<_>>();
+        assert_eq!(files, expected);
+    }
+
+    // gcc 12
+    const GCC: &'static str = r##"# 0 "main.c"
+# 0 "<built-in>"
+# 0 "<command-line>"
+# 1 "/usr/include/stdc-predef.h" 1 3 4
+# 0 "<command-line>" 2
+# 1 "main.c"
+# 1 "a.h" 1
+# 1 "sub/b.h" 1
+int b;
+# 2 "a.h" 2
+# 1 "/usr/lib/gcc/x86_64-linux-gnu/12/include/stddef.h" 1 3 4
+# 145 "/usr/lib/gcc/x86_64-linux-gnu/12/include/stddef.h" 3 4
+typedef long int ptrdiff_t;
+# 3 "a.h" 2
+# 3 "a.h"
+int a;
+# 2 "main.c" 2
+# 1 "./we\"ird.h" 1
+int w;
+# 3 "main.c" 2
+# 1 "caf\303\251 \\ x.h" 1
+# 4 "main.c" 2
+int main;
+"##;
+    t(
+        GCC,
+        &[
+            ("main.c", false),
+            ("/usr/include/stdc-predef.h", true),
+            ("a.h", false),
+            ("sub/b.h", false),
+            ("/usr/lib/gcc/x86_64-linux-gnu/12/include/stddef.h", true),
+            ("./we\"ird.h", false),
+            ("café \\ x.h", false),
+        ],
+    );
+
+    // clang 14
+    const CLANG: &'static str = r##"# 1 "main.c"
+# 1 "<built-in>" 1
+# 1 "<built-in>" 3
+# 366 "<built-in>" 3
+# 1 "<command line>" 1
+# 1 "<built-in>" 2
+# 1 "main.c" 2
+# 1 "./a.h" 1
+# 1 "./sub/b.h" 1
+int b;
+# 2 "./a.h" 2
+# 1 "/usr/lib/llvm-14/lib/clang/14.0.0/include/stddef.h" 1 3
+# 35 "/usr/lib/llvm-14/lib/clang/14.0.0/include/stddef.h" 3
+typedef long int ptrdiff_t;
+# 3 "./a.h" 2
+int a;
+# 2 "main.c" 2
+# 1 "./we\"ird.h" 1
+int w;
+# 3 "main.c" 2
+int main;
+"##;
+    t(
+        CLANG,
+        &[
+            ("main.c", false),
+            ("./a.h", false),
+            ("./sub/b.h", false),
+            ("/usr/lib/llvm-14/lib/clang/14.0.0/include/stddef.h", true),
+            ("./we\"ird.h", false),
+        ],
+    );
+
+    t(
+        "#line 1 \"C:\\\\src\\\\main.c\"\r\n#line 1 \"C:\\\\include\\\\stddef.h\"\r\n",
+        &[("C:\\src\\main.c", false), ("C:\\include\\stddef.h", false)],
+    );
+
+    t(
+        "# 1 \"main.c\"\rint a;\r# 1 \"a.h\" 1\r# 1 \"/usr/include/b.h\" 1 3\r",
+        &[("main.c", false), ("a.h", false), ("/usr/include/b.h", true)],
+    );
 }
 
 macro_rules! otry {
@@ -109,6 +388,26 @@ fn strip_prefix<'a>(s: &'a str, p: &str) -> Option<&'a str> {
 // https://gcc.gnu.org/onlinedocs/cpp/Preprocessor-Output.html
 fn parse_line_directive(s: &str) -> Option<(Location, u32)> {
     let s = otry!(strip_prefix(s, "# "));
+    let (loc, s) = otry!(parse_line_and_file(s));
+
+    let flags = s.bytes().filter(|&c| c >= b'1' && c <= b'4');
+    let flags = flags.fold(0, |a, f| a | 1 << (f - b'1'));
+
+    Some((loc, flags))
+}
+
+// `#line 12 "C:\\src\\main.c"`, as written by `cl.exe /E`
+fn parse_msvc_line_directive(s: &str) -> Option<Location> {
+    let s = otry!(strip_prefix(s, "#line "));
+    let (loc, s) = otry!(parse_line_and_file(s));
+    if s.trim().is_empty() {
+        Some(loc)
+    } else {
+        None
+    }
+}
+
+fn parse_line_and_file(s: &str) -> Option<(Location, &str)> {
     let n = otry!(s.find(" "));
     let line = otry!(usize::from_str_radix(&s[..n], 10).ok());
 
@@ -124,15 +423,12 @@ fn parse_line_directive(s: &str) -> Option<(Location, u32)> {
     let file = &s[..n];
     let s = otry!(strip_prefix(&s[n..], "\""));
 
-    let flags = s.bytes().filter(|&c| c >= b'1' && c <= b'4');
-    let flags = flags.fold(0, |a, f| a | 1 << (f - b'1'));
-
     Some((
         Location {
             file: file,
             line: line,
         },
-        flags,
+        s,
     ))
 }
 
@@ -159,4 +455,15 @@ fn test_line_directive() {
     assert_eq!(parse_line_directive("# 0 \"# #\x0a\x0a\\"), None);
     assert_eq!(parse_line_directive("# 0 \"\\"), None);
     assert_eq!(parse_line_directive("# 0 \"\\…"), None);
+
+    assert_eq!(
+        parse_msvc_line_directive("#line 7 \"C:\\\\src\\\\a.c\"\r"),
+        Some(Location {
+            file: r#"C:\\src\\a.c"#,
+            line: 7
+        })
+    );
+    assert_eq!(parse_msvc_line_directive("#line 7"), None);
+    assert_eq!(parse_msvc_line_directive("#line 7 \"a.c\" 1"), None);
+    assert_eq!(parse_line_directive("#line 7 \"a.c\""), None);
 }
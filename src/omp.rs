@@ -0,0 +1,132 @@
+//! OpenMP directives
+//!
+//! A `#pragma omp` line with a recognized directive and clauses is parsed into an
+//! `OmpDirective` and attached to the statement following it, see `ast::Statement::Omp`. Other
+//! OpenMP pragmas are kept as `ast::Pragma`.
+//!
+//! References in parenthesis refer to the [OpenMP 4.5
+//! specification](https://www.openmp.org/wp-content/uploads/openmp-4.5.pdf).
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use ast::{Expression, Identifier, Statement};
+use span::Node;
+
+/// Directive and the statement it applies to
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OmpStatement {
+    pub directive: Node<OmpDirective>,
+    /// Structured block, `None` for stand-alone directives like `barrier`
+    pub statement: Option<Box<Node<Statement>>>,
+}
+
+/// Single `#pragma omp` line
+///
+/// (OpenMP 4.5 2.1)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OmpDirective {
+    pub kind: OmpDirectiveKind,
+    pub clauses: Vec<Node<OmpClause>>,
+}
+
+/// Name of an OpenMP directive
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OmpDirectiveKind {
+    /// `parallel` (OpenMP 4.5 2.5)
+    Parallel,
+    /// `for` (OpenMP 4.5 2.7.1)
+    For,
+    /// `parallel for` (OpenMP 4.5 2.11.1)
+    ParallelFor,
+    /// `sections` (OpenMP 4.5 2.7.2)
+    Sections,
+    /// `single` (OpenMP 4.5 2.7.3)
+    Single,
+    /// `task` (OpenMP 4.5 2.9.1)
+    Task,
+    /// `critical`, optionally followed by a name (OpenMP 4.5 2.13.2)
+    Critical(Option<Node<Identifier>>),
+    /// `atomic` (OpenMP 4.5 2.13.6)
+    Atomic,
+    /// `barrier`, a stand-alone directive (OpenMP 4.5 2.13.3)
+    Barrier,
+}
+
+/// Clause of an OpenMP directive
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OmpClause {
+    /// `private(list)` (OpenMP 4.5 2.15.3.3)
+    Private(Vec<Node<Identifier>>),
+    /// `shared(list)` (OpenMP 4.5 2.15.3.2)
+    Shared(Vec<Node<Identifier>>),
+    /// `firstprivate(list)` (OpenMP 4.5 2.15.3.4)
+    FirstPrivate(Vec<Node<Identifier>>),
+    /// `reduction(operator: list)` (OpenMP 4.5 2.15.3.6)
+    Reduction(OmpReduction),
+    /// `num_threads(expression)` (OpenMP 4.5 2.5)
+    NumThreads(Box<Node<Expression>>),
+    /// `schedule(kind[, chunk_size])` (OpenMP 4.5 2.7.1)
+    Schedule(OmpSchedule),
+    /// `collapse(n)` (OpenMP 4.5 2.7.1)
+    Collapse(Box<Node<Expression>>),
+}
+
+/// Reduction clause
+///
+/// (OpenMP 4.5 2.15.3.6)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OmpReduction {
+    pub operator: Node<OmpReductionOperator>,
+    pub variables: Vec<Node<Identifier>>,
+}
+
+/// Operator combining the private copies in a reduction clause
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OmpReductionOperator {
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `*`
+    Multiply,
+    /// `&`
+    BitwiseAnd,
+    /// `|`
+    BitwiseOr,
+    /// `^`
+    BitwiseXor,
+    /// `&&`
+    LogicalAnd,
+    /// `||`
+    LogicalOr,
+    /// `max`, `min` or a user-defined reduction
+    Identifier(Node<Identifier>),
+}
+
+/// Schedule clause
+///
+/// (OpenMP 4.5 2.7.1)
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OmpSchedule {
+    pub kind: Node<OmpScheduleKind>,
+    pub chunk_size: Option<Box<Node<Expression>>>,
+}
+
+/// Distribution of loop iterations among threads
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OmpScheduleKind {
+    Static,
+    Dynamic,
+    Guided,
+    Auto,
+    Runtime,
+}
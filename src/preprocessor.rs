@@ -0,0 +1,1412 @@
+//! Built-in C preprocessor
+//!
+//! Handles the common subset of the language: `#include`, object- and function-like macros,
+//! conditional compilation and `__LINE__`/`__FILE__`. Output contains line markers in the format
+//! produced by gcc, so that `loc` can map offsets back to the original files.
+
+use std::cmp;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io;
+use std::io::Read;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use driver::{decode, Config, Diagnostic, Flavor, Macro};
+
+// Maximum nesting of `#include` directives, to stop runaway recursion
+const MAX_INCLUDE_DEPTH: usize = 200;
+
+// Line gap in the output above which a line marker is written instead of empty lines
+const MAX_LINE_GAP: usize = 8;
+
+/// Preprocess the file at `path`, returning the text and messages from `#warning`
+pub fn preprocess_file(config: &Config, path: &Path) -> io::Result<(String, Vec<Diagnostic>)> {
+    let text = try!(read_file(config, path));
+    let mut pp = Preprocessor::new(config);
+    try!(pp.run_file(&path.to_string_lossy(), Some(path), &text, None));
+    Ok((pp.out.finish(), pp.warnings))
+}
+
+/// Preprocess `source` as if it was read from a file called `name`
+pub fn preprocess_str(
+    config: &Config,
+    name: &str,
+    source: &str,
+) -> io::Result<(String, Vec<Diagnostic>)> {
+    let mut pp = Preprocessor::new(config);
+    try!(pp.run_file(name, None, source, None));
+    Ok((pp.out.finish(), pp.warnings))
+}
+
+fn read_file(config: &Config, path: &Path) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    try!(try!(File::open(path)).read_to_end(&mut bytes));
+    decode(bytes, config.encoding)
+}
+
+fn error<T>(file: &str, line: usize, msg: &str) -> io::Result<T> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        format!("{}:{}: error: {}", file, line, msg),
+    ))
+}
+
+fn escape(s: &str) -> String {
+    let mut r = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            r.push('\\');
+        }
+        r.push(c);
+    }
+    r
+}
+
+// Text of a directive line after the directive name
+fn directive_rest<'s>(line: &'s str, keyword: &str) -> &'s str {
+    let s = line.trim_left();
+    let s = if s.starts_with("%:") {
+        &s[2..]
+    } else {
+        &s[1..]
+    };
+    s.trim_left()[keyword.len()..].trim()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Ident,
+    Number,
+    Char,
+    Str,
+    Punct,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: Kind,
+    text: Rc<str>,
+    /// Token is preceded by whitespace
+    space: bool,
+    /// Output line in the current file
+    line: usize,
+    /// Macros that must not be expanded again from this token
+    hide: Rc<Vec<Rc<str>>>,
+}
+
+impl Token {
+    fn is(&self, text: &str) -> bool {
+        (self.kind == Kind::Punct || self.kind == Kind::Ident) && &*self.text == text
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hide.contains(&self.text)
+    }
+
+    fn with_text(&self, kind: Kind, text: String) -> Token {
+        Token {
+            kind: kind,
+            text: text.into(),
+            space: self.space,
+            line: self.line,
+            hide: self.hide.clone(),
+        }
+    }
+}
+
+const PUNCTUATORS: &'static [&'static str] = &[
+    "%:%:", "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||",
+    "*=", "/=", "%=", "+=", "-=", "&=", "^=", "|=", "##", "<:", ":>", "<%", "%>", "%:", "[", "]",
+    "(", ")", "{", "}", ".", "&", "*", "+", "-", "~", "!", "/", "%", "<", ">", "^", "|", "?", ":",
+    ";", "=", ",", "#",
+];
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c == '$' || c.is_ascii_alphabetic() || c as u32 >= 0x80
+}
+
+fn is_ident(c: char) -> bool {
+    is_ident_start(c) || c.is_ascii_digit()
+}
+
+// Source line after backslash-newline sequences are removed and comments are replaced with a
+// single space
+struct Line {
+    text: String,
+    /// Physical line number where the text starts
+    line: usize,
+    /// Offsets in `text` where a new physical line starts, with the number of lines since `line`
+    breaks: Vec<(usize, usize)>,
+}
+
+fn logical_lines(src: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut cur = String::new();
+    let mut breaks = Vec::new();
+    let mut start = 1;
+    let mut line = 1;
+    let mut chars = src.chars().peekable();
+    let mut quote = None;
+    let mut in_number = false;
+    let mut prev = ' ';
+
+    while let Some(c) = chars.next() {
+        if c == '\\' && (chars.peek() == Some(&'\n') || chars.peek() == Some(&'\r')) {
+            if chars.peek() == Some(&'\r') {
+                chars.next();
+            }
+            if chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            line += 1;
+            breaks.push((cur.len(), line - start));
+            continue;
+        }
+
+        if c == '\n' {
+            if cur.ends_with('\r') {
+                cur.pop();
+            }
+            lines.push(Line {
+                text: mem::replace(&mut cur, String::new()),
+                line: start,
+                breaks: mem::replace(&mut breaks, Vec::new()),
+            });
+            line += 1;
+            start = line;
+            quote = None;
+            in_number = false;
+            prev = ' ';
+            continue;
+        }
+
+        if let Some(q) = quote {
+            cur.push(c);
+            if c == '\\' && chars.peek().map_or(false, |&c| c != '\n') {
+                cur.push(chars.next().unwrap());
+            } else if c == q {
+                quote = None;
+            }
+            prev = c;
+            continue;
+        }
+
+        if c == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut last = ' ';
+            for c in chars.by_ref() {
+                if c == '\n' {
+                    line += 1;
+                }
+                if last == '*' && c == '/' {
+                    break;
+                }
+                last = c;
+            }
+            cur.push(' ');
+            if breaks.last().map_or(0, |b| b.1) != line - start {
+                breaks.push((cur.len(), line - start));
+            }
+            in_number = false;
+            prev = ' ';
+            continue;
+        }
+
+        if c == '/' && chars.peek() == Some(&'/') {
+            while chars.peek().map_or(false, |&c| c != '\n') {
+                chars.next();
+            }
+            continue;
+        }
+
+        // C23 digit separators look like the start of a character constant
+        let separator = in_number && chars.peek().map_or(false, |&c| is_ident(c));
+        if c == '"' || (c == '\'' && !separator) {
+            quote = Some(c);
+            in_number = false;
+        } else if !is_ident(prev) && prev != '.' && (c.is_ascii_digit() || c == '.') {
+            in_number = true;
+        } else if !is_ident(c) && c != '.' && c != '\'' {
+            in_number = false;
+        }
+
+        cur.push(c);
+        prev = c;
+    }
+
+    if !cur.is_empty() {
+        lines.push(Line {
+            text: cur,
+            line: start,
+            breaks: breaks,
+        });
+    }
+    lines
+}
+
+fn lex(src: &str, line: usize) -> Vec<Token> {
+    lex_line(src, line, &[])
+}
+
+fn lex_line(src: &str, line: usize, breaks: &[(usize, usize)]) -> Vec<Token> {
+    let hide = Rc::new(Vec::new());
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut space = false;
+    let mut p = 0;
+
+    while p < src.len() {
+        let c = src[p..].chars().next().unwrap();
+        let (kind, len) = if c == ' ' || c == '\t' || c == '\r' || c == '\x0b' || c == '\x0c' {
+            space = true;
+            p += 1;
+            continue;
+        } else if c.is_ascii_digit()
+            || (c == '.' && bytes.get(p + 1).map_or(false, |c| c.is_ascii_digit()))
+        {
+            (Kind::Number, number_len(&src[p..]))
+        } else if is_ident_start(c) {
+            let n = src[p..].find(|c| !is_ident(c)).unwrap_or(src.len() - p);
+            let next = bytes.get(p + n).cloned();
+            match &src[p..p + n] {
+                "L" | "u" | "U" | "u8" if next == Some(b'"') => {
+                    (Kind::Str, n + literal_len(&src[p + n..]))
+                }
+                "L" | "u" | "U" | "u8" if next == Some(b'\'') => {
+                    (Kind::Char, n + literal_len(&src[p + n..]))
+                }
+                _ => (Kind::Ident, n),
+            }
+        } else if c == '"' {
+            (Kind::Str, literal_len(&src[p..]))
+        } else if c == '\'' {
+            (Kind::Char, literal_len(&src[p..]))
+        } else {
+            match PUNCTUATORS.iter().find(|s| src[p..].starts_with(*s)) {
+                Some(s) => (Kind::Punct, s.len()),
+                None => (Kind::Other, c.len_utf8()),
+            }
+        };
+
+        let line = match breaks.iter().rposition(|b| b.0 <= p) {
+            Some(i) => line + breaks[i].1,
+            None => line,
+        };
+        tokens.push(Token {
+            kind: kind,
+            text: src[p..p + len].into(),
+            space: space,
+            line: line,
+            hide: hide.clone(),
+        });
+        space = false;
+        p += len;
+    }
+
+    tokens
+}
+
+fn number_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    let mut n = 1;
+    while n < b.len() {
+        let c = b[n];
+        let sign = (c == b'+' || c == b'-') && b"eEpP".contains(&b[n - 1]);
+        if c.is_ascii_alphanumeric() || c == b'_' || c == b'.' || sign {
+            n += 1;
+        } else if c == b'\'' && b.get(n + 1).map_or(false, |c| c.is_ascii_alphanumeric()) {
+            n += 2;
+        } else {
+            break;
+        }
+    }
+    n
+}
+
+fn literal_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    let mut n = 1;
+    while n < b.len() {
+        if b[n] == b'\\' {
+            n += 2;
+        } else if b[n] == b[0] {
+            return n + 1;
+        } else {
+            n += 1;
+        }
+    }
+    b.len()
+}
+
+// Whether two adjacent tokens need a space between them to be read back as two tokens
+fn needs_space(prev: &str, next: &str) -> bool {
+    let (a, b) = match (prev.chars().last(), next.chars().next()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+    if is_ident(a) {
+        let number = prev.starts_with(|c: char| c.is_ascii_digit());
+        return is_ident(b) || b == '"' || b == '\'' || (b == '.' && number);
+    }
+    if is_ident(b) {
+        return a == '.' && b.is_ascii_digit();
+    }
+    if (a == '/' && (b == '/' || b == '*')) || (a == '.' && b == '.') {
+        return true;
+    }
+    let joined = format!("{}{}", prev, next);
+    PUNCTUATORS
+        .iter()
+        .any(|p| p.len() > prev.len() && joined.starts_with(p))
+}
+
+#[derive(Clone, Debug)]
+struct MacroDef {
+    /// Parameter names of a function-like macro, variadic arguments are the last one
+    params: Option<Vec<Rc<str>>>,
+    variadic: bool,
+    body: Vec<Token>,
+}
+
+// Macro replacement list after parameter substitution
+enum Piece {
+    Tokens(Vec<Token>),
+    /// `##` operator, true for GNU `, ## __VA_ARGS__` with no variadic arguments
+    Paste(bool),
+}
+
+fn paste(left: Token, right: Token) -> Vec<Token> {
+    let text = format!("{}{}", left.text, right.text);
+    let mut tokens = lex(&text, left.line);
+    if tokens.len() == 1 {
+        let t = tokens.pop().unwrap();
+        vec![left.with_text(t.kind, text)]
+    } else {
+        vec![left, right]
+    }
+}
+
+fn stringify(arg: &[Token], site: &Token) -> Token {
+    let mut s = String::from("\"");
+    for (i, t) in arg.iter().enumerate() {
+        if i > 0 && t.space {
+            s.push(' ');
+        }
+        if t.kind == Kind::Str || t.kind == Kind::Char {
+            s.push_str(&escape(&t.text));
+        } else {
+            s.push_str(&t.text);
+        }
+    }
+    s.push('"');
+    site.with_text(Kind::Str, s)
+}
+
+struct Output {
+    text: String,
+    /// Line number of the current output line
+    line: usize,
+    /// Nothing was written on the current output line yet
+    fresh: bool,
+    prev: Rc<str>,
+}
+
+impl Output {
+    fn marker(&mut self, line: usize, file: &str, flag: Option<u8>) {
+        if !self.fresh {
+            self.text.push('\n');
+        }
+        self.text
+            .push_str(&format!("# {} \"{}\"", line, escape(file)));
+        if let Some(flag) = flag {
+            self.text.push_str(&format!(" {}", flag));
+        }
+        self.text.push('\n');
+        self.line = line;
+        self.fresh = true;
+    }
+
+    fn goto(&mut self, line: usize, file: &str) {
+        if line <= self.line {
+            return;
+        }
+        if line - self.line > MAX_LINE_GAP {
+            self.marker(line, file, None);
+        } else {
+            for _ in self.line..line {
+                self.text.push('\n');
+            }
+            self.line = line;
+            self.fresh = true;
+        }
+    }
+
+    fn token(&mut self, t: &Token, file: &str) {
+        self.goto(t.line, file);
+        if !self.fresh && (t.space || needs_space(&self.prev, &t.text)) {
+            self.text.push(' ');
+        }
+        self.text.push_str(&t.text);
+        self.fresh = false;
+        self.prev = t.text.clone();
+    }
+
+    // Write a line of text verbatim
+    fn line(&mut self, line: usize, file: &str, text: &str) {
+        self.goto(line, file);
+        if !self.fresh {
+            self.marker(line, file, None);
+        }
+        self.text.push_str(text);
+        self.fresh = false;
+        self.prev = "".into();
+    }
+
+    fn finish(mut self) -> String {
+        if !self.fresh {
+            self.text.push('\n');
+        }
+        self.text
+    }
+}
+
+struct Cond {
+    /// One of the branches was taken, or the enclosing region is skipped
+    taken: bool,
+    /// Current branch is active
+    active: bool,
+    seen_else: bool,
+}
+
+struct Eval<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    /// Inside an operand that is not evaluated, e.g. right side of `0 && x`
+    skip: usize,
+}
+
+fn precedence(op: &str) -> Option<u8> {
+    Some(match op {
+        "||" => 1,
+        "&&" => 2,
+        "|" => 3,
+        "^" => 4,
+        "&" => 5,
+        "==" | "!=" => 6,
+        "<" | ">" | "<=" | ">=" => 7,
+        "<<" | ">>" => 8,
+        "+" | "-" => 9,
+        "*" | "/" | "%" => 10,
+        _ => return None,
+    })
+}
+
+impl<'a> Eval<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.peek().map_or(false, |t| t.is(text)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expr(&mut self) -> Result<i64, String> {
+        let c = try!(self.binary(0));
+        if !self.eat("?") {
+            return Ok(c);
+        }
+        self.skip += (c == 0) as usize;
+        let a = try!(self.expr());
+        self.skip -= (c == 0) as usize;
+        if !self.eat(":") {
+            return Err("expected ':' in preprocessor expression".into());
+        }
+        self.skip += (c != 0) as usize;
+        let b = try!(self.expr());
+        self.skip -= (c != 0) as usize;
+        Ok(if c != 0 { a } else { b })
+    }
+
+    fn binary(&mut self, min: u8) -> Result<i64, String> {
+        let mut lhs = try!(self.unary());
+        loop {
+            let (op, prec) = match self.peek() {
+                Some(t) if t.kind == Kind::Punct => match precedence(&t.text) {
+                    Some(p) if p > min => (t.text.clone(), p),
+                    _ => return Ok(lhs),
+                },
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+
+            let skip = (&*op == "&&" && lhs == 0) || (&*op == "||" && lhs != 0);
+            self.skip += skip as usize;
+            let rhs = try!(self.binary(prec));
+            self.skip -= skip as usize;
+
+            lhs = match &*op {
+                "||" => (lhs != 0 || rhs != 0) as i64,
+                "&&" => (lhs != 0 && rhs != 0) as i64,
+                "|" => lhs | rhs,
+                "^" => lhs ^ rhs,
+                "&" => lhs & rhs,
+                "==" => (lhs == rhs) as i64,
+                "!=" => (lhs != rhs) as i64,
+                "<" => (lhs < rhs) as i64,
+                ">" => (lhs > rhs) as i64,
+                "<=" => (lhs <= rhs) as i64,
+                ">=" => (lhs >= rhs) as i64,
+                "<<" => lhs.wrapping_shl(rhs as u32),
+                ">>" => lhs.wrapping_shr(rhs as u32),
+                "+" => lhs.wrapping_add(rhs),
+                "-" => lhs.wrapping_sub(rhs),
+                "*" => lhs.wrapping_mul(rhs),
+                _ if rhs == 0 && self.skip == 0 => {
+                    return Err("division by zero in #if".into());
+                }
+                _ if rhs == 0 => 0,
+                "/" => lhs.wrapping_div(rhs),
+                _ => lhs.wrapping_rem(rhs),
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<i64, String> {
+        let t = match self.peek() {
+            Some(t) => t,
+            None => return Err("missing expression".into()),
+        };
+        self.pos += 1;
+        match t.kind {
+            Kind::Number => parse_integer(&t.text),
+            Kind::Char => parse_char(&t.text),
+            // Identifiers left after macro expansion evaluate to zero
+            Kind::Ident => Ok(0),
+            _ if t.is("+") => self.unary(),
+            _ if t.is("-") => self.unary().map(i64::wrapping_neg),
+            _ if t.is("~") => self.unary().map(|v| !v),
+            _ if t.is("!") => self.unary().map(|v| (v == 0) as i64),
+            _ if t.is("(") => {
+                let v = try!(self.expr());
+                if self.eat(")") {
+                    Ok(v)
+                } else {
+                    Err("missing ')' in expression".into())
+                }
+            }
+            _ => Err(format!(
+                "token \"{}\" is not valid in preprocessor expressions",
+                t.text
+            )),
+        }
+    }
+}
+
+fn parse_integer(s: &str) -> Result<i64, String> {
+    let digits: String = s.chars().filter(|&c| c != '\'').collect();
+    let digits = digits.trim_right_matches(|c| c == 'u' || c == 'U' || c == 'l' || c == 'L');
+    let lower = digits.to_ascii_lowercase();
+    let (radix, digits) = if lower.starts_with("0x") {
+        (16, &digits[2..])
+    } else if lower.starts_with("0b") {
+        (2, &digits[2..])
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (8, &digits[1..])
+    } else {
+        (10, digits)
+    };
+    match u64::from_str_radix(digits, radix) {
+        Ok(v) => Ok(v as i64),
+        Err(_) => Err(format!("invalid integer constant \"{}\" in #if", s)),
+    }
+}
+
+fn parse_char(s: &str) -> Result<i64, String> {
+    let q = s.find('\'').unwrap_or(0);
+    let body = s[q + 1..].trim_right_matches('\'');
+    let mut chars = body.chars();
+    let v = match chars.next() {
+        Some('\\') => match chars.next() {
+            Some('n') => 10,
+            Some('t') => 9,
+            Some('r') => 13,
+            Some('a') => 7,
+            Some('b') => 8,
+            Some('f') => 12,
+            Some('v') => 11,
+            Some('x') => u32::from_str_radix(chars.as_str(), 16).unwrap_or(0),
+            Some(c) if c.is_digit(8) => {
+                let digits: String = Some(c).into_iter().chain(chars).collect();
+                u32::from_str_radix(&digits, 8).unwrap_or(0)
+            }
+            Some(c) => c as u32,
+            None => return Err("invalid character constant in #if".into()),
+        },
+        Some(c) => c as u32,
+        None => return Err("empty character constant in #if".into()),
+    };
+    // Plain character constants have type int, but the value of a `char`
+    Ok(if q == 0 {
+        v as u8 as i8 as i64
+    } else {
+        v as i64
+    })
+}
+
+struct Preprocessor<'a> {
+    config: &'a Config,
+    macros: HashMap<Rc<str>, MacroDef>,
+    /// Files that contain `#pragma once`
+    once: HashSet<PathBuf>,
+    depth: usize,
+    /// Current file name, as reported by `__FILE__`
+    file: String,
+    out: Output,
+    warnings: Vec<Diagnostic>,
+}
+
+impl<'a> Preprocessor<'a> {
+    fn new(config: &'a Config) -> Preprocessor<'a> {
+        let mut pp = Preprocessor {
+            config: config,
+            macros: HashMap::new(),
+            once: HashSet::new(),
+            depth: 0,
+            file: String::new(),
+            out: Output {
+                text: String::new(),
+                line: 0,
+                fresh: true,
+                prev: "".into(),
+            },
+            warnings: Vec::new(),
+        };
+
+        pp.define_str("__STDC__ 1");
+        pp.define_str("__STDC_HOSTED__ 1");
+        match config.flavor {
+            Flavor::StdC89 => {}
+            Flavor::StdC99 | Flavor::OpenCl => pp.define_str("__STDC_VERSION__ 199901L"),
+            Flavor::StdC17 => pp.define_str("__STDC_VERSION__ 201710L"),
+            Flavor::StdC23 | Flavor::GnuC23 | Flavor::ClangC23 => {
+                pp.define_str("__STDC_VERSION__ 202311L")
+            }
+            _ => pp.define_str("__STDC_VERSION__ 201112L"),
+        }
+
+        for m in &config.macros {
+            match *m {
+                Macro::Define(ref name, None) => pp.define_str(&format!("{} 1", name)),
+                Macro::Define(ref name, Some(ref value)) => {
+                    pp.define_str(&format!("{} {}", name, value))
+                }
+                Macro::Undefine(ref name) => {
+                    pp.macros.remove(&**name);
+                }
+            }
+        }
+
+        pp
+    }
+
+    fn define_str(&mut self, s: &str) {
+        let tokens = lex(s, 0);
+        let _ = self.define(&tokens, 0);
+    }
+
+    fn run_file(
+        &mut self,
+        name: &str,
+        path: Option<&Path>,
+        text: &str,
+        flag: Option<u8>,
+    ) -> io::Result<()> {
+        let parent = mem::replace(&mut self.file, name.to_owned());
+        let dir = path
+            .and_then(Path::parent)
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        self.out.marker(1, name, flag);
+
+        let mut conds: Vec<Cond> = Vec::new();
+        let mut pending = Vec::new();
+        // Difference between output and physical line numbers, changed by `#line`
+        let mut delta = 0isize;
+
+        for l in logical_lines(text) {
+            let physical = l.line;
+            let text = l.text;
+            let line = cmp::max(physical as isize + delta, 1) as usize;
+            let active = conds.last().map_or(true, |c| c.active);
+            let tokens = lex_line(&text, line, &l.breaks);
+
+            if !tokens.first().map_or(false, |t| t.is("#") || t.is("%:")) {
+                if active {
+                    pending.extend(tokens);
+                }
+                continue;
+            }
+
+            try!(self.flush(&mut pending));
+
+            let keyword = match tokens.get(1) {
+                Some(t) if t.kind == Kind::Ident => &*t.text,
+                _ => "",
+            };
+            let args = if tokens.len() > 1 { &tokens[2..] } else { &[] };
+
+            match keyword {
+                "if" | "ifdef" | "ifndef" => {
+                    let value = active && try!(self.condition(keyword, args, line));
+                    conds.push(Cond {
+                        taken: value || !active,
+                        active: value,
+                        seen_else: false,
+                    });
+                    continue;
+                }
+                "elif" | "else" | "endif" => {
+                    let evaluate = match conds.last_mut() {
+                        None => {
+                            let msg = format!("#{} without #if", keyword);
+                            return error(&self.file, line, &msg);
+                        }
+                        Some(ref c) if c.seen_else && keyword != "endif" => {
+                            let msg = format!("#{} after #else", keyword);
+                            return error(&self.file, line, &msg);
+                        }
+                        Some(c) => {
+                            let evaluate = keyword == "elif" && !c.taken;
+                            c.active = keyword == "else" && !c.taken;
+                            c.taken = c.taken || keyword == "else";
+                            c.seen_else = keyword == "else";
+                            evaluate
+                        }
+                    };
+                    if evaluate {
+                        let value = try!(self.condition("if", args, line));
+                        let c = conds.last_mut().unwrap();
+                        c.active = value;
+                        c.taken = value;
+                    }
+                    if keyword == "endif" {
+                        conds.pop();
+                    }
+                    continue;
+                }
+                _ if !active => continue,
+                _ => {}
+            }
+
+            match keyword {
+                "define" => try!(self.define(args, line)),
+                "undef" => match args.first() {
+                    Some(t) if t.kind == Kind::Ident => {
+                        self.macros.remove(&t.text);
+                    }
+                    _ => {
+                        let msg = "no macro name given in #undef directive";
+                        return error(&self.file, line, msg);
+                    }
+                },
+                "include" | "include_next" => {
+                    let spec = directive_rest(&text, keyword);
+                    if try!(self.include(spec, args, &dir, line)) {
+                        let file = self.file.clone();
+                        self.out.marker(line + 1, &file, Some(2));
+                    }
+                }
+                "line" => {
+                    let args = try!(self.expand(args.to_vec()));
+                    let n = match args.first() {
+                        Some(t) if t.kind == Kind::Number => t.text.parse::<usize>().ok(),
+                        _ => None,
+                    };
+                    let n = match n {
+                        Some(n) => n,
+                        None => return error(&self.file, line, "invalid #line directive"),
+                    };
+                    match args.get(1) {
+                        Some(t) if t.kind == Kind::Str && t.text.starts_with('"') => {
+                            let f = &t.text[1..t.text.len() - 1];
+                            self.file = f.replace("\\\\", "\\").replace("\\\"", "\"");
+                        }
+                        _ => {}
+                    }
+                    delta = n as isize - physical as isize - 1;
+                    let file = self.file.clone();
+                    self.out.marker(n, &file, None);
+                }
+                "error" => {
+                    let msg = format!("#error {}", directive_rest(&text, keyword));
+                    return error(&self.file, line, &msg);
+                }
+                "pragma" => {
+                    if args.first().map_or(false, |t| t.is("once")) {
+                        if let Some(path) = path {
+                            self.once.insert(canonical(path));
+                        }
+                    } else {
+                        let pragma = format!("#pragma {}", directive_rest(&text, keyword));
+                        self.out.line(line, &self.file, &pragma);
+                    }
+                }
+                "" if tokens.len() == 1 => {}
+                "warning" => self.warnings.push(Diagnostic {
+                    file: Some(self.file.clone()),
+                    line: Some(line),
+                    column: None,
+                    message: format!("warning: #warning {}", directive_rest(&text, keyword)),
+                }),
+                "ident" | "sccs" => {}
+                _ => {
+                    let msg = format!("invalid preprocessing directive #{}", keyword);
+                    return error(&self.file, line, &msg);
+                }
+            }
+        }
+
+        try!(self.flush(&mut pending));
+
+        if !conds.is_empty() {
+            let line = self.out.line;
+            return error(&self.file, line, "unterminated conditional directive");
+        }
+        self.file = parent;
+        Ok(())
+    }
+
+    // Expand macros in pending text lines and write them out
+    fn flush(&mut self, pending: &mut Vec<Token>) -> io::Result<()> {
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let tokens = try!(self.expand(mem::replace(pending, Vec::new())));
+        for t in &tokens {
+            self.out.token(t, &self.file);
+        }
+        Ok(())
+    }
+
+    fn define(&mut self, tokens: &[Token], line: usize) -> io::Result<()> {
+        let name = match tokens.first() {
+            Some(t) if t.is("defined") => {
+                let msg = "\"defined\" cannot be used as a macro name";
+                return error(&self.file, line, msg);
+            }
+            Some(t) if t.kind == Kind::Ident => t.text.clone(),
+            _ => {
+                let msg = "no macro name given in #define directive";
+                return error(&self.file, line, msg);
+            }
+        };
+
+        let mut i = 1;
+        let mut params = None;
+        let mut variadic = false;
+
+        if tokens.get(1).map_or(false, |t| t.is("(") && !t.space) {
+            let mut list = Vec::new();
+            i = 2;
+            if tokens.get(i).map_or(false, |t| t.is(")")) {
+                i += 1;
+            } else {
+                loop {
+                    match tokens.get(i) {
+                        Some(t) if t.kind == Kind::Ident => {
+                            list.push(t.text.clone());
+                            i += 1;
+                            // GNU named variadic parameter, `args...`
+                            if tokens.get(i).map_or(false, |t| t.is("...")) {
+                                variadic = true;
+                                i += 1;
+                            }
+                        }
+                        Some(t) if t.is("...") => {
+                            list.push("__VA_ARGS__".into());
+                            variadic = true;
+                            i += 1;
+                        }
+                        _ => return error(&self.file, line, "expected parameter name"),
+                    }
+                    match tokens.get(i) {
+                        Some(t) if t.is(",") && !variadic => i += 1,
+                        Some(t) if t.is(")") => {
+                            i += 1;
+                            break;
+                        }
+                        _ => {
+                            let msg = "expected ',' or ')' in macro parameter list";
+                            return error(&self.file, line, msg);
+                        }
+                    }
+                }
+            }
+            params = Some(list);
+        }
+
+        let mut body = tokens[i..].to_vec();
+        if let Some(t) = body.first_mut() {
+            t.space = false;
+        }
+
+        self.macros.insert(
+            name,
+            MacroDef {
+                params: params,
+                variadic: variadic,
+                body: body,
+            },
+        );
+        Ok(())
+    }
+
+    fn is_defined(&self, name: &str) -> bool {
+        name == "__LINE__" || name == "__FILE__" || self.macros.contains_key(name)
+    }
+
+    fn condition(&self, keyword: &str, tokens: &[Token], line: usize) -> io::Result<bool> {
+        if keyword != "if" {
+            return match tokens.first() {
+                Some(t) if t.kind == Kind::Ident => {
+                    Ok(self.is_defined(&t.text) == (keyword == "ifdef"))
+                }
+                _ => {
+                    let msg = format!("no macro name given in #{} directive", keyword);
+                    error(&self.file, line, &msg)
+                }
+            };
+        }
+
+        // `defined` operators are replaced before macro expansion
+        let mut input = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let t = &tokens[i];
+            if !t.is("defined") {
+                input.push(t.clone());
+                i += 1;
+                continue;
+            }
+            let (name, n) = match (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3)) {
+                (Some(a), _, _) if a.kind == Kind::Ident => (a, 2),
+                (Some(l), Some(a), Some(r)) if l.is("(") && a.kind == Kind::Ident && r.is(")") => {
+                    (a, 4)
+                }
+                _ => {
+                    let msg = "operator \"defined\" requires an identifier";
+                    return error(&self.file, line, msg);
+                }
+            };
+            let value = if self.is_defined(&name.text) {
+                "1"
+            } else {
+                "0"
+            };
+            input.push(t.with_text(Kind::Number, value.into()));
+            i += n;
+        }
+
+        let tokens = try!(self.expand(input));
+        let mut eval = Eval {
+            tokens: &tokens,
+            pos: 0,
+            skip: 0,
+        };
+        let value = eval.expr().and_then(|v| match eval.peek() {
+            None => Ok(v),
+            Some(t) => Err(format!(
+                "missing binary operator before token \"{}\"",
+                t.text
+            )),
+        });
+        match value {
+            Ok(v) => Ok(v != 0),
+            Err(msg) => error(&self.file, line, &msg),
+        }
+    }
+
+    // Returns false if the file was skipped because of `#pragma once`
+    fn include(
+        &mut self,
+        spec: &str,
+        tokens: &[Token],
+        dir: &Path,
+        line: usize,
+    ) -> io::Result<bool> {
+        let mut spec = spec.to_owned();
+        if !spec.starts_with('"') && !spec.starts_with('<') {
+            spec.clear();
+            for t in &try!(self.expand(tokens.to_vec())) {
+                if t.space && !spec.is_empty() {
+                    spec.push(' ');
+                }
+                spec.push_str(&t.text);
+            }
+        }
+
+        let end = spec.get(1..).and_then(|s| s.find(|c| c == '"' || c == '>'));
+        let (name, quoted) = match (spec.chars().next(), end) {
+            (Some('"'), Some(n)) if spec[n + 1..].starts_with('"') => (&spec[1..n + 1], true),
+            (Some('<'), Some(n)) if spec[n + 1..].starts_with('>') => (&spec[1..n + 1], false),
+            _ => {
+                let msg = "#include expects \"FILENAME\" or <FILENAME>";
+                return error(&self.file, line, msg);
+            }
+        };
+
+        let mut candidates = Vec::new();
+        if quoted {
+            candidates.push(dir.join(name));
+        }
+        for d in &self.config.include_dirs {
+            candidates.push(d.join(name));
+        }
+        let path = match candidates.into_iter().find(|p| p.is_file()) {
+            Some(p) => p,
+            None => {
+                let msg = format!("{}: No such file or directory", name);
+                return error(&self.file, line, &msg);
+            }
+        };
+
+        if self.once.contains(&canonical(&path)) {
+            return Ok(false);
+        }
+        if self.depth >= MAX_INCLUDE_DEPTH {
+            return error(&self.file, line, "#include nested too deeply");
+        }
+
+        let text = try!(read_file(self.config, &path));
+        self.depth += 1;
+        let res = self.run_file(&path.to_string_lossy(), Some(&path), &text, Some(1));
+        self.depth -= 1;
+        try!(res);
+        Ok(true)
+    }
+
+    fn expand(&self, tokens: Vec<Token>) -> io::Result<Vec<Token>> {
+        let mut input: VecDeque<Token> = tokens.into_iter().collect();
+        let mut out = Vec::new();
+
+        while let Some(t) = input.pop_front() {
+            if t.kind != Kind::Ident || t.is_hidden() {
+                out.push(t);
+                continue;
+            }
+            match &*t.text {
+                "__LINE__" => {
+                    out.push(t.with_text(Kind::Number, t.line.to_string()));
+                    continue;
+                }
+                "__FILE__" => {
+                    let file = format!("\"{}\"", escape(&self.file));
+                    out.push(t.with_text(Kind::Str, file));
+                    continue;
+                }
+                _ => {}
+            }
+
+            let def = match self.macros.get(&t.text) {
+                Some(def) => def,
+                None => {
+                    out.push(t);
+                    continue;
+                }
+            };
+
+            let args = match def.params {
+                None => Vec::new(),
+                Some(ref params) => {
+                    if !input.front().map_or(false, |t| t.is("(")) {
+                        out.push(t);
+                        continue;
+                    }
+                    input.pop_front();
+                    try!(self.collect_args(&t, def, params.len(), &mut input))
+                }
+            };
+
+            let body = try!(self.substitute(def, &args));
+
+            let mut hide = (*t.hide).clone();
+            hide.push(t.text.clone());
+            let hide = Rc::new(hide);
+
+            for (i, mut b) in body.into_iter().enumerate().rev() {
+                b.line = t.line;
+                if i == 0 {
+                    b.space = t.space;
+                }
+                b.hide = if b.hide.is_empty() {
+                    hide.clone()
+                } else {
+                    let mut h = (*hide).clone();
+                    h.extend(b.hide.iter().cloned());
+                    Rc::new(h)
+                };
+                input.push_front(b);
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn collect_args(
+        &self,
+        site: &Token,
+        def: &MacroDef,
+        count: usize,
+        input: &mut VecDeque<Token>,
+    ) -> io::Result<Vec<Vec<Token>>> {
+        let mut args = vec![Vec::new()];
+        let mut depth = 0;
+
+        loop {
+            let t = match input.pop_front() {
+                Some(t) => t,
+                None => {
+                    let msg = format!(
+                        "unterminated argument list invoking macro \"{}\"",
+                        site.text
+                    );
+                    return error(&self.file, site.line, &msg);
+                }
+            };
+            if t.is("(") {
+                depth += 1;
+            } else if t.is(")") {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            } else if t.is(",") && depth == 0 && !(def.variadic && args.len() == count) {
+                args.push(Vec::new());
+                continue;
+            }
+            args.last_mut().unwrap().push(t);
+        }
+
+        if count == 0 && args.len() == 1 && args[0].is_empty() {
+            args.clear();
+        }
+        if def.variadic && args.len() + 1 == count {
+            args.push(Vec::new());
+        }
+        if args.len() != count {
+            let msg = format!(
+                "macro \"{}\" passed {} arguments, but takes {}",
+                site.text,
+                args.len(),
+                count
+            );
+            return error(&self.file, site.line, &msg);
+        }
+        Ok(args)
+    }
+
+    fn substitute(&self, def: &MacroDef, args: &[Vec<Token>]) -> io::Result<Vec<Token>> {
+        let no_params = Vec::new();
+        let params = def.params.as_ref().unwrap_or(&no_params);
+        let param = |t: &Token| {
+            if t.kind == Kind::Ident {
+                params.iter().position(|p| *p == t.text)
+            } else {
+                None
+            }
+        };
+        let is_paste = |t: Option<&Token>| t.map_or(false, |t| t.is("##") || t.is("%:%:"));
+
+        let body = &def.body;
+        let mut pieces = Vec::new();
+        let mut i = 0;
+        while i < body.len() {
+            let t = &body[i];
+            if def.params.is_some() && (t.is("#") || t.is("%:")) {
+                if let Some(n) = body.get(i + 1).and_then(&param) {
+                    pieces.push(Piece::Tokens(vec![stringify(&args[n], t)]));
+                    i += 2;
+                    continue;
+                }
+            }
+            if is_paste(Some(t)) {
+                let empty_va = def.variadic
+                    && body.get(i + 1).and_then(&param) == Some(params.len() - 1)
+                    && args[params.len() - 1].is_empty();
+                pieces.push(Piece::Paste(empty_va));
+                i += 1;
+                continue;
+            }
+            match param(t) {
+                Some(n) => {
+                    let raw = is_paste(body.get(i + 1)) || (i > 0 && is_paste(body.get(i - 1)));
+                    let mut tokens = if raw {
+                        args[n].clone()
+                    } else {
+                        try!(self.expand(args[n].clone()))
+                    };
+                    if let Some(first) = tokens.first_mut() {
+                        first.space = t.space;
+                    }
+                    pieces.push(Piece::Tokens(tokens));
+                }
+                None => pieces.push(Piece::Tokens(vec![t.clone()])),
+            }
+            i += 1;
+        }
+
+        let mut result: Vec<Token> = Vec::new();
+        // Previous piece produced no tokens
+        let mut placemarker = false;
+        // Pending `##`, and whether its left operand produced no tokens
+        let mut pasting = None;
+        for piece in pieces {
+            match piece {
+                Piece::Paste(true) => {
+                    if !placemarker && result.last().map_or(false, |t| t.is(",")) {
+                        result.pop();
+                    }
+                }
+                Piece::Paste(false) => pasting = Some(placemarker),
+                Piece::Tokens(tokens) => {
+                    let empty = tokens.is_empty();
+                    let mut tokens = tokens.into_iter();
+                    match pasting.take() {
+                        Some(false) => {
+                            placemarker = false;
+                            if let Some(right) = tokens.next() {
+                                let left = result.pop().unwrap();
+                                result.extend(paste(left, right));
+                            }
+                        }
+                        _ => placemarker = empty,
+                    }
+                    result.extend(tokens);
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+fn pp(src: &str) -> String {
+    let config = Config::with_internal_preprocessor();
+    preprocess_str(&config, "t.c", src).unwrap().0
+}
+
+// Non-empty output lines, without line markers
+#[cfg(test)]
+fn pp_lines(src: &str) -> Vec<String> {
+    pp(src)
+        .lines()
+        .filter(|l| !l.starts_with("# "))
+        .map(|l| l.trim().to_owned())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+#[test]
+fn test_macros() {
+    assert_eq!(
+        pp_lines("#define N 10\nint a[N];\n#undef N\nint b[N];\n"),
+        ["int a[10];", "int b[N];"]
+    );
+    assert_eq!(
+        pp_lines("#define MAX(a, b) ((a) > (b) ? (a) : (b))\nMAX(x, MAX(1, 2))\nMAX\n"),
+        [
+            "((x) > (((1) > (2) ? (1) : (2))) ? (x) : (((1) > (2) ? (1) : (2))))",
+            "MAX"
+        ]
+    );
+    assert_eq!(
+        pp_lines("#define S(x) #x\n#define CAT(a, b) a ## b\nS(a \"b\" c) CAT(x, 1) CAT(, y)\n"),
+        ["\"a \\\"b\\\" c\" x1 y"]
+    );
+    assert_eq!(
+        pp_lines("#define F(fmt, ...) f(fmt, ## __VA_ARGS__)\nF(a) F(a, b, c)\n"),
+        ["f(a) f(a, b, c)"]
+    );
+    assert_eq!(pp_lines("#define NEG -1\nx=-NEG;\n"), ["x=- -1;"]);
+}
+
+#[test]
+fn test_recursive_macros() {
+    assert_eq!(pp_lines("#define foo foo + 1\nfoo\n"), ["foo + 1"]);
+    assert_eq!(pp_lines("#define a b\n#define b a\na b\n"), ["a b"]);
+    assert_eq!(
+        pp_lines("#define f(x) g(x)\n#define g(x) f(x)\nf(f(1))\n"),
+        ["f(f(1))"]
+    );
+}
+
+#[test]
+fn test_conditionals() {
+    let src = r#"
+#define A 2
+#if A > 1 && defined(A) && !defined B
+yes1
+#elif 1
+no
+#else
+no
+#endif
+#ifdef B
+no
+#elif A == 2
+yes2
+#endif
+#if 0
+#if 1/0
+#error not reached
+#endif
+#else
+yes3
+#endif
+#if (1 ? 3 : 1 / 0) == 3 && 'a' == 97 && 0x10 == 16 && 010 == 8 && -1 < 0
+yes4
+#endif
+"#;
+    assert_eq!(pp_lines(src), ["yes1", "yes2", "yes3", "yes4"]);
+
+    let config = Config::with_internal_preprocessor();
+    assert!(preprocess_str(&config, "t.c", "#if 1\n").is_err());
+    assert!(preprocess_str(&config, "t.c", "#endif\n").is_err());
+    assert!(preprocess_str(&config, "t.c", "#error stop\n").is_err());
+
+    let (_, warnings) = preprocess_str(&config, "t.c", "\n#warning careful\n").unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].to_string(), "t.c:2: warning: #warning careful");
+}
+
+#[test]
+fn test_line_markers() {
+    let src = "/* multi\nline */ a __LINE__\n\\\nb __LINE__ __FILE__\n\n\n\n\n\n\n\n\n\nc\n\
+               #line 100 \"x.c\"\nd __LINE__\n";
+    let out = pp(src);
+    assert_eq!(
+        out,
+        "# 1 \"t.c\"\n\na 2\n\nb 4 \"t.c\"\n# 14 \"t.c\"\nc\n# 100 \"x.c\"\nd 100\n"
+    );
+
+    let location = |token: &str| {
+        let pos = out.find(&format!("\n{}", token)).unwrap() + 1;
+        let (loc, _) = ::loc::get_location_for_offset(&out, pos);
+        (loc.file.to_owned(), loc.line)
+    };
+    assert_eq!(location("c"), ("t.c".to_owned(), 14));
+    assert_eq!(location("d"), ("x.c".to_owned(), 100));
+}
@@ -3,6 +3,9 @@
 
 //! Debug printer for abstract syntax tree
 //!
+//! The tree is printed as indented text by default, or as JSON, S-expressions or a Graphviz
+//! graph.
+//!
 //! ```no_run
 //! # use lang_c::print::Printer;
 //! use lang_c::visit::Visit;
@@ -13,6 +16,7 @@
 use std::fmt;
 
 use ast::*;
+use omp::*;
 use span::Span;
 use visit::*;
 
@@ -21,53 +25,386 @@ use visit::*;
 /// Recursively prints the AST tree as indented list of AST nodes, one node per line.
 /// Each line contains name of the AST node type, followed by the enum variant
 /// (when it does not match name of contained node), and primitive fields.
+///
+/// The same tree can be written in other formats, see `Printer::json`. Output is complete
+/// when the printer is dropped.
 pub struct Printer<'a> {
-    w: &'a mut fmt::Write,
-    offset: usize,
+    format: Output<'a>,
+}
+
+enum Output<'a> {
+    Root(Box<Format + 'a>),
+    Node(&'a mut (Format + 'a)),
+}
+
+// Output format of the tree, receiving the nodes in order
+trait Format {
+    // Start of a node, followed by its fields, its children and then `end`
+    fn node(&mut self, name: &str, span: &Span);
+    fn field(&mut self, value: &str);
+    fn field_str(&mut self, value: &str);
+    fn field_strs(&mut self, values: &[&str]);
+    fn end(&mut self);
+    // All nodes have been written
+    fn finish(&mut self) {}
 }
 
 impl<'a> Printer<'a> {
     pub fn new(w: &mut fmt::Write) -> Printer {
-        Printer { w: w, offset: 0 }
+        Printer::with_format(Box::new(Text {
+            w: w,
+            depth: 0,
+            line: false,
+            spans: None,
+        }))
+    }
+
+    /// Printer writing the same text as `new`, and the span of the node of each line to `spans`
+    #[doc(hidden)]
+    pub fn with_spans(w: &'a mut fmt::Write, spans: &'a mut Vec<Span>) -> Printer<'a> {
+        Printer::with_format(Box::new(Text {
+            w: w,
+            depth: 0,
+            line: false,
+            spans: Some(spans),
+        }))
+    }
+
+    /// Printer writing one JSON object per node
+    ///
+    /// Each object has the name of the node in `kind`, its primitive fields as strings in
+    /// `fields`, its `children` and, unless disabled in the options, the byte offsets of
+    /// its `span`. Top-level objects are followed by a newline.
+    pub fn json(w: &'a mut fmt::Write, options: &JsonOptions) -> Printer<'a> {
+        Printer::with_format(Box::new(Json {
+            w: w,
+            options: options.clone(),
+            nodes: Vec::new(),
+        }))
+    }
+
+    /// Printer writing one S-expression per node
+    ///
+    /// Each node is a list of its name in kebab case, its primitive fields and its children,
+    /// like `(identifier "x")`. Identifiers and literals are strings, other fields are symbols
+    /// unless they contain characters not allowed in a symbol. Strings escape `"` and `\`
+    /// with a backslash, line breaks and tabs as `\n`, `\r` and `\t`, and other control
+    /// characters as `\xHH;`, as in R7RS Scheme. Each child is on its own line.
+    pub fn sexp(w: &'a mut fmt::Write, options: &SexpOptions) -> Printer<'a> {
+        Printer::with_format(Box::new(Sexp {
+            w: w,
+            options: options.clone(),
+            spans: Vec::new(),
+        }))
+    }
+
+    /// Printer writing a Graphviz digraph with a vertex per node
+    ///
+    /// Vertices are labelled with the name of the node and its primitive fields, like the
+    /// name of an identifier or the spelling of a literal, and numbered
+    /// in the order of the nodes, so the same tree gives the same graph. The graph is complete
+    /// when the printer is dropped.
+    pub fn dot(w: &'a mut fmt::Write, options: &DotOptions) -> Printer<'a> {
+        Printer::with_format(Box::new(Dot {
+            w: w,
+            options: options.clone(),
+            started: false,
+            count: 0,
+            nodes: Vec::new(),
+            pending: None,
+        }))
+    }
+
+    fn with_format(format: Box<Format + 'a>) -> Printer<'a> {
+        Printer {
+            format: Output::Root(format),
+        }
+    }
+
+    fn format(&mut self) -> &mut Format {
+        match self.format {
+            Output::Root(ref mut f) => &mut **f,
+            Output::Node(ref mut f) => &mut **f,
+        }
     }
 
     fn block(&mut self) -> Printer {
-        writeln!(&mut self.w, "").unwrap();
         Printer {
-            w: &mut self.w,
-            offset: self.offset + 1,
+            format: Output::Node(self.format()),
         }
     }
 
-    fn name(&mut self, name: &str) {
-        write!(&mut self.w, "{2:1$}{0}", name, self.offset * 4, "").unwrap();
+    fn name(&mut self, name: &str, span: &Span) {
+        self.format().node(name, span);
     }
 
     fn field<T: fmt::Display>(&mut self, s: T) {
-        write!(&mut self.w, " {}", s).unwrap();
+        self.format().field(&s.to_string());
     }
 
     fn field_str(&mut self, s: &str) {
-        self.field_str_ext(" ", s);
+        self.format().field_str(s);
+    }
+}
+
+impl<'a> Drop for Printer<'a> {
+    fn drop(&mut self) {
+        match self.format {
+            Output::Root(ref mut f) => f.finish(),
+            Output::Node(ref mut f) => f.end(),
+        }
+    }
+}
+
+/// Options of `Printer::json`
+#[derive(Debug, Clone)]
+pub struct JsonOptions {
+    /// Include the span of each node
+    pub spans: bool,
+    /// Indent the output, instead of writing each top-level node on one line
+    pub pretty: bool,
+}
+
+impl Default for JsonOptions {
+    fn default() -> JsonOptions {
+        JsonOptions {
+            spans: true,
+            pretty: false,
+        }
+    }
+}
+
+/// Translation unit as JSON, see `Printer::json`
+pub fn ast_to_json(unit: &TranslationUnit, options: &JsonOptions) -> String {
+    let mut s = String::new();
+    Printer::json(&mut s, options).visit_translation_unit(unit);
+    s
+}
+
+/// Options of `Printer::sexp`
+#[derive(Debug, Clone, Default)]
+pub struct SexpOptions {
+    /// End each list with the span of the node, as `@start:end`
+    pub spans: bool,
+}
+
+/// Translation unit as S-expressions, see `Printer::sexp`
+pub fn ast_to_sexp(unit: &TranslationUnit, options: &SexpOptions) -> String {
+    let mut s = String::new();
+    Printer::sexp(&mut s, options).visit_translation_unit(unit);
+    s
+}
+
+/// Options of `Printer::dot`
+#[derive(Debug, Clone)]
+pub struct DotOptions {
+    /// Put each external declaration in its own cluster
+    pub clusters: bool,
+    /// Include the span of each node in its label
+    pub spans: bool,
+    /// Maximum number of field characters shown in a label, longer fields are cut off with `...`
+    pub max_label_chars: usize,
+    /// Names of nodes left out of the graph, like `"DeclarationSpecifier"`
+    ///
+    /// Children of a skipped node are connected to its closest shown ancestor.
+    pub skip: Vec<String>,
+}
+
+impl Default for DotOptions {
+    fn default() -> DotOptions {
+        DotOptions {
+            clusters: false,
+            spans: false,
+            max_label_chars: 20,
+            skip: Vec::new(),
+        }
+    }
+}
+
+/// Translation unit as a Graphviz digraph, see `Printer::dot`
+pub fn ast_to_dot(unit: &TranslationUnit, options: &DotOptions) -> String {
+    let mut s = String::new();
+    Printer::dot(&mut s, options).visit_translation_unit(unit);
+    s
+}
+
+// Format of `Printer::new`
+struct Text<'a> {
+    w: &'a mut fmt::Write,
+    depth: usize,
+    // Line of the last node is not finished yet
+    line: bool,
+    // Span of each line, see `Printer::with_spans`
+    spans: Option<&'a mut Vec<Span>>,
+}
+
+impl<'a> Text<'a> {
+    fn end_line(&mut self) {
+        if self.line {
+            self.w.write_str("\n").unwrap();
+            self.line = false;
+        }
+    }
+}
+
+impl<'a> Format for Text<'a> {
+    fn node(&mut self, name: &str, span: &Span) {
+        if let Some(ref mut spans) = self.spans {
+            spans.push(*span);
+        }
+        self.end_line();
+        write!(&mut self.w, "{2:1$}{0}", name, self.depth * 4, "").unwrap();
+        self.depth += 1;
+        self.line = true;
+    }
+
+    fn field(&mut self, value: &str) {
+        write!(&mut self.w, " {}", value).unwrap();
+    }
+
+    fn field_str(&mut self, value: &str) {
+        write!(&mut self.w, " \"{}\"", Escape(value)).unwrap();
+    }
+
+    fn field_strs(&mut self, values: &[&str]) {
+        self.w.write_str(" [").unwrap();
+        for (i, v) in values.iter().enumerate() {
+            let comma = if i > 0 { ", " } else { "" };
+            write!(&mut self.w, "{}\"{}\"", comma, Escape(v)).unwrap();
+        }
+        self.w.write_str("]").unwrap();
+    }
+
+    fn end(&mut self) {
+        self.end_line();
+        self.depth -= 1;
+    }
+}
+
+// Format of `Printer::json`
+struct Json<'a> {
+    w: &'a mut fmt::Write,
+    options: JsonOptions,
+    // Open nodes, with their fields until the first child
+    nodes: Vec<JsonNode>,
+}
+
+struct JsonNode {
+    fields: Option<Vec<String>>,
+    children: bool,
+}
+
+impl<'a> Json<'a> {
+    // Line break and indentation in pretty output
+    fn indent(&mut self, level: usize) {
+        if self.options.pretty {
+            write!(&mut self.w, "\n{:1$}", "", level * 2).unwrap();
+        }
+    }
+
+    fn key(&mut self, level: usize, key: &str) {
+        self.w.write_str(",").unwrap();
+        self.indent(level);
+        let colon = if self.options.pretty { ": " } else { ":" };
+        write!(&mut self.w, "\"{}\"{}", key, colon).unwrap();
+    }
+
+    // Write the fields of the innermost node and start its children
+    fn start_children(&mut self) {
+        let fields = match self.nodes.last_mut().and_then(|n| n.fields.take()) {
+            Some(fields) => fields,
+            None => return,
+        };
+        let level = self.nodes.len() * 2 - 1;
+        self.key(level, "fields");
+        let comma = if self.options.pretty { ", " } else { "," };
+        write!(&mut self.w, "[{}]", fields.join(comma)).unwrap();
+        self.key(level, "children");
+        self.w.write_str("[").unwrap();
+    }
+
+    fn push_field(&mut self, value: String) {
+        if let Some(fields) = self.nodes.last_mut().and_then(|n| n.fields.as_mut()) {
+            fields.push(value);
+        }
     }
+}
 
-    fn field_str_ext(&mut self, prefix: &str, str: &str) {
-        write!(&mut self.w, "{}\"{}\"", prefix, Escape(str)).unwrap();
+impl<'a> Format for Json<'a> {
+    fn node(&mut self, name: &str, span: &Span) {
+        self.start_children();
+        let level = self.nodes.len() * 2;
+        if let Some(parent) = self.nodes.last_mut() {
+            if parent.children {
+                self.w.write_str(",").unwrap();
+            }
+            parent.children = true;
+        }
+        if level > 0 {
+            self.indent(level);
+        }
+        self.w.write_str("{").unwrap();
+        self.indent(level + 1);
+        let colon = if self.options.pretty { ": " } else { ":" };
+        write!(&mut self.w, "\"kind\"{}{}", colon, JsonString(name)).unwrap();
+        if self.options.spans && !span.is_none() {
+            let comma = if self.options.pretty { ", " } else { "," };
+            self.key(level + 1, "span");
+            write!(&mut self.w, "[{}{}{}]", span.start, comma, span.end).unwrap();
+        }
+        self.nodes.push(JsonNode {
+            fields: Some(Vec::new()),
+            children: false,
+        });
+    }
+
+    fn field(&mut self, value: &str) {
+        self.push_field(JsonString(value).to_string());
+    }
+
+    fn field_str(&mut self, value: &str) {
+        self.push_field(JsonString(value).to_string());
+    }
+
+    fn field_strs(&mut self, values: &[&str]) {
+        let comma = if self.options.pretty { ", " } else { "," };
+        let values: Vec<String> = values.iter().map(|v| JsonString(v).to_string()).collect();
+        self.push_field(format!("[{}]", values.join(comma)));
+    }
+
+    fn end(&mut self) {
+        self.start_children();
+        let level = self.nodes.len() * 2 - 2;
+        let children = self.nodes.pop().map(|n| n.children);
+        if children == Some(true) {
+            self.indent(level + 1);
+        }
+        self.w.write_str("]").unwrap();
+        self.indent(level);
+        self.w.write_str("}").unwrap();
+        if self.nodes.is_empty() {
+            self.w.write_str("\n").unwrap();
+        }
     }
 }
 
 impl<'ast, 'a> Visit<'ast> for Printer<'a> {
     fn visit_identifier(&mut self, n: &'ast Identifier, span: &'ast Span) {
-        self.name("Identifier");
+        self.name("Identifier", span);
         self.field_str(&n.name);
         visit_identifier(&mut self.block(), n, span);
     }
     fn visit_constant(&mut self, n: &'ast Constant, span: &'ast Span) {
-        self.name("Constant");
+        self.name("Constant", span);
         match *n {
             Constant::Character(ref c) => {
                 self.field("Character");
-                self.field(c);
+                self.field(&c.value);
+            }
+            Constant::Nullptr => self.field("Nullptr"),
+            Constant::Bool(b) => {
+                self.field("Bool");
+                self.field(b);
             }
             _ => {},
         }
@@ -75,42 +412,44 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         visit_constant(&mut self.block(), n, span);
     }
     fn visit_integer(&mut self, n: &'ast Integer, span: &'ast Span) {
-        self.name("Integer");
+        self.name("Integer", span);
         self.field_str(&n.number);
         visit_integer(&mut self.block(), n, span);
     }
     fn visit_integer_base(&mut self, n: &'ast IntegerBase, span: &'ast Span) {
-        self.name("IntegerBase");
+        self.name("IntegerBase", span);
         self.field(match *n {
             IntegerBase::Decimal => "Decimal",
             IntegerBase::Octal => "Octal",
+            IntegerBase::OctalExplicit => "OctalExplicit",
             IntegerBase::Hexadecimal => "Hexadecimal",
             IntegerBase::Binary => "Binary",
         });
         visit_integer_base(&mut self.block(), n, span);
     }
     fn visit_integer_suffix(&mut self, n: &'ast IntegerSuffix, span: &'ast Span) {
-        self.name("IntegerSuffix");
+        self.name("IntegerSuffix", span);
         self.field(n.unsigned);
         self.field(n.imaginary);
         visit_integer_suffix(&mut self.block(), n, span);
     }
     fn visit_integer_size(&mut self, n: &'ast IntegerSize, span: &'ast Span) {
-        self.name("IntegerSize");
+        self.name("IntegerSize", span);
         self.field(match *n {
             IntegerSize::Int => "Int",
             IntegerSize::Long => "Long",
             IntegerSize::LongLong => "LongLong",
+            IntegerSize::BitPrecise => "BitPrecise",
         });
         visit_integer_size(&mut self.block(), n, span);
     }
     fn visit_float(&mut self, n: &'ast Float, span: &'ast Span) {
-        self.name("Float");
+        self.name("Float", span);
         self.field_str(&n.number);
         visit_float(&mut self.block(), n, span);
     }
     fn visit_float_base(&mut self, n: &'ast FloatBase, span: &'ast Span) {
-        self.name("FloatBase");
+        self.name("FloatBase", span);
         self.field(match *n {
             FloatBase::Decimal => "Decimal",
             FloatBase::Hexadecimal => "Hexadecimal",
@@ -118,34 +457,49 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         visit_float_base(&mut self.block(), n, span);
     }
     fn visit_float_suffix(&mut self, n: &'ast FloatSuffix, span: &'ast Span) {
-        self.name("FloatSuffix");
+        self.name("FloatSuffix", span);
         self.field(n.imaginary);
         visit_float_suffix(&mut self.block(), n, span);
     }
     fn visit_float_format(&mut self, n: &'ast FloatFormat, span: &'ast Span) {
-        self.name("FloatFormat");
+        self.name("FloatFormat", span);
         print_float_format(self, n);
         visit_float_format(&mut self.block(), n, span);
     }
+    fn visit_fixed_point_format(&mut self, n: &'ast FixedPointFormat, span: &'ast Span) {
+        self.name("FixedPointFormat", span);
+        self.field(match n.kind {
+            FixedPointKind::Fract => "Fract",
+            FixedPointKind::Accum => "Accum",
+        });
+        self.field(match n.size {
+            FixedPointSize::Short => "Short",
+            FixedPointSize::Plain => "Plain",
+            FixedPointSize::Long => "Long",
+            FixedPointSize::LongLong => "LongLong",
+        });
+        self.field(n.unsigned);
+        visit_fixed_point_format(&mut self.block(), n, span);
+    }
     fn visit_string_literal(&mut self, n: &'ast StringLiteral, span: &'ast Span) {
-        self.name("StringLiteral");
+        self.name("StringLiteral", span);
 
-        self.w.write_str(" [").unwrap();
-        let mut comma = false;
-        for p in n {
-            self.field_str_ext(if comma { ", " } else { "" }, p);
-            comma = true;
-        }
-        self.w.write_str("]").unwrap();
+        let pieces: Vec<&str> = n.iter().map(|p| &p.value[..]).collect();
+        self.format().field_strs(&pieces);
 
         visit_string_literal(&mut self.block(), n, span);
     }
     fn visit_expression(&mut self, n: &'ast Expression, span: &'ast Span) {
-        self.name("Expression");
+        self.name("Expression", span);
+        match *n {
+            Expression::LabelAddress(_) => self.field("LabelAddress"),
+            Expression::Extension(_) => self.field("Extension"),
+            _ => {}
+        }
         visit_expression(&mut self.block(), n, span);
     }
     fn visit_member_operator(&mut self, n: &'ast MemberOperator, span: &'ast Span) {
-        self.name("MemberOperator");
+        self.name("MemberOperator", span);
         self.field(match *n {
             MemberOperator::Direct => "Direct",
             MemberOperator::Indirect => "Indirect",
@@ -153,11 +507,11 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         visit_member_operator(&mut self.block(), n, span);
     }
     fn visit_generic_selection(&mut self, n: &'ast GenericSelection, span: &'ast Span) {
-        self.name("GenericSelection");
+        self.name("GenericSelection", span);
         visit_generic_selection(&mut self.block(), n, span);
     }
     fn visit_generic_association(&mut self, n: &'ast GenericAssociation, span: &'ast Span) {
-        self.name("GenericAssociation");
+        self.name("GenericAssociation", span);
         visit_generic_association(&mut self.block(), n, span);
     }
     fn visit_generic_association_type(
@@ -165,35 +519,49 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         n: &'ast GenericAssociationType,
         span: &'ast Span,
     ) {
-        self.name("GenericAssociationType");
+        self.name("GenericAssociationType", span);
         visit_generic_association_type(&mut self.block(), n, span);
     }
     fn visit_member_expression(&mut self, n: &'ast MemberExpression, span: &'ast Span) {
-        self.name("MemberExpression");
+        self.name("MemberExpression", span);
         visit_member_expression(&mut self.block(), n, span);
     }
     fn visit_call_expression(&mut self, n: &'ast CallExpression, span: &'ast Span) {
-        self.name("CallExpression");
+        self.name("CallExpression", span);
         visit_call_expression(&mut self.block(), n, span);
     }
+    fn visit_kernel_call_expression(&mut self, n: &'ast KernelCallExpression, span: &'ast Span) {
+        self.name("KernelCallExpression", span);
+        self.field("Configuration");
+        self.field(n.configuration.len());
+        visit_kernel_call_expression(&mut self.block(), n, span);
+    }
     fn visit_compound_literal(&mut self, n: &'ast CompoundLiteral, span: &'ast Span) {
-        self.name("CompoundLiteral");
+        self.name("CompoundLiteral", span);
         visit_compound_literal(&mut self.block(), n, span);
     }
     fn visit_sizeofty(&mut self, n: &'ast SizeOfTy, span: &'ast Span) {
-        self.name("SizeOfTy");
+        self.name("SizeOfTy", span);
         visit_sizeofty(&mut self.block(), n, span);
     }
     fn visit_sizeofval(&mut self, n: &'ast SizeOfVal, span: &'ast Span) {
-        self.name("SizeOfVal");
+        self.name("SizeOfVal", span);
         visit_sizeofval(&mut self.block(), n, span);
     }
+    fn visit_countofty(&mut self, n: &'ast CountOfTy, span: &'ast Span) {
+        self.name("CountOfTy", span);
+        visit_countofty(&mut self.block(), n, span);
+    }
+    fn visit_countofval(&mut self, n: &'ast CountOfVal, span: &'ast Span) {
+        self.name("CountOfVal", span);
+        visit_countofval(&mut self.block(), n, span);
+    }
     fn visit_alignof(&mut self, n: &'ast AlignOf, span: &'ast Span) {
-        self.name("AlignOf");
+        self.name("AlignOf", span);
         visit_alignof(&mut self.block(), n, span);
     }
     fn visit_unary_operator(&mut self, n: &'ast UnaryOperator, span: &'ast Span) {
-        self.name("UnaryOperator");
+        self.name("UnaryOperator", span);
         self.field(match *n {
             UnaryOperator::PostIncrement => "PostIncrement",
             UnaryOperator::PostDecrement => "PostDecrement",
@@ -205,6 +573,8 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
             UnaryOperator::Minus => "Minus",
             UnaryOperator::Complement => "Complement",
             UnaryOperator::Negate => "Negate",
+            UnaryOperator::Real => "Real",
+            UnaryOperator::Imag => "Imag",
         });
         visit_unary_operator(&mut self.block(), n, span);
     }
@@ -213,15 +583,15 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         n: &'ast UnaryOperatorExpression,
         span: &'ast Span,
     ) {
-        self.name("UnaryOperatorExpression");
+        self.name("UnaryOperatorExpression", span);
         visit_unary_operator_expression(&mut self.block(), n, span);
     }
     fn visit_cast_expression(&mut self, n: &'ast CastExpression, span: &'ast Span) {
-        self.name("CastExpression");
+        self.name("CastExpression", span);
         visit_cast_expression(&mut self.block(), n, span);
     }
     fn visit_binary_operator(&mut self, n: &'ast BinaryOperator, span: &'ast Span) {
-        self.name("BinaryOperator");
+        self.name("BinaryOperator", span);
         self.field(match *n {
             BinaryOperator::Index => "Index",
             BinaryOperator::Multiply => "Multiply",
@@ -261,44 +631,60 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         n: &'ast BinaryOperatorExpression,
         span: &'ast Span,
     ) {
-        self.name("BinaryOperatorExpression");
+        self.name("BinaryOperatorExpression", span);
         visit_binary_operator_expression(&mut self.block(), n, span);
     }
     fn visit_conditional_expression(&mut self, n: &'ast ConditionalExpression, span: &'ast Span) {
-        self.name("ConditionalExpression");
+        self.name("ConditionalExpression", span);
         visit_conditional_expression(&mut self.block(), n, span);
     }
+    fn visit_choose_expression(&mut self, n: &'ast ChooseExpression, span: &'ast Span) {
+        self.name("ChooseExpression", span);
+        visit_choose_expression(&mut self.block(), n, span);
+    }
+    fn visit_types_compatible_expression(
+        &mut self,
+        n: &'ast TypesCompatibleExpression,
+        span: &'ast Span,
+    ) {
+        self.name("TypesCompatibleExpression", span);
+        visit_types_compatible_expression(&mut self.block(), n, span);
+    }
     fn visit_va_arg_expression(&mut self, n: &'ast VaArgExpression, span: &'ast Span) {
-        self.name("VaArgExpression");
+        self.name("VaArgExpression", span);
         visit_va_arg_expression(&mut self.block(), n, span);
     }
+    fn visit_block_expression(&mut self, n: &'ast BlockExpression, span: &'ast Span) {
+        self.name("BlockExpression", span);
+        visit_block_expression(&mut self.block(), n, span);
+    }
     fn visit_offset_of_expression(&mut self, n: &'ast OffsetOfExpression, span: &'ast Span) {
-        self.name("OffsetOfExpression");
+        self.name("OffsetOfExpression", span);
         visit_offset_of_expression(&mut self.block(), n, span);
     }
     fn visit_offset_designator(&mut self, n: &'ast OffsetDesignator, span: &'ast Span) {
-        self.name("OffsetDesignator");
+        self.name("OffsetDesignator", span);
         visit_offset_designator(&mut self.block(), n, span);
     }
     fn visit_offset_member(&mut self, n: &'ast OffsetMember, span: &'ast Span) {
-        self.name("OffsetMember");
+        self.name("OffsetMember", span);
         print_offset_member(self, n);
         visit_offset_member(&mut self.block(), n, span);
     }
     fn visit_declaration(&mut self, n: &'ast Declaration, span: &'ast Span) {
-        self.name("Declaration");
+        self.name("Declaration", span);
         visit_declaration(&mut self.block(), n, span);
     }
     fn visit_declaration_specifier(&mut self, n: &'ast DeclarationSpecifier, span: &'ast Span) {
-        self.name("DeclarationSpecifier");
+        self.name("DeclarationSpecifier", span);
         visit_declaration_specifier(&mut self.block(), n, span);
     }
     fn visit_init_declarator(&mut self, n: &'ast InitDeclarator, span: &'ast Span) {
-        self.name("InitDeclarator");
+        self.name("InitDeclarator", span);
         visit_init_declarator(&mut self.block(), n, span);
     }
     fn visit_storage_class_specifier(&mut self, n: &'ast StorageClassSpecifier, span: &'ast Span) {
-        self.name("StorageClassSpecifier");
+        self.name("StorageClassSpecifier", span);
         self.field(match *n {
             StorageClassSpecifier::Typedef => "Typedef",
             StorageClassSpecifier::Extern => "Extern",
@@ -306,21 +692,23 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
             StorageClassSpecifier::ThreadLocal => "ThreadLocal",
             StorageClassSpecifier::Auto => "Auto",
             StorageClassSpecifier::Register => "Register",
+            StorageClassSpecifier::Constexpr => "Constexpr",
+            StorageClassSpecifier::Block => "Block",
         });
         visit_storage_class_specifier(&mut self.block(), n, span);
     }
     fn visit_type_specifier(&mut self, n: &'ast TypeSpecifier, span: &'ast Span) {
-        self.name("TypeSpecifier");
+        self.name("TypeSpecifier", span);
         print_type_specifier(self, n);
         visit_type_specifier(&mut self.block(), n, span);
     }
     fn visit_ts18661_float_type(&mut self, n: &'ast TS18661FloatType, span: &'ast Span) {
-        self.name("TS18661FloatType");
+        self.name("TS18661FloatType", span);
         self.field(n.width);
         visit_ts18661_float_type(&mut self.block(), n, span);
     }
     fn visit_ts18661_float_format(&mut self, n: &'ast TS18661FloatFormat, span: &'ast Span) {
-        self.name("TS18661FloatFormat");
+        self.name("TS18661FloatFormat", span);
         self.field(match *n {
             TS18661FloatFormat::BinaryInterchange => "BinaryInterchange",
             TS18661FloatFormat::BinaryExtended => "BinaryExtended",
@@ -330,11 +718,11 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         visit_ts18661_float_format(&mut self.block(), n, span);
     }
     fn visit_struct_type(&mut self, n: &'ast StructType, span: &'ast Span) {
-        self.name("StructType");
+        self.name("StructType", span);
         visit_struct_type(&mut self.block(), n, span);
     }
     fn visit_struct_kind(&mut self, n: &'ast StructKind, span: &'ast Span) {
-        self.name("StructKind");
+        self.name("StructKind", span);
         self.field(match *n {
             StructKind::Struct => "Struct",
             StructKind::Union => "Union",
@@ -342,31 +730,34 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         visit_struct_kind(&mut self.block(), n, span);
     }
     fn visit_struct_declaration(&mut self, n: &'ast StructDeclaration, span: &'ast Span) {
-        self.name("StructDeclaration");
+        self.name("StructDeclaration", span);
         visit_struct_declaration(&mut self.block(), n, span);
     }
     fn visit_struct_field(&mut self, n: &'ast StructField, span: &'ast Span) {
-        self.name("StructField");
+        self.name("StructField", span);
+        if n.ms_unnamed {
+            self.field("MsUnnamed");
+        }
         visit_struct_field(&mut self.block(), n, span);
     }
     fn visit_specifier_qualifier(&mut self, n: &'ast SpecifierQualifier, span: &'ast Span) {
-        self.name("SpecifierQualifier");
+        self.name("SpecifierQualifier", span);
         visit_specifier_qualifier(&mut self.block(), n, span);
     }
     fn visit_struct_declarator(&mut self, n: &'ast StructDeclarator, span: &'ast Span) {
-        self.name("StructDeclarator");
+        self.name("StructDeclarator", span);
         visit_struct_declarator(&mut self.block(), n, span);
     }
     fn visit_enum_type(&mut self, n: &'ast EnumType, span: &'ast Span) {
-        self.name("EnumType");
+        self.name("EnumType", span);
         visit_enum_type(&mut self.block(), n, span);
     }
     fn visit_enumerator(&mut self, n: &'ast Enumerator, span: &'ast Span) {
-        self.name("Enumerator");
+        self.name("Enumerator", span);
         visit_enumerator(&mut self.block(), n, span);
     }
     fn visit_type_qualifier(&mut self, n: &'ast TypeQualifier, span: &'ast Span) {
-        self.name("TypeQualifier");
+        self.name("TypeQualifier", span);
         self.field(match *n {
             TypeQualifier::Const => "Const",
             TypeQualifier::Restrict => "Restrict",
@@ -375,58 +766,84 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
             TypeQualifier::NullUnspecified => "NullUnspecified",
             TypeQualifier::Nullable => "Nullable",
             TypeQualifier::Atomic => "Atomic",
+            TypeQualifier::Unaligned => "Unaligned",
+            TypeQualifier::AddressSpace(_) => "AddressSpace",
+            TypeQualifier::Access(_) => "Access",
         });
+        match *n {
+            TypeQualifier::AddressSpace(ref a) => self.field(match *a {
+                AddressSpace::Global => "Global",
+                AddressSpace::Local => "Local",
+                AddressSpace::Constant => "Constant",
+                AddressSpace::Private => "Private",
+                AddressSpace::Generic => "Generic",
+                AddressSpace::SegFs => "SegFs",
+                AddressSpace::SegGs => "SegGs",
+            }),
+            TypeQualifier::Access(ref a) => self.field(match *a {
+                AccessQualifier::ReadOnly => "ReadOnly",
+                AccessQualifier::WriteOnly => "WriteOnly",
+                AccessQualifier::ReadWrite => "ReadWrite",
+            }),
+            _ => {}
+        }
         visit_type_qualifier(&mut self.block(), n, span);
     }
     fn visit_function_specifier(&mut self, n: &'ast FunctionSpecifier, span: &'ast Span) {
-        self.name("FunctionSpecifier");
+        self.name("FunctionSpecifier", span);
         self.field(match *n {
             FunctionSpecifier::Inline => "Inline",
             FunctionSpecifier::Noreturn => "Noreturn",
+            FunctionSpecifier::ForceInline => "ForceInline",
+            FunctionSpecifier::Kernel => "Kernel",
         });
         visit_function_specifier(&mut self.block(), n, span);
     }
     fn visit_alignment_specifier(&mut self, n: &'ast AlignmentSpecifier, span: &'ast Span) {
-        self.name("AlignmentSpecifier");
+        self.name("AlignmentSpecifier", span);
         visit_alignment_specifier(&mut self.block(), n, span);
     }
     fn visit_declarator(&mut self, n: &'ast Declarator, span: &'ast Span) {
-        self.name("Declarator");
+        self.name("Declarator", span);
         visit_declarator(&mut self.block(), n, span);
     }
     fn visit_declarator_kind(&mut self, n: &'ast DeclaratorKind, span: &'ast Span) {
-        self.name("DeclaratorKind");
+        self.name("DeclaratorKind", span);
         print_declarator_kind(self, n);
         visit_declarator_kind(&mut self.block(), n, span);
     }
     fn visit_derived_declarator(&mut self, n: &'ast DerivedDeclarator, span: &'ast Span) {
-        self.name("DerivedDeclarator");
+        self.name("DerivedDeclarator", span);
         print_derived_declarator(self, n);
         visit_derived_declarator(&mut self.block(), n, span);
     }
     fn visit_array_declarator(&mut self, n: &'ast ArrayDeclarator, span: &'ast Span) {
-        self.name("ArrayDeclarator");
+        self.name("ArrayDeclarator", span);
         visit_array_declarator(&mut self.block(), n, span);
     }
     fn visit_function_declarator(&mut self, n: &'ast FunctionDeclarator, span: &'ast Span) {
-        self.name("FunctionDeclarator");
+        self.name("FunctionDeclarator", span);
+        if !n.forward_parameters.is_empty() {
+            self.field("ForwardParameters");
+            self.field(n.forward_parameters.len());
+        }
         visit_function_declarator(&mut self.block(), n, span);
     }
     fn visit_pointer_qualifier(&mut self, n: &'ast PointerQualifier, span: &'ast Span) {
-        self.name("PointerQualifier");
+        self.name("PointerQualifier", span);
         visit_pointer_qualifier(&mut self.block(), n, span);
     }
     fn visit_array_size(&mut self, n: &'ast ArraySize, span: &'ast Span) {
-        self.name("ArraySize");
+        self.name("ArraySize", span);
         print_array_size(self, n);
         visit_array_size(&mut self.block(), n, span);
     }
     fn visit_parameter_declaration(&mut self, n: &'ast ParameterDeclaration, span: &'ast Span) {
-        self.name("ParameterDeclaration");
+        self.name("ParameterDeclaration", span);
         visit_parameter_declaration(&mut self.block(), n, span);
     }
     fn visit_ellipsis(&mut self, n: &'ast Ellipsis, span: &'ast Span) {
-        self.name("Ellipsis");
+        self.name("Ellipsis", span);
         self.field(match *n {
             Ellipsis::Some => "Some",
             Ellipsis::None => "None",
@@ -434,200 +851,632 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         visit_ellipsis(&mut self.block(), n, span);
     }
     fn visit_type_name(&mut self, n: &'ast TypeName, span: &'ast Span) {
-        self.name("TypeName");
+        self.name("TypeName", span);
         visit_type_name(&mut self.block(), n, span);
     }
     fn visit_initializer(&mut self, n: &'ast Initializer, span: &'ast Span) {
-        self.name("Initializer");
+        self.name("Initializer", span);
         visit_initializer(&mut self.block(), n, span);
     }
     fn visit_initializer_list_item(&mut self, n: &'ast InitializerListItem, span: &'ast Span) {
-        self.name("InitializerListItem");
+        self.name("InitializerListItem", span);
         visit_initializer_list_item(&mut self.block(), n, span);
     }
     fn visit_designator(&mut self, n: &'ast Designator, span: &'ast Span) {
-        self.name("Designator");
+        self.name("Designator", span);
         visit_designator(&mut self.block(), n, span);
     }
     fn visit_range_designator(&mut self, n: &'ast RangeDesignator, span: &'ast Span) {
-        self.name("RangeDesignator");
+        self.name("RangeDesignator", span);
         visit_range_designator(&mut self.block(), n, span);
     }
     fn visit_static_assert(&mut self, n: &'ast StaticAssert, span: &'ast Span) {
-        self.name("StaticAssert");
+        self.name("StaticAssert", span);
         visit_static_assert(&mut self.block(), n, span);
     }
     fn visit_statement(&mut self, n: &'ast Statement, span: &'ast Span) {
-        self.name("Statement");
+        self.name("Statement", span);
         print_statement(self, n);
         visit_statement(&mut self.block(), n, span);
     }
     fn visit_labeled_statement(&mut self, n: &'ast LabeledStatement, span: &'ast Span) {
-        self.name("LabeledStatement");
+        self.name("LabeledStatement", span);
         visit_labeled_statement(&mut self.block(), n, span);
     }
+    fn visit_attributed_statement(&mut self, n: &'ast AttributedStatement, span: &'ast Span) {
+        self.name("AttributedStatement", span);
+        visit_attributed_statement(&mut self.block(), n, span);
+    }
+    fn visit_try_statement(&mut self, n: &'ast TryStatement, span: &'ast Span) {
+        self.name("TryStatement", span);
+        if let TryHandler::Finally(_) = n.handler {
+            self.field("Finally");
+        }
+        visit_try_statement(&mut self.block(), n, span);
+    }
+    fn visit_except_handler(&mut self, n: &'ast ExceptHandler, span: &'ast Span) {
+        self.name("ExceptHandler", span);
+        visit_except_handler(&mut self.block(), n, span);
+    }
     fn visit_if_statement(&mut self, n: &'ast IfStatement, span: &'ast Span) {
-        self.name("IfStatement");
+        self.name("IfStatement", span);
         visit_if_statement(&mut self.block(), n, span);
     }
     fn visit_switch_statement(&mut self, n: &'ast SwitchStatement, span: &'ast Span) {
-        self.name("SwitchStatement");
+        self.name("SwitchStatement", span);
         visit_switch_statement(&mut self.block(), n, span);
     }
     fn visit_while_statement(&mut self, n: &'ast WhileStatement, span: &'ast Span) {
-        self.name("WhileStatement");
+        self.name("WhileStatement", span);
         visit_while_statement(&mut self.block(), n, span);
     }
     fn visit_do_while_statement(&mut self, n: &'ast DoWhileStatement, span: &'ast Span) {
-        self.name("DoWhileStatement");
+        self.name("DoWhileStatement", span);
         visit_do_while_statement(&mut self.block(), n, span);
     }
     fn visit_for_statement(&mut self, n: &'ast ForStatement, span: &'ast Span) {
-        self.name("ForStatement");
+        self.name("ForStatement", span);
         visit_for_statement(&mut self.block(), n, span);
     }
     fn visit_label(&mut self, n: &'ast Label, span: &'ast Span) {
-        self.name("Label");
+        self.name("Label", span);
         print_label(self, n);
         visit_label(&mut self.block(), n, span);
     }
     fn visit_case_range(&mut self, n: &'ast CaseRange, span: &'ast Span) {
-        self.name("CaseRange");
+        self.name("CaseRange", span);
         visit_case_range(&mut self.block(), n, span);
     }
     fn visit_for_initializer(&mut self, n: &'ast ForInitializer, span: &'ast Span) {
-        self.name("ForInitializer");
+        self.name("ForInitializer", span);
         print_for_initializer(self, n);
         visit_for_initializer(&mut self.block(), n, span);
     }
     fn visit_block_item(&mut self, n: &'ast BlockItem, span: &'ast Span) {
-        self.name("BlockItem");
+        self.name("BlockItem", span);
         visit_block_item(&mut self.block(), n, span);
     }
     fn visit_external_declaration(&mut self, n: &'ast ExternalDeclaration, span: &'ast Span) {
-        self.name("ExternalDeclaration");
+        self.name("ExternalDeclaration", span);
+        print_external_declaration(self, n);
         visit_external_declaration(&mut self.block(), n, span);
     }
+    fn visit_pragma(&mut self, n: &'ast Pragma, span: &'ast Span) {
+        self.name("Pragma", span);
+        match n.kind {
+            PragmaKind::Directive => {}
+            PragmaKind::Operator => self.field("_Pragma"),
+            PragmaKind::Msvc => self.field("__pragma"),
+        }
+        self.field_str(&n.text);
+        visit_pragma(&mut self.block(), n, span);
+    }
+    fn visit_omp_statement(&mut self, n: &'ast OmpStatement, span: &'ast Span) {
+        self.name("OmpStatement", span);
+        visit_omp_statement(&mut self.block(), n, span);
+    }
+    fn visit_omp_directive(&mut self, n: &'ast OmpDirective, span: &'ast Span) {
+        self.name("OmpDirective", span);
+        visit_omp_directive(&mut self.block(), n, span);
+    }
+    fn visit_omp_directive_kind(&mut self, n: &'ast OmpDirectiveKind, span: &'ast Span) {
+        self.name("OmpDirectiveKind", span);
+        self.field(match *n {
+            OmpDirectiveKind::Parallel => "Parallel",
+            OmpDirectiveKind::For => "For",
+            OmpDirectiveKind::ParallelFor => "ParallelFor",
+            OmpDirectiveKind::Sections => "Sections",
+            OmpDirectiveKind::Single => "Single",
+            OmpDirectiveKind::Task => "Task",
+            OmpDirectiveKind::Critical(_) => "Critical",
+            OmpDirectiveKind::Atomic => "Atomic",
+            OmpDirectiveKind::Barrier => "Barrier",
+        });
+        visit_omp_directive_kind(&mut self.block(), n, span);
+    }
+    fn visit_omp_clause(&mut self, n: &'ast OmpClause, span: &'ast Span) {
+        self.name("OmpClause", span);
+        self.field(match *n {
+            OmpClause::Private(_) => "Private",
+            OmpClause::Shared(_) => "Shared",
+            OmpClause::FirstPrivate(_) => "FirstPrivate",
+            OmpClause::Reduction(_) => "Reduction",
+            OmpClause::NumThreads(_) => "NumThreads",
+            OmpClause::Schedule(_) => "Schedule",
+            OmpClause::Collapse(_) => "Collapse",
+        });
+        visit_omp_clause(&mut self.block(), n, span);
+    }
+    fn visit_omp_reduction(&mut self, n: &'ast OmpReduction, span: &'ast Span) {
+        self.name("OmpReduction", span);
+        visit_omp_reduction(&mut self.block(), n, span);
+    }
+    fn visit_omp_reduction_operator(&mut self, n: &'ast OmpReductionOperator, span: &'ast Span) {
+        self.name("OmpReductionOperator", span);
+        self.field(match *n {
+            OmpReductionOperator::Plus => "Plus",
+            OmpReductionOperator::Minus => "Minus",
+            OmpReductionOperator::Multiply => "Multiply",
+            OmpReductionOperator::BitwiseAnd => "BitwiseAnd",
+            OmpReductionOperator::BitwiseOr => "BitwiseOr",
+            OmpReductionOperator::BitwiseXor => "BitwiseXor",
+            OmpReductionOperator::LogicalAnd => "LogicalAnd",
+            OmpReductionOperator::LogicalOr => "LogicalOr",
+            OmpReductionOperator::Identifier(_) => "Identifier",
+        });
+        visit_omp_reduction_operator(&mut self.block(), n, span);
+    }
+    fn visit_omp_schedule(&mut self, n: &'ast OmpSchedule, span: &'ast Span) {
+        self.name("OmpSchedule", span);
+        visit_omp_schedule(&mut self.block(), n, span);
+    }
+    fn visit_omp_schedule_kind(&mut self, n: &'ast OmpScheduleKind, span: &'ast Span) {
+        self.name("OmpScheduleKind", span);
+        self.field(match *n {
+            OmpScheduleKind::Static => "Static",
+            OmpScheduleKind::Dynamic => "Dynamic",
+            OmpScheduleKind::Guided => "Guided",
+            OmpScheduleKind::Auto => "Auto",
+            OmpScheduleKind::Runtime => "Runtime",
+        });
+        visit_omp_schedule_kind(&mut self.block(), n, span);
+    }
     fn visit_function_definition(&mut self, n: &'ast FunctionDefinition, span: &'ast Span) {
-        self.name("FunctionDefinition");
+        self.name("FunctionDefinition", span);
         visit_function_definition(&mut self.block(), n, span);
     }
     fn visit_extension(&mut self, n: &'ast Extension, span: &'ast Span) {
-        self.name("Extension");
+        self.name("Extension", span);
+        match *n {
+            Extension::Declspec(_) => self.field("Declspec"),
+            Extension::CallingConvention(ref c) => self.field(match *c {
+                CallingConvention::Cdecl => "__cdecl",
+                CallingConvention::Stdcall => "__stdcall",
+                CallingConvention::Fastcall => "__fastcall",
+                CallingConvention::Vectorcall => "__vectorcall",
+                CallingConvention::Thiscall => "__thiscall",
+                CallingConvention::Clrcall => "__clrcall",
+            }),
+            Extension::Marker => self.field("__extension__"),
+            Extension::Cuda(ref c) => self.field(match *c {
+                CudaSpecifier::Global => "__global__",
+                CudaSpecifier::Device => "__device__",
+                CudaSpecifier::Host => "__host__",
+                CudaSpecifier::Shared => "__shared__",
+                CudaSpecifier::Constant => "__constant__",
+                CudaSpecifier::Managed => "__managed__",
+                CudaSpecifier::LaunchBounds(_) => "__launch_bounds__",
+            }),
+            _ => {}
+        }
         visit_extension(&mut self.block(), n, span);
     }
     fn visit_attribute(&mut self, n: &'ast Attribute, span: &'ast Span) {
-        self.name("Attribute");
+        self.name("Attribute", span);
         self.field_str(&n.name.node);
         visit_attribute(&mut self.block(), n, span);
     }
     fn visit_asm_statement(&mut self, n: &'ast AsmStatement, span: &'ast Span) {
-        self.name("AsmStatement");
+        self.name("AsmStatement", span);
+        if let AsmStatement::Msvc(ref t) = *n {
+            self.field_str(t);
+        }
         visit_asm_statement(&mut self.block(), n, span);
     }
     fn visit_availability_attribute(&mut self, n: &'ast AvailabilityAttribute, span: &'ast Span) {
-        self.name("AvailabilityAttribute");
+        self.name("AvailabilityAttribute", span);
         visit_availability_attribute(&mut self.block(), n, span);
     }
+    fn visit_availability_condition(&mut self, n: &'ast AvailabilityCondition, span: &'ast Span) {
+        self.name("AvailabilityCondition", span);
+        match *n {
+            AvailabilityCondition::Platform(ref p) => {
+                let v = &p.version.node;
+                let mut version = v.major.to_string();
+                if let Some(ref minor) = v.minor {
+                    version.push_str(&format!(".{}", minor));
+                }
+                if let Some(ref subminor) = v.subminor {
+                    version.push_str(&format!(".{}", subminor));
+                }
+                self.field(version);
+            }
+            AvailabilityCondition::Star => self.field("*"),
+        }
+        visit_availability_condition(&mut self.block(), n, span);
+    }
+    fn visit_std_attribute(&mut self, n: &'ast StdAttribute, span: &'ast Span) {
+        self.name("StdAttribute", span);
+        visit_std_attribute(&mut self.block(), n, span);
+    }
+    fn visit_std_attribute_arguments(&mut self, n: &'ast StdAttributeArguments, span: &'ast Span) {
+        self.name("StdAttributeArguments", span);
+        match *n {
+            StdAttributeArguments::Expressions(_) => self.field("Expressions"),
+            StdAttributeArguments::Tokens(ref t) => self.field_str(t),
+        }
+        visit_std_attribute_arguments(&mut self.block(), n, span);
+    }
     fn visit_gnu_extended_asm_statement(
         &mut self,
         n: &'ast GnuExtendedAsmStatement,
         span: &'ast Span,
     ) {
-        self.name("GnuExtendedAsmStatement");
+        self.name("GnuExtendedAsmStatement", span);
+        if n.goto {
+            self.field("goto");
+        }
         visit_gnu_extended_asm_statement(&mut self.block(), n, span);
     }
     fn visit_gnu_asm_operand(&mut self, n: &'ast GnuAsmOperand, span: &'ast Span) {
-        self.name("GnuAsmOperand");
+        self.name("GnuAsmOperand", span);
         visit_gnu_asm_operand(&mut self.block(), n, span);
     }
     fn visit_type_of(&mut self, n: &'ast TypeOf, span: &'ast Span) {
-        self.name("TypeOf");
+        self.name("TypeOf", span);
         visit_type_of(&mut self.block(), n, span);
     }
     fn visit_translation_unit(&mut self, translation_unit: &'ast TranslationUnit) {
-        self.name("TranslationUnit");
+        self.name("TranslationUnit", &Span::none());
         visit_translation_unit(&mut self.block(), translation_unit);
     }
 }
 
 fn print_float_format<'ast>(p: &mut Printer, n: &'ast FloatFormat) {
     match *n {
-        FloatFormat::Float => p.w.write_str(" Float").unwrap(),
-        FloatFormat::Double => p.w.write_str(" Double").unwrap(),
-        FloatFormat::LongDouble => p.w.write_str(" LongDouble").unwrap(),
+        FloatFormat::Float => p.field("Float"),
+        FloatFormat::Double => p.field("Double"),
+        FloatFormat::LongDouble => p.field("LongDouble"),
         _ => {}
     }
 }
 fn print_declarator_kind<'ast>(p: &mut Printer, n: &'ast DeclaratorKind) {
     match *n {
-        DeclaratorKind::Abstract => p.w.write_str(" Abstract").unwrap(),
+        DeclaratorKind::Abstract => p.field("Abstract"),
         _ => {}
     }
 }
 fn print_derived_declarator<'ast>(p: &mut Printer, n: &'ast DerivedDeclarator) {
     match *n {
-        DerivedDeclarator::Pointer(_) => p.w.write_str(" Pointer").unwrap(),
-        DerivedDeclarator::KRFunction(_) => p.w.write_str(" KRFunction").unwrap(),
-        DerivedDeclarator::Block(_) => p.w.write_str(" Block").unwrap(),
+        DerivedDeclarator::Pointer(_) => p.field("Pointer"),
+        DerivedDeclarator::KRFunction(_) => p.field("KRFunction"),
+        DerivedDeclarator::Block(_) => p.field("Block"),
         _ => {}
     }
 }
 fn print_array_size<'ast>(p: &mut Printer, n: &'ast ArraySize) {
     match *n {
-        ArraySize::Unknown => p.w.write_str(" Unknown").unwrap(),
-        ArraySize::VariableUnknown => p.w.write_str(" VariableUnknown").unwrap(),
-        ArraySize::VariableExpression(_) => p.w.write_str(" VariableExpression").unwrap(),
-        ArraySize::StaticExpression(_) => p.w.write_str(" StaticExpression").unwrap(),
+        ArraySize::Unknown => p.field("Unknown"),
+        ArraySize::VariableUnknown => p.field("VariableUnknown"),
+        ArraySize::VariableExpression(_) => p.field("VariableExpression"),
+        ArraySize::StaticExpression(_) => p.field("StaticExpression"),
     }
 }
 fn print_statement<'ast>(p: &mut Printer, n: &'ast Statement) {
     match *n {
-        Statement::Compound(_) => p.w.write_str(" Compound").unwrap(),
-        Statement::Goto(_) => p.w.write_str(" Goto").unwrap(),
-        Statement::Continue => p.w.write_str(" Continue").unwrap(),
-        Statement::Break => p.w.write_str(" Break").unwrap(),
-        Statement::Return(_) => p.w.write_str(" Return").unwrap(),
+        Statement::Compound(_) => p.field("Compound"),
+        Statement::Goto(_) => p.field("Goto"),
+        Statement::GotoIndirect(_) => p.field("GotoIndirect"),
+        Statement::Continue => p.field("Continue"),
+        Statement::Break => p.field("Break"),
+        Statement::Return(_) => p.field("Return"),
+        Statement::LocalLabels(_) => p.field("LocalLabels"),
+        Statement::NestedFunction(_) => p.field("NestedFunction"),
+        Statement::Leave => p.field("Leave"),
         _ => {}
     }
 }
 fn print_offset_member<'ast>(p: &mut Printer, n: &'ast OffsetMember) {
     match *n {
-        OffsetMember::Member(_) => p.w.write_str(" Member").unwrap(),
-        OffsetMember::IndirectMember(_) => p.w.write_str(" IndirectMember").unwrap(),
+        OffsetMember::Member(_) => p.field("Member"),
+        OffsetMember::IndirectMember(_) => p.field("IndirectMember"),
         _ => {}
     }
 }
 fn print_label<'ast>(p: &mut Printer, n: &'ast Label) {
     match *n {
-        Label::Default => p.w.write_str(" Default").unwrap(),
+        Label::Default => p.field("Default"),
+        _ => {}
+    }
+}
+fn print_external_declaration<'ast>(p: &mut Printer, n: &'ast ExternalDeclaration) {
+    match *n {
+        ExternalDeclaration::Error => p.field("Error"),
+        ExternalDeclaration::Asm(_) => p.field("Asm"),
         _ => {}
     }
 }
 fn print_for_initializer<'ast>(p: &mut Printer, n: &'ast ForInitializer) {
     match *n {
-        ForInitializer::Empty => p.w.write_str(" Empty").unwrap(),
+        ForInitializer::Empty => p.field("Empty"),
         _ => {}
     }
 }
 fn print_type_specifier<'ast>(p: &mut Printer, n: &'ast TypeSpecifier) {
     match *n {
-        TypeSpecifier::Void => p.w.write_str(" Void").unwrap(),
-        TypeSpecifier::Char => p.w.write_str(" Char").unwrap(),
-        TypeSpecifier::Short => p.w.write_str(" Short").unwrap(),
-        TypeSpecifier::Int => p.w.write_str(" Int").unwrap(),
-        TypeSpecifier::Long => p.w.write_str(" Long").unwrap(),
-        TypeSpecifier::Float => p.w.write_str(" Float").unwrap(),
-        TypeSpecifier::Double => p.w.write_str(" Double").unwrap(),
-        TypeSpecifier::Signed => p.w.write_str(" Signed").unwrap(),
-        TypeSpecifier::Unsigned => p.w.write_str(" Unsigned").unwrap(),
-        TypeSpecifier::Complex => p.w.write_str(" Complex").unwrap(),
-        TypeSpecifier::Atomic(_) => p.w.write_str(" Atomic").unwrap(),
-        TypeSpecifier::TypedefName(_) => p.w.write_str(" TypedefName").unwrap(),
+        TypeSpecifier::Void => p.field("Void"),
+        TypeSpecifier::Char => p.field("Char"),
+        TypeSpecifier::Short => p.field("Short"),
+        TypeSpecifier::Int => p.field("Int"),
+        TypeSpecifier::Long => p.field("Long"),
+        TypeSpecifier::Float => p.field("Float"),
+        TypeSpecifier::Double => p.field("Double"),
+        TypeSpecifier::Signed => p.field("Signed"),
+        TypeSpecifier::Unsigned => p.field("Unsigned"),
+        TypeSpecifier::Complex => p.field("Complex"),
+        TypeSpecifier::Atomic(_) => p.field("Atomic"),
+        TypeSpecifier::TypedefName(_) => p.field("TypedefName"),
+        TypeSpecifier::BitInt(_) => p.field("BitInt"),
+        TypeSpecifier::Auto => p.field("Auto"),
+        TypeSpecifier::AutoType => p.field("AutoType"),
+        TypeSpecifier::ImplicitInt => p.field("ImplicitInt"),
+        TypeSpecifier::TypeOfUnqual(_) => p.field("TypeOfUnqual"),
+        TypeSpecifier::Int128 => p.field("__int128"),
+        TypeSpecifier::Int8 => p.field("__int8"),
+        TypeSpecifier::Int16 => p.field("__int16"),
+        TypeSpecifier::Int32 => p.field("__int32"),
+        TypeSpecifier::Int64 => p.field("__int64"),
+        TypeSpecifier::Fract => p.field("Fract"),
+        TypeSpecifier::Accum => p.field("Accum"),
+        TypeSpecifier::Sat => p.field("Sat"),
+        TypeSpecifier::TargetFloat(ref t) => p.field(match *t {
+            TargetFloat::Fp16 => "__fp16",
+            TargetFloat::Float80 => "__float80",
+            TargetFloat::Float128 => "__float128",
+            TargetFloat::Ibm128 => "__ibm128",
+        }),
         _ => {}
     }
 }
 
+// Format of `Printer::dot`
+struct Dot<'a> {
+    w: &'a mut fmt::Write,
+    options: DotOptions,
+    started: bool,
+    // Number of nodes so far
+    count: usize,
+    nodes: Vec<DotNode>,
+    // Vertex of the innermost node, written once all its fields are known
+    pending: Option<DotVertex>,
+}
+
+struct DotNode {
+    // Number of the vertex, `None` for skipped nodes
+    vertex: Option<usize>,
+    cluster: bool,
+}
+
+struct DotVertex {
+    id: usize,
+    parent: Option<usize>,
+    name: String,
+    fields: Vec<String>,
+    span: Span,
+}
+
+impl<'a> Dot<'a> {
+    fn start(&mut self) {
+        if !self.started {
+            self.w.write_str("digraph ast {\n    node [shape=box];\n").unwrap();
+            self.started = true;
+        }
+    }
+
+    fn indent(&mut self) {
+        let clusters = self.nodes.iter().filter(|n| n.cluster).count();
+        write!(&mut self.w, "{:1$}", "", (clusters + 1) * 4).unwrap();
+    }
+
+    fn write_vertex(&mut self) {
+        let v = match self.pending.take() {
+            Some(v) => v,
+            None => return,
+        };
+        let mut label = v.name;
+        let fields = v.fields.join(" ");
+        if !fields.is_empty() {
+            label.push('\n');
+            label.extend(fields.chars().take(self.options.max_label_chars));
+            if fields.chars().count() > self.options.max_label_chars {
+                label.push_str("...");
+            }
+        }
+        if self.options.spans && !v.span.is_none() {
+            label.push_str(&format!("\n{}:{}", v.span.start, v.span.end));
+        }
+        self.indent();
+        writeln!(&mut self.w, "n{} [label={}];", v.id, DotString(&label)).unwrap();
+        if let Some(parent) = v.parent {
+            self.indent();
+            writeln!(&mut self.w, "n{} -> n{};", parent, v.id).unwrap();
+        }
+    }
+
+    fn push_field(&mut self, value: String) {
+        if let Some(ref mut v) = self.pending {
+            v.fields.push(value);
+        }
+    }
+}
+
+impl<'a> Format for Dot<'a> {
+    fn node(&mut self, name: &str, span: &Span) {
+        self.write_vertex();
+        self.start();
+        let id = self.count;
+        self.count += 1;
+
+        let cluster = self.options.clusters && name == "ExternalDeclaration";
+        if cluster {
+            self.indent();
+            writeln!(&mut self.w, "subgraph cluster_{} {{", id).unwrap();
+        }
+        if self.options.skip.iter().any(|s| s == name) {
+            self.nodes.push(DotNode {
+                vertex: None,
+                cluster: cluster,
+            });
+            return;
+        }
+        self.pending = Some(DotVertex {
+            id: id,
+            parent: self.nodes.iter().rev().filter_map(|n| n.vertex).next(),
+            name: name.to_owned(),
+            fields: Vec::new(),
+            span: *span,
+        });
+        self.nodes.push(DotNode {
+            vertex: Some(id),
+            cluster: cluster,
+        });
+    }
+
+    fn field(&mut self, value: &str) {
+        self.push_field(value.to_owned());
+    }
+
+    fn field_str(&mut self, value: &str) {
+        self.push_field(value.to_owned());
+    }
+
+    fn field_strs(&mut self, values: &[&str]) {
+        for v in values {
+            self.field_str(v);
+        }
+    }
+
+    fn end(&mut self) {
+        self.write_vertex();
+        if self.nodes.pop().map(|n| n.cluster) == Some(true) {
+            self.indent();
+            self.w.write_str("}\n").unwrap();
+        }
+    }
+
+    fn finish(&mut self) {
+        self.start();
+        self.w.write_str("}\n").unwrap();
+    }
+}
+
+// Quoted string in DOT
+struct DotString<'a>(&'a str);
+
+impl<'a> fmt::Display for DotString<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use std::fmt::Write;
+
+        try!(fmt.write_char('"'));
+        for c in self.0.chars() {
+            match c {
+                '"' | '\\' => try!(write!(fmt, "\\{}", c)),
+                '\n' => try!(fmt.write_str("\\n")),
+                _ => try!(fmt.write_char(c)),
+            }
+        }
+        fmt.write_char('"')
+    }
+}
+
+// Format of `Printer::sexp`
+struct Sexp<'a> {
+    w: &'a mut fmt::Write,
+    options: SexpOptions,
+    // Spans of the open nodes
+    spans: Vec<Span>,
+}
+
+impl<'a> Format for Sexp<'a> {
+    fn node(&mut self, name: &str, span: &Span) {
+        if !self.spans.is_empty() {
+            write!(&mut self.w, "\n{:1$}", "", self.spans.len() * 2).unwrap();
+        }
+        self.w.write_str("(").unwrap();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                self.w.write_char('-').unwrap();
+            }
+            self.w.write_char(c.to_ascii_lowercase()).unwrap();
+        }
+        self.spans.push(*span);
+    }
+
+    fn field(&mut self, value: &str) {
+        let symbol = |c: char| c.is_ascii_alphanumeric() || "_.*+-<>=!?/".contains(c);
+        if !value.is_empty() && value.chars().all(symbol) {
+            write!(&mut self.w, " {}", value).unwrap();
+        } else {
+            self.field_str(value);
+        }
+    }
+
+    fn field_str(&mut self, value: &str) {
+        write!(&mut self.w, " {}", SexpString(value)).unwrap();
+    }
+
+    fn field_strs(&mut self, values: &[&str]) {
+        for v in values {
+            self.field_str(v);
+        }
+    }
+
+    fn end(&mut self) {
+        let span = self.spans.pop().unwrap_or_else(Span::none);
+        if self.options.spans && !span.is_none() {
+            write!(&mut self.w, " @{}:{}", span.start, span.end).unwrap();
+        }
+        self.w.write_str(")").unwrap();
+        if self.spans.is_empty() {
+            self.w.write_str("\n").unwrap();
+        }
+    }
+}
+
+// String in an S-expression, with quotes
+struct SexpString<'a>(&'a str);
+
+impl<'a> fmt::Display for SexpString<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use std::fmt::Write;
+
+        try!(fmt.write_char('"'));
+        for c in self.0.chars() {
+            match c {
+                '"' | '\\' => try!(write!(fmt, "\\{}", c)),
+                '\n' => try!(fmt.write_str("\\n")),
+                '\r' => try!(fmt.write_str("\\r")),
+                '\t' => try!(fmt.write_str("\\t")),
+                '\0'...'\x1f' | '\x7f' => try!(write!(fmt, "\\x{:x};", c as u32)),
+                _ => try!(fmt.write_char(c)),
+            }
+        }
+        fmt.write_char('"')
+    }
+}
+
+// String in JSON, with quotes
+struct JsonString<'a>(&'a str);
+
+impl<'a> fmt::Display for JsonString<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use std::fmt::Write;
+
+        try!(fmt.write_char('"'));
+        for c in self.0.chars() {
+            match c {
+                '"' | '\\' => try!(write!(fmt, "\\{}", c)),
+                '\n' => try!(fmt.write_str("\\n")),
+                '\r' => try!(fmt.write_str("\\r")),
+                '\t' => try!(fmt.write_str("\\t")),
+                '\0'...'\x1f' | '\x7f' => try!(write!(fmt, "\\u{:04x}", c as u32)),
+                _ => try!(fmt.write_char(c)),
+            }
+        }
+        fmt.write_char('"')
+    }
+}
+
 struct Escape<'a>(&'a str);
 
 impl<'a> fmt::Display for Escape<'a> {
@@ -646,8 +1495,450 @@ impl<'a> fmt::Display for Escape<'a> {
     }
 }
 
+#[test]
+fn test_json_string() {
+    let s = format!("{}", JsonString("a\"\\\n\u{1}\u{3042}"));
+    assert_eq!(s, "\"a\\\"\\\\\\n\\u0001\u{3042}\"");
+}
+
 #[test]
 fn test_escape() {
     let s = format!("{}", Escape(r#"a'"\ あ \'"#));
     assert_eq!(s, r#"a\'\"\\ \u{3042} \\\'"#);
 }
+
+
+#[cfg(test)]
+fn parse_unit(source: &str) -> TranslationUnit {
+    use driver::{parse_preprocessed, Config};
+    parse_preprocessed(&Config::default(), source.into())
+        .expect("parse failed")
+        .unit
+}
+
+#[test]
+fn test_json_compact() {
+    let unit = parse_unit(r#"char *s = "a\"b";"#);
+    let json = ast_to_json(&unit, &JsonOptions::default());
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"kind":"TranslationUnit","fields":[],"children":["#,
+            r#"{"kind":"ExternalDeclaration","span":[0,17],"fields":[],"children":["#,
+            r#"{"kind":"Declaration","span":[0,17],"fields":[],"children":["#,
+            r#"{"kind":"DeclarationSpecifier","span":[0,4],"fields":[],"children":["#,
+            r#"{"kind":"TypeSpecifier","span":[0,4],"fields":["Char"],"children":[]}]},"#,
+            r#"{"kind":"InitDeclarator","span":[5,16],"fields":[],"children":["#,
+            r#"{"kind":"Declarator","span":[5,8],"fields":[],"children":["#,
+            r#"{"kind":"DeclaratorKind","span":[6,7],"fields":[],"children":["#,
+            r#"{"kind":"Identifier","span":[6,7],"fields":["s"],"children":[]}]},"#,
+            r#"{"kind":"DerivedDeclarator","span":[5,6],"fields":["Pointer"],"children":[]}]},"#,
+            r#"{"kind":"Initializer","span":[8,16],"fields":[],"children":["#,
+            r#"{"kind":"Expression","span":[10,16],"fields":[],"children":["#,
+            r#"{"kind":"StringLiteral","span":[10,16],"fields":[["\"a\\\"b\""]],"children":[]}"#,
+            r#"]}]}]}]}]}]}"#,
+            "\n"
+        )
+    );
+}
+
+#[test]
+fn test_json_pretty() {
+    let unit = parse_unit("int x;");
+    let options = JsonOptions {
+        spans: false,
+        pretty: true,
+    };
+    let expected = r#"{
+  "kind": "TranslationUnit",
+  "fields": [],
+  "children": [
+    {
+      "kind": "ExternalDeclaration",
+      "fields": [],
+      "children": [
+        {
+          "kind": "Declaration",
+          "fields": [],
+          "children": [
+            {
+              "kind": "DeclarationSpecifier",
+              "fields": [],
+              "children": [
+                {
+                  "kind": "TypeSpecifier",
+                  "fields": ["Int"],
+                  "children": []
+                }
+              ]
+            },
+            {
+              "kind": "InitDeclarator",
+              "fields": [],
+              "children": [
+                {
+                  "kind": "Declarator",
+                  "fields": [],
+                  "children": [
+                    {
+                      "kind": "DeclaratorKind",
+                      "fields": [],
+                      "children": [
+                        {
+                          "kind": "Identifier",
+                          "fields": ["x"],
+                          "children": []
+                        }
+                      ]
+                    }
+                  ]
+                }
+              ]
+            }
+          ]
+        }
+      ]
+    }
+  ]
+}
+"#;
+    assert_eq!(ast_to_json(&unit, &options), expected);
+}
+
+#[test]
+fn test_json_literals() {
+    let big = "9".repeat(200);
+    let unit = parse_unit(&format!("int x = {}; char *s = \"\\t\\x01é\";", big));
+    let json = ast_to_json(&unit, &JsonOptions::default());
+    assert!(json.contains(&format!(r#""fields":["{}"]"#, big)));
+    assert!(json.contains(r#""fields":[["\"\\t\\x01é\""]]"#));
+}
+
+// Every node line of the text dump has an object in the JSON dump
+#[test]
+fn test_json_node_count() {
+    let unit = parse_unit(
+        r#"
+        struct point { int x, y : 4; };
+        typedef enum { A = 1, B } e;
+        static int f(int n, ...) {
+            for (int i = 0; i < n; i++) { if (i % 2) continue; else break; }
+            switch (n) { case 1: return sizeof(struct point); default: ; }
+            return n ? (int)1.5e3 : _Generic(n, int: 'c', default: L"s" "t")[0];
+        }
+        "#,
+    );
+    let mut text = String::new();
+    Printer::new(&mut text).visit_translation_unit(&unit);
+    let json = ast_to_json(&unit, &JsonOptions::default());
+    assert_eq!(json.matches(r#"{"kind":"#).count(), text.lines().count());
+}
+
+
+#[test]
+fn test_sexp() {
+    let unit = parse_unit("int n = 1;\nint f(int a) { if (a) return a + n; return -1; }");
+    let expected = r#"(translation-unit
+  (external-declaration
+    (declaration
+      (declaration-specifier
+        (type-specifier Int))
+      (init-declarator
+        (declarator
+          (declarator-kind
+            (identifier "n")))
+        (initializer
+          (expression
+            (constant
+              (integer "1"
+                (integer-base Decimal)
+                (integer-suffix false false
+                  (integer-size Int)))))))))
+  (external-declaration
+    (function-definition
+      (declaration-specifier
+        (type-specifier Int))
+      (declarator
+        (declarator-kind
+          (identifier "f"))
+        (derived-declarator
+          (function-declarator
+            (parameter-declaration
+              (declaration-specifier
+                (type-specifier Int))
+              (declarator
+                (declarator-kind
+                  (identifier "a"))))
+            (ellipsis None))))
+      (statement Compound
+        (block-item
+          (statement
+            (if-statement
+              (expression
+                (identifier "a"))
+              (statement Return
+                (expression
+                  (binary-operator-expression
+                    (expression
+                      (identifier "a"))
+                    (expression
+                      (identifier "n"))
+                    (binary-operator Plus)))))))
+        (block-item
+          (statement Return
+            (expression
+              (unary-operator-expression
+                (unary-operator Minus)
+                (expression
+                  (constant
+                    (integer "1"
+                      (integer-base Decimal)
+                      (integer-suffix false false
+                        (integer-size Int)))))))))))))
+"#;
+    assert_eq!(ast_to_sexp(&unit, &SexpOptions::default()), expected);
+}
+
+#[test]
+fn test_sexp_spans() {
+    let unit = parse_unit("char c = 'a';");
+    let expected = r#"(translation-unit
+  (external-declaration
+    (declaration
+      (declaration-specifier
+        (type-specifier Char @0:4) @0:4)
+      (init-declarator
+        (declarator
+          (declarator-kind
+            (identifier "c" @5:6) @5:6) @5:7)
+        (initializer
+          (expression
+            (constant Character "'a'" @9:12) @9:12) @7:12) @5:12) @0:13) @0:13))
+"#;
+    assert_eq!(ast_to_sexp(&unit, &SexpOptions { spans: true }), expected);
+}
+
+#[test]
+fn test_sexp_string() {
+    let s = format!("{}", SexpString("(\"a\\b\")\n\u{1}"));
+    assert_eq!(s, r#""(\"a\\b\")\n\x1;""#);
+}
+
+// Kind and children of a node
+#[cfg(test)]
+#[derive(Debug, PartialEq)]
+struct Shape(String, Vec<Shape>);
+
+// Read back the output of `Printer::sexp`, keeping only lists
+#[cfg(test)]
+fn read_sexp(s: &str) -> Vec<Shape> {
+    fn list<I: Iterator<Item = char>>(chars: &mut ::std::iter::Peekable<I>) -> Shape {
+        let mut kind = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == ' ' || c == '\n' || c == ')' {
+                break;
+            }
+            kind.push(c);
+            chars.next();
+        }
+        let mut children = Vec::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '(' => children.push(list(chars)),
+                ')' => break,
+                '"' => {
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '"' => break,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Shape(kind, children)
+    }
+
+    let mut chars = s.chars().peekable();
+    let mut forms = Vec::new();
+    while let Some(c) = chars.next() {
+        if c == '(' {
+            forms.push(list(&mut chars));
+        }
+    }
+    forms
+}
+
+// Read back the output of `Printer::new` from indentation
+#[cfg(test)]
+fn read_text(s: &str) -> Vec<Shape> {
+    let mut stack: Vec<(usize, Shape)> = Vec::new();
+    let mut forms = Vec::new();
+    for line in s.lines() {
+        let depth = (line.len() - line.trim_left().len()) / 4;
+        let kind = line.trim_left().split(' ').next().unwrap();
+        while stack.len() > depth {
+            let (_, shape) = stack.pop().unwrap();
+            match stack.last_mut() {
+                Some(parent) => (parent.1).1.push(shape),
+                None => forms.push(shape),
+            }
+        }
+        let mut name = String::new();
+        for (i, c) in kind.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                name.push('-');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        stack.push((depth, Shape(name, Vec::new())));
+    }
+    while let Some((_, shape)) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => (parent.1).1.push(shape),
+            None => forms.push(shape),
+        }
+    }
+    forms
+}
+
+#[test]
+fn test_sexp_shape() {
+    let unit = parse_unit(
+        r#"
+        struct point { int x, y : 4; } origin = { .x = 0 };
+        static int f(int n, ...) {
+            char *s[] = { "(a)", "\"b\"", "\\" };
+            for (int i = 0; i < n; i++) { if (i % 2) continue; else break; }
+            switch (n) { case 1: return sizeof(struct point); default: ; }
+            return n ? (int)1.5e3 : s[0][')'] + '"';
+        }
+        "#,
+    );
+    let mut text = String::new();
+    Printer::new(&mut text).visit_translation_unit(&unit);
+    for &spans in &[false, true] {
+        let sexp = ast_to_sexp(&unit, &SexpOptions { spans: spans });
+        assert_eq!(read_sexp(&sexp), read_text(&text));
+    }
+}
+
+
+// Check that quotes and braces of a DOT graph are balanced, returning the number of vertices
+#[cfg(test)]
+fn check_dot(dot: &str) -> usize {
+    let mut depth = 0;
+    let mut vertices = 0;
+    let mut chars = dot.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                assert!(depth > 0, "unbalanced braces");
+                depth -= 1;
+                assert!(depth > 0 || chars.as_str().trim().is_empty());
+            }
+            '"' => loop {
+                match chars.next() {
+                    Some('\\') => assert!(chars.next().is_some()),
+                    Some('"') => break,
+                    Some('\n') | None => panic!("unterminated string"),
+                    Some(_) => {}
+                }
+            },
+            _ => {}
+        }
+    }
+    assert_eq!(depth, 0);
+    for line in dot.lines() {
+        let line = line.trim();
+        if line.contains("[label=") {
+            assert!(line.starts_with('n') && line.ends_with("\"];"), "{}", line);
+            vertices += 1;
+        }
+    }
+    vertices
+}
+
+#[test]
+fn test_dot() {
+    let unit = parse_unit(r#"char *f(void) { return "a\"{b}\\"; }"#);
+    let options = DotOptions {
+        skip: vec!["DeclarationSpecifier".into(), "DeclaratorKind".into()],
+        ..Default::default()
+    };
+    let expected = r#"digraph ast {
+    node [shape=box];
+    n0 [label="TranslationUnit"];
+    n1 [label="ExternalDeclaration"];
+    n0 -> n1;
+    n2 [label="FunctionDefinition"];
+    n1 -> n2;
+    n4 [label="TypeSpecifier\nChar"];
+    n2 -> n4;
+    n5 [label="Declarator"];
+    n2 -> n5;
+    n7 [label="Identifier\nf"];
+    n5 -> n7;
+    n8 [label="DerivedDeclarator\nPointer"];
+    n5 -> n8;
+    n9 [label="DerivedDeclarator"];
+    n5 -> n9;
+    n10 [label="FunctionDeclarator"];
+    n9 -> n10;
+    n11 [label="ParameterDeclaration"];
+    n10 -> n11;
+    n13 [label="TypeSpecifier\nVoid"];
+    n11 -> n13;
+    n14 [label="Ellipsis\nNone"];
+    n10 -> n14;
+    n15 [label="Statement\nCompound"];
+    n2 -> n15;
+    n16 [label="BlockItem"];
+    n15 -> n16;
+    n17 [label="Statement\nReturn"];
+    n16 -> n17;
+    n18 [label="Expression"];
+    n17 -> n18;
+    n19 [label="StringLiteral\n\"a\\\"{b}\\\\\""];
+    n18 -> n19;
+}
+"#;
+    let dot = ast_to_dot(&unit, &options);
+    assert_eq!(dot, expected);
+    assert_eq!(check_dot(&dot), 17);
+}
+
+#[test]
+fn test_dot_options() {
+    let source = r#"
+        char *s = "\"}{\\";
+        int f(int a) { return a ? '"' : '\\'; }
+    "#;
+    let unit = parse_unit(source);
+    let options = DotOptions {
+        clusters: true,
+        spans: true,
+        max_label_chars: 3,
+        skip: Vec::new(),
+    };
+    let dot = ast_to_dot(&unit, &options);
+    assert_eq!(dot, ast_to_dot(&parse_unit(source), &options));
+    assert_eq!(dot.matches("subgraph cluster_").count(), 2);
+    assert!(dot.contains(r#"[label="StringLiteral\n\"\\\"...\n19:27"];"#));
+
+    let mut text = String::new();
+    Printer::new(&mut text).visit_translation_unit(&unit);
+    assert_eq!(check_dot(&dot), text.lines().count());
+}
+
+#[test]
+fn test_dot_empty() {
+    let mut s = String::new();
+    Printer::dot(&mut s, &DotOptions::default());
+    assert_eq!(s, "digraph ast {\n    node [shape=box];\n}\n");
+}
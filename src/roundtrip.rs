@@ -0,0 +1,347 @@
+#![allow(unknown_lints)]
+#![allow(bare_trait_objects)]
+
+//! Round-trip checks of the C emitter
+//!
+//! `check` parses a preprocessed source, writes the tree back with `codegen` and parses the
+//! result again. Both trees must be the same apart from spans, as compared by their dumps by
+//! `print::Printer`. `check_stable` also requires the source emitted from the second tree to
+//! be the same as the first one, byte for byte.
+//!
+//! Running them over a corpus of preprocessed sources tests the parser and the emitter
+//! together:
+//!
+//! ```
+//! use lang_c::driver::Flavor;
+//! use lang_c::roundtrip;
+//!
+//! assert!(roundtrip::check("int x = (1 + 2) * 3;", Flavor::StdC11).is_ok());
+//! assert!(roundtrip::check_stable("int (*f)(void);", Flavor::GnuC11).is_ok());
+//! ```
+
+use std::error;
+use std::fmt;
+use std::iter;
+
+use ast::TranslationUnit;
+use codegen::{emit_translation_unit, FormatOptions};
+use driver::{parse_preprocessed, Config, Flavor, SyntaxError};
+use print::Printer;
+use span::Span;
+use visit::Visit;
+
+/// Check that the source emitted for `source` parses into the same tree
+///
+/// `source` is parsed as preprocessed source in `flavor`, and the emitted source is written
+/// with the default `FormatOptions`.
+pub fn check(source: &str, flavor: Flavor) -> Result<(), RoundtripError> {
+    roundtrip(source, flavor).map(|_| ())
+}
+
+/// Check like `check`, and that emitting the re-parsed tree gives the same source
+pub fn check_stable(source: &str, flavor: Flavor) -> Result<(), RoundtripError> {
+    let (first, reparsed) = try!(roundtrip(source, flavor));
+    let second = emit_translation_unit(&reparsed, &FormatOptions::default());
+    if first == second {
+        return Ok(());
+    }
+    let line = first
+        .lines()
+        .zip(second.lines())
+        .take_while(|&(a, b)| a == b)
+        .count();
+    Err(RoundtripError::Unstable(Unstable {
+        first: first,
+        second: second,
+        line: line + 1,
+    }))
+}
+
+/// Failure of a round-trip check
+#[derive(Debug, Clone)]
+pub enum RoundtripError {
+    /// The source does not parse
+    Parse(SyntaxError),
+    /// The emitted source does not parse, it is the `source` of the error
+    Reparse(SyntaxError),
+    /// The emitted source parses into a different tree
+    Mismatch(Mismatch),
+    /// The source emitted from the re-parsed tree is different, see `check_stable`
+    Unstable(Unstable),
+}
+
+/// First node that differs between the tree of the source and the tree of the emitted source
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    /// Kinds of the nodes from the translation unit down to the one that differs, like
+    /// `["TranslationUnit", "ExternalDeclaration", "Declaration"]`
+    pub path: Vec<String>,
+    /// Node in the tree of the source, as printed by `Printer::new`
+    ///
+    /// `None` when the node is missing, because the tree of the emitted source has more nodes.
+    pub expected: Option<String>,
+    /// Node in the tree of the emitted source, `None` when it is missing
+    pub actual: Option<String>,
+    /// Span of the node in the source
+    ///
+    /// It is the span of the closest ancestor with a span when the node has none or is
+    /// missing.
+    pub span: Span,
+    /// Text of the node in the source
+    pub original: String,
+    /// Text of the node in the emitted source, found like `span`
+    pub emitted: String,
+    /// Whole emitted source
+    pub emitted_source: String,
+}
+
+/// Sources emitted from the tree of the source and from the tree of the emitted source
+#[derive(Debug, Clone)]
+pub struct Unstable {
+    pub first: String,
+    pub second: String,
+    /// First line that differs, counted from 1
+    pub line: usize,
+}
+
+impl fmt::Display for RoundtripError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RoundtripError::Parse(ref e) => write!(fmt, "source does not parse: {}", e),
+            RoundtripError::Reparse(ref e) => {
+                try!(fmt.write_str("emitted source does not parse: "));
+                e.fmt_with_snippet(fmt)
+            }
+            RoundtripError::Mismatch(ref m) => {
+                try!(write!(
+                    fmt,
+                    "emitted source parses differently at {}: expected {}, found {}",
+                    m.path.join(" > "),
+                    Quoted(m.expected.as_ref().map(|s| &s[..])),
+                    Quoted(m.actual.as_ref().map(|s| &s[..])),
+                ));
+                if !m.span.is_none() {
+                    let (start, end) = (m.span.start, m.span.end);
+                    let original = Quoted(Some(&m.original));
+                    try!(write!(
+                        fmt,
+                        "\n  source at {}..{}: {}",
+                        start, end, original
+                    ));
+                }
+                write!(fmt, "\n  emitted: {}", Quoted(Some(&m.emitted)))
+            }
+            RoundtripError::Unstable(ref u) => {
+                let line = |s: &str| s.lines().nth(u.line - 1).unwrap_or("").to_owned();
+                write!(
+                    fmt,
+                    "emitted source changes when emitted again, at line {}\n  first:  {}\n  second: {}",
+                    u.line,
+                    Quoted(Some(&line(&u.first))),
+                    Quoted(Some(&line(&u.second))),
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for RoundtripError {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            RoundtripError::Parse(ref e) | RoundtripError::Reparse(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Text in backquotes, cut at the first line break
+struct Quoted<'a>(Option<&'a str>);
+
+impl<'a> fmt::Display for Quoted<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self.0 {
+            Some(s) => s,
+            None => return fmt.write_str("nothing"),
+        };
+        match s.find('\n') {
+            Some(i) => write!(fmt, "`{} ...`", &s[..i]),
+            None => write!(fmt, "`{}`", s),
+        }
+    }
+}
+
+// Emitted source and its tree, after checking that it is the tree of `source`
+fn roundtrip(source: &str, flavor: Flavor) -> Result<(String, TranslationUnit), RoundtripError> {
+    let config = Config {
+        flavor: flavor,
+        ..Config::default()
+    };
+
+    let parse = match parse_preprocessed(&config, source.to_owned()) {
+        Ok(p) => p,
+        Err(e) => return Err(RoundtripError::Parse(e)),
+    };
+    let emitted = emit_translation_unit(&parse.unit, &FormatOptions::default());
+    let reparse = match parse_preprocessed(&config, emitted.clone()) {
+        Ok(p) => p,
+        Err(e) => return Err(RoundtripError::Reparse(e)),
+    };
+
+    match mismatch(source, &parse.unit, &emitted, &reparse.unit) {
+        Some(m) => Err(RoundtripError::Mismatch(m)),
+        None => Ok((emitted, reparse.unit)),
+    }
+}
+
+// Dump of a tree by `Printer::new`, one line per node, and the span of each node
+struct Dump {
+    lines: Vec<String>,
+    spans: Vec<Span>,
+}
+
+impl Dump {
+    fn new(unit: &TranslationUnit) -> Dump {
+        let mut text = String::new();
+        let mut spans = Vec::new();
+        Printer::with_spans(&mut text, &mut spans).visit_translation_unit(unit);
+        Dump {
+            lines: text.lines().map(String::from).collect(),
+            spans: spans,
+        }
+    }
+}
+
+fn depth(line: &str) -> usize {
+    (line.len() - line.trim_left().len()) / 4
+}
+
+fn kind(line: &str) -> String {
+    line.trim_left().split(' ').next().unwrap_or("").to_owned()
+}
+
+fn mismatch(
+    source: &str,
+    expected: &TranslationUnit,
+    emitted: &str,
+    actual: &TranslationUnit,
+) -> Option<Mismatch> {
+    let expected = Dump::new(expected);
+    let actual = Dump::new(actual);
+    let len = expected.lines.len().max(actual.lines.len());
+    (0..len)
+        .find(|&i| expected.lines.get(i) != actual.lines.get(i))
+        .map(|i| mismatch_at(i, source, &expected, emitted, &actual))
+}
+
+// Mismatch at the line `i` of the dumps
+fn mismatch_at(i: usize, source: &str, expected: &Dump, emitted: &str, actual: &Dump) -> Mismatch {
+    // Lines before the difference are the same in both dumps, and so are the ancestors
+    let lines = if i < expected.lines.len() {
+        &expected.lines
+    } else {
+        &actual.lines
+    };
+    let mut ancestors = Vec::new();
+    let mut level = depth(&lines[i]);
+    for j in (0..i).rev() {
+        if level == 0 {
+            break;
+        }
+        if depth(&lines[j]) < level {
+            level = depth(&lines[j]);
+            ancestors.push(j);
+        }
+    }
+    ancestors.reverse();
+
+    let mut path: Vec<String> = ancestors.iter().map(|&j| kind(&lines[j])).collect();
+    path.push(kind(&lines[i]));
+
+    // Span of the innermost node with one, from the node that differs up
+    let span = |spans: &[Span]| {
+        iter::once(i)
+            .filter(|&i| i < spans.len())
+            .chain(ancestors.iter().rev().cloned())
+            .map(|j| spans[j])
+            .find(|s| !s.is_none())
+            .unwrap_or_else(Span::none)
+    };
+    let expected_span = span(&expected.spans);
+    let actual_span = span(&actual.spans);
+
+    Mismatch {
+        path: path,
+        expected: expected.lines.get(i).map(|l| l.trim_left().to_owned()),
+        actual: actual.lines.get(i).map(|l| l.trim_left().to_owned()),
+        span: expected_span,
+        original: snippet(source, expected_span),
+        emitted: snippet(emitted, actual_span),
+        emitted_source: emitted.to_owned(),
+    }
+}
+
+fn snippet(source: &str, span: Span) -> String {
+    if span.is_none() {
+        return String::new();
+    }
+    source.get(span.start..span.end).unwrap_or("").to_owned()
+}
+
+#[cfg(test)]
+fn parse_unit(source: &str) -> TranslationUnit {
+    parse_preprocessed(&Config::default(), source.into())
+        .expect("parse failed")
+        .unit
+}
+
+#[test]
+fn test_check() {
+    assert!(check("int f(int x) { return x ? 1 : 2; }", Flavor::GnuC11).is_ok());
+    assert!(check_stable("struct s { int a : 3; } v = {1};", Flavor::StdC11).is_ok());
+    match check("int x = ;", Flavor::StdC11) {
+        Err(RoundtripError::Parse(e)) => assert_eq!(e.offset, 8),
+        r => panic!("{:?}", r),
+    }
+}
+
+#[test]
+fn test_mismatch() {
+    let source = "int x = 1, y = 2;";
+    let emitted = "int x = 1, y = 3;";
+    let m = mismatch(source, &parse_unit(source), emitted, &parse_unit(emitted)).unwrap();
+    assert_eq!(
+        m.path,
+        [
+            "TranslationUnit",
+            "ExternalDeclaration",
+            "Declaration",
+            "InitDeclarator",
+            "Initializer",
+            "Expression",
+            "Constant",
+            "Integer",
+        ]
+    );
+    assert_eq!(m.expected.as_ref().unwrap(), "Integer \"2\"");
+    assert_eq!(m.actual.as_ref().unwrap(), "Integer \"3\"");
+    assert_eq!((m.span.start, m.span.end), (15, 16));
+    assert_eq!((&m.original[..], &m.emitted[..]), ("2", "3"));
+    assert_eq!(
+        RoundtripError::Mismatch(m).to_string(),
+        concat!(
+            "emitted source parses differently at TranslationUnit > ExternalDeclaration > ",
+            "Declaration > InitDeclarator > Initializer > Expression > Constant > Integer: ",
+            "expected `Integer \"2\"`, found `Integer \"3\"`\n",
+            "  source at 15..16: `2`\n",
+            "  emitted: `3`",
+        )
+    );
+
+    // A missing node is reported at its parent
+    let emitted = "int x = 1;";
+    let m = mismatch(source, &parse_unit(source), emitted, &parse_unit(emitted)).unwrap();
+    assert_eq!(m.path.last().unwrap(), "InitDeclarator");
+    assert_eq!(m.actual, None);
+    assert_eq!(m.original, "y = 2");
+    assert_eq!(m.emitted, "int x = 1;");
+}
@@ -1,9 +1,14 @@
 //! Source text location tracking
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::usize::MAX;
 use std::{cmp, fmt};
 
 /// Byte offset of a node start and end positions in the input stream
+///
+/// An undefined span is serialized with `usize::MAX` as start and end.
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -50,6 +55,7 @@ impl fmt::Debug for Span {
 
 /// Associate a span with an arbitrary type
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Node<T> {
     pub node: T,
     pub span: Span,
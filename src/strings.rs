@@ -1,4 +1,4 @@
-pub const RESERVED_C11: &'static [&'static str] = &[
+pub const RESERVED_C89: &'static [&'static str] = &[
     "auto",
     "break",
     "case",
@@ -15,11 +15,9 @@ pub const RESERVED_C11: &'static [&'static str] = &[
     "for",
     "goto",
     "if",
-    "inline",
     "int",
     "long",
     "register",
-    "restrict",
     "return",
     "short",
     "signed",
@@ -33,13 +31,23 @@ pub const RESERVED_C11: &'static [&'static str] = &[
     "void",
     "volatile",
     "while",
+];
+
+// Added in C99
+pub const RESERVED_C99: &'static [&'static str] = &[
+    "inline",
+    "restrict",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+];
+
+// Added in C11
+pub const RESERVED_C11: &'static [&'static str] = &[
     "_Alignas",
     "_Alignof",
     "_Atomic",
-    "_Bool",
-    "_Complex",
     "_Generic",
-    "_Imaginary",
     "_Noreturn",
     "_Static_assert",
     "_Thread_local",
@@ -59,7 +67,28 @@ pub const RESERVED_C11: &'static [&'static str] = &[
     "_Decimal128x",
 ];
 
+// Added in C23
+pub const RESERVED_C23: &'static [&'static str] = &[
+    "alignas",
+    "alignof",
+    "bool",
+    "constexpr",
+    "false",
+    "nullptr",
+    "static_assert",
+    "thread_local",
+    "true",
+    "typeof",
+    "typeof_unqual",
+    "_BitInt",
+    "_Countof",
+];
+
 pub const RESERVED_GNU: &'static [&'static str] = &[
+    "_Accum",
+    "_Countof",
+    "_Fract",
+    "_Sat",
     "__FUNCTION__",
     "__PRETTY_FUNCTION__",
     "__alignof",
@@ -68,17 +97,24 @@ pub const RESERVED_GNU: &'static [&'static str] = &[
     "__asm__",
     "__attribute",
     "__attribute__",
+    "__auto_type",
     "__builtin_offsetof",
+    "__builtin_types_compatible_p",
     "__builtin_va_arg",
     "__complex",
     "__complex__",
     "__const",
     "__extension__",
+    "__float128",
+    "__float80",
+    "__fp16",
     "__func__",
+    "__ibm128",
     "__imag",
     "__imag__",
     "__inline",
     "__inline__",
+    "__int128",
     "__label__",
     "__null",
     "__real",
@@ -89,8 +125,11 @@ pub const RESERVED_GNU: &'static [&'static str] = &[
     "__signed__",
     "__thread",
     "__typeof",
+    "__typeof__",
     "__volatile",
     "__volatile__",
+    "asm",
+    "typeof",
 ];
 
 // Ref: https://clang.llvm.org/docs/AttributeReference.html
@@ -110,4 +149,55 @@ pub const RESERVED_CLANG: &'static [&'static str] = &[
     "_Nonnull",
     "_Null_unspecified",
     "_Nullable",
+    // Bit-precise integers, also available before C23
+    "_BitInt",
+    "_ExtInt",
+    // Blocks
+    "__block",
+];
+
+pub const RESERVED_CUDA: &'static [&'static str] = &[
+    "__constant__",
+    "__device__",
+    "__global__",
+    "__host__",
+    "__launch_bounds__",
+    "__managed__",
+    "__shared__",
+];
+
+pub const RESERVED_OPENCL: &'static [&'static str] = &[
+    "__constant",
+    "__generic",
+    "__global",
+    "__kernel",
+    "__local",
+    "__private",
+    "__read_only",
+    "__read_write",
+    "__write_only",
+];
+
+pub const RESERVED_MSVC: &'static [&'static str] = &[
+    "__asm",
+    "__cdecl",
+    "__clrcall",
+    "__declspec",
+    "__except",
+    "__fastcall",
+    "__finally",
+    "__forceinline",
+    "__inline",
+    "__int16",
+    "__int32",
+    "__int64",
+    "__int8",
+    "__leave",
+    "__pragma",
+    "__restrict",
+    "__stdcall",
+    "__thiscall",
+    "__try",
+    "__unaligned",
+    "__vectorcall",
 ];
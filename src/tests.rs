@@ -9,9 +9,12 @@ use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::mem;
 use std::path::PathBuf;
 
-use env::Env;
+use codegen::{self, FormatOptions};
+use driver::Flavor;
+use env::{Env, Standard};
 use parser;
 use print::Printer;
+use roundtrip;
 use span::Span;
 use visit::Visit;
 
@@ -48,8 +51,8 @@ impl Case {
             let line = line.trim_right();
             if line.is_empty() || line.starts_with("//") {
                 continue;
-            } else if line.starts_with("#pragma") {
-                pragma.push(Pragma::from_str(line).expect("unknown pragma"));
+            } else if let Some(p) = Pragma::from_line(line) {
+                pragma.push(p);
             } else if line == OUTPUT_START {
                 in_exp = true;
             } else if line == OUTPUT_END {
@@ -70,13 +73,22 @@ impl Case {
         })
     }
 
-    fn run(&self) -> bool {
+    // Environment selected by the pragmas
+    fn env(&self) -> Env {
         let mut env = None;
 
         for pragma in &self.pragma {
             match *pragma {
                 Pragma::Gnu => env = Some(Env::with_gnu()),
                 Pragma::Clang => env = Some(Env::with_clang()),
+                Pragma::Msvc => env = Some(Env::with_msvc()),
+                Pragma::Cuda => env = Some(Env::with_cuda()),
+                Pragma::OpenCl => env = Some(Env::with_opencl()),
+                Pragma::C89 => env = Some(Env::with_standard(Standard::C89)),
+                Pragma::C99 => env = Some(Env::with_standard(Standard::C99)),
+                Pragma::C23 => env = Some(Env::with_standard(Standard::C23)),
+                Pragma::Gnu23 => env = Some(Env::new(Standard::C23, true, false)),
+                Pragma::Clang23 => env = Some(Env::new(Standard::C23, true, true)),
                 _ => {}
             }
         }
@@ -86,10 +98,44 @@ impl Case {
         for pragma in &self.pragma {
             match *pragma {
                 Pragma::Typedef(ref name) => env.add_typename(&name),
+                Pragma::MsExtensions => env.ms_extensions = true,
+                Pragma::ImplicitInt => env.implicit_int = true,
+                Pragma::NoDigraphs => env.digraphs = false,
                 _ => {}
             }
         }
 
+        env
+    }
+
+    // Flavor selecting the same environment as the pragmas, if there is one
+    fn flavor(&self) -> Option<Flavor> {
+        let mut flavor = Flavor::StdC11;
+        for pragma in &self.pragma {
+            flavor = match *pragma {
+                Pragma::Gnu => Flavor::GnuC11,
+                Pragma::Clang => Flavor::ClangC11,
+                Pragma::Msvc => Flavor::MsvcC11,
+                Pragma::Cuda => Flavor::Cuda,
+                Pragma::OpenCl => Flavor::OpenCl,
+                Pragma::C89 => Flavor::StdC89,
+                Pragma::C99 => Flavor::StdC99,
+                Pragma::C23 => Flavor::StdC23,
+                Pragma::Gnu23 => Flavor::GnuC23,
+                Pragma::Clang23 => Flavor::ClangC23,
+                Pragma::Typedef(_)
+                | Pragma::MsExtensions
+                | Pragma::ImplicitInt
+                | Pragma::NoDigraphs => return None,
+                _ => flavor,
+            };
+        }
+        Some(flavor)
+    }
+
+    fn run(&self) -> bool {
+        let mut env = self.env();
+
         pegviz::marker_start(&self.source);
 
         let (actual, error) = match self.kind.parse_and_print(&self.source, &mut env) {
@@ -213,6 +259,32 @@ impl Kind {
         })
     }
 
+    fn parse_and_emit(
+        &self,
+        source: &str,
+        env: &mut Env,
+        options: &FormatOptions,
+    ) -> Result<String, parser::ParseError> {
+        let source = source.trim_right();
+
+        Ok(match *self {
+            Kind::Constant => codegen::emit_constant(&try!(parser::constant(source, env)), options),
+            Kind::Declaration => {
+                codegen::emit_declaration(&try!(parser::declaration(source, env)).node, options)
+            }
+            Kind::Statement => {
+                codegen::emit_statement(&try!(parser::statement(source, env)).node, options)
+            }
+            Kind::Expression => {
+                codegen::emit_expression(&try!(parser::expression(source, env)).node, options)
+            }
+            Kind::TranslationUnit => codegen::emit_translation_unit(
+                &try!(parser::translation_unit(source, env)),
+                options,
+            ),
+        })
+    }
+
     fn parse_and_print(&self, source: &str, env: &mut Env) -> Result<String, parser::ParseError> {
         let source = source.trim_right();
 
@@ -253,6 +325,28 @@ enum Pragma {
     Gnu,
     // Enable clang extensions
     Clang,
+    /// Enable msvc extensions
+    Msvc,
+    /// Enable CUDA extensions
+    Cuda,
+    /// Enable OpenCL extensions
+    OpenCl,
+    /// Accept `-fms-extensions` unnamed members
+    MsExtensions,
+    /// Accept declarations without a type specifier
+    ImplicitInt,
+    /// Reject digraphs like `<:`
+    NoDigraphs,
+    /// Restrict to standard C89
+    C89,
+    /// Restrict to standard C99
+    C99,
+    /// Enable standard C23
+    C23,
+    /// Enable standard C23 with gnu extensions
+    Gnu23,
+    /// Enable standard C23 with clang extensions
+    Clang23,
     /// Define typename
     Typedef(String),
     /// Assert argument is a typename
@@ -260,6 +354,15 @@ enum Pragma {
 }
 
 impl Pragma {
+    // Test case options, other `#pragma` lines are part of the source
+    fn from_line(line: &str) -> Option<Pragma> {
+        if line.starts_with("#pragma") {
+            Pragma::from_str(line)
+        } else {
+            None
+        }
+    }
+
     fn from_str(line: &str) -> Option<Pragma> {
         let mut line = line
             .split(" ")
@@ -269,6 +372,17 @@ impl Pragma {
         Some(match line[0].trim() {
             "gnu" => Pragma::Gnu,
             "clang" => Pragma::Clang,
+            "msvc" => Pragma::Msvc,
+            "cuda" => Pragma::Cuda,
+            "opencl" => Pragma::OpenCl,
+            "ms_extensions" => Pragma::MsExtensions,
+            "implicit_int" => Pragma::ImplicitInt,
+            "no_digraphs" => Pragma::NoDigraphs,
+            "c89" => Pragma::C89,
+            "c99" => Pragma::C99,
+            "c23" => Pragma::C23,
+            "gnu23" => Pragma::Gnu23,
+            "clang23" => Pragma::Clang23,
             "typedef" => Pragma::Typedef(match line.pop() {
                 Some(v) => v,
                 None => return None,
@@ -298,8 +412,7 @@ fn filter_entry(a: &DirEntry, filter: Option<&OsString>) -> bool {
     true
 }
 
-#[test]
-fn reftest_main() {
+fn read_cases() -> Vec<Case> {
     let mut cases = Vec::new();
     let filter = env::var_os("TEST_FILTER");
     for entry in fs::read_dir("reftests").expect("listing reftests/") {
@@ -315,13 +428,87 @@ fn reftest_main() {
         };
         cases.push(case);
     }
+    cases
+}
 
+#[test]
+fn reftest_main() {
+    let cases = read_cases();
     let failed = cases.iter().filter(|c| !c.run()).count();
     if failed > 0 {
         panic!("{} cases failed", failed);
     }
 }
 
+// Source emitted for each case parses into the tree of the original, as printed without spans,
+// in the default style, the LLVM one and a narrow one wrapping most lists
+#[test]
+fn reftest_codegen() {
+    let styles = [
+        FormatOptions::default(),
+        FormatOptions::llvm(),
+        FormatOptions {
+            use_tabs: true,
+            max_width: Some(30),
+            ..FormatOptions::llvm()
+        },
+    ];
+    let cases = read_cases();
+    let mut failed = 0;
+    for case in cases.iter().filter(|c| c.expect != "~ERROR\n") {
+        for options in &styles {
+            let emitted = match case
+                .kind
+                .parse_and_emit(&case.source, &mut case.env(), options)
+            {
+                Ok(s) => s,
+                // Reported by `reftest_main`
+                Err(_) => continue,
+            };
+            let actual = match case.kind.parse_and_print(&emitted, &mut case.env()) {
+                Ok(s) => s,
+                Err(e) => format!("~ERROR\n{}\n", e),
+            };
+            if actual != case.expect {
+                writeln!(
+                    stdout(),
+                    "\n{}:\n{}\nEMITTED WITH {:?}:\n{}",
+                    case.name,
+                    case.source,
+                    options,
+                    emitted
+                )
+                .unwrap();
+                writeln!(stdout(), "REPARSED:\n{}", actual).unwrap();
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        panic!("{} cases failed", failed);
+    }
+}
+
+// Translation units pass `roundtrip::check_stable` in the flavor of their pragmas
+#[test]
+fn reftest_roundtrip() {
+    let cases = read_cases();
+    let mut failed = 0;
+    for case in &cases {
+        let flavor = match (&case.kind, case.flavor()) {
+            (&Kind::TranslationUnit, Some(f)) if case.expect != "~ERROR\n" => f,
+            _ => continue,
+        };
+        if let Err(e) = roundtrip::check_stable(case.source.trim_right(), flavor) {
+            writeln!(stdout(), "\n{}:\n{}", case.name, e).unwrap();
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        panic!("{} cases failed", failed);
+    }
+}
+
 #[cfg(feature = "dev-pegviz")]
 mod pegviz {
     pub fn marker_start(source: &str) {
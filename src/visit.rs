@@ -19,6 +19,7 @@
 //! Free functions apply the visitor to sub-nodes of any given AST node.
 
 use ast::*;
+use omp::*;
 use span::Span;
 
 pub trait Visit<'ast> {
@@ -62,6 +63,14 @@ pub trait Visit<'ast> {
         visit_float_format(self, float_format, span)
     }
 
+    fn visit_fixed_point_format(
+        &mut self,
+        fixed_point_format: &'ast FixedPointFormat,
+        span: &'ast Span,
+    ) {
+        visit_fixed_point_format(self, fixed_point_format, span)
+    }
+
     fn visit_string_literal(&mut self, string_literal: &'ast StringLiteral, span: &'ast Span) {
         visit_string_literal(self, string_literal, span)
     }
@@ -110,6 +119,14 @@ pub trait Visit<'ast> {
         visit_call_expression(self, call_expression, span)
     }
 
+    fn visit_kernel_call_expression(
+        &mut self,
+        kernel_call_expression: &'ast KernelCallExpression,
+        span: &'ast Span,
+    ) {
+        visit_kernel_call_expression(self, kernel_call_expression, span)
+    }
+
     fn visit_compound_literal(
         &mut self,
         compound_literal: &'ast CompoundLiteral,
@@ -124,6 +141,12 @@ pub trait Visit<'ast> {
     fn visit_sizeofval(&mut self, sizeofval: &'ast SizeOfVal, span: &'ast Span) {
         visit_sizeofval(self, sizeofval, span)
     }
+    fn visit_countofty(&mut self, countofty: &'ast CountOfTy, span: &'ast Span) {
+        visit_countofty(self, countofty, span)
+    }
+    fn visit_countofval(&mut self, countofval: &'ast CountOfVal, span: &'ast Span) {
+        visit_countofval(self, countofval, span)
+    }
 
     fn visit_alignof(&mut self, alignofty: &'ast AlignOf, span: &'ast Span) {
         visit_alignof(self, alignofty, span)
@@ -173,6 +196,30 @@ pub trait Visit<'ast> {
         visit_va_arg_expression(self, va_arg_expression, span)
     }
 
+    fn visit_choose_expression(
+        &mut self,
+        choose_expression: &'ast ChooseExpression,
+        span: &'ast Span,
+    ) {
+        visit_choose_expression(self, choose_expression, span)
+    }
+
+    fn visit_types_compatible_expression(
+        &mut self,
+        types_compatible_expression: &'ast TypesCompatibleExpression,
+        span: &'ast Span,
+    ) {
+        visit_types_compatible_expression(self, types_compatible_expression, span)
+    }
+
+    fn visit_block_expression(
+        &mut self,
+        block_expression: &'ast BlockExpression,
+        span: &'ast Span,
+    ) {
+        visit_block_expression(self, block_expression, span)
+    }
+
     fn visit_offset_of_expression(
         &mut self,
         offset_of_expression: &'ast OffsetOfExpression,
@@ -401,6 +448,22 @@ pub trait Visit<'ast> {
         visit_labeled_statement(self, labeled_statement, span)
     }
 
+    fn visit_attributed_statement(
+        &mut self,
+        attributed_statement: &'ast AttributedStatement,
+        span: &'ast Span,
+    ) {
+        visit_attributed_statement(self, attributed_statement, span)
+    }
+
+    fn visit_try_statement(&mut self, try_statement: &'ast TryStatement, span: &'ast Span) {
+        visit_try_statement(self, try_statement, span)
+    }
+
+    fn visit_except_handler(&mut self, except_handler: &'ast ExceptHandler, span: &'ast Span) {
+        visit_except_handler(self, except_handler, span)
+    }
+
     fn visit_if_statement(&mut self, if_statement: &'ast IfStatement, span: &'ast Span) {
         visit_if_statement(self, if_statement, span)
     }
@@ -457,6 +520,54 @@ pub trait Visit<'ast> {
         visit_external_declaration(self, external_declaration, span)
     }
 
+    fn visit_pragma(&mut self, pragma: &'ast Pragma, span: &'ast Span) {
+        visit_pragma(self, pragma, span)
+    }
+
+    fn visit_omp_statement(&mut self, omp_statement: &'ast OmpStatement, span: &'ast Span) {
+        visit_omp_statement(self, omp_statement, span)
+    }
+
+    fn visit_omp_directive(&mut self, omp_directive: &'ast OmpDirective, span: &'ast Span) {
+        visit_omp_directive(self, omp_directive, span)
+    }
+
+    fn visit_omp_directive_kind(
+        &mut self,
+        omp_directive_kind: &'ast OmpDirectiveKind,
+        span: &'ast Span,
+    ) {
+        visit_omp_directive_kind(self, omp_directive_kind, span)
+    }
+
+    fn visit_omp_clause(&mut self, omp_clause: &'ast OmpClause, span: &'ast Span) {
+        visit_omp_clause(self, omp_clause, span)
+    }
+
+    fn visit_omp_reduction(&mut self, omp_reduction: &'ast OmpReduction, span: &'ast Span) {
+        visit_omp_reduction(self, omp_reduction, span)
+    }
+
+    fn visit_omp_reduction_operator(
+        &mut self,
+        omp_reduction_operator: &'ast OmpReductionOperator,
+        span: &'ast Span,
+    ) {
+        visit_omp_reduction_operator(self, omp_reduction_operator, span)
+    }
+
+    fn visit_omp_schedule(&mut self, omp_schedule: &'ast OmpSchedule, span: &'ast Span) {
+        visit_omp_schedule(self, omp_schedule, span)
+    }
+
+    fn visit_omp_schedule_kind(
+        &mut self,
+        omp_schedule_kind: &'ast OmpScheduleKind,
+        span: &'ast Span,
+    ) {
+        visit_omp_schedule_kind(self, omp_schedule_kind, span)
+    }
+
     fn visit_function_definition(
         &mut self,
         function_definition: &'ast FunctionDefinition,
@@ -487,6 +598,26 @@ pub trait Visit<'ast> {
 
     fn visit_availability_clause(&mut self, _clause: &'ast AvailabilityClause, _span: &'ast Span) {}
 
+    fn visit_availability_condition(
+        &mut self,
+        condition: &'ast AvailabilityCondition,
+        span: &'ast Span,
+    ) {
+        visit_availability_condition(self, condition, span)
+    }
+
+    fn visit_std_attribute(&mut self, std_attribute: &'ast StdAttribute, span: &'ast Span) {
+        visit_std_attribute(self, std_attribute, span)
+    }
+
+    fn visit_std_attribute_arguments(
+        &mut self,
+        arguments: &'ast StdAttributeArguments,
+        span: &'ast Span,
+    ) {
+        visit_std_attribute_arguments(self, arguments, span)
+    }
+
     fn visit_gnu_extended_asm_statement(
         &mut self,
         gnu_extended_asm_statement: &'ast GnuExtendedAsmStatement,
@@ -519,7 +650,7 @@ pub fn visit_constant<'ast, V: Visit<'ast> + ?Sized>(
     match *constant {
         Constant::Integer(ref i) => visitor.visit_integer(i, span),
         Constant::Float(ref f) => visitor.visit_float(f, span),
-        Constant::Character(_) => {}
+        Constant::Character(_) | Constant::Nullptr | Constant::Bool(_) => {}
     }
 }
 
@@ -585,10 +716,18 @@ pub fn visit_float_format<'ast, V: Visit<'ast> + ?Sized>(
 ) {
     match *float_format {
         FloatFormat::TS18661Format(ref f) => visitor.visit_ts18661_float_type(f, span),
+        FloatFormat::FixedPoint(ref f) => visitor.visit_fixed_point_format(f, span),
         _ => {}
     }
 }
 
+pub fn visit_fixed_point_format<'ast, V: Visit<'ast> + ?Sized>(
+    _visitor: &mut V,
+    _fixed_point_format: &'ast FixedPointFormat,
+    _span: &'ast Span,
+) {
+}
+
 pub fn visit_string_literal<'ast, V: Visit<'ast> + ?Sized>(
     _visitor: &mut V,
     _string_literal: &'ast StringLiteral,
@@ -608,9 +747,12 @@ pub fn visit_expression<'ast, V: Visit<'ast> + ?Sized>(
         Expression::GenericSelection(ref g) => visitor.visit_generic_selection(&g.node, &g.span),
         Expression::Member(ref m) => visitor.visit_member_expression(&m.node, &m.span),
         Expression::Call(ref c) => visitor.visit_call_expression(&c.node, &c.span),
+        Expression::KernelCall(ref c) => visitor.visit_kernel_call_expression(&c.node, &c.span),
         Expression::CompoundLiteral(ref c) => visitor.visit_compound_literal(&c.node, &c.span),
         Expression::SizeOfTy(ref s) => visitor.visit_sizeofty(&s.node, &s.span),
         Expression::SizeOfVal(ref s) => visitor.visit_sizeofval(&s.node, &s.span),
+        Expression::CountOfTy(ref c) => visitor.visit_countofty(&c.node, &c.span),
+        Expression::CountOfVal(ref c) => visitor.visit_countofval(&c.node, &c.span),
         Expression::AlignOf(ref a) => visitor.visit_alignof(&a.node, &a.span),
         Expression::UnaryOperator(ref u) => {
             visitor.visit_unary_operator_expression(&u.node, &u.span)
@@ -627,7 +769,19 @@ pub fn visit_expression<'ast, V: Visit<'ast> + ?Sized>(
         }
         Expression::OffsetOf(ref o) => visitor.visit_offset_of_expression(&o.node, &o.span),
         Expression::VaArg(ref v) => visitor.visit_va_arg_expression(&v.node, &v.span),
+        Expression::ChooseExpr(ref c) => visitor.visit_choose_expression(&c.node, &c.span),
+        Expression::TypesCompatible(ref t) => {
+            visitor.visit_types_compatible_expression(&t.node, &t.span)
+        }
         Expression::Statement(ref s) => visitor.visit_statement(&s.node, &s.span),
+        Expression::LabelAddress(ref i) => visitor.visit_identifier(&i.node, &i.span),
+        Expression::Extension(ref e) => visitor.visit_expression(&e.node, &e.span),
+        Expression::Block(ref b) => visitor.visit_block_expression(&b.node, &b.span),
+        Expression::BuiltinAvailable(ref conditions) => {
+            for condition in conditions.iter() {
+                visitor.visit_availability_condition(&condition.node, &condition.span);
+            }
+        }
     }
 }
 
@@ -708,6 +862,23 @@ pub fn visit_call_expression<'ast, V: Visit<'ast> + ?Sized>(
     }
 }
 
+pub fn visit_kernel_call_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    kernel_call_expression: &'ast KernelCallExpression,
+    _span: &'ast Span,
+) {
+    visitor.visit_expression(
+        &kernel_call_expression.callee.node,
+        &kernel_call_expression.callee.span,
+    );
+    for expression in &kernel_call_expression.configuration {
+        visitor.visit_expression(&expression.node, &expression.span);
+    }
+    for argument in &kernel_call_expression.arguments {
+        visitor.visit_expression(&argument.node, &argument.span);
+    }
+}
+
 pub fn visit_compound_literal<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     compound_literal: &'ast CompoundLiteral,
@@ -738,6 +909,22 @@ pub fn visit_sizeofval<'ast, V: Visit<'ast> + ?Sized>(
     visitor.visit_expression(&sizeofval.0.node, &sizeofval.0.span);
 }
 
+pub fn visit_countofty<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    countofty: &'ast CountOfTy,
+    _span: &'ast Span,
+) {
+    visitor.visit_type_name(&countofty.0.node, &countofty.0.span);
+}
+
+pub fn visit_countofval<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    countofval: &'ast CountOfVal,
+    _span: &'ast Span,
+) {
+    visitor.visit_expression(&countofval.0.node, &countofval.0.span);
+}
+
 pub fn visit_alignof<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     alignofty: &'ast AlignOf,
@@ -857,6 +1044,54 @@ pub fn visit_va_arg_expression<'ast, V: Visit<'ast> + ?Sized>(
     );
 }
 
+pub fn visit_choose_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    choose_expression: &'ast ChooseExpression,
+    _span: &'ast Span,
+) {
+    visitor.visit_expression(
+        &choose_expression.condition.node,
+        &choose_expression.condition.span,
+    );
+    visitor.visit_expression(
+        &choose_expression.then_expression.node,
+        &choose_expression.then_expression.span,
+    );
+    visitor.visit_expression(
+        &choose_expression.else_expression.node,
+        &choose_expression.else_expression.span,
+    );
+}
+
+pub fn visit_types_compatible_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    types_compatible_expression: &'ast TypesCompatibleExpression,
+    _span: &'ast Span,
+) {
+    visitor.visit_type_name(
+        &types_compatible_expression.first.node,
+        &types_compatible_expression.first.span,
+    );
+    visitor.visit_type_name(
+        &types_compatible_expression.second.node,
+        &types_compatible_expression.second.span,
+    );
+}
+
+pub fn visit_block_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    block_expression: &'ast BlockExpression,
+    _span: &'ast Span,
+) {
+    if let Some(ref return_type) = block_expression.return_type {
+        visitor.visit_type_name(&return_type.node, &return_type.span);
+    }
+    if let Some(ref parameters) = block_expression.parameters {
+        visitor.visit_function_declarator(&parameters.node, &parameters.span);
+    }
+    visitor.visit_statement(&block_expression.body.node, &block_expression.body.span);
+}
+
 pub fn visit_offset_of_expression<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     offset_of_expression: &'ast OffsetOfExpression,
@@ -967,8 +1202,11 @@ pub fn visit_type_specifier<'ast, V: Visit<'ast> + ?Sized>(
         TypeSpecifier::Struct(ref s) => visitor.visit_struct_type(&s.node, &s.span),
         TypeSpecifier::Enum(ref e) => visitor.visit_enum_type(&e.node, &e.span),
         TypeSpecifier::TypedefName(ref t) => visitor.visit_identifier(&t.node, &t.span),
-        TypeSpecifier::TypeOf(ref t) => visitor.visit_type_of(&t.node, &t.span),
+        TypeSpecifier::TypeOf(ref t) | TypeSpecifier::TypeOfUnqual(ref t) => {
+            visitor.visit_type_of(&t.node, &t.span)
+        }
         TypeSpecifier::TS18661Float(ref t) => visitor.visit_ts18661_float_type(t, span),
+        TypeSpecifier::BitInt(ref e) => visitor.visit_expression(&e.node, &e.span),
         _ => {}
     }
 }
@@ -994,6 +1232,9 @@ pub fn visit_struct_type<'ast, V: Visit<'ast> + ?Sized>(
     _span: &'ast Span,
 ) {
     visitor.visit_struct_kind(&struct_type.kind.node, &struct_type.kind.span);
+    for extension in &struct_type.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
     if let Some(ref identifier) = struct_type.identifier {
         visitor.visit_identifier(&identifier.node, &identifier.span);
     }
@@ -1002,6 +1243,9 @@ pub fn visit_struct_type<'ast, V: Visit<'ast> + ?Sized>(
             visitor.visit_struct_declaration(&declaration.node, &declaration.span);
         }
     }
+    for extension in &struct_type.trailing_extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
 }
 
 pub fn visit_struct_kind<'ast, V: Visit<'ast> + ?Sized>(
@@ -1069,12 +1313,23 @@ pub fn visit_enum_type<'ast, V: Visit<'ast> + ?Sized>(
     enum_type: &'ast EnumType,
     _span: &'ast Span,
 ) {
+    for extension in &enum_type.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
     if let Some(ref identifier) = enum_type.identifier {
         visitor.visit_identifier(&identifier.node, &identifier.span);
     }
+    if let Some(ref underlying) = enum_type.underlying {
+        for specifier in underlying {
+            visitor.visit_specifier_qualifier(&specifier.node, &specifier.span);
+        }
+    }
     for enumerator in &enum_type.enumerators {
         visitor.visit_enumerator(&enumerator.node, &enumerator.span);
     }
+    for extension in &enum_type.trailing_extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
 }
 
 pub fn visit_enumerator<'ast, V: Visit<'ast> + ?Sized>(
@@ -1184,6 +1439,9 @@ pub fn visit_function_declarator<'ast, V: Visit<'ast> + ?Sized>(
     function_declarator: &'ast FunctionDeclarator,
     span: &'ast Span,
 ) {
+    for parameter in &function_declarator.forward_parameters {
+        visitor.visit_parameter_declaration(&parameter.node, &parameter.span);
+    }
     for parameter in &function_declarator.parameters {
         visitor.visit_parameter_declaration(&parameter.node, &parameter.span);
     }
@@ -1312,7 +1570,9 @@ pub fn visit_static_assert<'ast, V: Visit<'ast> + ?Sized>(
         &static_assert.expression.node,
         &static_assert.expression.span,
     );
-    visitor.visit_string_literal(&static_assert.message.node, &static_assert.message.span);
+    if let Some(ref message) = static_assert.message {
+        visitor.visit_string_literal(&message.node, &message.span);
+    }
 }
 
 pub fn visit_statement<'ast, V: Visit<'ast> + ?Sized>(
@@ -1336,10 +1596,21 @@ pub fn visit_statement<'ast, V: Visit<'ast> + ?Sized>(
         Statement::DoWhile(ref d) => visitor.visit_do_while_statement(&d.node, &d.span),
         Statement::For(ref f) => visitor.visit_for_statement(&f.node, &f.span),
         Statement::Goto(ref g) => visitor.visit_identifier(&g.node, &g.span),
+        Statement::GotoIndirect(ref e) => visitor.visit_expression(&e.node, &e.span),
         Statement::Return(Some(ref r)) => {
             visitor.visit_expression(&r.node, &r.span);
         }
         Statement::Asm(ref a) => visitor.visit_asm_statement(&a.node, &a.span),
+        Statement::Pragma(ref p) => visitor.visit_pragma(&p.node, &p.span),
+        Statement::Omp(ref o) => visitor.visit_omp_statement(&o.node, &o.span),
+        Statement::Attributed(ref a) => visitor.visit_attributed_statement(&a.node, &a.span),
+        Statement::LocalLabels(ref l) => {
+            for identifier in l {
+                visitor.visit_identifier(&identifier.node, &identifier.span);
+            }
+        }
+        Statement::NestedFunction(ref f) => visitor.visit_function_definition(&f.node, &f.span),
+        Statement::Try(ref t) => visitor.visit_try_statement(&t.node, &t.span),
         _ => {}
     }
 }
@@ -1350,12 +1621,53 @@ pub fn visit_labeled_statement<'ast, V: Visit<'ast> + ?Sized>(
     _span: &'ast Span,
 ) {
     visitor.visit_label(&labeled_statement.label.node, &labeled_statement.label.span);
+    for attribute in &labeled_statement.attributes {
+        visitor.visit_extension(&attribute.node, &attribute.span);
+    }
     visitor.visit_statement(
         &labeled_statement.statement.node,
         &labeled_statement.statement.span,
     );
 }
 
+pub fn visit_attributed_statement<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    attributed_statement: &'ast AttributedStatement,
+    _span: &'ast Span,
+) {
+    for attribute in &attributed_statement.attributes {
+        visitor.visit_extension(&attribute.node, &attribute.span);
+    }
+    visitor.visit_statement(
+        &attributed_statement.statement.node,
+        &attributed_statement.statement.span,
+    );
+}
+
+pub fn visit_try_statement<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    try_statement: &'ast TryStatement,
+    span: &'ast Span,
+) {
+    visitor.visit_statement(&try_statement.statement.node, &try_statement.statement.span);
+    match try_statement.handler {
+        TryHandler::Except(ref e) => visitor.visit_except_handler(e, span),
+        TryHandler::Finally(ref s) => visitor.visit_statement(&s.node, &s.span),
+    }
+}
+
+pub fn visit_except_handler<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    except_handler: &'ast ExceptHandler,
+    _span: &'ast Span,
+) {
+    visitor.visit_expression(&except_handler.filter.node, &except_handler.filter.span);
+    visitor.visit_statement(
+        &except_handler.statement.node,
+        &except_handler.statement.span,
+    );
+}
+
 pub fn visit_if_statement<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     if_statement: &'ast IfStatement,
@@ -1478,6 +1790,7 @@ pub fn visit_block_item<'ast, V: Visit<'ast> + ?Sized>(
         BlockItem::Declaration(ref d) => visitor.visit_declaration(&d.node, &d.span),
         BlockItem::StaticAssert(ref s) => visitor.visit_static_assert(&s.node, &s.span),
         BlockItem::Statement(ref s) => visitor.visit_statement(&s.node, &s.span),
+        BlockItem::Label(ref l) => visitor.visit_label(&l.node, &l.span),
     }
 }
 
@@ -1501,9 +1814,110 @@ pub fn visit_external_declaration<'ast, V: Visit<'ast> + ?Sized>(
         ExternalDeclaration::FunctionDefinition(ref f) => {
             visitor.visit_function_definition(&f.node, &f.span)
         }
+        ExternalDeclaration::Pragma(ref p) => visitor.visit_pragma(&p.node, &p.span),
+        ExternalDeclaration::Asm(ref a) => visitor.visit_string_literal(&a.node, &a.span),
+        ExternalDeclaration::Error => {}
     }
 }
 
+pub fn visit_pragma<'ast, V: Visit<'ast> + ?Sized>(
+    _visitor: &mut V,
+    _pragma: &'ast Pragma,
+    _span: &'ast Span,
+) {
+}
+
+pub fn visit_omp_statement<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_statement: &'ast OmpStatement,
+    _span: &'ast Span,
+) {
+    visitor.visit_omp_directive(&omp_statement.directive.node, &omp_statement.directive.span);
+    if let Some(ref s) = omp_statement.statement {
+        visitor.visit_statement(&s.node, &s.span);
+    }
+}
+
+pub fn visit_omp_directive<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_directive: &'ast OmpDirective,
+    span: &'ast Span,
+) {
+    visitor.visit_omp_directive_kind(&omp_directive.kind, span);
+    for clause in &omp_directive.clauses {
+        visitor.visit_omp_clause(&clause.node, &clause.span);
+    }
+}
+
+pub fn visit_omp_directive_kind<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_directive_kind: &'ast OmpDirectiveKind,
+    _span: &'ast Span,
+) {
+    if let OmpDirectiveKind::Critical(Some(ref n)) = *omp_directive_kind {
+        visitor.visit_identifier(&n.node, &n.span);
+    }
+}
+
+pub fn visit_omp_clause<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_clause: &'ast OmpClause,
+    span: &'ast Span,
+) {
+    match *omp_clause {
+        OmpClause::Private(ref l) | OmpClause::Shared(ref l) | OmpClause::FirstPrivate(ref l) => {
+            for variable in l {
+                visitor.visit_identifier(&variable.node, &variable.span);
+            }
+        }
+        OmpClause::Reduction(ref r) => visitor.visit_omp_reduction(r, span),
+        OmpClause::NumThreads(ref e) | OmpClause::Collapse(ref e) => {
+            visitor.visit_expression(&e.node, &e.span)
+        }
+        OmpClause::Schedule(ref s) => visitor.visit_omp_schedule(s, span),
+    }
+}
+
+pub fn visit_omp_reduction<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_reduction: &'ast OmpReduction,
+    _span: &'ast Span,
+) {
+    visitor
+        .visit_omp_reduction_operator(&omp_reduction.operator.node, &omp_reduction.operator.span);
+    for variable in &omp_reduction.variables {
+        visitor.visit_identifier(&variable.node, &variable.span);
+    }
+}
+
+pub fn visit_omp_reduction_operator<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_reduction_operator: &'ast OmpReductionOperator,
+    _span: &'ast Span,
+) {
+    if let OmpReductionOperator::Identifier(ref i) = *omp_reduction_operator {
+        visitor.visit_identifier(&i.node, &i.span);
+    }
+}
+
+pub fn visit_omp_schedule<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    omp_schedule: &'ast OmpSchedule,
+    _span: &'ast Span,
+) {
+    visitor.visit_omp_schedule_kind(&omp_schedule.kind.node, &omp_schedule.kind.span);
+    if let Some(ref c) = omp_schedule.chunk_size {
+        visitor.visit_expression(&c.node, &c.span);
+    }
+}
+
+pub fn visit_omp_schedule_kind<'ast, V: Visit<'ast> + ?Sized>(
+    _visitor: &mut V,
+    _omp_schedule_kind: &'ast OmpScheduleKind,
+    _span: &'ast Span,
+) {
+}
+
 pub fn visit_function_definition<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     function_definition: &'ast FunctionDefinition,
@@ -1536,6 +1950,20 @@ pub fn visit_extension<'ast, V: Visit<'ast> + ?Sized>(
         Extension::AvailabilityAttribute(ref a) => {
             visitor.visit_availability_attribute(&a.node, &a.span)
         }
+        Extension::StdAttribute(ref a) => visitor.visit_std_attribute(a, span),
+        Extension::Declspec(ref d) => {
+            for attribute in d {
+                visitor.visit_attribute(&attribute.node, &attribute.span);
+            }
+        }
+        Extension::CallingConvention(_) => {}
+        Extension::Marker => {}
+        Extension::Cuda(CudaSpecifier::LaunchBounds(ref b)) => {
+            for expression in b {
+                visitor.visit_expression(&expression.node, &expression.span);
+            }
+        }
+        Extension::Cuda(_) => {}
     }
 }
 
@@ -1557,6 +1985,7 @@ pub fn visit_asm_statement<'ast, V: Visit<'ast> + ?Sized>(
     match *asm_statement {
         AsmStatement::GnuBasic(ref g) => visitor.visit_string_literal(&g.node, &g.span),
         AsmStatement::GnuExtended(ref g) => visitor.visit_gnu_extended_asm_statement(g, span),
+        AsmStatement::Msvc(_) => {}
     }
 }
 
@@ -1570,6 +1999,42 @@ pub fn visit_availability_attribute<'ast, V: Visit<'ast> + ?Sized>(
     }
 }
 
+pub fn visit_availability_condition<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    condition: &'ast AvailabilityCondition,
+    _span: &'ast Span,
+) {
+    if let AvailabilityCondition::Platform(ref p) = *condition {
+        visitor.visit_identifier(&p.platform.node, &p.platform.span);
+    }
+}
+
+pub fn visit_std_attribute<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    std_attribute: &'ast StdAttribute,
+    _span: &'ast Span,
+) {
+    if let Some(ref namespace) = std_attribute.namespace {
+        visitor.visit_identifier(&namespace.node, &namespace.span);
+    }
+    visitor.visit_identifier(&std_attribute.name.node, &std_attribute.name.span);
+    if let Some(ref arguments) = std_attribute.arguments {
+        visitor.visit_std_attribute_arguments(&arguments.node, &arguments.span);
+    }
+}
+
+pub fn visit_std_attribute_arguments<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    arguments: &'ast StdAttributeArguments,
+    _span: &'ast Span,
+) {
+    if let StdAttributeArguments::Expressions(ref expressions) = *arguments {
+        for expression in expressions {
+            visitor.visit_expression(&expression.node, &expression.span);
+        }
+    }
+}
+
 pub fn visit_gnu_extended_asm_statement<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     gnu_extended_asm_statement: &'ast GnuExtendedAsmStatement,
@@ -1591,6 +2056,9 @@ pub fn visit_gnu_extended_asm_statement<'ast, V: Visit<'ast> + ?Sized>(
     for clobber in &gnu_extended_asm_statement.clobbers {
         visitor.visit_string_literal(&clobber.node, &clobber.span);
     }
+    for label in &gnu_extended_asm_statement.labels {
+        visitor.visit_identifier(&label.node, &label.span);
+    }
 }
 
 pub fn visit_gnu_asm_operand<'ast, V: Visit<'ast> + ?Sized>(